@@ -26,7 +26,7 @@ struct Args {
 async fn main() {
     let args = Args::parse();
     let auth = IamAuthenticator::new(args.api_key).await.unwrap();
-    let tts = TextToSpeech::new(&auth, &args.service_url);
+    let tts = TextToSpeech::new(&auth, &args.service_url).unwrap();
     let voices = tts.list_voices().await.unwrap();
     println!("{:#?}", voices);
     let voice = tts.get_voice(WatsonVoice::EnGbKateV3, None).await.unwrap();
@@ -0,0 +1,80 @@
+use std::fmt;
+
+use reqwest::header::HeaderValue;
+
+use super::{basic_header, config::ServiceConfig, AuthenticationError, Authenticator, Redacted};
+
+#[derive(Clone)]
+/// Authenticates requests with a username and password, as used by IBM Cloud Pak for Data and
+/// some dedicated Watson instances. Its `Debug` output hides the password
+pub struct BasicAuthenticator {
+    username: String,
+    password: String,
+}
+
+impl fmt::Debug for BasicAuthenticator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BasicAuthenticator")
+            .field("username", &self.username)
+            .field("password", &Redacted)
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for BasicAuthenticator {
+    fn drop(&mut self) {
+        super::zeroize::wipe(&mut self.password);
+    }
+}
+
+impl BasicAuthenticator {
+    /// Create an authenticator that sends `Authorization: Basic ...` with every request
+    ///
+    /// # Parameters
+    ///
+    /// * `username` - The username for your service instance
+    /// * `password` - The password for your service instance
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::BasicAuthenticator;
+    /// let auth = BasicAuthenticator::new("username", "password");
+    /// ```
+    pub fn new(username: impl AsRef<str>, password: impl AsRef<str>) -> Self {
+        Self {
+            username: username.as_ref().to_owned(),
+            password: password.as_ref().to_owned(),
+        }
+    }
+
+    /// Create a [`BasicAuthenticator`] from the credentials file or environment variables
+    ///
+    /// # Parameters
+    ///
+    /// * `service` - The name of the service, such as `text_to_speech`. The credentials are read from `<SERVICE>_USERNAME` and `<SERVICE>_PASSWORD`
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::BasicAuthenticator;
+    /// # fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = BasicAuthenticator::from_env("text_to_speech")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`BasicAuthenticator`]: Self
+    pub fn from_env(service: impl AsRef<str>) -> Result<Self, AuthenticationError> {
+        let config = ServiceConfig::new(service.as_ref());
+        config.check_auth_type("basic")?;
+        Ok(Self::new(
+            config.require("USERNAME")?,
+            config.require("PASSWORD")?,
+        ))
+    }
+}
+
+impl Authenticator for BasicAuthenticator {
+    async fn authorization_header(&self) -> Result<Option<HeaderValue>, AuthenticationError> {
+        Ok(Some(basic_header(&self.username, &self.password)?))
+    }
+}
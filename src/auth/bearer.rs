@@ -0,0 +1,84 @@
+use std::{fmt, sync::RwLock};
+
+use reqwest::header::HeaderValue;
+
+use super::{bearer_header, config::ServiceConfig, AuthenticationError, Authenticator, Redacted};
+
+#[derive(Default)]
+/// Authenticates requests with an access token that you obtained yourself, for example from your
+/// own IAM proxy. The crate never sees your API key and does not refresh the token for you; use
+/// [`set_token()`] to replace it before it expires
+///
+/// [`set_token()`]: Self::set_token()
+pub struct BearerTokenAuthenticator {
+    token: RwLock<String>,
+}
+
+impl fmt::Debug for BearerTokenAuthenticator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BearerTokenAuthenticator")
+            .field("token", &Redacted)
+            .finish()
+    }
+}
+
+impl BearerTokenAuthenticator {
+    /// Create an authenticator from a pre-obtained access token
+    ///
+    /// # Parameters
+    ///
+    /// * `token` - The bearer token to send with every request
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::BearerTokenAuthenticator;
+    /// let auth = BearerTokenAuthenticator::new("access_token");
+    /// ```
+    pub fn new(token: impl AsRef<str>) -> Self {
+        Self {
+            token: RwLock::new(token.as_ref().to_owned()),
+        }
+    }
+
+    /// Create a [`BearerTokenAuthenticator`] from the credentials file or environment variables
+    ///
+    /// # Parameters
+    ///
+    /// * `service` - The name of the service, such as `text_to_speech`. The token is read from `<SERVICE>_BEARER_TOKEN`
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::BearerTokenAuthenticator;
+    /// # fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = BearerTokenAuthenticator::from_env("text_to_speech")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`BearerTokenAuthenticator`]: Self
+    pub fn from_env(service: impl AsRef<str>) -> Result<Self, AuthenticationError> {
+        let config = ServiceConfig::new(service.as_ref());
+        config.check_auth_type("bearerToken")?;
+        Ok(Self::new(config.require("BEARER_TOKEN")?))
+    }
+
+    /// Replace the token sent with subsequent requests
+    ///
+    /// # Parameters
+    ///
+    /// * `token` - The new bearer token
+    pub fn set_token(&self, token: impl AsRef<str>) {
+        *self.token.write().unwrap() = token.as_ref().to_owned();
+    }
+}
+
+impl Clone for BearerTokenAuthenticator {
+    fn clone(&self) -> Self {
+        Self::new(&*self.token.read().unwrap())
+    }
+}
+
+impl Authenticator for BearerTokenAuthenticator {
+    async fn authorization_header(&self) -> Result<Option<HeaderValue>, AuthenticationError> {
+        Ok(Some(bearer_header(&self.token.read().unwrap())?))
+    }
+}
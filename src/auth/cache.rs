@@ -0,0 +1,47 @@
+use std::path::Path;
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::{fs, io::AsyncWriteExt};
+
+use super::TokenResponse;
+
+/// Reads a token cached at `path`, returning [`None`] if there is no usable, unexpired token
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn read(path: &Path) -> Option<TokenResponse> {
+    let contents = fs::read(path).await.ok()?;
+    let token: TokenResponse = serde_json::from_slice(&contents).ok()?;
+    (!token.needs_refresh(None)).then_some(token)
+}
+
+/// Caches `token` at `path`. The file is only readable by its owner, as it holds a live access
+/// token. Failures are ignored, the cache only saves a round trip to IAM
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn write(path: &Path, token: &TokenResponse) {
+    let _ = try_write(path, token).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn try_write(path: &Path, token: &TokenResponse) -> std::io::Result<()> {
+    let contents = serde_json::to_vec(token)?;
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path).await?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))
+            .await?;
+    }
+    file.write_all(&contents).await?;
+    file.flush().await
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn read(_path: &Path) -> Option<TokenResponse> {
+    None
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn write(_path: &Path, _token: &TokenResponse) {}
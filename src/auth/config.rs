@@ -0,0 +1,194 @@
+use std::{
+    collections::HashMap,
+    env, fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use super::{AuthenticationError, TrustedProfile};
+
+/// The name of the credentials file that IBM Cloud lets you download for a service instance
+pub const CREDENTIALS_FILE_NAME: &str = "ibm-credentials.env";
+
+#[derive(Clone, Default, PartialEq, Eq)]
+/// The entries of an `ibm-credentials.env` file, such as `TEXT_TO_SPEECH_APIKEY=...`. Its `Debug`
+/// output only lists the names of the entries
+pub struct CredentialsFile {
+    entries: HashMap<String, String>,
+}
+
+impl fmt::Debug for CredentialsFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CredentialsFile")
+            .field("entries", &self.entries.keys())
+            .finish()
+    }
+}
+
+impl CredentialsFile {
+    /// Locate and read the credentials file. The file named by the `IBM_CREDENTIALS_FILE`
+    /// environment variable is used if it is set, otherwise [`CREDENTIALS_FILE_NAME`] is looked
+    /// for in the working directory and then in the home directory. Returns [`None`] if no file
+    /// could be found
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::CredentialsFile;
+    /// # fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// if let Some(credentials) = CredentialsFile::load()? {
+    ///     let api_key = credentials.service("text_to_speech").get("APIKEY");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`CREDENTIALS_FILE_NAME`]: self::CREDENTIALS_FILE_NAME
+    /// [`None`]: std::option::Option::None
+    pub fn load() -> Result<Option<Self>, AuthenticationError> {
+        if let Some(path) = env::var_os("IBM_CREDENTIALS_FILE") {
+            return Self::from_path(path).map(Some);
+        }
+        let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"));
+        let candidates = env::current_dir()
+            .ok()
+            .into_iter()
+            .chain(home.map(PathBuf::from))
+            .map(|dir| dir.join(CREDENTIALS_FILE_NAME));
+        for path in candidates {
+            if path.is_file() {
+                return Self::from_path(path).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Read the credentials file at `path`
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The location of the credentials file
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, AuthenticationError> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| AuthenticationError::FileReadError(e.to_string()))?;
+        Ok(Self::parse(&contents))
+    }
+
+    /// Parse the contents of a credentials file. Each line holds a `KEY=VALUE` pair; blank lines
+    /// and lines starting with `#` are ignored
+    ///
+    /// # Parameters
+    ///
+    /// * `contents` - The contents of the credentials file
+    pub fn parse(contents: &str) -> Self {
+        let entries = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim().to_owned(), value.trim().to_owned()))
+            .collect();
+        Self { entries }
+    }
+
+    /// The entries belonging to `service`, keyed by property name with the `<SERVICE>_` prefix
+    /// removed, e.g. `APIKEY` and `URL`
+    ///
+    /// # Parameters
+    ///
+    /// * `service` - The name of the service, such as `text_to_speech`
+    pub fn service(&self, service: impl AsRef<str>) -> HashMap<String, String> {
+        let prefix = format!("{}_", service_prefix(service.as_ref()));
+        self.entries
+            .iter()
+            .filter_map(|(key, value)| {
+                key.strip_prefix(&prefix)
+                    .map(|property| (property.to_owned(), value.clone()))
+            })
+            .collect()
+    }
+
+    /// The value of `key`, such as `TEXT_TO_SPEECH_APIKEY`
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The full name of the entry
+    pub fn get(&self, key: impl AsRef<str>) -> Option<&str> {
+        self.entries.get(key.as_ref()).map(String::as_str)
+    }
+}
+
+fn service_prefix(service: &str) -> String {
+    service.to_uppercase().replace('-', "_")
+}
+
+/// Looks up the configuration properties of a service, following the `<SERVICE>_<PROPERTY>`
+/// naming convention used by IBM's SDKs, e.g. `TEXT_TO_SPEECH_APIKEY`. As in those SDKs, entries
+/// in the credentials file take precedence over environment variables
+pub(crate) struct ServiceConfig {
+    prefix: String,
+    credentials: Option<CredentialsFile>,
+}
+
+impl ServiceConfig {
+    pub(crate) fn new(service: &str) -> Self {
+        Self {
+            prefix: service_prefix(service),
+            credentials: CredentialsFile::load().ok().flatten(),
+        }
+    }
+
+    /// The value of `property`, if it has been set to a non-empty value
+    pub(crate) fn get(&self, property: &str) -> Option<String> {
+        let name = self.name(property);
+        self.credentials
+            .as_ref()
+            .and_then(|credentials| credentials.get(&name))
+            .map(str::to_owned)
+            .or_else(|| env::var(&name).ok())
+            .filter(|value| !value.is_empty())
+    }
+
+    pub(crate) fn require(&self, property: &str) -> Result<String, AuthenticationError> {
+        self.get(property)
+            .ok_or_else(|| AuthenticationError::MissingConfiguration(self.name(property)))
+    }
+
+    /// Checks that `AUTH_TYPE`, if it has been set, names the authenticator being created
+    pub(crate) fn check_auth_type(&self, auth_type: &str) -> Result<(), AuthenticationError> {
+        match self.get("AUTH_TYPE") {
+            Some(configured) if !configured.eq_ignore_ascii_case(auth_type) => {
+                Err(AuthenticationError::UnsupportedAuthType(configured))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The trusted profile named by `IAM_PROFILE_CRN` or `IAM_PROFILE_ID`, if either has been set
+    pub(crate) fn trusted_profile(&self) -> Option<TrustedProfile> {
+        match self.get("IAM_PROFILE_CRN") {
+            Some(crn) => Some(TrustedProfile::Crn(crn)),
+            None => self.get("IAM_PROFILE_ID").map(TrustedProfile::Id),
+        }
+    }
+
+    fn name(&self, property: &str) -> String {
+        format!("{}_{}", self.prefix, property)
+    }
+}
+
+/// Read the endpoint of a service from `<SERVICE>_URL`, in the credentials file or the environment
+///
+/// # Parameters
+///
+/// * `service` - The name of the service, such as `text_to_speech`
+///
+/// # Example
+/// ``` no_run
+/// # use ibm_watson::auth::{service_url_from_env, IamAuthenticator};
+/// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+/// let auth = IamAuthenticator::from_env("text_to_speech").await?;
+/// let service_url = service_url_from_env("text_to_speech").unwrap();
+/// # Ok(())
+/// # }
+/// ```
+pub fn service_url_from_env(service: impl AsRef<str>) -> Option<String> {
+    ServiceConfig::new(service.as_ref()).get("URL")
+}
@@ -0,0 +1,305 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Client, Url,
+};
+use tokio::sync::Mutex;
+
+use super::{
+    bearer_header, config::ServiceConfig, iam_token_url, request_iam_token, AuthenticationError,
+    Authenticator, TokenResponse, TrustedProfile, DEFAULT_IAM_URL,
+};
+
+/// Where Kubernetes projects the compute resource token by default
+pub const DEFAULT_CR_TOKEN_PATH: &str = "/var/run/secrets/tokens/vault-token";
+
+#[derive(Debug, Clone)]
+enum Profile {
+    Name(String),
+    Trusted(TrustedProfile),
+}
+
+#[derive(Debug)]
+/// Authenticates workloads running on IBM Cloud Kubernetes Service, Red Hat OpenShift or Code
+/// Engine. The compute resource (CR) token projected into the container is exchanged at IAM for
+/// an access token bound to a trusted profile. The CR token file is read again each time the
+/// access token has expired, so rotated CR tokens are picked up automatically
+pub struct ContainerAuthenticator {
+    cr_token_path: PathBuf,
+    profile: Profile,
+    url: Url,
+    client: Client,
+    access_token: RwLock<TokenResponse>,
+    refresh_lock: Mutex<()>,
+}
+
+impl Clone for ContainerAuthenticator {
+    fn clone(&self) -> Self {
+        Self {
+            cr_token_path: self.cr_token_path.clone(),
+            profile: self.profile.clone(),
+            url: self.url.clone(),
+            client: self.client.clone(),
+            access_token: RwLock::new(self.access_token.read().unwrap().clone()),
+            refresh_lock: Mutex::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Configures a [`ContainerAuthenticator`] before requesting its first access token
+///
+/// [`ContainerAuthenticator`]: self::ContainerAuthenticator
+pub struct ContainerAuthenticatorBuilder {
+    cr_token_path: Option<PathBuf>,
+    profile: Option<Profile>,
+    url: Option<String>,
+}
+
+impl ContainerAuthenticatorBuilder {
+    /// Read the CR token from `cr_token_path`. Defaults to [`DEFAULT_CR_TOKEN_PATH`]
+    ///
+    /// # Parameters
+    ///
+    /// * `cr_token_path` - The file containing the compute resource token
+    ///
+    /// [`DEFAULT_CR_TOKEN_PATH`]: self::DEFAULT_CR_TOKEN_PATH
+    pub fn cr_token_path(mut self, cr_token_path: impl AsRef<Path>) -> Self {
+        self.cr_token_path = Some(cr_token_path.as_ref().to_owned());
+        self
+    }
+
+    /// Assume the trusted profile named `iam_profile_name`
+    ///
+    /// # Parameters
+    ///
+    /// * `iam_profile_name` - The name of the trusted profile to assume
+    pub fn iam_profile_name(mut self, iam_profile_name: impl AsRef<str>) -> Self {
+        self.profile = Some(Profile::Name(iam_profile_name.as_ref().to_owned()));
+        self
+    }
+
+    /// Assume the trusted profile identified by its ID or CRN
+    ///
+    /// # Parameters
+    ///
+    /// * `trusted_profile` - The [`TrustedProfile`] to assume
+    ///
+    /// [`TrustedProfile`]: super::TrustedProfile
+    pub fn trusted_profile(mut self, trusted_profile: TrustedProfile) -> Self {
+        self.profile = Some(Profile::Trusted(trusted_profile));
+        self
+    }
+
+    /// Exchange the CR token at another IAM endpoint, such as
+    /// `https://private.iam.cloud.ibm.com`. Defaults to [`DEFAULT_IAM_URL`]
+    ///
+    /// # Parameters
+    ///
+    /// * `url` - The URL of the IAM service. `/identity/token` is appended to it
+    ///
+    /// [`DEFAULT_IAM_URL`]: super::DEFAULT_IAM_URL
+    pub fn url(mut self, url: impl AsRef<str>) -> Self {
+        self.url = Some(url.as_ref().to_owned());
+        self
+    }
+
+    /// Request the first access token, failing if no trusted profile has been given
+    pub async fn build(self) -> Result<ContainerAuthenticator, AuthenticationError> {
+        let profile = self.profile.ok_or_else(|| {
+            AuthenticationError::MissingConfiguration("iam_profile_name".to_owned())
+        })?;
+        let client = Client::builder()
+            .build()
+            .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+        let authenticator = ContainerAuthenticator {
+            cr_token_path: self
+                .cr_token_path
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_CR_TOKEN_PATH)),
+            profile,
+            url: iam_token_url(self.url.as_deref().unwrap_or(DEFAULT_IAM_URL))?,
+            client,
+            access_token: RwLock::default(),
+            refresh_lock: Mutex::default(),
+        };
+        let token = authenticator.request_token().await?;
+        *authenticator.access_token.write().unwrap() = token;
+        Ok(authenticator)
+    }
+}
+
+impl ContainerAuthenticator {
+    /// Get an IAM access token for a trusted profile using the CR token at [`DEFAULT_CR_TOKEN_PATH`]
+    ///
+    /// # Parameters
+    ///
+    /// * `iam_profile_name` - The name of the trusted profile to assume
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::ContainerAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = ContainerAuthenticator::new("my-profile").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`DEFAULT_CR_TOKEN_PATH`]: self::DEFAULT_CR_TOKEN_PATH
+    pub async fn new(iam_profile_name: impl AsRef<str>) -> Result<Self, AuthenticationError> {
+        Self::with_cr_token_path(DEFAULT_CR_TOKEN_PATH, iam_profile_name).await
+    }
+
+    /// Get an IAM access token for a trusted profile using the CR token stored at `cr_token_path`
+    ///
+    /// # Parameters
+    ///
+    /// * `cr_token_path` - The file containing the compute resource token
+    /// * `iam_profile_name` - The name of the trusted profile to assume
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::ContainerAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = ContainerAuthenticator::with_cr_token_path("/var/run/secrets/tokens/sa-token", "my-profile").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_cr_token_path(
+        cr_token_path: impl AsRef<Path>,
+        iam_profile_name: impl AsRef<str>,
+    ) -> Result<Self, AuthenticationError> {
+        Self::builder()
+            .cr_token_path(cr_token_path)
+            .iam_profile_name(iam_profile_name)
+            .build()
+            .await
+    }
+
+    /// Get an IAM access token for the trusted profile identified by its ID or CRN, using the CR
+    /// token stored at `cr_token_path`
+    ///
+    /// # Parameters
+    ///
+    /// * `cr_token_path` - The file containing the compute resource token, such as [`DEFAULT_CR_TOKEN_PATH`]
+    /// * `trusted_profile` - The [`TrustedProfile`] to assume
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::{ContainerAuthenticator, TrustedProfile, DEFAULT_CR_TOKEN_PATH};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let profile = TrustedProfile::Id("Profile-9942d4b6".to_owned());
+    /// let auth = ContainerAuthenticator::with_trusted_profile(DEFAULT_CR_TOKEN_PATH, profile).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`DEFAULT_CR_TOKEN_PATH`]: self::DEFAULT_CR_TOKEN_PATH
+    /// [`TrustedProfile`]: super::TrustedProfile
+    pub async fn with_trusted_profile(
+        cr_token_path: impl AsRef<Path>,
+        trusted_profile: TrustedProfile,
+    ) -> Result<Self, AuthenticationError> {
+        Self::builder()
+            .cr_token_path(cr_token_path)
+            .trusted_profile(trusted_profile)
+            .build()
+            .await
+    }
+
+    /// Configure a [`ContainerAuthenticator`], for example to use a private IAM endpoint
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::ContainerAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = ContainerAuthenticator::builder()
+    ///     .iam_profile_name("my-profile")
+    ///     .url("https://private.iam.cloud.ibm.com")
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`ContainerAuthenticator`]: Self
+    pub fn builder() -> ContainerAuthenticatorBuilder {
+        ContainerAuthenticatorBuilder::default()
+    }
+
+    /// Create a [`ContainerAuthenticator`] from the credentials file or environment variables
+    ///
+    /// # Parameters
+    ///
+    /// * `service` - The name of the service, such as `text_to_speech`. The trusted profile is read from `<SERVICE>_IAM_PROFILE_NAME`, `<SERVICE>_IAM_PROFILE_CRN` or `<SERVICE>_IAM_PROFILE_ID`, and the CR token from the file named by `<SERVICE>_CR_TOKEN_FILENAME`, or [`DEFAULT_CR_TOKEN_PATH`] if it is not set. The IAM endpoint is read from `<SERVICE>_AUTH_URL`, if it is set
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::ContainerAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = ContainerAuthenticator::from_env("text_to_speech").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`ContainerAuthenticator`]: Self
+    /// [`DEFAULT_CR_TOKEN_PATH`]: self::DEFAULT_CR_TOKEN_PATH
+    pub async fn from_env(service: impl AsRef<str>) -> Result<Self, AuthenticationError> {
+        let config = ServiceConfig::new(service.as_ref());
+        config.check_auth_type("container")?;
+        let mut builder = match config.trusted_profile() {
+            Some(trusted_profile) => Self::builder().trusted_profile(trusted_profile),
+            None => Self::builder().iam_profile_name(config.require("IAM_PROFILE_NAME")?),
+        };
+        if let Some(cr_token_path) = config.get("CR_TOKEN_FILENAME") {
+            builder = builder.cr_token_path(cr_token_path);
+        }
+        if let Some(url) = config.get("AUTH_URL") {
+            builder = builder.url(url);
+        }
+        builder.build().await
+    }
+
+    async fn request_token(&self) -> Result<TokenResponse, AuthenticationError> {
+        let cr_token = tokio::fs::read_to_string(&self.cr_token_path)
+            .await
+            .map_err(|e| AuthenticationError::FileReadError(e.to_string()))?;
+        request_iam_token(
+            &self.client,
+            &self.url,
+            &HeaderMap::new(),
+            &[
+                ("grant_type", "urn:ibm:params:oauth:grant-type:cr-token"),
+                ("cr_token", cr_token.trim()),
+                match &self.profile {
+                    Profile::Name(name) => ("profile_name", name),
+                    Profile::Trusted(trusted_profile) => trusted_profile.form_field(),
+                },
+            ],
+        )
+        .await
+    }
+
+    async fn access_token(&self) -> Result<String, AuthenticationError> {
+        let token = self.access_token.read().unwrap().clone();
+        if !token.needs_refresh(None) {
+            return Ok(token.access_token.clone());
+        }
+        let _guard = self.refresh_lock.lock().await;
+        // another request may have refreshed the token while we waited for the lock
+        let token = self.access_token.read().unwrap().clone();
+        if !token.needs_refresh(None) {
+            return Ok(token.access_token.clone());
+        }
+        let token = self.request_token().await?;
+        let access_token = token.access_token.clone();
+        *self.access_token.write().unwrap() = token;
+        Ok(access_token)
+    }
+}
+
+impl Authenticator for ContainerAuthenticator {
+    async fn authorization_header(&self) -> Result<Option<HeaderValue>, AuthenticationError> {
+        let token = self.access_token().await?;
+        Ok(Some(bearer_header(&token)?))
+    }
+}
@@ -0,0 +1,238 @@
+use std::{fmt, sync::RwLock};
+
+use reqwest::{header::HeaderValue, Client, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use super::{
+    bearer_header, config::ServiceConfig, has_expired, jwt_expiration, refresh_time, status_error,
+    AuthenticationError, Authenticator, Redacted,
+};
+
+#[derive(Clone, Default)]
+struct Cp4dToken {
+    token: String,
+    /// When the token should be renewed, in seconds since the Unix epoch
+    refresh_at: i64,
+}
+
+impl fmt::Debug for Cp4dToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cp4dToken")
+            .field("token", &Redacted)
+            .field("refresh_at", &self.refresh_at)
+            .finish()
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Credential {
+    Password(String),
+    ApiKey(String),
+}
+
+impl fmt::Debug for Credential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Credential::Password(_) => f.debug_tuple("Password").field(&Redacted).finish(),
+            Credential::ApiKey(_) => f.debug_tuple("ApiKey").field(&Redacted).finish(),
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Authenticates requests against IBM Cloud Pak for Data. The JWT returned by the cluster's
+/// `/v1/authorize` endpoint is refreshed with the same credentials whenever it has expired
+pub struct CloudPakForDataAuthenticator {
+    url: Url,
+    username: String,
+    credential: Credential,
+    client: Client,
+    token: RwLock<Cp4dToken>,
+    refresh_lock: Mutex<()>,
+}
+
+impl Clone for CloudPakForDataAuthenticator {
+    fn clone(&self) -> Self {
+        Self {
+            url: self.url.clone(),
+            username: self.username.clone(),
+            credential: self.credential.clone(),
+            client: self.client.clone(),
+            token: RwLock::new(self.token.read().unwrap().clone()),
+            refresh_lock: Mutex::default(),
+        }
+    }
+}
+
+impl CloudPakForDataAuthenticator {
+    /// Get a Cloud Pak for Data token with a username and password
+    ///
+    /// # Parameters
+    ///
+    /// * `url` - The URL of your cluster's authorisation service, such as `https://{cpd_cluster_host}/icp4d-api`. `/v1/authorize` is appended to it
+    /// * `username` - The username of your Cloud Pak for Data user
+    /// * `password` - The password of your Cloud Pak for Data user
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::CloudPakForDataAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = CloudPakForDataAuthenticator::new("https://cpd.example.com/icp4d-api", "admin", "password").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn new(
+        url: impl AsRef<str>,
+        username: impl AsRef<str>,
+        password: impl AsRef<str>,
+    ) -> Result<Self, AuthenticationError> {
+        Self::authorise(
+            url.as_ref(),
+            username.as_ref(),
+            Credential::Password(password.as_ref().to_owned()),
+        )
+        .await
+    }
+
+    /// Get a Cloud Pak for Data token with a username and API key
+    ///
+    /// # Parameters
+    ///
+    /// * `url` - The URL of your cluster's authorisation service, such as `https://{cpd_cluster_host}/icp4d-api`. `/v1/authorize` is appended to it
+    /// * `username` - The username of your Cloud Pak for Data user
+    /// * `api_key` - The API key of your Cloud Pak for Data user
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::CloudPakForDataAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = CloudPakForDataAuthenticator::with_api_key("https://cpd.example.com/icp4d-api", "admin", "api_key").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_api_key(
+        url: impl AsRef<str>,
+        username: impl AsRef<str>,
+        api_key: impl AsRef<str>,
+    ) -> Result<Self, AuthenticationError> {
+        Self::authorise(
+            url.as_ref(),
+            username.as_ref(),
+            Credential::ApiKey(api_key.as_ref().to_owned()),
+        )
+        .await
+    }
+
+    /// Create a [`CloudPakForDataAuthenticator`] from the credentials file or environment variables
+    ///
+    /// # Parameters
+    ///
+    /// * `service` - The name of the service, such as `text_to_speech`. The cluster URL and username are read from `<SERVICE>_AUTH_URL` and `<SERVICE>_USERNAME`, along with either `<SERVICE>_PASSWORD` or `<SERVICE>_APIKEY`
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::CloudPakForDataAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = CloudPakForDataAuthenticator::from_env("text_to_speech").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`CloudPakForDataAuthenticator`]: Self
+    pub async fn from_env(service: impl AsRef<str>) -> Result<Self, AuthenticationError> {
+        let config = ServiceConfig::new(service.as_ref());
+        config.check_auth_type("cp4d")?;
+        let url = config.require("AUTH_URL")?;
+        let username = config.require("USERNAME")?;
+        let credential = match config.get("PASSWORD") {
+            Some(password) => Credential::Password(password),
+            None => Credential::ApiKey(config.require("APIKEY")?),
+        };
+        Self::authorise(&url, &username, credential).await
+    }
+
+    async fn authorise(
+        url: &str,
+        username: &str,
+        credential: Credential,
+    ) -> Result<Self, AuthenticationError> {
+        let url = Url::parse(&format!("{}/v1/authorize", url.trim_end_matches('/')))
+            .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+        let client = Client::builder()
+            .build()
+            .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+        let authenticator = Self {
+            url,
+            username: username.to_owned(),
+            credential,
+            client,
+            token: RwLock::default(),
+            refresh_lock: Mutex::default(),
+        };
+        let token = authenticator.request_token().await?;
+        *authenticator.token.write().unwrap() = token;
+        Ok(authenticator)
+    }
+
+    async fn request_token(&self) -> Result<Cp4dToken, AuthenticationError> {
+        #[derive(Serialize)]
+        struct Body<'a> {
+            username: &'a str,
+            #[serde(flatten)]
+            credential: &'a Credential,
+        }
+        #[derive(Deserialize)]
+        struct Root {
+            token: String,
+        }
+        let resp = self
+            .client
+            .post(self.url.clone())
+            .json(&Body {
+                username: &self.username,
+                credential: &self.credential,
+            })
+            .send()
+            .await
+            .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+        match resp.status() {
+            StatusCode::OK => {
+                let root: Root = resp
+                    .json()
+                    .await
+                    .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+                let expiration = jwt_expiration(&root.token)?;
+                Ok(Cp4dToken {
+                    token: root.token,
+                    refresh_at: refresh_time(expiration),
+                })
+            }
+            _ => Err(status_error(resp).await),
+        }
+    }
+
+    async fn access_token(&self) -> Result<String, AuthenticationError> {
+        let token = self.token.read().unwrap().clone();
+        if !has_expired(token.refresh_at) {
+            return Ok(token.token);
+        }
+        let _guard = self.refresh_lock.lock().await;
+        // another request may have refreshed the token while we waited for the lock
+        let token = self.token.read().unwrap().clone();
+        if !has_expired(token.refresh_at) {
+            return Ok(token.token);
+        }
+        let token = self.request_token().await?;
+        let access_token = token.token.clone();
+        *self.token.write().unwrap() = token;
+        Ok(access_token)
+    }
+}
+
+impl Authenticator for CloudPakForDataAuthenticator {
+    async fn authorization_header(&self) -> Result<Option<HeaderValue>, AuthenticationError> {
+        let token = self.access_token().await?;
+        Ok(Some(bearer_header(&token)?))
+    }
+}
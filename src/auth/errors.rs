@@ -1,23 +1,115 @@
+use std::{fmt::Display, time::Duration};
+
+use reqwest::StatusCode;
+use serde::Deserialize;
 use thiserror::Error;
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// The details IAM returns with an unsuccessful response
+pub struct IamErrorDetails {
+    /// The IAM error code, such as `BXNIM0415E`
+    pub error_code: String,
+    /// A description of the error
+    pub error_message: String,
+    /// Identifies the request in IBM's logs, should you need to contact support
+    #[serde(skip)]
+    pub request_id: Option<String>,
+}
+
+impl IamErrorDetails {
+    /// Parses the body of an IAM error response
+    pub(crate) fn from_body(body: &str) -> Option<Self> {
+        #[derive(Deserialize)]
+        struct Context {
+            #[serde(rename = "requestId")]
+            request_id: Option<String>,
+        }
+        #[derive(Deserialize)]
+        struct Root {
+            #[serde(flatten)]
+            details: IamErrorDetails,
+            context: Option<Context>,
+        }
+        let root: Root = serde_json::from_str(body).ok()?;
+        Some(IamErrorDetails {
+            request_id: root.context.and_then(|context| context.request_id),
+            ..root.details
+        })
+    }
+}
+
+impl Display for IamErrorDetails {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.error_code, self.error_message)
+    }
+}
+
+/// Describes the error with the details returned by IAM if there are any, or `fallback` otherwise
+fn describe(details: &Option<IamErrorDetails>, fallback: &str) -> String {
+    match details {
+        Some(details) => details.to_string(),
+        None => fallback.to_owned(),
+    }
+}
+
 #[derive(Error, Debug)]
 /// Errors that may be returned in creating an IAM access token for a user or service ID using an API key
 pub enum AuthenticationError {
-    #[error("Parameter validation failed. Required parameters are missing or parameter values are invalid.")]
-    /// Parameter validation failed. Response if required parameters are missing or if parameter values are invalid.
-    ParameterValidationFailed,
-    #[error("The incoming request did not contain a valid authentication information")]
-    /// The incoming request did not contain a valid authentication information.
-    InvalidAPIKey,
-    #[error(
-        "The incoming request is valid but the user is not allowed to perform the requested action."
-    )]
+    #[error("{}", describe(.0, "Parameter validation failed. Required parameters are missing or parameter values are invalid."))]
+    /// Parameter validation failed. Response if required parameters are missing or if parameter
+    /// values are invalid. Holds the details returned by IAM, if any
+    ParameterValidationFailed(Option<IamErrorDetails>),
+    #[error("{}", describe(.0, "The incoming request did not contain a valid authentication information"))]
+    /// The incoming request did not contain a valid authentication information. Holds the details
+    /// returned by IAM, if any
+    InvalidAPIKey(Option<IamErrorDetails>),
+    #[error("{}", describe(.0, "The incoming request is valid but the user is not allowed to perform the requested action."))]
     /// The incoming request is valid but the user is not allowed to perform the requested action.
-    NotAllowed,
+    /// Holds the details returned by IAM, if any
+    NotAllowed(Option<IamErrorDetails>),
+    #[error("{}", match .retry_after {
+        Some(retry_after) => format!("Too many requests, retry after {}s", retry_after.as_secs()),
+        None => "Too many requests".to_owned(),
+    })]
+    /// The token service is throttling requests
+    RateLimited {
+        /// How long the service asked to wait before retrying, taken from the `Retry-After`
+        /// header, if it was sent
+        retry_after: Option<Duration>,
+    },
     #[error("Internal Server error. Response if unexpected error situation happened.")]
     /// Internal Server error. Response if unexpected error situation happened.
     ServerError,
+    #[error("The token service responded with {status}: {body}")]
+    /// The token service responded with a status code that is not otherwise handled, such as a
+    /// `5xx` error
+    UnexpectedStatus {
+        /// The status code of the response
+        status: StatusCode,
+        /// The body of the response
+        body: String,
+    },
     #[error("{0}")]
     /// Network Error
     ConnectionError(String),
+    #[error("There was an error reading the file: {0}")]
+    /// There was an error reading a file containing credentials
+    FileReadError(String),
+    #[error("The token returned by the service could not be parsed")]
+    /// The token returned by the service could not be parsed
+    InvalidToken,
+    #[error("The configuration property {0} is not set")]
+    /// A configuration property required by the authenticator has not been set
+    MissingConfiguration(String),
+    #[error("The configuration properties {0} and {1} cannot be used together")]
+    /// Two configuration properties that cannot be combined have both been set
+    ConflictingConfiguration(String, String),
+    #[error("The configured authentication type {0} does not match the authenticator")]
+    /// The configured `AUTH_TYPE` does not match the authenticator being created
+    UnsupportedAuthType(String),
+    #[error("The credentials cannot be sent in an HTTP header: {0}")]
+    /// A token or credential contains characters, such as a newline, that cannot be sent in an
+    /// HTTP header
+    InvalidHeader(String),
 }
@@ -0,0 +1,174 @@
+use std::{fmt, sync::RwLock};
+
+use reqwest::{header::HeaderValue, Client, StatusCode, Url};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::Mutex;
+
+use super::{
+    bearer_header, config::ServiceConfig, has_expired, refresh_time, status_error,
+    AuthenticationError, Authenticator, Redacted,
+};
+
+#[derive(Clone, Default)]
+struct McspToken {
+    token: String,
+    /// When the token should be renewed, in seconds since the Unix epoch
+    refresh_at: i64,
+}
+
+impl fmt::Debug for McspToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("McspToken")
+            .field("token", &Redacted)
+            .field("refresh_at", &self.refresh_at)
+            .finish()
+    }
+}
+
+/// Authenticates requests against watsonx SaaS deployments that use the Multi-Cloud SaaS Platform
+/// (MCSP) token service instead of IAM. The token is refreshed with the same API key whenever it
+/// has expired
+pub struct McspAuthenticator {
+    url: Url,
+    api_key: String,
+    client: Client,
+    token: RwLock<McspToken>,
+    refresh_lock: Mutex<()>,
+}
+
+impl fmt::Debug for McspAuthenticator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("McspAuthenticator")
+            .field("url", &self.url.as_str())
+            .field("api_key", &Redacted)
+            .field("token", &*self.token.read().unwrap())
+            .finish()
+    }
+}
+
+impl Clone for McspAuthenticator {
+    fn clone(&self) -> Self {
+        Self {
+            url: self.url.clone(),
+            api_key: self.api_key.clone(),
+            client: self.client.clone(),
+            token: RwLock::new(self.token.read().unwrap().clone()),
+            refresh_lock: Mutex::default(),
+        }
+    }
+}
+
+impl McspAuthenticator {
+    /// Get an MCSP token from an API key
+    ///
+    /// # Parameters
+    ///
+    /// * `url` - The URL of the MCSP token service, such as `https://account-iam.platform.saas.ibm.com`. `/siusermgr/api/1.0/apikeys/token` is appended to it
+    /// * `api_key` - The API key for your watsonx account
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::McspAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = McspAuthenticator::new("https://account-iam.platform.saas.ibm.com", "api_key").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn new(
+        url: impl AsRef<str>,
+        api_key: impl AsRef<str>,
+    ) -> Result<Self, AuthenticationError> {
+        let url = Url::parse(&format!(
+            "{}/siusermgr/api/1.0/apikeys/token",
+            url.as_ref().trim_end_matches('/')
+        ))
+        .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+        let client = Client::builder()
+            .build()
+            .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+        let authenticator = Self {
+            url,
+            api_key: api_key.as_ref().to_owned(),
+            client,
+            token: RwLock::default(),
+            refresh_lock: Mutex::default(),
+        };
+        let token = authenticator.request_token().await?;
+        *authenticator.token.write().unwrap() = token;
+        Ok(authenticator)
+    }
+
+    /// Create a [`McspAuthenticator`] from the credentials file or environment variables
+    ///
+    /// # Parameters
+    ///
+    /// * `service` - The name of the service, such as `text_to_speech`. The token service URL and API key are read from `<SERVICE>_AUTH_URL` and `<SERVICE>_APIKEY`
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::McspAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = McspAuthenticator::from_env("text_to_speech").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`McspAuthenticator`]: Self
+    pub async fn from_env(service: impl AsRef<str>) -> Result<Self, AuthenticationError> {
+        let config = ServiceConfig::new(service.as_ref());
+        config.check_auth_type("mcsp")?;
+        Self::new(config.require("AUTH_URL")?, config.require("APIKEY")?).await
+    }
+
+    async fn request_token(&self) -> Result<McspToken, AuthenticationError> {
+        #[derive(Deserialize)]
+        struct Root {
+            token: String,
+            expiration: i64,
+        }
+        let resp = self
+            .client
+            .post(self.url.clone())
+            .json(&json!({ "apikey": self.api_key }))
+            .send()
+            .await
+            .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+        match resp.status() {
+            StatusCode::OK => {
+                let root: Root = resp
+                    .json()
+                    .await
+                    .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+                Ok(McspToken {
+                    token: root.token,
+                    refresh_at: refresh_time(root.expiration),
+                })
+            }
+            _ => Err(status_error(resp).await),
+        }
+    }
+
+    async fn access_token(&self) -> Result<String, AuthenticationError> {
+        let token = self.token.read().unwrap().clone();
+        if !has_expired(token.refresh_at) {
+            return Ok(token.token);
+        }
+        let _guard = self.refresh_lock.lock().await;
+        // another request may have refreshed the token while we waited for the lock
+        let token = self.token.read().unwrap().clone();
+        if !has_expired(token.refresh_at) {
+            return Ok(token.token);
+        }
+        let token = self.request_token().await?;
+        let access_token = token.token.clone();
+        *self.token.write().unwrap() = token;
+        Ok(access_token)
+    }
+}
+
+impl Authenticator for McspAuthenticator {
+    async fn authorization_header(&self) -> Result<Option<HeaderValue>, AuthenticationError> {
+        let token = self.access_token().await?;
+        Ok(Some(bearer_header(&token)?))
+    }
+}
@@ -1,16 +1,185 @@
+mod basic;
+mod bearer;
+pub(crate) mod cache;
+mod config;
+#[cfg(not(target_arch = "wasm32"))]
+mod container;
+mod cp4d;
 mod errors;
+mod mcsp;
+mod noauth;
+mod retry;
+mod vpc;
+#[cfg(feature = "zeroize")]
+mod zeroize;
+use std::{
+    fmt,
+    future::Future,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
 use reqwest::{
-    header::{HeaderValue, CONTENT_TYPE},
-    Body, ClientBuilder, Method, Request, StatusCode, Url,
+    header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE, RETRY_AFTER},
+    Body, Client, ClientBuilder, Method, Request, Response, StatusCode, Url,
 };
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::{NoProxy, Proxy};
 use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::task::JoinHandle;
+use url::form_urlencoded;
+
+use self::config::ServiceConfig;
 
-pub use errors::AuthenticationError;
+pub use basic::BasicAuthenticator;
+pub use bearer::BearerTokenAuthenticator;
+pub use config::{service_url_from_env, CredentialsFile, CREDENTIALS_FILE_NAME};
+#[cfg(not(target_arch = "wasm32"))]
+pub use container::{ContainerAuthenticator, ContainerAuthenticatorBuilder, DEFAULT_CR_TOKEN_PATH};
+pub use cp4d::CloudPakForDataAuthenticator;
+pub use errors::{AuthenticationError, IamErrorDetails};
+pub use mcsp::McspAuthenticator;
+pub use noauth::NoAuthAuthenticator;
+#[cfg_attr(not(any(feature = "tts", feature = "stt")), allow(unused_imports))]
+pub(crate) use retry::sleep;
+pub use retry::RetryPolicy;
+pub use vpc::{VpcInstanceAuthenticator, DEFAULT_METADATA_URL};
+
+/// A source of credentials that Watson service clients use to authenticate their requests.
+/// Service clients such as `TextToSpeech` are generic over this trait, so you can implement it
+/// to fetch credentials from your own secret store
+///
+/// # Example
+/// ``` no_run
+/// # use ibm_watson::auth::{AuthenticationError, Authenticator};
+/// # use reqwest::header::HeaderValue;
+/// struct VaultAuthenticator;
+///
+/// impl Authenticator for VaultAuthenticator {
+///     async fn authorization_header(&self) -> Result<Option<HeaderValue>, AuthenticationError> {
+///         let token = "fetched from vault";
+///         Ok(Some(HeaderValue::from_str(&format!("Bearer {}", token)).unwrap()))
+///     }
+/// }
+/// ```
+pub trait Authenticator {
+    /// The value of the `Authorization` header to send with a request, or [`None`] if requests
+    /// should be sent without one, as with the [`NoAuthAuthenticator`]
+    ///
+    /// [`None`]: std::option::Option::None
+    /// [`NoAuthAuthenticator`]: self::NoAuthAuthenticator
+    #[cfg(not(target_arch = "wasm32"))]
+    fn authorization_header(
+        &self,
+    ) -> impl Future<Output = Result<Option<HeaderValue>, AuthenticationError>> + Send;
+
+    /// The value of the `Authorization` header to send with a request, or [`None`] if requests
+    /// should be sent without one. Requests in the browser cannot be sent across threads, so the
+    /// future does not need to be [`Send`]
+    ///
+    /// [`None`]: std::option::Option::None
+    /// [`Send`]: std::marker::Send
+    #[cfg(target_arch = "wasm32")]
+    fn authorization_header(
+        &self,
+    ) -> impl Future<Output = Result<Option<HeaderValue>, AuthenticationError>>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Identifies a trusted profile to assume when authenticating
+pub enum TrustedProfile {
+    /// The ID of the trusted profile
+    Id(String),
+    /// The CRN of the trusted profile
+    Crn(String),
+}
+
+impl TrustedProfile {
+    /// The form field identifying the profile in an IAM token request
+    pub(crate) fn form_field(&self) -> (&'static str, &str) {
+        match self {
+            TrustedProfile::Id(id) => ("profile_id", id),
+            TrustedProfile::Crn(crn) => ("profile_crn", crn),
+        }
+    }
+}
 
-const AUTH_URL: &str = "https://iam.cloud.ibm.com/identity/token";
-#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// Maps an unsuccessful response from a token service to an [`AuthenticationError`]
+pub(crate) async fn status_error(resp: Response) -> AuthenticationError {
+    let status = resp.status();
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = resp
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_retry_after);
+        return AuthenticationError::RateLimited { retry_after };
+    }
+    let body = resp.text().await.unwrap_or_default();
+    let details = IamErrorDetails::from_body(&body);
+    match status {
+        StatusCode::BAD_REQUEST => AuthenticationError::ParameterValidationFailed(details),
+        StatusCode::UNAUTHORIZED => AuthenticationError::InvalidAPIKey(details),
+        StatusCode::FORBIDDEN => AuthenticationError::NotAllowed(details),
+        status => AuthenticationError::UnexpectedStatus { status, body },
+    }
+}
+
+/// Parses a `Retry-After` header, given either in seconds or as an HTTP date
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    match value.trim().parse::<u64>() {
+        Ok(secs) => Some(Duration::from_secs(secs)),
+        Err(_) => httpdate::parse_http_date(value.trim()).ok().map(|date| {
+            let date = date.duration_since(UNIX_EPOCH).unwrap_or_default();
+            date.saturating_sub(Duration::from_secs(now().max(0) as u64))
+        }),
+    }
+}
+
+/// Stands in for a secret in `Debug` output
+pub(crate) struct Redacted;
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[redacted]")
+    }
+}
+
+pub(crate) fn basic_header(
+    username: &str,
+    password: &str,
+) -> Result<HeaderValue, AuthenticationError> {
+    let credentials = STANDARD.encode(format!("{}:{}", username, password));
+    sensitive_header(format!("Basic {}", credentials))
+}
+
+pub(crate) fn bearer_header(token: &str) -> Result<HeaderValue, AuthenticationError> {
+    sensitive_header(format!("Bearer {}", token))
+}
+
+fn sensitive_header(value: String) -> Result<HeaderValue, AuthenticationError> {
+    let mut value = HeaderValue::try_from(value)
+        .map_err(|e| AuthenticationError::InvalidHeader(e.to_string()))?;
+    value.set_sensitive(true);
+    Ok(value)
+}
+
+/// How long a background refresh waits before trying again after a failure
+pub const BACKGROUND_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The public IAM endpoint, used unless another one has been configured
+pub const DEFAULT_IAM_URL: &str = "https://iam.cloud.ibm.com";
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct TokenResponse {
+/// A token issued by IAM. Its `Debug` output hides the tokens themselves
+pub struct TokenResponse {
     #[serde(rename = "access_token")]
     access_token: String,
     #[serde(rename = "refresh_token")]
@@ -25,41 +194,1037 @@ pub(crate) struct TokenResponse {
     scope: Option<String>,
 }
 
-#[allow(dead_code)]
+impl fmt::Debug for TokenResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenResponse")
+            .field("access_token", &Redacted)
+            .field("refresh_token", &Redacted)
+            .field(
+                "delegated_refresh_token",
+                &self.delegated_refresh_token.as_ref().map(|_| Redacted),
+            )
+            .field("token_type", &self.token_type)
+            .field("expires_in", &self.expires_in)
+            .field("expiration", &self.expiration)
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for TokenResponse {
+    fn drop(&mut self) {
+        zeroize::wipe(&mut self.access_token);
+        zeroize::wipe(&mut self.refresh_token);
+        if let Some(token) = &mut self.delegated_refresh_token {
+            zeroize::wipe(token);
+        }
+    }
+}
+
 impl TokenResponse {
+    /// The access token to send with requests to Watson services
     pub fn access_token(&self) -> &str {
         &self.access_token
     }
 
+    /// The token used to request a new access token once this one expires
     pub fn refresh_token(&self) -> &str {
         &self.refresh_token
     }
 
+    /// The type of the access token, usually `Bearer`
     pub fn token_type(&self) -> &str {
         &self.token_type
     }
 
+    /// How many seconds the access token was valid for when it was issued
     pub fn expires_in(&self) -> i64 {
         self.expires_in
     }
 
+    /// When the access token expires, in seconds since the Unix epoch
     pub fn expiration(&self) -> i64 {
         self.expiration
     }
 
+    /// The scope granted to the access token
     pub fn scope(&self) -> Option<&String> {
         self.scope.as_ref()
     }
 
+    /// The delegated refresh token, if one was requested
     pub fn delegated_refresh_token(&self) -> Option<&String> {
         self.delegated_refresh_token.as_ref()
     }
+
+    /// Whether the token has passed its `expiration` time
+    pub fn is_expired(&self) -> bool {
+        has_expired(self.expiration)
+    }
+
+    /// Whether the token should be renewed before it is next used: once it is within `skew` of
+    /// its expiration, or within [`DEFAULT_EXPIRY_SKEW`] of its lifetime if `skew` is [`None`]
+    ///
+    /// [`DEFAULT_EXPIRY_SKEW`]: self::DEFAULT_EXPIRY_SKEW
+    /// [`None`]: std::option::Option::None
+    pub(crate) fn needs_refresh(&self, skew: Option<Duration>) -> bool {
+        let skew = match skew {
+            Some(skew) => skew.as_secs() as i64,
+            None => (self.expires_in as f64 * DEFAULT_EXPIRY_SKEW) as i64,
+        };
+        has_expired(self.expiration.saturating_sub(skew))
+    }
+}
+
+/// The fraction of a token's lifetime, counted back from its expiration, during which it is
+/// renewed before being used. This leaves room for clock skew between your system and the token
+/// service, and for requests that are still in flight when the token expires
+pub const DEFAULT_EXPIRY_SKEW: f64 = 0.2;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+// `SystemTime::now()` panics in the browser, so ask JavaScript for the time instead
+#[cfg(target_arch = "wasm32")]
+fn now() -> i64 {
+    (js_sys::Date::now() / 1000.0) as i64
+}
+
+/// Whether `expiration`, in seconds since the Unix epoch, is in the past
+pub(crate) fn has_expired(expiration: i64) -> bool {
+    now() >= expiration
+}
+
+/// When a token that expires at `expiration` should be renewed, leaving [`DEFAULT_EXPIRY_SKEW`]
+/// of its remaining lifetime unused
+///
+/// [`DEFAULT_EXPIRY_SKEW`]: self::DEFAULT_EXPIRY_SKEW
+pub(crate) fn refresh_time(expiration: i64) -> i64 {
+    let now = now();
+    let lifetime = expiration.saturating_sub(now).max(0);
+    now + (lifetime as f64 * (1.0 - DEFAULT_EXPIRY_SKEW)) as i64
+}
+
+/// Reads the `exp` claim from the payload of a JWT
+pub(crate) fn jwt_expiration(jwt: &str) -> Result<i64, AuthenticationError> {
+    #[derive(Deserialize)]
+    struct Claims {
+        exp: i64,
+    }
+    let payload = jwt
+        .split('.')
+        .nth(1)
+        .ok_or(AuthenticationError::InvalidToken)?;
+    let payload = URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|_| AuthenticationError::InvalidToken)?;
+    let claims: Claims =
+        serde_json::from_slice(&payload).map_err(|_| AuthenticationError::InvalidToken)?;
+    Ok(claims.exp)
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-/// Holds the IAM Access token generated by IBM Watson
+/// The token endpoint of the IAM service at `url`. `/identity/token` is appended unless `url`
+/// already ends with it
+pub(crate) fn iam_token_url(url: &str) -> Result<Url, AuthenticationError> {
+    let url = url
+        .trim_end_matches('/')
+        .trim_end_matches("/identity/token");
+    Url::parse(&format!("{}/identity/token", url))
+        .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))
+}
+
+/// Requests an access token from the IAM token endpoint at `url` with the grant described by
+/// `form`, sending `extra_headers` along with the request
+pub(crate) async fn request_iam_token(
+    client: &Client,
+    url: &Url,
+    extra_headers: &HeaderMap,
+    form: &[(&str, &str)],
+) -> Result<TokenResponse, AuthenticationError> {
+    let mut req = Request::new(Method::POST, url.clone());
+    let headers = req.headers_mut();
+    headers.extend(extra_headers.clone());
+    let _ = headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_str("application/x-www-form-urlencoded").unwrap(),
+    );
+    let body = req.body_mut();
+    *body = Some(Body::from(
+        form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(form)
+            .finish(),
+    ));
+    let resp = client
+        .execute(req)
+        .await
+        .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+    match resp.status() {
+        StatusCode::OK => {
+            // asynchronously aggregate the chunks of the body
+            let access_token: TokenResponse = resp
+                .json()
+                .await
+                .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+            Ok(access_token)
+        }
+        _ => Err(status_error(resp).await),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Holds the IAM Access token generated by IBM Watson. The token is refreshed with the API key
+/// it was created with whenever it has expired, so a single instance can be used for the lifetime
+/// of your application. Clones are cheap and share the same token, and concurrent requests wait
+/// for a single refresh rather than each requesting a new token. It serialises to the current
+/// token's expiry and scope, with the tokens themselves masked
 pub struct IamAuthenticator {
-    access_token: TokenResponse,
+    inner: Arc<IamInner>,
+}
+
+struct IamInner {
+    api_key: String,
+    url: Url,
+    scope: Option<String>,
+    cache_path: Option<PathBuf>,
+    retry: RetryPolicy,
+    headers: HeaderMap,
+    client_credentials: bool,
+    delegation: Option<Delegation>,
+    trusted_profile: Option<TrustedProfile>,
+    expiry_skew: Option<Duration>,
+    client: Client,
+    access_token: RwLock<TokenResponse>,
+    refresh_lock: Mutex<()>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Delegation {
+    /// Comma separated IDs of the clients allowed to redeem the delegated refresh token
+    receiver_client_ids: String,
+    /// How long the delegated refresh token is valid for, in seconds
+    expiry: Option<u64>,
+}
+
+impl fmt::Debug for IamInner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IamAuthenticator")
+            .field("api_key", &Redacted)
+            .field("url", &self.url.as_str())
+            .field("scope", &self.scope)
+            .field("cache_path", &self.cache_path)
+            .field("retry", &self.retry)
+            .field("headers", &self.headers)
+            .field("client_credentials", &self.client_credentials)
+            .field("delegation", &self.delegation)
+            .field("trusted_profile", &self.trusted_profile)
+            .field("expiry_skew", &self.expiry_skew)
+            .field("access_token", &*self.access_token.read().unwrap())
+            .finish_non_exhaustive()
+    }
+}
+
+pub(crate) fn default_iam_token_url() -> Url {
+    iam_token_url(DEFAULT_IAM_URL).unwrap()
+}
+
+impl Default for IamInner {
+    fn default() -> Self {
+        Self {
+            api_key: String::default(),
+            url: default_iam_token_url(),
+            scope: None,
+            cache_path: None,
+            retry: RetryPolicy::default(),
+            headers: HeaderMap::default(),
+            client_credentials: false,
+            delegation: None,
+            trusted_profile: None,
+            expiry_skew: None,
+            client: Client::default(),
+            access_token: RwLock::default(),
+            refresh_lock: Mutex::default(),
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for IamInner {
+    fn drop(&mut self) {
+        zeroize::wipe(&mut self.api_key);
+    }
+}
+
+impl IamInner {
+    async fn request_token(&self) -> Result<TokenResponse, AuthenticationError> {
+        let refresh_token = self.token_response().refresh_token.clone();
+        // IAM only accepts refresh tokens from clients that authenticate themselves; fall back to
+        // the API key if the refresh token has been revoked or has expired
+        let refreshed = match self.client_credentials && !refresh_token.is_empty() {
+            true => Some(
+                self.grant(vec![
+                    ("grant_type", "refresh_token"),
+                    ("refresh_token", &refresh_token),
+                ])
+                .await,
+            ),
+            false => None,
+        };
+        let mut token = match refreshed {
+            Some(Ok(token)) => token,
+            refreshed => self
+                .grant(vec![
+                    ("grant_type", "urn:ibm:params:oauth:grant-type:apikey"),
+                    ("apikey", &self.api_key),
+                ])
+                .await
+                .map_err(|e| {
+                    // both grants fail when IAM rejects the client ID or secret, so report why
+                    // the first one did too
+                    if let Some(Err(refresh_error)) = refreshed {
+                        log::warn!(
+                            "the IAM refresh token grant failed before the API key grant: \
+                             {refresh_error}"
+                        );
+                    }
+                    e
+                })?,
+        };
+        if let Some(trusted_profile) = &self.trusted_profile {
+            // IAM does not accept a scope or delegation when assuming a profile
+            token = self
+                .send_grant(&[
+                    ("grant_type", "urn:ibm:params:oauth:grant-type:assume"),
+                    ("access_token", &token.access_token),
+                    trusted_profile.form_field(),
+                ])
+                .await?;
+        }
+        if let Some(path) = &self.cache_path {
+            cache::write(path, &token).await;
+        }
+        Ok(token)
+    }
+
+    /// Requests a token with the grant in `form`, along with the scope and delegation configured
+    async fn grant(
+        &self,
+        mut form: Vec<(&str, &str)>,
+    ) -> Result<TokenResponse, AuthenticationError> {
+        if let Some(scope) = &self.scope {
+            form.push(("scope", scope));
+        }
+        let expiry;
+        if let Some(delegation) = &self.delegation {
+            form.push(("response_type", "cloud_iam delegated_refresh_token"));
+            form.push(("receiver_client_ids", &delegation.receiver_client_ids));
+            if let Some(seconds) = delegation.expiry {
+                expiry = seconds.to_string();
+                form.push(("delegated_refresh_token_expiry", &expiry));
+            }
+        }
+        self.send_grant(&form).await
+    }
+
+    /// Requests a token with the grant in `form` alone
+    async fn send_grant(
+        &self,
+        form: &[(&str, &str)],
+    ) -> Result<TokenResponse, AuthenticationError> {
+        self.retry
+            .run(|| request_iam_token(&self.client, &self.url, &self.headers, form))
+            .await
+    }
+
+    fn token_response(&self) -> TokenResponse {
+        self.access_token.read().unwrap().clone()
+    }
+
+    async fn refresh(&self) -> Result<(), AuthenticationError> {
+        let _guard = self.refresh_lock.lock().await;
+        let token = self.request_token().await?;
+        *self.access_token.write().unwrap() = token;
+        Ok(())
+    }
+
+    async fn access_token(&self) -> Result<String, AuthenticationError> {
+        let token = self.token_response();
+        if !token.needs_refresh(self.expiry_skew) {
+            return Ok(token.access_token.clone());
+        }
+        let _guard = self.refresh_lock.lock().await;
+        // another request may have refreshed the token while we waited for the lock
+        let token = self.token_response();
+        if !token.needs_refresh(self.expiry_skew) {
+            return Ok(token.access_token.clone());
+        }
+        let token = self.request_token().await?;
+        let access_token = token.access_token.clone();
+        *self.access_token.write().unwrap() = token;
+        Ok(access_token)
+    }
+
+    /// How long to wait after a successful refresh before refreshing the token `margin` ahead of
+    /// its expiration. The wait is never shorter than a tenth of the token's lifetime, or
+    /// `retry_interval`, so a margin longer than the lifetime does not refresh the token over and
+    /// over
+    #[cfg(not(target_arch = "wasm32"))]
+    fn refresh_delay(&self, margin: Duration, retry_interval: Duration) -> Duration {
+        let token = self.access_token.read().unwrap();
+        let refresh_at = UNIX_EPOCH + Duration::from_secs(token.expiration.max(0) as u64);
+        let delay = refresh_at
+            .checked_sub(margin)
+            .and_then(|refresh_at| refresh_at.duration_since(SystemTime::now()).ok())
+            .unwrap_or_default();
+        let floor = Duration::from_secs(token.expires_in.max(0) as u64 / 10);
+        delay.max(floor).max(retry_interval)
+    }
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+/// Configures an [`IamAuthenticator`] before requesting its first access token
+///
+/// [`IamAuthenticator`]: self::IamAuthenticator
+pub struct IamAuthenticatorBuilder {
+    api_key: Option<String>,
+    url: Option<String>,
+    scope: Option<String>,
+    cache_path: Option<PathBuf>,
+    retry: RetryPolicy,
+    #[cfg(not(target_arch = "wasm32"))]
+    refresh_margin: Option<Duration>,
+    headers: HeaderMap,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<String>,
+    proxy_credentials: Option<(String, String)>,
+    blocking: bool,
+    client: Option<Client>,
+    delegation: Option<Delegation>,
+    trusted_profile: Option<TrustedProfile>,
+    expiry_skew: Option<Duration>,
+}
+
+impl fmt::Debug for IamAuthenticatorBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IamAuthenticatorBuilder")
+            .field("api_key", &self.api_key.as_ref().map(|_| Redacted))
+            .field("url", &self.url)
+            .field("scope", &self.scope)
+            .field("client_id", &self.client_id)
+            .field(
+                "client_secret",
+                &self.client_secret.as_ref().map(|_| Redacted),
+            )
+            .field("trusted_profile", &self.trusted_profile)
+            .finish_non_exhaustive()
+    }
+}
+
+impl IamAuthenticatorBuilder {
+    /// The API key for your Watson service
+    ///
+    /// # Parameters
+    ///
+    /// * `api_key` - The API key to exchange for access tokens
+    pub fn api_key(mut self, api_key: impl AsRef<str>) -> Self {
+        self.api_key = Some(api_key.as_ref().to_owned());
+        self
+    }
+
+    /// Request tokens from another IAM endpoint, such as `https://private.iam.cloud.ibm.com`.
+    /// Defaults to [`DEFAULT_IAM_URL`]
+    ///
+    /// # Parameters
+    ///
+    /// * `url` - The URL of the IAM service. `/identity/token` is appended to it
+    ///
+    /// [`DEFAULT_IAM_URL`]: self::DEFAULT_IAM_URL
+    pub fn url(mut self, url: impl AsRef<str>) -> Self {
+        self.url = Some(url.as_ref().to_owned());
+        self
+    }
+
+    /// Restrict the access tokens to `scope`. IAM only grants scopes that the API key is allowed
+    /// to request; the granted scope is available from [`granted_scope()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `scope` - A space separated list of scopes to request
+    ///
+    /// [`granted_scope()`]: self::IamAuthenticator::granted_scope()
+    pub fn scope(mut self, scope: impl AsRef<str>) -> Self {
+        self.scope = Some(scope.as_ref().to_owned());
+        self
+    }
+
+    /// Cache access tokens in the file at `path`, so that an unexpired token can be reused after
+    /// your process restarts. The file is created readable only by its owner. Use a separate file
+    /// for each API key, as the cached token is reused regardless of the key it was issued for.
+    /// There is no file system on `wasm32`, so nothing is cached there
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The file to cache access tokens in
+    pub fn cache(mut self, path: impl AsRef<Path>) -> Self {
+        self.cache_path = Some(path.as_ref().to_owned());
+        self
+    }
+
+    /// Change how token requests that fail with a connection error or a `5xx` response are
+    /// retried. Defaults to [`RetryPolicy::default()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `retry` - The [`RetryPolicy`] to use for every token request
+    ///
+    /// [`RetryPolicy`]: self::RetryPolicy
+    /// [`RetryPolicy::default()`]: self::RetryPolicy::default()
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Send an extra header with every token request, for example one required by a proxy in
+    /// front of a private IAM endpoint. Can be called multiple times to add several headers
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the header
+    /// * `value` - The value of the header
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # use reqwest::header::{HeaderName, HeaderValue};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::builder()
+    ///     .api_key("api_key")
+    ///     .header(
+    ///         HeaderName::from_static("x-request-id"),
+    ///         HeaderValue::from_static("2b9f7e6c"),
+    ///     )
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Send `headers` with every token request, in addition to any added with [`header()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `headers` - The headers to send
+    ///
+    /// [`header()`]: Self::header()
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    /// The client ID to authenticate token requests with. IAM only issues usable refresh tokens to
+    /// clients that authenticate themselves, so once a client ID and secret are set, expired
+    /// access tokens are renewed with the refresh token instead of the API key
+    ///
+    /// # Parameters
+    ///
+    /// * `client_id` - The ID of your IAM client. Requires a [`client_secret()`]
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::builder()
+    ///     .api_key("api_key")
+    ///     .client_id("bx")
+    ///     .client_secret("bx")
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`client_secret()`]: Self::client_secret()
+    pub fn client_id(mut self, client_id: impl AsRef<str>) -> Self {
+        self.client_id = Some(client_id.as_ref().to_owned());
+        self
+    }
+
+    /// The client secret to authenticate token requests with
+    ///
+    /// # Parameters
+    ///
+    /// * `client_secret` - The secret of your IAM client. Requires a [`client_id()`]
+    ///
+    /// [`client_id()`]: Self::client_id()
+    pub fn client_secret(mut self, client_secret: impl AsRef<str>) -> Self {
+        self.client_secret = Some(client_secret.as_ref().to_owned());
+        self
+    }
+
+    /// Give up on a token request that has not completed within `timeout`, including any time
+    /// spent connecting. By default token requests never time out. This is ignored on `wasm32`,
+    /// where the browser applies its own timeouts
+    ///
+    /// # Parameters
+    ///
+    /// * `timeout` - How long to wait for IAM to respond
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # use std::time::Duration;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::builder()
+    ///     .api_key("api_key")
+    ///     .connect_timeout(Duration::from_secs(5))
+    ///     .timeout(Duration::from_secs(30))
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Give up on connecting to IAM after `timeout`. This is ignored on `wasm32`
+    ///
+    /// # Parameters
+    ///
+    /// * `timeout` - How long to wait for a connection to IAM to be established
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Send token requests through the HTTP(S) proxy at `url`. Without one, the proxy in the
+    /// `HTTPS_PROXY` environment variable is used, if it is set. Hosts listed in `NO_PROXY` are
+    /// always reached directly. This is ignored on `wasm32`, where the browser's proxy settings
+    /// apply
+    ///
+    /// # Parameters
+    ///
+    /// * `url` - The URL of the proxy, such as `http://proxy.example.com:3128`
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::builder()
+    ///     .api_key("api_key")
+    ///     .proxy("http://proxy.example.com:3128")
+    ///     .proxy_credentials("username", "password")
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Authenticate with the proxy using Basic authentication. The credentials apply to the proxy
+    /// set with [`proxy()`] or, failing that, the one in `HTTPS_PROXY`
+    ///
+    /// # Parameters
+    ///
+    /// * `username` - The username to authenticate with the proxy
+    /// * `password` - The password to authenticate with the proxy
+    ///
+    /// [`proxy()`]: Self::proxy()
+    pub fn proxy_credentials(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.proxy_credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Send token requests with `client`, so that they share its connection pool and its proxy
+    /// and TLS configuration. When a client is given, [`timeout()`], [`connect_timeout()`] and
+    /// [`proxy()`] have no effect; configure them on the client instead
+    ///
+    /// # Parameters
+    ///
+    /// * `client` - The [`Client`] to send token requests with
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let client = reqwest::Client::new();
+    /// let auth = IamAuthenticator::builder()
+    ///     .api_key("api_key")
+    ///     .client(client.clone())
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`timeout()`]: Self::timeout()
+    /// [`connect_timeout()`]: Self::connect_timeout()
+    /// [`proxy()`]: Self::proxy()
+    /// [`Client`]: reqwest::Client
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Also request a delegated refresh token, which the clients identified by
+    /// `receiver_client_ids` can redeem for access tokens acting on behalf of this API key. The
+    /// token is available from [`delegated_refresh_token()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `receiver_client_ids` - The IDs of the clients allowed to redeem the token
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # use std::time::Duration;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::builder()
+    ///     .api_key("api_key")
+    ///     .receiver_client_ids(["receiver"])
+    ///     .delegated_refresh_token_expiry(Duration::from_secs(3600))
+    ///     .build()
+    ///     .await?;
+    /// let token = auth.delegated_refresh_token();
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`delegated_refresh_token()`]: self::IamAuthenticator::delegated_refresh_token()
+    pub fn receiver_client_ids<I, S>(mut self, receiver_client_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let ids: Vec<String> = receiver_client_ids
+            .into_iter()
+            .map(|id| id.as_ref().to_owned())
+            .collect();
+        self.delegation
+            .get_or_insert_with(Delegation::default)
+            .receiver_client_ids = ids.join(",");
+        self
+    }
+
+    /// How long delegated refresh tokens remain valid. Only used together with
+    /// [`receiver_client_ids()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `expiry` - The lifetime of the delegated refresh token, with second precision
+    ///
+    /// [`receiver_client_ids()`]: Self::receiver_client_ids()
+    pub fn delegated_refresh_token_expiry(mut self, expiry: Duration) -> Self {
+        self.delegation
+            .get_or_insert_with(Delegation::default)
+            .expiry = Some(expiry.as_secs());
+        self
+    }
+
+    /// Assume a trusted profile instead of acting as the owner of the API key. Each access token
+    /// obtained with the API key is exchanged for one bound to `trusted_profile`. Any [`scope()`]
+    /// only restricts the token obtained with the API key, and a trusted profile cannot be combined
+    /// with [`receiver_client_ids()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `trusted_profile` - The [`TrustedProfile`] to assume
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::{IamAuthenticator, TrustedProfile};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::builder()
+    ///     .api_key("api_key")
+    ///     .trusted_profile(TrustedProfile::Id("Profile-9942d4b6".to_owned()))
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`TrustedProfile`]: self::TrustedProfile
+    /// [`scope()`]: Self::scope()
+    /// [`receiver_client_ids()`]: Self::receiver_client_ids()
+    pub fn trusted_profile(mut self, trusted_profile: TrustedProfile) -> Self {
+        self.trusted_profile = Some(trusted_profile);
+        self
+    }
+
+    /// Renew access tokens once they are within `skew` of expiring, rather than within
+    /// [`DEFAULT_EXPIRY_SKEW`] of their lifetime
+    ///
+    /// # Parameters
+    ///
+    /// * `skew` - How long before a token expires to stop using it
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # use std::time::Duration;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::builder()
+    ///     .api_key("api_key")
+    ///     .expiry_skew(Duration::from_secs(60))
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`DEFAULT_EXPIRY_SKEW`]: self::DEFAULT_EXPIRY_SKEW
+    pub fn expiry_skew(mut self, skew: Duration) -> Self {
+        self.expiry_skew = Some(skew);
+        self
+    }
+
+    /// Refresh the access token in a background task, `margin` before it expires, so requests
+    /// never wait for a new token. See [`spawn_refresh()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `margin` - How long before the token expires to refresh it
+    ///
+    /// [`spawn_refresh()`]: self::IamAuthenticator::spawn_refresh()
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn refresh_in_background(mut self, margin: Duration) -> Self {
+        self.refresh_margin = Some(margin);
+        self
+    }
+
+    /// Request the first access token and create the [`IamAuthenticator`]
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::builder()
+    ///     .api_key("api_key")
+    ///     .url("https://private.iam.cloud.ibm.com")
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`IamAuthenticator`]: self::IamAuthenticator
+    pub async fn build(mut self) -> Result<IamAuthenticator, AuthenticationError> {
+        let api_key = self
+            .api_key
+            .take()
+            .ok_or_else(|| AuthenticationError::MissingConfiguration("api_key".to_owned()))?;
+        #[cfg(not(target_arch = "wasm32"))]
+        let refresh_margin = self.refresh_margin;
+        let mut inner = self.into_inner(api_key)?;
+        let cached = match &inner.cache_path {
+            Some(path) => cache::read(path).await,
+            None => None,
+        };
+        let access_token = match cached {
+            Some(token) => token,
+            None => inner.request_token().await?,
+        };
+        inner.access_token = RwLock::new(access_token);
+        let authenticator = IamAuthenticator {
+            inner: Arc::new(inner),
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(margin) = refresh_margin {
+            authenticator.spawn_refresh(margin);
+        }
+        Ok(authenticator)
+    }
+
+    /// Exchange a delegated refresh token, issued to another client with this client listed in
+    /// [`receiver_client_ids()`], for a [`TokenResponse`]. This client must authenticate itself
+    /// with a [`client_id()`] and [`client_secret()`]; no API key is needed
+    ///
+    /// # Parameters
+    ///
+    /// * `delegated_refresh_token` - The delegated refresh token to redeem
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let token = IamAuthenticator::builder()
+    ///     .client_id("receiver")
+    ///     .client_secret("secret")
+    ///     .redeem_delegated_refresh_token("delegated_refresh_token")
+    ///     .await?;
+    /// println!("{}", token.access_token());
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`receiver_client_ids()`]: Self::receiver_client_ids()
+    /// [`TokenResponse`]: self::TokenResponse
+    /// [`client_id()`]: Self::client_id()
+    /// [`client_secret()`]: Self::client_secret()
+    pub async fn redeem_delegated_refresh_token(
+        self,
+        delegated_refresh_token: impl AsRef<str>,
+    ) -> Result<TokenResponse, AuthenticationError> {
+        let inner = self.into_inner(String::new())?;
+        if !inner.client_credentials {
+            return Err(AuthenticationError::MissingConfiguration(
+                "client_id".to_owned(),
+            ));
+        }
+        inner
+            .grant(vec![
+                (
+                    "grant_type",
+                    "urn:ibm:params:oauth:grant-type:delegated-refresh-token",
+                ),
+                ("refresh_token", delegated_refresh_token.as_ref()),
+            ])
+            .await
+    }
+
+    fn into_inner(self, api_key: String) -> Result<IamInner, AuthenticationError> {
+        if self.delegation.is_some() && self.trusted_profile.is_some() {
+            return Err(AuthenticationError::ConflictingConfiguration(
+                "trusted_profile".to_owned(),
+                "receiver_client_ids".to_owned(),
+            ));
+        }
+        let client = self.build_client()?;
+        let url = match self.url {
+            Some(url) => iam_token_url(&url)?,
+            None => default_iam_token_url(),
+        };
+        let mut inner = IamInner {
+            api_key,
+            url,
+            scope: self.scope,
+            cache_path: self.cache_path,
+            retry: self.retry,
+            headers: self.headers,
+            client_credentials: false,
+            delegation: self.delegation,
+            trusted_profile: self.trusted_profile,
+            expiry_skew: self.expiry_skew,
+            client,
+            access_token: RwLock::default(),
+            refresh_lock: Mutex::default(),
+        };
+        match (self.client_id, self.client_secret) {
+            (Some(client_id), Some(client_secret)) => {
+                inner
+                    .headers
+                    .insert(AUTHORIZATION, basic_header(&client_id, &client_secret)?);
+                inner.client_credentials = true;
+            }
+            (Some(_), None) => {
+                return Err(AuthenticationError::MissingConfiguration(
+                    "client_secret".to_owned(),
+                ))
+            }
+            (None, Some(_)) => {
+                return Err(AuthenticationError::MissingConfiguration(
+                    "client_id".to_owned(),
+                ))
+            }
+            (None, None) => {}
+        }
+        Ok(inner)
+    }
+
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+    /// Request the first access token and create the [`IamAuthenticator`] without an async
+    /// runtime, blocking the current thread until IAM has responded. This must not be called
+    /// from within an async runtime. [`refresh_in_background()`] is ignored, as the background
+    /// task would need a runtime to run on
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::builder()
+    ///     .api_key("api_key")
+    ///     .build_blocking()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`IamAuthenticator`]: self::IamAuthenticator
+    /// [`refresh_in_background()`]: Self::refresh_in_background()
+    pub fn build_blocking(mut self) -> Result<IamAuthenticator, AuthenticationError> {
+        self.refresh_margin = None;
+        // connections opened on the temporary runtime cannot be reused once it has shut down
+        self.blocking = true;
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?
+            .block_on(self.build())
+    }
+
+    /// The proxy configured with [`proxy()`], or the one in `HTTPS_PROXY`
+    ///
+    /// [`proxy()`]: Self::proxy()
+    #[cfg(not(target_arch = "wasm32"))]
+    fn build_proxy(&self) -> Result<Option<Proxy>, AuthenticationError> {
+        let proxy = match &self.proxy {
+            Some(url) => Proxy::all(url.as_str()),
+            None => match ["HTTPS_PROXY", "https_proxy"]
+                .iter()
+                .find_map(|name| std::env::var(name).ok().filter(|url| !url.is_empty()))
+            {
+                Some(url) => Proxy::https(url.as_str()),
+                None => return Ok(None),
+            },
+        };
+        let mut proxy = proxy
+            .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?
+            .no_proxy(NoProxy::from_env());
+        if let Some((username, password)) = &self.proxy_credentials {
+            proxy = proxy.basic_auth(username, password);
+        }
+        Ok(Some(proxy))
+    }
+
+    fn build_client(&self) -> Result<Client, AuthenticationError> {
+        if let Some(client) = &self.client {
+            return Ok(client.clone());
+        }
+        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+        let mut client = ClientBuilder::new();
+        // the browser manages connections and timeouts itself
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if self.blocking {
+                client = client.pool_max_idle_per_host(0);
+            }
+            if let Some(timeout) = self.timeout {
+                client = client.timeout(timeout);
+            }
+            if let Some(timeout) = self.connect_timeout {
+                client = client.connect_timeout(timeout);
+            }
+            if let Some(proxy) = self.build_proxy()? {
+                client = client.proxy(proxy);
+            }
+        }
+        client
+            .build()
+            .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))
+    }
 }
 
 impl IamAuthenticator {
@@ -78,37 +1243,283 @@ impl IamAuthenticator {
     /// # }
     /// ```
     pub async fn new(api_key: impl AsRef<str>) -> Result<Self, AuthenticationError> {
-        let url = Url::parse(AUTH_URL).unwrap();
-        let mut req = Request::new(Method::POST, url);
-        let headers = req.headers_mut();
-        let _ = headers.insert(
-            CONTENT_TYPE,
-            HeaderValue::from_str("application/x-www-form-urlencoded").unwrap(),
-        );
-        let body = req.body_mut();
-        *body = Some(Body::from(format!(
-            "grant_type=urn:ibm:params:oauth:grant-type:apikey&apikey={}",
-            api_key.as_ref()
-        )));
-        let client = ClientBuilder::new();
-
-        let client = client.build().unwrap();
-        let resp = client
-            .execute(req)
-            .await
-            .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
-        match resp.status() {
-            StatusCode::OK => {
-                // asynchronously aggregate the chunks of the body
-                let access_token: TokenResponse = resp.json().await.unwrap();
-                Ok(Self { access_token })
+        Self::builder().api_key(api_key).build().await
+    }
+
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+    /// Get an IAM Access token from an API key without an async runtime, blocking the current
+    /// thread until IAM has responded. This must not be called from within an async runtime
+    ///
+    /// # Parameters
+    ///
+    /// * `api_key` - The API key for your Watson service
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::new_blocking("api_key")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_blocking(api_key: impl AsRef<str>) -> Result<Self, AuthenticationError> {
+        Self::builder().api_key(api_key).build_blocking()
+    }
+
+    /// Configure an [`IamAuthenticator`], for example to use a private IAM endpoint
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::builder()
+    ///     .api_key("api_key")
+    ///     .url("https://private.iam.cloud.ibm.com")
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`IamAuthenticator`]: Self
+    pub fn builder() -> IamAuthenticatorBuilder {
+        IamAuthenticatorBuilder::default()
+    }
+
+    /// Get an IAM Access token from an API key, reusing the token cached at `path` if it has not
+    /// expired. New tokens are written to `path`
+    ///
+    /// # Parameters
+    ///
+    /// * `api_key` - The API key for your Watson service
+    /// * `path` - The file to cache access tokens in
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::with_cache("api_key", "/tmp/watson-token.json").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_cache(
+        api_key: impl AsRef<str>,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, AuthenticationError> {
+        Self::builder().api_key(api_key).cache(path).build().await
+    }
+
+    /// Create an [`IamAuthenticator`] from the credentials file or environment variables
+    ///
+    /// # Parameters
+    ///
+    /// * `service` - The name of the service, such as `text_to_speech`. The API key is read from `<SERVICE>_APIKEY`, or `<SERVICE>_IAM_APIKEY`, the IAM endpoint from `<SERVICE>_AUTH_URL`, the client credentials from `<SERVICE>_CLIENT_ID` and `<SERVICE>_CLIENT_SECRET`, and the trusted profile to assume from `<SERVICE>_IAM_PROFILE_CRN` or `<SERVICE>_IAM_PROFILE_ID` if they are set
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::from_env("text_to_speech").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`IamAuthenticator`]: Self
+    pub async fn from_env(service: impl AsRef<str>) -> Result<Self, AuthenticationError> {
+        let config = ServiceConfig::new(service.as_ref());
+        config.check_auth_type("iam")?;
+        let api_key = match config.get("IAM_APIKEY") {
+            Some(api_key) => api_key,
+            None => config.require("APIKEY")?,
+        };
+        let mut builder = Self::builder().api_key(api_key);
+        if let Some(url) = config.get("AUTH_URL") {
+            builder = builder.url(url);
+        }
+        if let Some(client_id) = config.get("CLIENT_ID") {
+            builder = builder.client_id(client_id);
+        }
+        if let Some(client_secret) = config.get("CLIENT_SECRET") {
+            builder = builder.client_secret(client_secret);
+        }
+        if let Some(trusted_profile) = config.trusted_profile() {
+            builder = builder.trusted_profile(trusted_profile);
+        }
+        builder.build().await
+    }
+
+    /// The scope granted to the current access token, if IAM returned one
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::builder()
+    ///     .api_key("api_key")
+    ///     .scope("ibm openid")
+    ///     .build()
+    ///     .await?;
+    /// println!("{:?}", auth.granted_scope());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn granted_scope(&self) -> Option<String> {
+        self.inner.access_token.read().unwrap().scope.clone()
+    }
+
+    /// The delegated refresh token issued along with the current access token, if one was
+    /// requested with [`receiver_client_ids()`]
+    ///
+    /// [`receiver_client_ids()`]: self::IamAuthenticatorBuilder::receiver_client_ids()
+    pub fn delegated_refresh_token(&self) -> Option<String> {
+        self.inner
+            .access_token
+            .read()
+            .unwrap()
+            .delegated_refresh_token
+            .clone()
+    }
+
+    /// When the current access token expires
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::new("api_key").await?;
+    /// println!("{:?}", auth.expires_at());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn expires_at(&self) -> SystemTime {
+        let expiration = self.inner.access_token.read().unwrap().expiration;
+        UNIX_EPOCH + Duration::from_secs(expiration.max(0) as u64)
+    }
+
+    /// Whether the current access token has expired. An expired token is renewed automatically
+    /// before the next request is sent
+    pub fn is_expired(&self) -> bool {
+        self.inner.access_token.read().unwrap().is_expired()
+    }
+
+    /// Request a new access token from IAM now, regardless of whether the current one has
+    /// expired. This is useful if a service has rejected the current token with a `401`
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::new("api_key").await?;
+    /// auth.refresh().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn refresh(&self) -> Result<(), AuthenticationError> {
+        self.inner.refresh().await
+    }
+
+    /// Spawn a task on the current Tokio runtime that refreshes the access token `margin` before
+    /// it expires. The task stops once every clone of this authenticator has been dropped, or
+    /// when the returned handle is aborted. If a refresh fails it is tried again every
+    /// [`BACKGROUND_RETRY_INTERVAL`] until it succeeds or the token expires
+    ///
+    /// # Parameters
+    ///
+    /// * `margin` - How long before the token expires to refresh it
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::IamAuthenticator;
+    /// # use std::time::Duration;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::new("api_key").await?;
+    /// let handle = auth.spawn_refresh(Duration::from_secs(300));
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`BACKGROUND_RETRY_INTERVAL`]: self::BACKGROUND_RETRY_INTERVAL
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spawn_refresh(&self, margin: Duration) -> JoinHandle<()> {
+        self.spawn_refresh_every(margin, BACKGROUND_RETRY_INTERVAL)
+    }
+
+    /// [`spawn_refresh()`], retrying a failed refresh every `retry_interval`
+    ///
+    /// [`spawn_refresh()`]: Self::spawn_refresh()
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn spawn_refresh_every(
+        &self,
+        margin: Duration,
+        retry_interval: Duration,
+    ) -> JoinHandle<()> {
+        let inner = Arc::downgrade(&self.inner);
+        tokio::spawn(async move {
+            loop {
+                let delay = match inner.upgrade() {
+                    Some(inner) => inner.refresh_delay(margin, retry_interval),
+                    None => return,
+                };
+                tokio::time::sleep(delay).await;
+                // the old token is due for a refresh by now, so keep trying until it expires, when
+                // the next request will ask for a new one itself
+                loop {
+                    let Some(inner) = inner.upgrade() else {
+                        return;
+                    };
+                    if inner.refresh().await.is_ok() || inner.token_response().is_expired() {
+                        break;
+                    }
+                    drop(inner);
+                    tokio::time::sleep(retry_interval).await;
+                }
             }
-            StatusCode::BAD_REQUEST => Err(AuthenticationError::ParameterValidationFailed),
-            _ => unreachable!(),
+        })
+    }
+
+    /// Returns a valid access token, requesting a new one from IAM if the current one has expired
+    pub(crate) async fn access_token(&self) -> Result<String, AuthenticationError> {
+        self.inner.access_token().await
+    }
+}
+
+/// What an [`IamAuthenticator`] serialises to: the current token, with the tokens masked
+#[derive(Serialize)]
+struct RedactedIam<'a> {
+    access_token: RedactedToken<'a>,
+}
+
+#[derive(Serialize)]
+struct RedactedToken<'a> {
+    access_token: &'static str,
+    refresh_token: &'static str,
+    delegated_refresh_token: Option<&'static str>,
+    token_type: &'a str,
+    expires_in: i64,
+    expiration: i64,
+    scope: Option<&'a str>,
+}
+
+impl Serialize for IamAuthenticator {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        const REDACTED: &str = "[redacted]";
+        let token = self.inner.access_token.read().unwrap();
+        RedactedIam {
+            access_token: RedactedToken {
+                access_token: REDACTED,
+                refresh_token: REDACTED,
+                delegated_refresh_token: token.delegated_refresh_token.as_ref().map(|_| REDACTED),
+                token_type: &token.token_type,
+                expires_in: token.expires_in,
+                expiration: token.expiration,
+                scope: token.scope.as_deref(),
+            },
         }
+        .serialize(serializer)
     }
+}
 
-    pub(crate) fn token_response(&self) -> &TokenResponse {
-        &self.access_token
+impl Authenticator for IamAuthenticator {
+    async fn authorization_header(&self) -> Result<Option<HeaderValue>, AuthenticationError> {
+        let token = self.access_token().await?;
+        Ok(Some(bearer_header(&token)?))
     }
 }
@@ -0,0 +1,20 @@
+use reqwest::header::HeaderValue;
+
+use super::{AuthenticationError, Authenticator};
+
+#[derive(Debug, Clone, Copy, Default)]
+/// Sends requests without any credentials. Use this with local Watson containers or mock servers
+/// that do not require authentication
+///
+/// # Example
+/// ``` no_run
+/// # use ibm_watson::auth::NoAuthAuthenticator;
+/// let auth = NoAuthAuthenticator;
+/// ```
+pub struct NoAuthAuthenticator;
+
+impl Authenticator for NoAuthAuthenticator {
+    async fn authorization_header(&self) -> Result<Option<HeaderValue>, AuthenticationError> {
+        Ok(None)
+    }
+}
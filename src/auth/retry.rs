@@ -0,0 +1,163 @@
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+use super::AuthenticationError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How often, and how long to wait before, a token request is retried after a connection error,
+/// a `5xx` response or a `429 Too Many Requests` response. The wait doubles after every attempt, up
+/// to a maximum, and is randomised so that many clients do not retry in lockstep. A rate limited
+/// request waits as long as the service asks instead, unless that is longer than the maximum
+/// wait, in which case [`AuthenticationError::RateLimited`] is returned. Service clients, such as
+/// `TextToSpeech`, can use a policy to retry requests the service throttled in the same way
+///
+/// [`AuthenticationError::RateLimited`]: super::AuthenticationError::RateLimited
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// Retry up to 3 times, waiting 500ms before the first retry and at most 8s between retries
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Retry up to `max_retries` times with the default backoff
+    ///
+    /// # Parameters
+    ///
+    /// * `max_retries` - The number of times to retry a failed request
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::RetryPolicy;
+    /// # use std::time::Duration;
+    /// let retry = RetryPolicy::new(5).backoff(Duration::from_secs(1), Duration::from_secs(30));
+    /// ```
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Self::default()
+        }
+    }
+
+    /// Fail immediately instead of retrying
+    pub fn none() -> Self {
+        Self::new(0)
+    }
+
+    /// Change how long to wait between retries
+    ///
+    /// # Parameters
+    ///
+    /// * `initial` - The wait before the first retry
+    /// * `max` - The longest wait between two retries
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// The number of times a failed request is retried
+    #[cfg_attr(not(any(feature = "tts", feature = "stt")), allow(dead_code))]
+    pub(crate) fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// How long to wait before retrying a request that was throttled, or [`None`] if the service
+    /// asked for a longer wait than the maximum
+    pub(crate) fn throttled_wait(
+        &self,
+        retry_after: Option<Duration>,
+        attempt: u32,
+    ) -> Option<Duration> {
+        match retry_after {
+            Some(retry_after) => (retry_after <= self.max_backoff).then_some(retry_after),
+            None => Some(self.delay(attempt)),
+        }
+    }
+
+    /// Runs `request`, retrying it while it fails with a transient error
+    pub(crate) async fn run<T, F, Fut>(&self, mut request: F) -> Result<T, AuthenticationError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, AuthenticationError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match request().await {
+                Err(e) if attempt < self.max_retries => match self.wait(&e, attempt) {
+                    Some(wait) => {
+                        sleep(wait).await;
+                        attempt += 1;
+                    }
+                    None => return Err(e),
+                },
+                result => return result,
+            }
+        }
+    }
+
+    /// How long to wait before retrying after `error`, or [`None`] if it should not be retried
+    fn wait(&self, error: &AuthenticationError, attempt: u32) -> Option<Duration> {
+        match error {
+            AuthenticationError::RateLimited { retry_after } => {
+                self.throttled_wait(*retry_after, attempt)
+            }
+            error if is_transient(error) => Some(self.delay(attempt)),
+            _ => None,
+        }
+    }
+
+    /// A random wait of up to `initial_backoff * 2^attempt`, capped at `max_backoff`
+    fn delay(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff);
+        let jitter = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        backoff.mul_f64(0.5 + jitter / 2.0)
+    }
+}
+
+fn is_transient(error: &AuthenticationError) -> bool {
+    match error {
+        AuthenticationError::ConnectionError(_) | AuthenticationError::ServerError => true,
+        AuthenticationError::UnexpectedStatus { status, .. } => status.is_server_error(),
+        _ => false,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+/// There is no Tokio timer in the browser, so wait on a JavaScript `setTimeout()` instead
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn sleep(duration: Duration) {
+    use wasm_bindgen::{JsCast, JsValue};
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        let set_timeout = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("setTimeout"))
+            .map(|f| f.unchecked_into::<js_sys::Function>());
+        let millis = JsValue::from_f64(duration.as_millis() as f64);
+        let scheduled = set_timeout
+            .and_then(|set_timeout| set_timeout.call2(&JsValue::UNDEFINED, &resolve, &millis));
+        if scheduled.is_err() {
+            let _ = resolve.call0(&JsValue::UNDEFINED);
+        }
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
@@ -0,0 +1,242 @@
+use std::{fmt, sync::RwLock};
+
+use reqwest::{header::HeaderValue, Client, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::sync::Mutex;
+
+use super::{
+    bearer_header, config::ServiceConfig, has_expired, jwt_expiration, refresh_time, status_error,
+    AuthenticationError, Authenticator, Redacted, TrustedProfile,
+};
+
+/// The VPC instance metadata service, used unless another URL has been given
+pub const DEFAULT_METADATA_URL: &str = "http://169.254.169.254";
+const METADATA_VERSION: &str = "2022-03-01";
+
+#[derive(Clone, Default)]
+struct VpcToken {
+    access_token: String,
+    /// When the token should be renewed, in seconds since the Unix epoch
+    refresh_at: i64,
+}
+
+impl fmt::Debug for VpcToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VpcToken")
+            .field("access_token", &Redacted)
+            .field("refresh_at", &self.refresh_at)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+/// Authenticates from an IBM Cloud VPC virtual server instance without an API key. An instance
+/// identity token is fetched from the VPC metadata service and exchanged for an IAM access token,
+/// which is renewed the same way once it has expired
+pub struct VpcInstanceAuthenticator {
+    url: Url,
+    trusted_profile: Option<TrustedProfile>,
+    client: Client,
+    token: RwLock<VpcToken>,
+    refresh_lock: Mutex<()>,
+}
+
+impl Clone for VpcInstanceAuthenticator {
+    fn clone(&self) -> Self {
+        Self {
+            url: self.url.clone(),
+            trusted_profile: self.trusted_profile.clone(),
+            client: self.client.clone(),
+            token: RwLock::new(self.token.read().unwrap().clone()),
+            refresh_lock: Mutex::default(),
+        }
+    }
+}
+
+impl VpcInstanceAuthenticator {
+    /// Get an IAM access token for this virtual server instance
+    ///
+    /// # Parameters
+    ///
+    /// * `trusted_profile` - The [`TrustedProfile`] to assume. If [`None`], the default trusted profile linked to the instance is used
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::VpcInstanceAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = VpcInstanceAuthenticator::new(None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`TrustedProfile`]: super::TrustedProfile
+    /// [`None`]: std::option::Option::None
+    pub async fn new(trusted_profile: Option<TrustedProfile>) -> Result<Self, AuthenticationError> {
+        Self::with_url(DEFAULT_METADATA_URL, trusted_profile).await
+    }
+
+    /// Get an IAM access token for this virtual server instance from the metadata service at
+    /// `url`, rather than [`DEFAULT_METADATA_URL`]
+    ///
+    /// # Parameters
+    ///
+    /// * `url` - The URL of the VPC instance metadata service. `/instance_identity/v1` is appended to it
+    /// * `trusted_profile` - The [`TrustedProfile`] to assume. If [`None`], the default trusted profile linked to the instance is used
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::VpcInstanceAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = VpcInstanceAuthenticator::with_url("http://api.metadata.cloud.ibm.com", None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`DEFAULT_METADATA_URL`]: self::DEFAULT_METADATA_URL
+    /// [`TrustedProfile`]: super::TrustedProfile
+    /// [`None`]: std::option::Option::None
+    pub async fn with_url(
+        url: impl AsRef<str>,
+        trusted_profile: Option<TrustedProfile>,
+    ) -> Result<Self, AuthenticationError> {
+        let url = Url::parse(&format!(
+            "{}/instance_identity/v1/",
+            url.as_ref().trim_end_matches('/')
+        ))
+        .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+        let client = Client::builder()
+            .build()
+            .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+        let authenticator = Self {
+            url,
+            trusted_profile,
+            client,
+            token: RwLock::default(),
+            refresh_lock: Mutex::default(),
+        };
+        let token = authenticator.request_token().await?;
+        *authenticator.token.write().unwrap() = token;
+        Ok(authenticator)
+    }
+
+    /// Create a [`VpcInstanceAuthenticator`] from the credentials file or environment variables
+    ///
+    /// # Parameters
+    ///
+    /// * `service` - The name of the service, such as `text_to_speech`. The trusted profile is read from `<SERVICE>_IAM_PROFILE_CRN` or `<SERVICE>_IAM_PROFILE_ID`. If neither is set, the default trusted profile linked to the instance is used. The metadata service URL is read from `<SERVICE>_AUTH_URL`, or is [`DEFAULT_METADATA_URL`] if it is not set
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::auth::VpcInstanceAuthenticator;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = VpcInstanceAuthenticator::from_env("text_to_speech").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`VpcInstanceAuthenticator`]: Self
+    /// [`DEFAULT_METADATA_URL`]: self::DEFAULT_METADATA_URL
+    pub async fn from_env(service: impl AsRef<str>) -> Result<Self, AuthenticationError> {
+        let config = ServiceConfig::new(service.as_ref());
+        config.check_auth_type("vpc")?;
+        let url = config
+            .get("AUTH_URL")
+            .unwrap_or_else(|| DEFAULT_METADATA_URL.to_owned());
+        Self::with_url(url, config.trusted_profile()).await
+    }
+
+    async fn request_token(&self) -> Result<VpcToken, AuthenticationError> {
+        #[derive(Deserialize)]
+        struct Root {
+            access_token: String,
+        }
+        #[derive(Serialize)]
+        struct ProfileBody<'a> {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            id: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            crn: Option<&'a str>,
+        }
+        let resp = self
+            .client
+            .put(self.metadata_url("token"))
+            .header("Metadata-Flavor", "ibm")
+            .json(&json!({ "expires_in": 300 }))
+            .send()
+            .await
+            .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+        let identity_token: Root = match resp.status() {
+            StatusCode::OK => resp
+                .json()
+                .await
+                .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?,
+            _ => return Err(status_error(resp).await),
+        };
+
+        let profile = match &self.trusted_profile {
+            Some(TrustedProfile::Id(id)) => Some(ProfileBody {
+                id: Some(id),
+                crn: None,
+            }),
+            Some(TrustedProfile::Crn(crn)) => Some(ProfileBody {
+                id: None,
+                crn: Some(crn),
+            }),
+            None => None,
+        };
+        let mut req = self
+            .client
+            .post(self.metadata_url("iam_token"))
+            .bearer_auth(identity_token.access_token);
+        if let Some(profile) = profile {
+            req = req.json(&json!({ "trusted_profile": profile }));
+        }
+        let resp = req
+            .send()
+            .await
+            .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+        match resp.status() {
+            StatusCode::OK => {
+                let root: Root = resp
+                    .json()
+                    .await
+                    .map_err(|e| AuthenticationError::ConnectionError(e.to_string()))?;
+                let expiration = jwt_expiration(&root.access_token)?;
+                Ok(VpcToken {
+                    access_token: root.access_token,
+                    refresh_at: refresh_time(expiration),
+                })
+            }
+            _ => Err(status_error(resp).await),
+        }
+    }
+
+    fn metadata_url(&self, path: &str) -> Url {
+        let mut url = self.url.join(path).unwrap_or_else(|_| self.url.clone());
+        url.query_pairs_mut()
+            .append_pair("version", METADATA_VERSION);
+        url
+    }
+
+    async fn access_token(&self) -> Result<String, AuthenticationError> {
+        let token = self.token.read().unwrap().clone();
+        if !has_expired(token.refresh_at) {
+            return Ok(token.access_token);
+        }
+        let _guard = self.refresh_lock.lock().await;
+        // another request may have refreshed the token while we waited for the lock
+        let token = self.token.read().unwrap().clone();
+        if !has_expired(token.refresh_at) {
+            return Ok(token.access_token);
+        }
+        let token = self.request_token().await?;
+        let access_token = token.access_token.clone();
+        *self.token.write().unwrap() = token;
+        Ok(access_token)
+    }
+}
+
+impl Authenticator for VpcInstanceAuthenticator {
+    async fn authorization_header(&self) -> Result<Option<HeaderValue>, AuthenticationError> {
+        let token = self.access_token().await?;
+        Ok(Some(bearer_header(&token)?))
+    }
+}
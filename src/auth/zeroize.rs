@@ -0,0 +1,15 @@
+use std::sync::atomic::{compiler_fence, Ordering};
+
+/// Overwrites the whole allocation of `value` with zeros before clearing it. Volatile writes are
+/// used so the compiler cannot optimise away stores to memory that is about to be freed
+pub(crate) fn wipe(value: &mut String) {
+    // SAFETY: only zeros are written, which is valid UTF-8, and the string is cleared afterwards
+    let bytes = unsafe { value.as_mut_vec() };
+    let ptr = bytes.as_mut_ptr();
+    for i in 0..bytes.capacity() {
+        // SAFETY: `i` is within the allocation, which is owned by `bytes`
+        unsafe { ptr.add(i).write_volatile(0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+    bytes.clear();
+}
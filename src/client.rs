@@ -0,0 +1,308 @@
+use std::{ops::Deref, sync::Arc};
+
+use reqwest::{
+    header::{HeaderName, HeaderValue, AUTHORIZATION, RETRY_AFTER},
+    Client, ClientBuilder, Request, Response, StatusCode, Url,
+};
+use thiserror::Error;
+
+use crate::auth::{parse_retry_after, sleep, Authenticator, RetryPolicy};
+#[cfg(all(feature = "compression", not(target_arch = "wasm32")))]
+use crate::compression;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{
+    transport::Transport,
+    websocket::{Handshake, WebSocket},
+};
+
+/// Asks IBM not to use the data sent with a request to improve its services
+pub(crate) const LEARNING_OPT_OUT: HeaderName =
+    HeaderName::from_static("x-watson-learning-opt-out");
+
+/// Associates the data sent with a request with a customer ID, so it can later be deleted
+pub(crate) const WATSON_METADATA: HeaderName = HeaderName::from_static("x-watson-metadata");
+
+/// The `X-Watson-Metadata` header that associates a request with `customer_id`
+pub(crate) fn metadata_header(customer_id: &str) -> Result<HeaderValue, String> {
+    HeaderValue::from_str(&format!("customer_id={customer_id}"))
+        .map_err(|_| format!("the customer ID {customer_id:?} is not a valid header value"))
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+/// Errors that may be returned when building a service client, such as a `TextToSpeech` or
+/// `SpeechToText` client
+pub enum BuildError {
+    /// Neither a service URL nor a region was given
+    #[error("A service URL or region is required")]
+    MissingServiceUrl,
+    /// The service URL could not be parsed
+    #[error("The service URL {0} is invalid")]
+    InvalidServiceUrl(String),
+    /// The HTTP client could not be created, such as when no TLS backend could be initialised
+    #[error("The HTTP client could not be created: {0}")]
+    HttpClient(String),
+}
+
+/// The authenticator of a service client, either borrowed or shared by the client and its clones
+pub(crate) enum AuthenticatorRef<'a, A> {
+    Borrowed(&'a A),
+    Shared(Arc<A>),
+}
+
+impl<A> Clone for AuthenticatorRef<'_, A> {
+    fn clone(&self) -> Self {
+        match self {
+            AuthenticatorRef::Borrowed(authenticator) => AuthenticatorRef::Borrowed(authenticator),
+            AuthenticatorRef::Shared(authenticator) => {
+                AuthenticatorRef::Shared(Arc::clone(authenticator))
+            }
+        }
+    }
+}
+
+impl<A> Deref for AuthenticatorRef<'_, A> {
+    type Target = A;
+
+    fn deref(&self) -> &A {
+        match self {
+            AuthenticatorRef::Borrowed(authenticator) => authenticator,
+            AuthenticatorRef::Shared(authenticator) => authenticator,
+        }
+    }
+}
+
+/// The state every service client shares: where requests go, how they are authorised, which
+/// headers they carry and how they are sent and retried
+pub(crate) struct ServiceClient<'a, A> {
+    service_url: Url,
+    pub(crate) learning_opt_out: bool,
+    pub(crate) customer_id: Option<String>,
+    pub(crate) retry: RetryPolicy,
+    authenticator: AuthenticatorRef<'a, A>,
+    client: Client,
+    /// Sends WebSocket handshakes, which must be sent over HTTP/1.1
+    #[cfg(not(target_arch = "wasm32"))]
+    upgrade_client: Client,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) transport: Option<Arc<dyn Transport>>,
+}
+
+impl<A> Clone for ServiceClient<'_, A> {
+    fn clone(&self) -> Self {
+        Self {
+            service_url: self.service_url.clone(),
+            learning_opt_out: self.learning_opt_out,
+            customer_id: self.customer_id.clone(),
+            retry: self.retry,
+            authenticator: self.authenticator.clone(),
+            client: self.client.clone(),
+            #[cfg(not(target_arch = "wasm32"))]
+            upgrade_client: self.upgrade_client.clone(),
+            #[cfg(not(target_arch = "wasm32"))]
+            transport: self.transport.clone(),
+        }
+    }
+}
+
+impl<'a, A: Authenticator> ServiceClient<'a, A> {
+    /// A client for the instance at `service_url` with none of the optional headers set
+    pub(crate) fn new(
+        authenticator: AuthenticatorRef<'a, A>,
+        service_url: &str,
+    ) -> Result<Self, BuildError> {
+        let service_url = Url::parse(service_url)
+            .map_err(|_| BuildError::InvalidServiceUrl(service_url.to_owned()))?;
+        let client = client_builder();
+
+        #[cfg(all(feature = "http2", not(target_arch = "wasm32")))]
+        let client = client.http2_prior_knowledge();
+
+        let client = client
+            .build()
+            .map_err(|e| BuildError::HttpClient(e.to_string()))?;
+        #[cfg(not(target_arch = "wasm32"))]
+        let upgrade_client = client_builder()
+            .http1_only()
+            .build()
+            .map_err(|e| BuildError::HttpClient(e.to_string()))?;
+
+        Ok(Self {
+            service_url,
+            learning_opt_out: false,
+            customer_id: None,
+            retry: RetryPolicy::none(),
+            authenticator,
+            client,
+            #[cfg(not(target_arch = "wasm32"))]
+            upgrade_client,
+            #[cfg(not(target_arch = "wasm32"))]
+            transport: None,
+        })
+    }
+
+    /// The HTTP client requests are sent with, for building them
+    #[cfg_attr(not(feature = "tts"), allow(dead_code))]
+    pub(crate) fn http(&self) -> &Client {
+        &self.client
+    }
+
+    /// The URL of `path` on the service. Unlike [`Url::set_path()`], this keeps the path of the
+    /// service URL, such as `/instances/{instance_id}`
+    ///
+    /// [`Url::set_path()`]: reqwest::Url::set_path()
+    pub(crate) fn endpoint(&self, path: &str) -> Url {
+        let mut url = self.service_url.clone();
+        let base = url.path().trim_end_matches('/').to_owned();
+        url.set_path(&format!("{base}/{}", path.trim_start_matches('/')));
+        url
+    }
+
+    /// Adds the `Authorization` header provided by the authenticator to `req`, along with the
+    /// headers set on this client that `req` does not already have
+    pub(crate) async fn authorise(&self, req: &mut Request) -> Result<(), String> {
+        if self.learning_opt_out {
+            req.headers_mut()
+                .entry(LEARNING_OPT_OUT)
+                .or_insert(HeaderValue::from_static("true"));
+        }
+        if let Some(customer_id) = &self.customer_id {
+            if !req.headers().contains_key(WATSON_METADATA) {
+                let value = metadata_header(customer_id)?;
+                req.headers_mut().insert(WATSON_METADATA, value);
+            }
+        }
+        if let Some(auth_value) = self
+            .authenticator
+            .authorization_header()
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            req.headers_mut().insert(AUTHORIZATION, auth_value);
+        }
+        Ok(())
+    }
+
+    /// Sends `req` with the `Authorization` header provided by the authenticator
+    pub(crate) async fn execute(&self, mut req: Request) -> Result<Response, String> {
+        self.authorise(&mut req).await?;
+        #[cfg(all(feature = "http2", not(target_arch = "wasm32")))]
+        {
+            *req.version_mut() = reqwest::Version::HTTP_2;
+        }
+        #[cfg(all(feature = "compression", not(target_arch = "wasm32")))]
+        compression::accept_encoding(&mut req);
+        #[cfg(not(target_arch = "wasm32"))]
+        let response = match &self.transport {
+            Some(transport) => transport.send(req).await.map_err(|e| e.to_string())?,
+            None => self.client.execute(req).await.map_err(|e| e.to_string())?,
+        };
+        #[cfg(target_arch = "wasm32")]
+        let response = self.client.execute(req).await.map_err(|e| e.to_string())?;
+        #[cfg(all(feature = "compression", not(target_arch = "wasm32")))]
+        let response = compression::decode(response).await?;
+        Ok(response)
+    }
+
+    /// Opens a WebSocket with the handshake `req`, authorised like any other request. The
+    /// handshake is sent through the client's transport, if it has one, or over HTTP/1.1 with the
+    /// same settings as other requests
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) async fn connect_websocket(&self, mut req: Request) -> Result<Handshake, String> {
+        self.authorise(&mut req).await?;
+        let key = WebSocket::handshake(&mut req)?;
+        let response = match &self.transport {
+            Some(transport) => transport.send(req).await.map_err(|e| e.to_string())?,
+            None => self
+                .upgrade_client
+                .execute(req)
+                .await
+                .map_err(|e| e.to_string())?,
+        };
+        WebSocket::accept(response, &key).await
+    }
+
+    /// Sends `req` like [`execute()`], retrying it as the client's [`RetryPolicy`] allows while
+    /// the service throttles it with a `429 Too Many Requests` or `503 Service Unavailable`
+    /// response. Requests whose body cannot be cloned, such as streamed audio, are sent only
+    /// once, and the last response is returned once no more retries are allowed
+    ///
+    /// [`execute()`]: Self::execute()
+    /// [`RetryPolicy`]: crate::auth::RetryPolicy
+    pub(crate) async fn execute_retrying(&self, mut req: Request) -> Result<Response, String> {
+        let mut attempt = 0;
+        loop {
+            let retry = (attempt < self.retry.max_retries())
+                .then(|| req.try_clone())
+                .flatten();
+            let response = self.execute(req).await?;
+            let throttled = matches!(
+                response.status(),
+                StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+            );
+            let Some(retry) = retry.filter(|_| throttled) else {
+                return Ok(response);
+            };
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after);
+            let Some(wait) = self.retry.throttled_wait(retry_after, attempt) else {
+                return Ok(response);
+            };
+            sleep(wait).await;
+            attempt += 1;
+            req = retry;
+        }
+    }
+}
+
+/// A builder with the settings shared by every HTTP client of a service
+fn client_builder() -> ClientBuilder {
+    let client = ClientBuilder::new();
+
+    #[cfg(all(feature = "http2", not(target_arch = "wasm32")))]
+    let client = ClientBuilder::use_rustls_tls(client);
+
+    client
+}
+
+/// The settings every service client's builder shares
+pub(crate) struct ServiceBuilder<'a, A> {
+    authenticator: AuthenticatorRef<'a, A>,
+    pub(crate) service_url: Option<String>,
+    pub(crate) learning_opt_out: bool,
+    pub(crate) customer_id: Option<String>,
+    pub(crate) retry: RetryPolicy,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) transport: Option<Arc<dyn Transport>>,
+}
+
+impl<'a, A: Authenticator> ServiceBuilder<'a, A> {
+    /// A builder with nothing but its authenticator set
+    pub(crate) fn new(authenticator: AuthenticatorRef<'a, A>) -> Self {
+        Self {
+            authenticator,
+            service_url: None,
+            learning_opt_out: false,
+            customer_id: None,
+            retry: RetryPolicy::none(),
+            #[cfg(not(target_arch = "wasm32"))]
+            transport: None,
+        }
+    }
+
+    /// Creates the client, checking that its service URL is valid
+    pub(crate) fn build(self) -> Result<ServiceClient<'a, A>, BuildError> {
+        let service_url = self.service_url.ok_or(BuildError::MissingServiceUrl)?;
+        let mut service = ServiceClient::new(self.authenticator, &service_url)?;
+        service.learning_opt_out = self.learning_opt_out;
+        service.customer_id = self.customer_id;
+        service.retry = self.retry;
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            service.transport = self.transport;
+        }
+        Ok(service)
+    }
+}
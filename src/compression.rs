@@ -0,0 +1,362 @@
+use reqwest::{
+    header::{HeaderValue, ACCEPT, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH},
+    Request, Response, ResponseBuilderExt,
+};
+
+/// The encodings offered to services whose responses are JSON
+const ENCODINGS: &str = "gzip, deflate";
+
+/// Offers [`ENCODINGS`] for the response to `req`, unless it asks for something other than JSON,
+/// such as audio, which is already compressed and is best streamed as it arrives
+pub(crate) fn accept_encoding(req: &mut Request) {
+    let wants_json = match req.headers().get(ACCEPT) {
+        Some(accept) => accept.to_str().is_ok_and(|accept| accept.contains("json")),
+        None => true,
+    };
+    if wants_json {
+        req.headers_mut()
+            .entry(ACCEPT_ENCODING)
+            .or_insert(HeaderValue::from_static(ENCODINGS));
+    }
+}
+
+/// Decompresses the body of `response` if the service sent it with a `Content-Encoding` of
+/// `gzip` or `deflate`, returning any other response as it is
+pub(crate) async fn decode(response: Response) -> Result<Response, String> {
+    let encoding = match response.headers().get(CONTENT_ENCODING) {
+        Some(encoding) => encoding
+            .to_str()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase(),
+        None => return Ok(response),
+    };
+    let decompress = match encoding.as_str() {
+        "gzip" | "x-gzip" => gunzip,
+        "deflate" => unzlib,
+        _ => return Ok(response),
+    };
+    let mut builder = http::Response::builder()
+        .status(response.status())
+        .version(response.version())
+        .url(response.url().clone());
+    for (name, value) in response.headers() {
+        if name != CONTENT_ENCODING && name != CONTENT_LENGTH {
+            builder = builder.header(name, value);
+        }
+    }
+    let body = response.bytes().await.map_err(|e| e.to_string())?;
+    let body = decompress(&body)?;
+    builder
+        .body(body)
+        .map(Response::from)
+        .map_err(|e| e.to_string())
+}
+
+/// Decompresses a gzip member, as described in RFC 1952
+pub(crate) fn gunzip(data: &[u8]) -> Result<Vec<u8>, String> {
+    const FHCRC: u8 = 0x02;
+    const FEXTRA: u8 = 0x04;
+    const FNAME: u8 = 0x08;
+    const FCOMMENT: u8 = 0x10;
+    let truncated = || "the gzip body is truncated".to_owned();
+    if data.len() < 18 || data[..3] != [0x1f, 0x8b, 8] {
+        return Err("the body is not gzip compressed".to_owned());
+    }
+    let flags = data[3];
+    let mut pos = 10;
+    if flags & FEXTRA != 0 {
+        let len = data.get(pos..pos + 2).ok_or_else(truncated)?;
+        pos += 2 + u16::from_le_bytes([len[0], len[1]]) as usize;
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            let end = data
+                .get(pos..)
+                .and_then(|rest| rest.iter().position(|&b| b == 0))
+                .ok_or_else(truncated)?;
+            pos += end + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        pos += 2;
+    }
+    let mut bits = Bits::new(data.get(pos..).ok_or_else(truncated)?);
+    let out = inflate(&mut bits)?;
+    let trailer = bits.rest().get(..8).ok_or_else(truncated)?;
+    let crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    let size = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+    if crc != crc32(&out) || size != out.len() as u32 {
+        return Err("the gzip body is corrupt".to_owned());
+    }
+    Ok(out)
+}
+
+/// Decompresses a `deflate` body, which should be a zlib stream as described in RFC 1950, but
+/// which some servers send as raw DEFLATE data
+pub(crate) fn unzlib(data: &[u8]) -> Result<Vec<u8>, String> {
+    let zlib = data.len() >= 2
+        && data[0] & 0x0f == 8
+        && data[1] & 0x20 == 0
+        && u16::from_be_bytes([data[0], data[1]]) % 31 == 0;
+    if !zlib {
+        return inflate(&mut Bits::new(data));
+    }
+    let mut bits = Bits::new(&data[2..]);
+    let out = inflate(&mut bits)?;
+    let trailer = bits
+        .rest()
+        .get(..4)
+        .ok_or_else(|| "the deflate body is truncated".to_owned())?;
+    if u32::from_be_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]) != adler32(&out) {
+        return Err("the deflate body is corrupt".to_owned());
+    }
+    Ok(out)
+}
+
+/// Reads the bits of a DEFLATE stream, least significant first
+struct Bits<'a> {
+    data: &'a [u8],
+    pos: usize,
+    buffer: u32,
+    count: u32,
+}
+
+impl<'a> Bits<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            buffer: 0,
+            count: 0,
+        }
+    }
+
+    /// Reads the next `n` bits, where `n` is at most 16
+    fn take(&mut self, n: u32) -> Result<u32, String> {
+        while self.count < n {
+            let byte = *self
+                .data
+                .get(self.pos)
+                .ok_or_else(|| "the compressed body ends early".to_owned())?;
+            self.pos += 1;
+            self.buffer |= (byte as u32) << self.count;
+            self.count += 8;
+        }
+        let value = self.buffer & ((1 << n) - 1);
+        self.buffer >>= n;
+        self.count -= n;
+        Ok(value)
+    }
+
+    /// Skips to the next byte boundary, returning the bytes from there on
+    fn rest(&mut self) -> &'a [u8] {
+        self.buffer = 0;
+        self.count = 0;
+        &self.data[self.pos..]
+    }
+}
+
+/// A canonical Huffman code, stored as the number of codes of each length and the symbols in
+/// order of their codes
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    /// The code in which symbol `i` has a code `lengths[i]` bits long, or none if it is zero
+    fn new(lengths: &[u8]) -> Result<Self, String> {
+        let mut counts = [0u16; 16];
+        for &len in lengths {
+            counts[len as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut left = 1i32;
+        for &count in &counts[1..] {
+            left = (left << 1) - count as i32;
+            if left < 0 {
+                return Err("the compressed body has an invalid Huffman code".to_owned());
+            }
+        }
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                symbols[offsets[len as usize] as usize] = symbol as u16;
+                offsets[len as usize] += 1;
+            }
+        }
+        Ok(Self { counts, symbols })
+    }
+
+    fn decode(&self, bits: &mut Bits) -> Result<u16, String> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for &count in &self.counts[1..] {
+            code |= bits.take(1)? as i32;
+            let count = count as i32;
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err("the compressed body has an invalid Huffman code".to_owned())
+    }
+}
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+/// The order in which the lengths of the code length code are sent
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Decompresses the DEFLATE blocks read from `bits`, as described in RFC 1951
+fn inflate(bits: &mut Bits) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    loop {
+        let last = bits.take(1)? == 1;
+        match bits.take(2)? {
+            0 => {
+                let rest = bits.rest();
+                let header = rest
+                    .get(..4)
+                    .ok_or_else(|| "the compressed body ends early".to_owned())?;
+                let len = u16::from_le_bytes([header[0], header[1]]);
+                if len != !u16::from_le_bytes([header[2], header[3]]) {
+                    return Err("the compressed body has a corrupt stored block".to_owned());
+                }
+                let block = rest
+                    .get(4..4 + len as usize)
+                    .ok_or_else(|| "the compressed body ends early".to_owned())?;
+                out.extend_from_slice(block);
+                bits.pos += 4 + len as usize;
+            }
+            1 => {
+                let mut lengths = [0u8; 288];
+                lengths[..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..].fill(8);
+                let literals = Huffman::new(&lengths)?;
+                let distances = Huffman::new(&[5; 30])?;
+                inflate_block(bits, &literals, &distances, &mut out)?;
+            }
+            2 => {
+                let (literals, distances) = read_codes(bits)?;
+                inflate_block(bits, &literals, &distances, &mut out)?;
+            }
+            _ => return Err("the compressed body has an invalid block type".to_owned()),
+        }
+        if last {
+            return Ok(out);
+        }
+    }
+}
+
+/// Reads the literal/length and distance codes of a block compressed with dynamic codes
+fn read_codes(bits: &mut Bits) -> Result<(Huffman, Huffman), String> {
+    let literal_count = bits.take(5)? as usize + 257;
+    let distance_count = bits.take(5)? as usize + 1;
+    let code_length_count = bits.take(4)? as usize + 4;
+    let mut code_lengths = [0u8; 19];
+    for &symbol in &CODE_LENGTH_ORDER[..code_length_count] {
+        code_lengths[symbol] = bits.take(3)? as u8;
+    }
+    let code_lengths = Huffman::new(&code_lengths)?;
+    let mut lengths = Vec::with_capacity(literal_count + distance_count);
+    while lengths.len() < literal_count + distance_count {
+        let (len, repeat) = match code_lengths.decode(bits)? {
+            len @ 0..=15 => (len as u8, 1),
+            16 => {
+                let previous = *lengths
+                    .last()
+                    .ok_or_else(|| "the compressed body repeats a missing length".to_owned())?;
+                (previous, 3 + bits.take(2)?)
+            }
+            17 => (0, 3 + bits.take(3)?),
+            _ => (0, 11 + bits.take(7)?),
+        };
+        lengths.extend(std::iter::repeat(len).take(repeat as usize));
+    }
+    if lengths.len() > literal_count + distance_count || lengths[256] == 0 {
+        return Err("the compressed body has invalid code lengths".to_owned());
+    }
+    let (literals, distances) = lengths.split_at(literal_count);
+    Ok((Huffman::new(literals)?, Huffman::new(distances)?))
+}
+
+/// Decompresses the symbols of a block until its end-of-block symbol
+fn inflate_block(
+    bits: &mut Bits,
+    literals: &Huffman,
+    distances: &Huffman,
+    out: &mut Vec<u8>,
+) -> Result<(), String> {
+    let invalid = || "the compressed body refers to data it does not have".to_owned();
+    loop {
+        let symbol = literals.decode(bits)? as usize;
+        if symbol < 256 {
+            out.push(symbol as u8);
+            continue;
+        }
+        if symbol == 256 {
+            return Ok(());
+        }
+        let index = symbol - 257;
+        let (&base, &extra) = LENGTH_BASE
+            .get(index)
+            .zip(LENGTH_EXTRA.get(index))
+            .ok_or_else(invalid)?;
+        let len = base as usize + bits.take(extra as u32)? as usize;
+        let index = distances.decode(bits)? as usize;
+        let (&base, &extra) = DISTANCE_BASE
+            .get(index)
+            .zip(DISTANCE_EXTRA.get(index))
+            .ok_or_else(invalid)?;
+        let distance = base as usize + bits.take(extra as u32)? as usize;
+        let start = out.len().checked_sub(distance).ok_or_else(invalid)?;
+        // the copy may overlap the bytes it produces, so it is made one byte at a time
+        for i in start..start + len {
+            out.push(out[i]);
+        }
+    }
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
@@ -0,0 +1,65 @@
+use std::fmt::Display;
+
+use reqwest::{header::HeaderName, Response, StatusCode};
+use serde::Deserialize;
+
+/// Identifies a request in IBM's logs
+const TRANSACTION_ID: HeaderName = HeaderName::from_static("x-global-transaction-id");
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+/// The details a Watson service returns with an unsuccessful response. The error variants for
+/// unsuccessful responses, such as `BadRequest400`, hold these details when the service sends them
+pub struct ServiceErrorDetails {
+    /// The HTTP status code of the response
+    pub code: u16,
+    /// A description of the error
+    pub error: String,
+    /// A description of the status code, such as `Bad Request`
+    pub code_description: Option<String>,
+    /// Identifies the request in IBM's logs, taken from the `X-Global-Transaction-Id` header,
+    /// should you need to contact support
+    #[serde(skip)]
+    pub transaction_id: Option<String>,
+}
+
+#[cfg_attr(not(any(feature = "tts", feature = "stt")), allow(dead_code))]
+impl ServiceErrorDetails {
+    /// Reads the status of an unsuccessful response, along with the details in its body, if
+    /// there are any
+    pub(crate) async fn read(response: Response) -> (StatusCode, Option<Self>) {
+        let status = response.status();
+        let transaction_id = response
+            .headers()
+            .get(TRANSACTION_ID)
+            .and_then(|value| value.to_str().ok())
+            .map(ToOwned::to_owned);
+        let body = response.text().await.unwrap_or_default();
+        let details = serde_json::from_str::<Self>(&body)
+            .ok()
+            .map(|details| Self {
+                transaction_id,
+                ..details
+            });
+        (status, details)
+    }
+}
+
+impl Display for ServiceErrorDetails {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code, self.error)?;
+        if let Some(transaction_id) = &self.transaction_id {
+            write!(f, " (transaction ID {transaction_id})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Describes the error with the details returned by the service if there are any, or `fallback`
+/// otherwise
+#[cfg_attr(not(any(feature = "tts", feature = "stt")), allow(dead_code))]
+pub(crate) fn describe(details: &Option<ServiceErrorDetails>, fallback: &str) -> String {
+    match details {
+        Some(details) => details.to_string(),
+        None => fallback.to_owned(),
+    }
+}
@@ -23,8 +23,24 @@
 //! services you intend to use.
 //!
 //! * `full` - Enables all the features listed below
+//! * `blocking` - Enables creating authenticators without an async runtime
+//! * `zeroize` - Wipes API keys and tokens from memory when they are dropped
 //! * `http2` - Enables support of `HTTP/2.0` requests
 //! * `tts` - Enables interacting with the Text To Speech API
+//! * `stt` - Enables interacting with the Speech To Text API
+//! * `pcm` - Enables decoding uncompressed synthesised audio, such as `audio/l16`, to PCM samples and WAV
+//! * `compression` - Asks services to compress JSON responses with gzip or deflate
+//! * `playback` - Enables playing synthesised audio through an audio player such as `ffplay`
+//!
+//! # WebAssembly
+//!
+//! The crate can be built for `wasm32-unknown-unknown`, in which case requests are sent with the
+//! browser's `fetch`. There is no file system or Tokio runtime in the browser, so token caching,
+//! background refreshes, the `blocking` constructors, the [`ContainerAuthenticator`], uploading
+//! audio files and `playback` are unavailable, and `http2` has no effect. The browser negotiates compression
+//! itself, so `compression` has no effect either.
+//!
+//! [`ContainerAuthenticator`]: crate::auth::ContainerAuthenticator
 //!
 //! # Example
 //!
@@ -36,17 +52,22 @@
 //! ```
 //!
 //! ``` no_run
+//!# #[cfg(feature = "tts")]
 //!# use std::{fs::File, io::Write};
+//!# #[cfg(feature = "tts")]
 //!# use ibm_watson::{
 //!#     auth::IamAuthenticator,
 //!#     tts::TextToSpeech
 //!# };
+//! # #[cfg(not(feature = "tts"))]
+//! # fn main() {}
+//! # #[cfg(feature = "tts")]
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box <dyn std::error::Error>> {
 //! // Get your IAM access token with the API Key of the particular service you want to use
 //! let auth = IamAuthenticator::new("my_api_key").await?;
 //! // Create a new Text To Speech instance that you will use to interact with the API
-//! let tts = TextToSpeech::new(&auth, "tts-endpoint");
+//! let tts = TextToSpeech::new(&auth, "tts-endpoint")?;
 //! // Call whatever method you would like to use from it
 //! let synth = tts.synthesise("Hello world", None, None).await?;
 //! let mut file = File::create("file.ogg")?;
@@ -58,16 +79,19 @@
 //! To perform synthesis with a custom voice and in a different audio format:
 //!
 //! ``` no_run
-//!# use std::{fs::File, io::Write};
+//!# #[cfg(feature = "tts")]
 //!# use ibm_watson::{
 //!#     auth::IamAuthenticator,
 //!#     tts::{synthesis::AudioFormat, TextToSpeech,
 //!#     voices::WatsonVoice},
 //!# };
+//! # #[cfg(not(feature = "tts"))]
+//! # fn main() {}
+//! # #[cfg(feature = "tts")]
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box <dyn std::error::Error>> {
 //! # let auth = IamAuthenticator::new("my_api_key").await?;
-//! # let mut tts = TextToSpeech::new(&auth, "tts-endpoint");
+//! # let mut tts = TextToSpeech::new(&auth, "tts-endpoint")?;
 //! // This sets Kate (United Kingdom) to be the default voice for your requests
 //! tts.set_voice(WatsonVoice::EnGbKateV3);
 //! // set the format to MP3 with a sample rate of 44100khz
@@ -116,27 +140,53 @@
    // unreachable_pub,
    // rustdoc::missing_doc_code_examples
 )]
-
 /// Retrieve an IAM access token to use for authentication with your IBM Watson services
 ///
 /// # Example
 ///
 /// ``` no_run
-/// # use ibm_watson::{
-/// #     auth::IamAuthenticator,
-/// #     tts::{voices::WatsonVoice, TextToSpeech},
-/// # };
+/// # use ibm_watson::auth::IamAuthenticator;
 /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
 /// let auth = IamAuthenticator::new("api_key").await?;
 /// # Ok(())
 /// # }
 /// ```
 pub mod auth;
+/// Details of the errors reported by Watson services
+pub mod errors;
+/// Wait for resources that are processed in the background
+#[cfg(any(feature = "tts", feature = "stt"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "tts", feature = "stt"))))]
+pub mod polling;
+/// The IBM Cloud regions that host Watson services
+pub mod region;
+/// Interact with the IBM Watson™ Speech to Text service
+#[cfg(feature = "stt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stt")))]
+#[path = "speech-to-text/mod.rs"]
+pub mod stt;
+/// Send the requests of service clients, or answer them in tests
+#[cfg(all(any(feature = "tts", feature = "stt"), not(target_arch = "wasm32")))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "tts", feature = "stt"))))]
+pub mod transport;
 /// Interact with the IBM Watson™ Text to Speech service
 #[cfg(feature = "tts")]
 #[cfg_attr(docsrs, doc(cfg(feature = "tts")))]
 #[path = "text-to-speech/mod.rs"]
 pub mod tts;
 
+#[cfg(any(feature = "tts", feature = "stt"))]
+mod client;
+
+#[cfg(all(
+    feature = "compression",
+    any(feature = "tts", feature = "stt"),
+    not(target_arch = "wasm32")
+))]
+mod compression;
+
+#[cfg(all(any(feature = "tts", feature = "stt"), not(target_arch = "wasm32")))]
+mod websocket;
+
 #[cfg(test)]
 mod tests;
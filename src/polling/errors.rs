@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+/// Waiting for a resource failed, either because checking it failed with the request's error `E`
+/// or because the resource never became available
+pub enum WaitError<E> {
+    /// Checking the resource failed
+    #[error("{0}")]
+    Request(E),
+    /// The service could not process the resource
+    #[error("The service could not process the resource: {0}")]
+    Failed(String),
+    /// The resource was still being processed when the timeout ran out
+    #[error("The resource was still being processed after {0:?}")]
+    TimedOut(Duration),
+}
@@ -0,0 +1,93 @@
+use std::{future::Future, time::Duration};
+
+use crate::auth::sleep;
+
+use self::errors::WaitError;
+
+/// Errors that may be returned while waiting for a resource
+pub mod errors;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How often, and for how long, to check a resource that the service processes in the
+/// background, such as a custom prompt or a recognition job, before giving up on it
+///
+/// # Example
+/// ``` no_run
+/// # use ibm_watson::polling::Polling;
+/// # use std::time::Duration;
+/// let polling = Polling::new(Duration::from_secs(1), Duration::from_secs(60));
+/// ```
+pub struct Polling {
+    interval: Duration,
+    timeout: Duration,
+}
+
+impl Default for Polling {
+    /// Check every 5s, for up to 5 minutes
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5),
+            timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+impl Polling {
+    /// Check every `interval` until `timeout` has passed since the first check
+    ///
+    /// # Parameters
+    ///
+    /// * `interval` - The wait between two checks, of at least a millisecond
+    /// * `timeout` - The longest time to wait in total, including the time spent checking, before
+    ///   returning [`TimedOut`]
+    ///
+    /// [`TimedOut`]: self::errors::WaitError::TimedOut
+    pub fn new(interval: Duration, timeout: Duration) -> Self {
+        Self { interval, timeout }
+    }
+
+    /// Runs `check` until it is no longer pending, waiting between two checks
+    pub(crate) async fn until<T, E, F, Fut>(&self, mut check: F) -> Result<T, WaitError<E>>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<Status<T>, E>>,
+    {
+        let interval = self.interval.max(Duration::from_millis(1));
+        // the time spent in each check counts towards the timeout, as well as the waits
+        let elapsed = stopwatch();
+        loop {
+            match check().await.map_err(WaitError::Request)? {
+                Status::Ready(ready) => return Ok(ready),
+                Status::Failed(reason) => return Err(WaitError::Failed(reason)),
+                Status::Pending => match self.timeout.checked_sub(elapsed()) {
+                    Some(left) if !left.is_zero() => sleep(interval.min(left)).await,
+                    _ => return Err(WaitError::TimedOut(self.timeout)),
+                },
+            }
+        }
+    }
+}
+
+/// A clock started now, which returns the time elapsed since then each time it is called
+#[cfg(not(target_arch = "wasm32"))]
+fn stopwatch() -> impl Fn() -> Duration {
+    let start = tokio::time::Instant::now();
+    move || start.elapsed()
+}
+
+/// There is no Tokio clock in the browser, so measure with JavaScript's `Date.now()`
+#[cfg(target_arch = "wasm32")]
+fn stopwatch() -> impl Fn() -> Duration {
+    let start = js_sys::Date::now();
+    move || Duration::from_secs_f64((js_sys::Date::now() - start).max(0.0) / 1000.0)
+}
+
+/// What a single check found a resource to be
+pub(crate) enum Status<T> {
+    /// The resource can be used
+    Ready(T),
+    /// The service is still processing the resource
+    Pending,
+    /// The service could not process the resource, for the given reason
+    Failed(String),
+}
@@ -0,0 +1,42 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// The IBM Cloud regions that host Watson services
+pub enum Region {
+    /// Dallas, `us-south`
+    Dallas,
+    /// Frankfurt, `eu-de`
+    Frankfurt,
+    /// Sydney, `au-syd`
+    Sydney,
+    /// Tokyo, `jp-tok`
+    Tokyo,
+    /// London, `eu-gb`
+    London,
+    /// Washington DC, `us-east`
+    Washington,
+    /// Seoul, `kr-seo`
+    Seoul,
+}
+
+impl Region {
+    /// The ID of the region, as it appears in service URLs
+    pub fn id(&self) -> &'static str {
+        match self {
+            Region::Dallas => "us-south",
+            Region::Frankfurt => "eu-de",
+            Region::Sydney => "au-syd",
+            Region::Tokyo => "jp-tok",
+            Region::London => "eu-gb",
+            Region::Washington => "us-east",
+            Region::Seoul => "kr-seo",
+        }
+    }
+
+    /// The URL of an instance of `service`, such as `text-to-speech`, in this region
+    #[cfg_attr(not(any(feature = "tts", feature = "stt")), allow(dead_code))]
+    pub(crate) fn service_url(&self, service: &str, instance_id: &str) -> String {
+        format!(
+            "https://api.{}.{service}.watson.cloud.ibm.com/instances/{instance_id}",
+            self.id()
+        )
+    }
+}
@@ -0,0 +1,580 @@
+use reqwest::{Method, Request, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::{auth::Authenticator, errors::ServiceErrorDetails, stt::SpeechToText};
+
+use super::{
+    errors::{
+        CreateAcousticModelError, DeleteAcousticModelError, GetAcousticModelError,
+        ListAcousticModelsError, ResetAcousticModelError, TrainAcousticModelError,
+        UpgradeAcousticModelError,
+    },
+    TrainingStatus,
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// A custom acoustic model, which adapts a base model to the acoustic characteristics of some
+/// audio, such as its speakers, their accents or the noise of their surroundings
+pub struct AcousticModel {
+    /// The customisation ID (GUID) of the custom model
+    #[serde(rename = "customization_id")]
+    pub customisation_id: String,
+    /// The name of the custom model
+    pub name: String,
+    /// The language identifier of the custom model (for example, en-US)
+    pub language: String,
+    /// The name of the model the custom model is based on
+    pub base_model_name: String,
+    /// The versions of the base model the custom model can be used with. Using it with another
+    /// version needs it to be upgraded
+    #[serde(default)]
+    pub versions: Vec<String>,
+    /// The GUID of the credentials for the instance of the service that owns the custom model
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// The date and time in coordinated universal time (UTC) at which the custom model was
+    /// created, in ISO 8601 format
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    /// The date and time in coordinated universal time (UTC) at which the custom model was last
+    /// modified, in ISO 8601 format
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
+    /// The description of the custom model
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Where the custom model is in being trained
+    pub status: TrainingStatus,
+    /// How far training the custom model has got, as a percentage. It only moves from 0 to 100
+    /// once training is complete
+    #[serde(default)]
+    pub progress: u32,
+    /// Warnings about training the custom model, such as audio that was not used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AcousticModels {
+    customizations: Vec<AcousticModel>,
+}
+
+impl<A: Authenticator> SpeechToText<'_, A> {
+    /// Creates a new empty custom acoustic model, based on a model the service provides. The
+    /// model is owned by the instance of the service whose credentials are used to create it.
+    /// Returns the customisation ID (GUID) of the new model, which the other customisation
+    /// methods take
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the new custom model. Use a name that is unique among the custom
+    ///   models of the instance
+    /// * `base_model_name` - The name of the model to base the custom model on, such as
+    ///   `en-US_BroadbandModel`. The model must support acoustic customisation, as
+    ///   [`SupportedFeatures`] tells
+    /// * `description` - A description of the new custom model. Specifying a description is
+    ///   recommended
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let customisation_id = stt
+    ///     .create_acoustic_model("call centre", "en-GB_Telephony", Some("Noisy calls"))
+    ///     .await?;
+    /// println!("created: {customisation_id}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`SupportedFeatures`]: crate::stt::models::SupportedFeatures
+    pub async fn create_acoustic_model(
+        &self,
+        name: impl AsRef<str>,
+        base_model_name: impl AsRef<str>,
+        description: Option<&str>,
+    ) -> Result<String, CreateAcousticModelError> {
+        #[derive(Serialize)]
+        struct Body<'a> {
+            name: &'a str,
+            base_model_name: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            description: Option<&'a str>,
+        }
+        let req = self
+            .service
+            .http()
+            .post(self.service.endpoint("v1/acoustic_customizations"))
+            .json(&Body {
+                name: name.as_ref(),
+                base_model_name: base_model_name.as_ref(),
+                description,
+            })
+            .build()
+            .map_err(|e| CreateAcousticModelError::ConnectionError(e.to_string()))?;
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(CreateAcousticModelError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK | StatusCode::CREATED => {
+                #[derive(Deserialize)]
+                struct Root {
+                    customization_id: String,
+                }
+                let root: Root = response
+                    .json()
+                    .await
+                    .map_err(|e| CreateAcousticModelError::ConnectionError(e.to_string()))?;
+                Ok(root.customization_id)
+            }
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => {
+                        Err(CreateAcousticModelError::BadRequest400(details))
+                    }
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(CreateAcousticModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(CreateAcousticModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => {
+                        Err(CreateAcousticModelError::Unauthorised401(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(CreateAcousticModelError::Forbidden403(details)),
+                    status => Err(CreateAcousticModelError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Lists information about all custom acoustic models that are owned by the instance of the
+    /// service. Specify a `language` to list the custom models for that language only
+    ///
+    /// # Parameters
+    ///
+    /// * `language` - The language identifier, such as `en-GB`, of the custom models to list, or
+    ///   [`None`] to list them all
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// for model in stt.list_acoustic_models(None).await? {
+    ///     println!("{}: {:?}", model.name, model.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`None`]: std::option::Option::None
+    pub async fn list_acoustic_models(
+        &self,
+        language: Option<&str>,
+    ) -> Result<Vec<AcousticModel>, ListAcousticModelsError> {
+        let mut url = self.service.endpoint("v1/acoustic_customizations");
+        if let Some(language) = language {
+            url.query_pairs_mut().append_pair("language", language);
+        }
+        let req = Request::new(Method::GET, url);
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(ListAcousticModelsError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => {
+                let models: AcousticModels = response
+                    .json()
+                    .await
+                    .map_err(|e| ListAcousticModelsError::ConnectionError(e.to_string()))?;
+                Ok(models.customizations)
+            }
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(ListAcousticModelsError::BadRequest400(details)),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(ListAcousticModelsError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(ListAcousticModelsError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => {
+                        Err(ListAcousticModelsError::Unauthorised401(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(ListAcousticModelsError::Forbidden403(details)),
+                    status => Err(ListAcousticModelsError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Gets information about a custom acoustic model, including where it is in being trained
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let model = stt.get_acoustic_model("cust-id").await?;
+    /// println!("{:?} ({}%)", model.status, model.progress);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_acoustic_model(
+        &self,
+        customisation_id: impl AsRef<str>,
+    ) -> Result<AcousticModel, GetAcousticModelError> {
+        let customisation_id = customisation_id.as_ref();
+        let req = Request::new(
+            Method::GET,
+            self.acoustic_model_endpoint(customisation_id, None),
+        );
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(GetAcousticModelError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| GetAcousticModelError::ConnectionError(e.to_string())),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(GetAcousticModelError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(GetAcousticModelError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(GetAcousticModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(GetAcousticModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(GetAcousticModelError::Forbidden403(details)),
+                    status => Err(GetAcousticModelError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Deletes a custom acoustic model. A model cannot be deleted while it is being trained
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// if stt.delete_acoustic_model("cust-id").await.is_ok() {
+    ///     println!("model deleted");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_acoustic_model(
+        &self,
+        customisation_id: impl AsRef<str>,
+    ) -> Result<(), DeleteAcousticModelError> {
+        let customisation_id = customisation_id.as_ref();
+        let req = Request::new(
+            Method::DELETE,
+            self.acoustic_model_endpoint(customisation_id, None),
+        );
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(DeleteAcousticModelError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK | StatusCode::NO_CONTENT => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(DeleteAcousticModelError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(DeleteAcousticModelError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::CONFLICT => Err(DeleteAcousticModelError::Conflict409(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(DeleteAcousticModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(DeleteAcousticModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(DeleteAcousticModelError::Forbidden403(details)),
+                    status => Err(DeleteAcousticModelError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Starts training a custom acoustic model on the audio resources added to it, which must
+    /// hold between 10 minutes and 200 hours of audio. Training happens in the background: the
+    /// request returns once it has started, and the model has the [`Available`] status once it
+    /// is trained. Training can take as long as the audio lasts
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `custom_language_model_id` - The customisation ID (GUID) of a custom language model to
+    ///   train with, if the audio has domain-specific words that it knows. It must be based on
+    ///   the same version of the same base model as the acoustic model
+    /// * `strict` - Whether to fail if the audio resources are not fit for training, such as
+    ///   when some of them are still being processed. Defaults to `true`; with `false`, the
+    ///   model is trained on the resources that are fit and a warning is returned
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// stt.train_acoustic_model("cust-id", Some("lang-cust-id"), None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`Available`]: self::TrainingStatus::Available
+    pub async fn train_acoustic_model(
+        &self,
+        customisation_id: impl AsRef<str>,
+        custom_language_model_id: Option<&str>,
+        strict: Option<bool>,
+    ) -> Result<(), TrainAcousticModelError> {
+        let customisation_id = customisation_id.as_ref();
+        let mut url = self.acoustic_model_endpoint(customisation_id, Some("train"));
+        {
+            let mut query = url.query_pairs_mut();
+            if let Some(language_model) = custom_language_model_id {
+                query.append_pair("custom_language_model_id", language_model);
+            }
+            if let Some(strict) = strict {
+                query.append_pair("strict", &strict.to_string());
+            }
+        }
+        let req = Request::new(Method::POST, url);
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(TrainAcousticModelError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(TrainAcousticModelError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(TrainAcousticModelError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::CONFLICT => Err(TrainAcousticModelError::Conflict409(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(TrainAcousticModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(TrainAcousticModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(TrainAcousticModelError::Forbidden403(details)),
+                    status => Err(TrainAcousticModelError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Resets a custom acoustic model, removing all of its audio resources so that it is as it
+    /// was when it was created. Its name, description and base model are kept
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// stt.reset_acoustic_model("cust-id").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn reset_acoustic_model(
+        &self,
+        customisation_id: impl AsRef<str>,
+    ) -> Result<(), ResetAcousticModelError> {
+        let customisation_id = customisation_id.as_ref();
+        let req = Request::new(
+            Method::POST,
+            self.acoustic_model_endpoint(customisation_id, Some("reset")),
+        );
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(ResetAcousticModelError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(ResetAcousticModelError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(ResetAcousticModelError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::CONFLICT => Err(ResetAcousticModelError::Conflict409(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(ResetAcousticModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(ResetAcousticModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(ResetAcousticModelError::Forbidden403(details)),
+                    status => Err(ResetAcousticModelError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Starts upgrading a custom acoustic model to the latest version of its base model, after
+    /// which it has the [`Upgrading`] status until it is [`Available`] again
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `custom_language_model_id` - The customisation ID (GUID) of the custom language model
+    ///   the acoustic model was trained with, which must be upgraded first
+    /// * `force` - Whether to upgrade the model even if it is already on the latest version of
+    ///   its base model, which is needed to upgrade it again after its custom language model was
+    ///   upgraded
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// stt.upgrade_acoustic_model("cust-id", None, false).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`Upgrading`]: self::TrainingStatus::Upgrading
+    /// [`Available`]: self::TrainingStatus::Available
+    pub async fn upgrade_acoustic_model(
+        &self,
+        customisation_id: impl AsRef<str>,
+        custom_language_model_id: Option<&str>,
+        force: bool,
+    ) -> Result<(), UpgradeAcousticModelError> {
+        let customisation_id = customisation_id.as_ref();
+        let mut url = self.acoustic_model_endpoint(customisation_id, Some("upgrade_model"));
+        {
+            let mut query = url.query_pairs_mut();
+            if let Some(language_model) = custom_language_model_id {
+                query.append_pair("custom_language_model_id", language_model);
+            }
+            if force {
+                query.append_pair("force", "true");
+            }
+        }
+        let req = Request::new(Method::POST, url);
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(UpgradeAcousticModelError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(UpgradeAcousticModelError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(UpgradeAcousticModelError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::CONFLICT => Err(UpgradeAcousticModelError::Conflict409(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(UpgradeAcousticModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(UpgradeAcousticModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(UpgradeAcousticModelError::Forbidden403(details)),
+                    status => Err(UpgradeAcousticModelError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// The URL of a custom acoustic model, or of `action` on it
+    pub(crate) fn acoustic_model_endpoint(
+        &self,
+        customisation_id: &str,
+        action: Option<&str>,
+    ) -> Url {
+        let mut url = self.service.endpoint("v1/acoustic_customizations");
+        {
+            let mut segments = url.path_segments_mut().unwrap();
+            segments.push(customisation_id);
+            if let Some(action) = action {
+                segments.push(action);
+            }
+        }
+        url
+    }
+}
@@ -0,0 +1,403 @@
+use bytes::Bytes;
+use reqwest::{
+    header::{HeaderName, HeaderValue, CONTENT_TYPE},
+    Method, Request, StatusCode, Url,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{auth::Authenticator, errors::ServiceErrorDetails, stt::SpeechToText};
+
+use super::errors::{AddAudioError, DeleteAudioError, GetAudioError, ListAudioError};
+
+/// The header giving the format of the audio files in an archive
+const CONTAINED_CONTENT_TYPE: HeaderName = HeaderName::from_static("contained-content-type");
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// Where the service is in processing an audio resource
+pub enum AudioStatus {
+    /// The service has processed the audio, and it can be trained on
+    Ok,
+    /// The service is still processing the audio
+    BeingProcessed,
+    /// The audio could not be processed
+    Invalid,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// What an audio resource holds
+pub enum AudioResourceType {
+    /// A single audio file
+    Audio,
+    /// An archive of audio files
+    Archive,
+    /// The service could not work out what the resource holds
+    Undetermined,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// What the service found out about an audio resource
+pub struct AudioDetails {
+    /// What the resource holds
+    #[serde(rename = "type")]
+    pub resource_type: AudioResourceType,
+    /// The codec the audio is encoded with, for a single audio file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codec: Option<String>,
+    /// The sampling rate of the audio in Hz, for a single audio file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency: Option<u32>,
+    /// The format the archive is compressed in, `zip` or `gzip`, for an archive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compression: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// An audio resource of a custom acoustic model, or an audio file in an archive resource
+pub struct AudioResource {
+    /// The name of the audio resource, or of the file in its archive
+    pub name: String,
+    /// How long the audio lasts in seconds, or `0` while it is being processed
+    #[serde(default)]
+    pub duration: u32,
+    /// What the service found out about the audio
+    pub details: AudioDetails,
+    /// Where the service is in processing the audio
+    pub status: AudioStatus,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// The audio resources of a custom acoustic model
+pub struct AudioResources {
+    /// How many minutes of audio the resources hold in total, which training needs to be between
+    /// 10 minutes and 200 hours
+    pub total_minutes_of_audio: f64,
+    /// The audio resources
+    pub audio: Vec<AudioResource>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// An audio resource of a custom acoustic model. A single audio file is described by the
+/// [`resource`] itself, while an archive is described by its [`container`] and the [`files`] in it
+///
+/// [`resource`]: Self::resource
+/// [`container`]: Self::container
+/// [`files`]: Self::files
+pub struct AudioListing {
+    /// The audio resource, if it is a single audio file
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub resource: Option<AudioResource>,
+    /// The archive, if the audio resource is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<AudioResource>,
+    /// The audio files in the archive, if the audio resource is one
+    #[serde(default, rename = "audio", skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<AudioResource>,
+}
+
+impl<A: Authenticator> SpeechToText<'_, A> {
+    /// Adds an audio resource, a single audio file or an archive of them, to a custom acoustic
+    /// model. The service processes the audio in the background; train the model once every
+    /// resource has the [`Ok`] status. Audio resources should be like the audio the model will
+    /// recognise, from the same speakers or in the same surroundings
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `audio_name` - The name of the audio resource. Use a name that is unique among the audio
+    ///   resources of the custom model
+    /// * `audio` - The audio file or archive, of at most 100 MB
+    /// * `content_type` - The MIME type of the resource: that of the audio file, such as
+    ///   `audio/wav`, or `application/zip` or `application/gzip` for an archive
+    /// * `contained_content_type` - The MIME type of the files in an archive, which must all be
+    ///   in the same format. Needed for formats without a header, such as `audio/l16;rate=16000`,
+    ///   and ignored for a single audio file
+    /// * `allow_overwrite` - Whether to replace an audio resource of the custom model that has
+    ///   the same name. Adding audio with the name of a resource that exists fails otherwise
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let archive = std::fs::read("calls.zip")?;
+    /// stt.add_audio(
+    ///     "cust-id",
+    ///     "calls",
+    ///     archive,
+    ///     "application/zip",
+    ///     Some("audio/l16;rate=8000"),
+    ///     false,
+    /// )
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`Ok`]: self::AudioStatus::Ok
+    pub async fn add_audio(
+        &self,
+        customisation_id: impl AsRef<str>,
+        audio_name: impl AsRef<str>,
+        audio: impl Into<Bytes>,
+        content_type: &str,
+        contained_content_type: Option<&str>,
+        allow_overwrite: bool,
+    ) -> Result<(), AddAudioError> {
+        let customisation_id = customisation_id.as_ref();
+        let mime_type = |value: &str| {
+            HeaderValue::from_str(value).map_err(|_| {
+                AddAudioError::ConnectionError(format!("{value:?} is not a valid MIME type"))
+            })
+        };
+        let mut url = self.audio_endpoint(customisation_id, Some(audio_name.as_ref()));
+        if allow_overwrite {
+            url.query_pairs_mut().append_pair("allow_overwrite", "true");
+        }
+        let mut req = Request::new(Method::POST, url);
+        req.headers_mut()
+            .insert(CONTENT_TYPE, mime_type(content_type)?);
+        if let Some(contained_content_type) = contained_content_type {
+            req.headers_mut()
+                .insert(CONTAINED_CONTENT_TYPE, mime_type(contained_content_type)?);
+        }
+        *req.body_mut() = Some(audio.into().into());
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(AddAudioError::ConnectionError)?;
+        match response.status() {
+            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(AddAudioError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(AddAudioError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::CONFLICT => Err(AddAudioError::Conflict409(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::PAYLOAD_TOO_LARGE => {
+                        Err(AddAudioError::PayloadTooLarge413(details))
+                    }
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE => {
+                        Err(AddAudioError::UnsupportedMediaType415(details))
+                    }
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(AddAudioError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(AddAudioError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(AddAudioError::Forbidden403(details)),
+                    status => Err(AddAudioError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Lists the audio resources of a custom acoustic model, with how much audio they hold in
+    /// total
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let resources = stt.list_audio("cust-id").await?;
+    /// println!("{} minutes of audio", resources.total_minutes_of_audio);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_audio(
+        &self,
+        customisation_id: impl AsRef<str>,
+    ) -> Result<AudioResources, ListAudioError> {
+        let customisation_id = customisation_id.as_ref();
+        let req = Request::new(Method::GET, self.audio_endpoint(customisation_id, None));
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(ListAudioError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| ListAudioError::ConnectionError(e.to_string())),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(ListAudioError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(ListAudioError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(ListAudioError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(ListAudioError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(ListAudioError::Forbidden403(details)),
+                    status => Err(ListAudioError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Gets an audio resource of a custom acoustic model, with the files in it if it is an
+    /// archive
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `audio_name` - The name of the audio resource
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let listing = stt.get_audio("cust-id", "calls").await?;
+    /// for file in listing.files {
+    ///     println!("{}: {:?}", file.name, file.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_audio(
+        &self,
+        customisation_id: impl AsRef<str>,
+        audio_name: impl AsRef<str>,
+    ) -> Result<AudioListing, GetAudioError> {
+        let customisation_id = customisation_id.as_ref();
+        let audio_name = audio_name.as_ref();
+        let req = Request::new(
+            Method::GET,
+            self.audio_endpoint(customisation_id, Some(audio_name)),
+        );
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(GetAudioError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| GetAudioError::ConnectionError(e.to_string())),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => {
+                        Err(GetAudioError::BadRequest400(audio_name.to_owned(), details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(GetAudioError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(GetAudioError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(GetAudioError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(GetAudioError::Forbidden403(details)),
+                    status => Err(GetAudioError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Deletes an audio resource from a custom acoustic model. Train the model again for the
+    /// change to take effect
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `audio_name` - The name of the audio resource
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// stt.delete_audio("cust-id", "calls").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_audio(
+        &self,
+        customisation_id: impl AsRef<str>,
+        audio_name: impl AsRef<str>,
+    ) -> Result<(), DeleteAudioError> {
+        let customisation_id = customisation_id.as_ref();
+        let audio_name = audio_name.as_ref();
+        let req = Request::new(
+            Method::DELETE,
+            self.audio_endpoint(customisation_id, Some(audio_name)),
+        );
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(DeleteAudioError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK | StatusCode::NO_CONTENT => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(DeleteAudioError::BadRequest400(
+                        audio_name.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(DeleteAudioError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::CONFLICT => Err(DeleteAudioError::Conflict409(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(DeleteAudioError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(DeleteAudioError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(DeleteAudioError::Forbidden403(details)),
+                    status => Err(DeleteAudioError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// The URL of the audio resources of a custom acoustic model, or of one of them
+    fn audio_endpoint(&self, customisation_id: &str, audio_name: Option<&str>) -> Url {
+        let mut url = self.acoustic_model_endpoint(customisation_id, Some("audio"));
+        if let Some(audio_name) = audio_name {
+            url.path_segments_mut().unwrap().push(audio_name);
+        }
+        url
+    }
+}
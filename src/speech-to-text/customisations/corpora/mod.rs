@@ -0,0 +1,336 @@
+use reqwest::{
+    multipart::{Form, Part},
+    Method, Request, StatusCode, Url,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{auth::Authenticator, errors::ServiceErrorDetails, stt::SpeechToText};
+
+use super::errors::{AddCorpusError, DeleteCorpusError, GetCorpusError, ListCorporaError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// Where the service is in analysing a corpus or grammar
+pub enum AnalysisStatus {
+    /// The service has analysed the corpus or grammar and added its words to the custom model
+    #[serde(rename = "analyzed")]
+    Analysed,
+    /// The service is still analysing the corpus or grammar
+    BeingProcessed,
+    /// The service ran into an error analysing the corpus or grammar
+    Undetermined,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// A corpus, a text document whose words are added to the vocabulary of a custom language model
+pub struct Corpus {
+    /// The name of the corpus
+    pub name: String,
+    /// The number of words in the corpus. It is `0` while the corpus is being analysed
+    #[serde(default)]
+    pub total_words: u32,
+    /// The number of words in the corpus that are not in the vocabulary of the base model. It is
+    /// `0` while the corpus is being analysed
+    #[serde(default)]
+    pub out_of_vocabulary_words: u32,
+    /// Where the service is in analysing the corpus
+    pub status: AnalysisStatus,
+    /// Why the corpus could not be analysed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Corpora {
+    corpora: Vec<Corpus>,
+}
+
+impl<A: Authenticator> SpeechToText<'_, A> {
+    /// Adds a corpus, a plain text document of the way speakers talk about a domain, to a custom
+    /// language model. The service analyses the corpus in the background, adding the words it
+    /// does not know to the vocabulary of the custom model. Each corpus is used again whenever
+    /// the model is trained, so train the model once the corpus is [`Analysed`] for its words to
+    /// be recognised
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `corpus_name` - The name of the corpus. Use a name that is unique among the corpora of
+    ///   the custom model, and not `user`, which is reserved for words added by the user
+    /// * `corpus` - The text of the corpus, encoded as UTF-8
+    /// * `allow_overwrite` - Whether to replace a corpus of the custom model that has the same
+    ///   name. Adding a corpus with the name of one that exists fails otherwise
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let corpus = std::fs::read_to_string("/home/user/notes.txt")?;
+    /// stt.add_corpus("cust-id", "notes", corpus, true).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`Analysed`]: self::AnalysisStatus::Analysed
+    pub async fn add_corpus(
+        &self,
+        customisation_id: impl AsRef<str>,
+        corpus_name: impl AsRef<str>,
+        corpus: impl Into<String>,
+        allow_overwrite: bool,
+    ) -> Result<(), AddCorpusError> {
+        let customisation_id = customisation_id.as_ref();
+        let corpus_name = corpus_name.as_ref();
+        let file = Part::text(corpus.into())
+            .file_name(format!("{corpus_name}.txt"))
+            .mime_str("text/plain")
+            .map_err(|e| AddCorpusError::ConnectionError(e.to_string()))?;
+        let form = Form::new().part("corpus_file", file);
+
+        let mut url = self.corpus_endpoint(customisation_id, Some(corpus_name));
+        if allow_overwrite {
+            url.query_pairs_mut().append_pair("allow_overwrite", "true");
+        }
+        let req = self
+            .service
+            .http()
+            .post(url)
+            .multipart(form)
+            .build()
+            .map_err(|e| AddCorpusError::ConnectionError(e.to_string()))?;
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(AddCorpusError::ConnectionError)?;
+        match response.status() {
+            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(AddCorpusError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(AddCorpusError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::CONFLICT => Err(AddCorpusError::Conflict409(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(AddCorpusError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(AddCorpusError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(AddCorpusError::Forbidden403(details)),
+                    status => Err(AddCorpusError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Lists the corpora added to a custom language model, with how many words each has and
+    /// where the service is in analysing it
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// for corpus in stt.list_corpora("cust-id").await? {
+    ///     println!("{}: {} new words", corpus.name, corpus.out_of_vocabulary_words);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_corpora(
+        &self,
+        customisation_id: impl AsRef<str>,
+    ) -> Result<Vec<Corpus>, ListCorporaError> {
+        let customisation_id = customisation_id.as_ref();
+        let req = Request::new(Method::GET, self.corpus_endpoint(customisation_id, None));
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(ListCorporaError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => {
+                let corpora: Corpora = response
+                    .json()
+                    .await
+                    .map_err(|e| ListCorporaError::ConnectionError(e.to_string()))?;
+                Ok(corpora.corpora)
+            }
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(ListCorporaError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(ListCorporaError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(ListCorporaError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(ListCorporaError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(ListCorporaError::Forbidden403(details)),
+                    status => Err(ListCorporaError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Gets a corpus of a custom language model, with how many words it has and where the
+    /// service is in analysing it
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `corpus_name` - The name of the corpus
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let corpus = stt.get_corpus("cust-id", "notes").await?;
+    /// println!("{:?}", corpus.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_corpus(
+        &self,
+        customisation_id: impl AsRef<str>,
+        corpus_name: impl AsRef<str>,
+    ) -> Result<Corpus, GetCorpusError> {
+        let customisation_id = customisation_id.as_ref();
+        let corpus_name = corpus_name.as_ref();
+        let req = Request::new(
+            Method::GET,
+            self.corpus_endpoint(customisation_id, Some(corpus_name)),
+        );
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(GetCorpusError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| GetCorpusError::ConnectionError(e.to_string())),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(GetCorpusError::BadRequest400(
+                        corpus_name.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(GetCorpusError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(GetCorpusError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(GetCorpusError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(GetCorpusError::Forbidden403(details)),
+                    status => Err(GetCorpusError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Deletes a corpus from a custom language model. The words the corpus added are removed
+    /// from the vocabulary of the model, unless they were also added by another corpus or
+    /// grammar, or by the user. Train the model again for the change to take effect
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `corpus_name` - The name of the corpus
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// stt.delete_corpus("cust-id", "notes").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_corpus(
+        &self,
+        customisation_id: impl AsRef<str>,
+        corpus_name: impl AsRef<str>,
+    ) -> Result<(), DeleteCorpusError> {
+        let customisation_id = customisation_id.as_ref();
+        let corpus_name = corpus_name.as_ref();
+        let req = Request::new(
+            Method::DELETE,
+            self.corpus_endpoint(customisation_id, Some(corpus_name)),
+        );
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(DeleteCorpusError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK | StatusCode::NO_CONTENT => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(DeleteCorpusError::BadRequest400(
+                        corpus_name.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(DeleteCorpusError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::CONFLICT => Err(DeleteCorpusError::Conflict409(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(DeleteCorpusError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(DeleteCorpusError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(DeleteCorpusError::Forbidden403(details)),
+                    status => Err(DeleteCorpusError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// The URL of the corpora of a custom language model, or of one of them
+    fn corpus_endpoint(&self, customisation_id: &str, corpus_name: Option<&str>) -> Url {
+        let mut url = self.language_model_endpoint(customisation_id, Some("corpora"));
+        if let Some(corpus_name) = corpus_name {
+            url.path_segments_mut().unwrap().push(corpus_name);
+        }
+        url
+    }
+}
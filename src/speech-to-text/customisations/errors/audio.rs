@@ -0,0 +1,125 @@
+use reqwest::StatusCode;
+use thiserror::Error;
+
+use crate::errors::{describe, ServiceErrorDetails};
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when adding an audio resource to a custom acoustic model
+pub enum AddAudioError {
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+    #[error("{}", describe(.1, &format!("The audio could not be added to the custom model {}", .0)))]
+    /// The audio is invalid, has the name of an audio resource that exists and may not be
+    /// overwritten, or the request is otherwise invalid
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    /// The specified customisation_id is invalid for the requesting credentials
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The custom model {} is being processed and cannot be changed now", .0)))]
+    /// The service is training the custom model, or processing audio added to it, and cannot
+    /// accept the request until it has finished
+    Conflict409(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The audio is larger than 100 MB"))]
+    /// The audio is larger than 100 MB
+    PayloadTooLarge413(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The content type of the audio is not supported"))]
+    /// The content type, or contained content type, of the audio is not supported
+    UnsupportedMediaType415(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when listing the audio resources of a custom acoustic model
+pub enum ListAudioError {
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+    #[error("{}", describe(.1, &format!("The customisation ID {} is invalid", .0)))]
+    /// The customisation ID is not a valid GUID
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    /// The specified customisation_id is invalid for the requesting credentials
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when getting an audio resource of a custom acoustic model
+pub enum GetAudioError {
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+    #[error("{}", describe(.1, &format!("The audio resource {} does not exist, or the customisation ID is invalid", .0)))]
+    /// The custom model has no audio resource with the name, or the customisation ID is not a
+    /// valid GUID
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    /// The specified customisation_id is invalid for the requesting credentials
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when deleting an audio resource from a custom acoustic model
+pub enum DeleteAudioError {
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+    #[error("{}", describe(.1, &format!("The audio resource {} does not exist, or the customisation ID is invalid", .0)))]
+    /// The custom model has no audio resource with the name, or the customisation ID is not a
+    /// valid GUID
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    /// The specified customisation_id is invalid for the requesting credentials
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The custom model {} is being processed and cannot be changed now", .0)))]
+    /// The service is training the custom model, or processing audio added to it, and cannot
+    /// accept the request until it has finished
+    Conflict409(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+}
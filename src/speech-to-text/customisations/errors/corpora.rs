@@ -0,0 +1,117 @@
+use reqwest::StatusCode;
+use thiserror::Error;
+
+use crate::errors::{describe, ServiceErrorDetails};
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when adding a corpus to a custom language model
+pub enum AddCorpusError {
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+    #[error("{}", describe(.1, &format!("The corpus could not be added to the custom model {}", .0)))]
+    /// The corpus is empty, has the name of a corpus that exists and may not be overwritten, or
+    /// the request is otherwise invalid
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    /// The specified customisation_id is invalid for the requesting credentials
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The custom model {} is being processed and cannot be changed now", .0)))]
+    /// The service is training the custom model, or processing data added to it, and cannot
+    /// accept the request until it has finished
+    Conflict409(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when listing the corpora of a custom language model
+pub enum ListCorporaError {
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+    #[error("{}", describe(.1, &format!("The customisation ID {} is invalid", .0)))]
+    /// The customisation ID is not a valid GUID
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    /// The specified customisation_id is invalid for the requesting credentials
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when getting a corpus of a custom language model
+pub enum GetCorpusError {
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+    #[error("{}", describe(.1, &format!("The corpus {} does not exist, or the customisation ID is invalid", .0)))]
+    /// The custom model has no corpus with the name, or the customisation ID is not a valid GUID
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    /// The specified customisation_id is invalid for the requesting credentials
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when deleting a corpus from a custom language model
+pub enum DeleteCorpusError {
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+    #[error("{}", describe(.1, &format!("The corpus {} does not exist, or the customisation ID is invalid", .0)))]
+    /// The custom model has no corpus with the name, or the customisation ID is not a valid GUID
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    /// The specified customisation_id is invalid for the requesting credentials
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The custom model {} is being processed and cannot be changed now", .0)))]
+    /// The service is training the custom model, or processing data added to it, and cannot
+    /// accept the request until it has finished
+    Conflict409(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+}
@@ -0,0 +1,206 @@
+use reqwest::StatusCode;
+use thiserror::Error;
+
+use crate::errors::{describe, ServiceErrorDetails};
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when creating a custom language model
+pub enum CreateLanguageModelError {
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
+    /// A required input parameter is null or a specified input parameter or header value is invalid or not supported
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when listing custom language models
+pub enum ListLanguageModelsError {
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
+    /// A required input parameter is null or a specified input parameter or header value is invalid or not supported
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when getting a custom language model
+pub enum GetLanguageModelError {
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+    #[error("{}", describe(.1, &format!("The customisation ID {} is invalid", .0)))]
+    /// The customisation ID is not a valid GUID
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    /// The specified customisation_id is invalid for the requesting credentials
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when deleting a custom language model
+pub enum DeleteLanguageModelError {
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+    #[error("{}", describe(.1, &format!("The custom model {} is not ready for the request, or the request is invalid", .0)))]
+    /// The request is invalid, or the custom model is not in a state that allows it, such as
+    /// training a model that has no new data
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    /// The specified customisation_id is invalid for the requesting credentials
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The custom model {} is being processed and cannot be changed now", .0)))]
+    /// The service is training the custom model, or processing data added to it, and cannot
+    /// accept the request until it has finished
+    Conflict409(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when training a custom language model
+pub enum TrainLanguageModelError {
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+    #[error("{}", describe(.1, &format!("The custom model {} is not ready for the request, or the request is invalid", .0)))]
+    /// The request is invalid, or the custom model is not in a state that allows it, such as
+    /// training a model that has no new data
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    /// The specified customisation_id is invalid for the requesting credentials
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The custom model {} is being processed and cannot be changed now", .0)))]
+    /// The service is training the custom model, or processing data added to it, and cannot
+    /// accept the request until it has finished
+    Conflict409(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when resetting a custom language model
+pub enum ResetLanguageModelError {
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+    #[error("{}", describe(.1, &format!("The custom model {} is not ready for the request, or the request is invalid", .0)))]
+    /// The request is invalid, or the custom model is not in a state that allows it, such as
+    /// training a model that has no new data
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    /// The specified customisation_id is invalid for the requesting credentials
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The custom model {} is being processed and cannot be changed now", .0)))]
+    /// The service is training the custom model, or processing data added to it, and cannot
+    /// accept the request until it has finished
+    Conflict409(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when upgrading a custom language model
+pub enum UpgradeLanguageModelError {
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+    #[error("{}", describe(.1, &format!("The custom model {} is not ready for the request, or the request is invalid", .0)))]
+    /// The request is invalid, or the custom model is not in a state that allows it, such as
+    /// training a model that has no new data
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    /// The specified customisation_id is invalid for the requesting credentials
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The custom model {} is being processed and cannot be changed now", .0)))]
+    /// The service is training the custom model, or processing data added to it, and cannot
+    /// accept the request until it has finished
+    Conflict409(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+}
@@ -0,0 +1,12 @@
+mod acoustic;
+mod audio;
+mod corpora;
+mod grammars;
+mod language;
+mod words;
+pub use acoustic::*;
+pub use audio::*;
+pub use corpora::*;
+pub use grammars::*;
+pub use language::*;
+pub use words::*;
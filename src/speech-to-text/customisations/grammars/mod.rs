@@ -0,0 +1,343 @@
+use reqwest::{header::CONTENT_TYPE, Method, Request, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::{auth::Authenticator, errors::ServiceErrorDetails, stt::SpeechToText};
+
+use super::{
+    errors::{AddGrammarError, DeleteGrammarError, GetGrammarError, ListGrammarsError},
+    AnalysisStatus,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// The format a grammar is written in. Both are formats of the W3C Speech Recognition Grammar
+/// Specification (SRGS)
+pub enum GrammarFormat {
+    /// Augmented Backus-Naur Form, a plain text format
+    Abnf,
+    /// XML
+    Xml,
+}
+
+impl GrammarFormat {
+    /// The MIME type of the format known to IBM Watson
+    pub fn id(&self) -> &str {
+        match self {
+            GrammarFormat::Abnf => "application/srgs",
+            GrammarFormat::Xml => "application/srgs+xml",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// A grammar, which describes the phrases recognition can be limited to with
+/// [`RecognitionOptions::grammar_name()`]
+///
+/// [`RecognitionOptions::grammar_name()`]: crate::stt::recognition::RecognitionOptions::grammar_name()
+pub struct Grammar {
+    /// The name of the grammar
+    pub name: String,
+    /// The number of words in the grammar that are not in the vocabulary of the base model. It
+    /// is `0` while the grammar is being analysed
+    #[serde(default)]
+    pub out_of_vocabulary_words: u32,
+    /// Where the service is in analysing the grammar
+    pub status: AnalysisStatus,
+    /// Why the grammar could not be analysed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Grammars {
+    grammars: Vec<Grammar>,
+}
+
+impl<A: Authenticator> SpeechToText<'_, A> {
+    /// Adds a grammar to a custom language model. The service analyses the grammar in the
+    /// background, adding the words it does not know to the vocabulary of the custom model.
+    /// Train the model once the grammar is [`Analysed`] to recognise with it
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `grammar_name` - The name of the grammar. Use a name that is unique among the grammars
+    ///   and corpora of the custom model, and not `user`, which is reserved for words added by
+    ///   the user
+    /// * `grammar` - The rules of the grammar, encoded as UTF-8
+    /// * `format` - The [`GrammarFormat`] the rules are written in
+    /// * `allow_overwrite` - Whether to replace a grammar of the custom model that has the same
+    ///   name. Adding a grammar with the name of one that exists fails otherwise
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     stt::{customisations::GrammarFormat, SpeechToText},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let grammar = "#ABNF 1.0 ISO-8859-1;\nlanguage en-GB;\nroot $yesno;\n$yesno = yes | no;";
+    /// stt.add_grammar("cust-id", "yes-no", grammar, GrammarFormat::Abnf, false)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`Analysed`]: self::AnalysisStatus::Analysed
+    /// [`GrammarFormat`]: self::GrammarFormat
+    pub async fn add_grammar(
+        &self,
+        customisation_id: impl AsRef<str>,
+        grammar_name: impl AsRef<str>,
+        grammar: impl Into<String>,
+        format: GrammarFormat,
+        allow_overwrite: bool,
+    ) -> Result<(), AddGrammarError> {
+        let customisation_id = customisation_id.as_ref();
+        let mut url = self.grammar_endpoint(customisation_id, Some(grammar_name.as_ref()));
+        if allow_overwrite {
+            url.query_pairs_mut().append_pair("allow_overwrite", "true");
+        }
+        let req = self
+            .service
+            .http()
+            .post(url)
+            .header(CONTENT_TYPE, format.id())
+            .body(grammar.into())
+            .build()
+            .map_err(|e| AddGrammarError::ConnectionError(e.to_string()))?;
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(AddGrammarError::ConnectionError)?;
+        match response.status() {
+            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(AddGrammarError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(AddGrammarError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::CONFLICT => Err(AddGrammarError::Conflict409(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(AddGrammarError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(AddGrammarError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(AddGrammarError::Forbidden403(details)),
+                    status => Err(AddGrammarError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Lists the grammars added to a custom language model, with where the service is in
+    /// analysing each
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// for grammar in stt.list_grammars("cust-id").await? {
+    ///     println!("{}: {:?}", grammar.name, grammar.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_grammars(
+        &self,
+        customisation_id: impl AsRef<str>,
+    ) -> Result<Vec<Grammar>, ListGrammarsError> {
+        let customisation_id = customisation_id.as_ref();
+        let req = Request::new(Method::GET, self.grammar_endpoint(customisation_id, None));
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(ListGrammarsError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => {
+                let grammars: Grammars = response
+                    .json()
+                    .await
+                    .map_err(|e| ListGrammarsError::ConnectionError(e.to_string()))?;
+                Ok(grammars.grammars)
+            }
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(ListGrammarsError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(ListGrammarsError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(ListGrammarsError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(ListGrammarsError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(ListGrammarsError::Forbidden403(details)),
+                    status => Err(ListGrammarsError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Gets a grammar of a custom language model, with where the service is in analysing it
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `grammar_name` - The name of the grammar
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let grammar = stt.get_grammar("cust-id", "yes-no").await?;
+    /// println!("{:?}", grammar.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_grammar(
+        &self,
+        customisation_id: impl AsRef<str>,
+        grammar_name: impl AsRef<str>,
+    ) -> Result<Grammar, GetGrammarError> {
+        let customisation_id = customisation_id.as_ref();
+        let grammar_name = grammar_name.as_ref();
+        let req = Request::new(
+            Method::GET,
+            self.grammar_endpoint(customisation_id, Some(grammar_name)),
+        );
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(GetGrammarError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| GetGrammarError::ConnectionError(e.to_string())),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(GetGrammarError::BadRequest400(
+                        grammar_name.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(GetGrammarError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(GetGrammarError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(GetGrammarError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(GetGrammarError::Forbidden403(details)),
+                    status => Err(GetGrammarError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Deletes a grammar from a custom language model. The words the grammar added are removed
+    /// from the vocabulary of the model, unless they were also added by another grammar or
+    /// corpus, or by the user. Train the model again for the change to take effect
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `grammar_name` - The name of the grammar
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// stt.delete_grammar("cust-id", "yes-no").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_grammar(
+        &self,
+        customisation_id: impl AsRef<str>,
+        grammar_name: impl AsRef<str>,
+    ) -> Result<(), DeleteGrammarError> {
+        let customisation_id = customisation_id.as_ref();
+        let grammar_name = grammar_name.as_ref();
+        let req = Request::new(
+            Method::DELETE,
+            self.grammar_endpoint(customisation_id, Some(grammar_name)),
+        );
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(DeleteGrammarError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK | StatusCode::NO_CONTENT => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(DeleteGrammarError::BadRequest400(
+                        grammar_name.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(DeleteGrammarError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::CONFLICT => Err(DeleteGrammarError::Conflict409(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(DeleteGrammarError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(DeleteGrammarError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(DeleteGrammarError::Forbidden403(details)),
+                    status => Err(DeleteGrammarError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// The URL of the grammars of a custom language model, or of one of them
+    fn grammar_endpoint(&self, customisation_id: &str, grammar_name: Option<&str>) -> Url {
+        let mut url = self.language_model_endpoint(customisation_id, Some("grammars"));
+        if let Some(grammar_name) = grammar_name {
+            url.path_segments_mut().unwrap().push(grammar_name);
+        }
+        url
+    }
+}
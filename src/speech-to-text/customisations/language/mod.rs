@@ -0,0 +1,683 @@
+use reqwest::{Method, Request, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::Authenticator,
+    errors::ServiceErrorDetails,
+    polling::{errors::WaitError, Polling, Status},
+    stt::SpeechToText,
+};
+
+use super::errors::{
+    CreateLanguageModelError, DeleteLanguageModelError, GetLanguageModelError,
+    ListLanguageModelsError, ResetLanguageModelError, TrainLanguageModelError,
+    UpgradeLanguageModelError,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// Where a custom model is in being trained
+pub enum TrainingStatus {
+    /// The model was created, but is waiting for training data or for the service to finish
+    /// analysing the data added to it
+    Pending,
+    /// The model has data and is ready to be trained
+    Ready,
+    /// The model is being trained
+    Training,
+    /// The model is trained and can be used to recognise speech
+    Available,
+    /// The model is being upgraded to the latest version of its base model
+    Upgrading,
+    /// Training the model failed
+    Failed,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// A custom language model, which adds domain-specific words to the vocabulary of a base model
+pub struct LanguageModel {
+    /// The customisation ID (GUID) of the custom model
+    #[serde(rename = "customization_id")]
+    pub customisation_id: String,
+    /// The name of the custom model
+    pub name: String,
+    /// The language identifier of the custom model (for example, en-US)
+    pub language: String,
+    /// The dialect of the language the custom model uses, which differs from its language for
+    /// Spanish models
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dialect: Option<String>,
+    /// The name of the model the custom model is based on
+    pub base_model_name: String,
+    /// The versions of the base model the custom model can be used with. Using it with another
+    /// version needs it to be upgraded
+    #[serde(default)]
+    pub versions: Vec<String>,
+    /// The GUID of the credentials for the instance of the service that owns the custom model
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// The date and time in coordinated universal time (UTC) at which the custom model was
+    /// created, in ISO 8601 format
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    /// The date and time in coordinated universal time (UTC) at which the custom model was last
+    /// modified, in ISO 8601 format
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
+    /// The description of the custom model
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Where the custom model is in being trained
+    pub status: TrainingStatus,
+    /// How far training the custom model has got, as a percentage. It only moves from 0 to 100
+    /// once training is complete
+    #[serde(default)]
+    pub progress: u32,
+    /// Why training the custom model failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Warnings about training the custom model, such as words that were not added
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// Which words added to a custom language model are taken into training
+pub enum WordTypeToAdd {
+    /// All words, including those found in corpora and grammars
+    All,
+    /// Only the words added or changed by the user
+    User,
+}
+
+impl WordTypeToAdd {
+    /// The value of the word type known to IBM Watson
+    pub fn id(&self) -> &str {
+        match self {
+            WordTypeToAdd::All => "all",
+            WordTypeToAdd::User => "user",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct LanguageModels {
+    customizations: Vec<LanguageModel>,
+}
+
+impl<A: Authenticator> SpeechToText<'_, A> {
+    /// Creates a new empty custom language model, based on a model the service provides. The
+    /// model is owned by the instance of the service whose credentials are used to create it.
+    /// Returns the customisation ID (GUID) of the new model, which the other customisation
+    /// methods take
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the new custom model. Use a name that is unique among the custom
+    ///   models of the instance
+    /// * `base_model_name` - The name of the model to base the custom model on, such as
+    ///   `en-US_BroadbandModel`. The model must support customisation, as [`SupportedFeatures`]
+    ///   tells
+    /// * `dialect` - The dialect of the language, only needed for Spanish models
+    /// * `description` - A description of the new custom model. Specifying a description is
+    ///   recommended
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let customisation_id = stt
+    ///     .create_language_model("medical", "en-GB_Multimedia", None, Some("Drug names"))
+    ///     .await?;
+    /// println!("created: {customisation_id}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`SupportedFeatures`]: crate::stt::models::SupportedFeatures
+    pub async fn create_language_model(
+        &self,
+        name: impl AsRef<str>,
+        base_model_name: impl AsRef<str>,
+        dialect: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<String, CreateLanguageModelError> {
+        #[derive(Serialize)]
+        struct Body<'a> {
+            name: &'a str,
+            base_model_name: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            dialect: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            description: Option<&'a str>,
+        }
+        let req = self
+            .service
+            .http()
+            .post(self.service.endpoint("v1/customizations"))
+            .json(&Body {
+                name: name.as_ref(),
+                base_model_name: base_model_name.as_ref(),
+                dialect,
+                description,
+            })
+            .build()
+            .map_err(|e| CreateLanguageModelError::ConnectionError(e.to_string()))?;
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(CreateLanguageModelError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK | StatusCode::CREATED => {
+                #[derive(Deserialize)]
+                struct Root {
+                    customization_id: String,
+                }
+                let root: Root = response
+                    .json()
+                    .await
+                    .map_err(|e| CreateLanguageModelError::ConnectionError(e.to_string()))?;
+                Ok(root.customization_id)
+            }
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => {
+                        Err(CreateLanguageModelError::BadRequest400(details))
+                    }
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(CreateLanguageModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(CreateLanguageModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => {
+                        Err(CreateLanguageModelError::Unauthorised401(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(CreateLanguageModelError::Forbidden403(details)),
+                    status => Err(CreateLanguageModelError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Lists information about all custom language models that are owned by the instance of the
+    /// service. Specify a `language` to list the custom models for that language only
+    ///
+    /// # Parameters
+    ///
+    /// * `language` - The language identifier, such as `en-GB`, of the custom models to list, or
+    ///   [`None`] to list them all
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// for model in stt.list_language_models(Some("en-GB")).await? {
+    ///     println!("{}: {:?}", model.name, model.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`None`]: std::option::Option::None
+    pub async fn list_language_models(
+        &self,
+        language: Option<&str>,
+    ) -> Result<Vec<LanguageModel>, ListLanguageModelsError> {
+        let mut url = self.service.endpoint("v1/customizations");
+        if let Some(language) = language {
+            url.query_pairs_mut().append_pair("language", language);
+        }
+        let req = Request::new(Method::GET, url);
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(ListLanguageModelsError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => {
+                let models: LanguageModels = response
+                    .json()
+                    .await
+                    .map_err(|e| ListLanguageModelsError::ConnectionError(e.to_string()))?;
+                Ok(models.customizations)
+            }
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(ListLanguageModelsError::BadRequest400(details)),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(ListLanguageModelsError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(ListLanguageModelsError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => {
+                        Err(ListLanguageModelsError::Unauthorised401(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(ListLanguageModelsError::Forbidden403(details)),
+                    status => Err(ListLanguageModelsError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Gets information about a custom language model, including where it is in being trained
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let model = stt.get_language_model("cust-id").await?;
+    /// println!("{:?} ({}%)", model.status, model.progress);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_language_model(
+        &self,
+        customisation_id: impl AsRef<str>,
+    ) -> Result<LanguageModel, GetLanguageModelError> {
+        let customisation_id = customisation_id.as_ref();
+        let req = Request::new(
+            Method::GET,
+            self.language_model_endpoint(customisation_id, None),
+        );
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(GetLanguageModelError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| GetLanguageModelError::ConnectionError(e.to_string())),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(GetLanguageModelError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(GetLanguageModelError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(GetLanguageModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(GetLanguageModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(GetLanguageModelError::Forbidden403(details)),
+                    status => Err(GetLanguageModelError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Deletes a custom language model. A model cannot be deleted while it is being trained
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// if stt.delete_language_model("cust-id").await.is_ok() {
+    ///     println!("model deleted");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_language_model(
+        &self,
+        customisation_id: impl AsRef<str>,
+    ) -> Result<(), DeleteLanguageModelError> {
+        let customisation_id = customisation_id.as_ref();
+        let req = Request::new(
+            Method::DELETE,
+            self.language_model_endpoint(customisation_id, None),
+        );
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(DeleteLanguageModelError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK | StatusCode::NO_CONTENT => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(DeleteLanguageModelError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(DeleteLanguageModelError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::CONFLICT => Err(DeleteLanguageModelError::Conflict409(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(DeleteLanguageModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(DeleteLanguageModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(DeleteLanguageModelError::Forbidden403(details)),
+                    status => Err(DeleteLanguageModelError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Starts training a custom language model on the words, corpora and grammars added to it.
+    /// Training happens in the background: the request returns once it has started, and
+    /// [`wait_until_trained()`] waits for it to finish. A model that is trained has the
+    /// [`Available`] status
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `word_type_to_add` - Which words to train on. Defaults to [`All`]
+    /// * `customisation_weight` - How much weight to give words from the custom model over words
+    ///   from the base model, between `0.0` and `1.0`, when recognising with the model later. The
+    ///   service picks a weight from the data of the model by default
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     polling::Polling,
+    /// #     stt::SpeechToText,
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// stt.train_language_model("cust-id", None, None).await?;
+    /// let model = stt.wait_until_trained("cust-id", &Polling::default()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`wait_until_trained()`]: Self::wait_until_trained()
+    /// [`Available`]: self::TrainingStatus::Available
+    /// [`All`]: self::WordTypeToAdd::All
+    pub async fn train_language_model(
+        &self,
+        customisation_id: impl AsRef<str>,
+        word_type_to_add: Option<WordTypeToAdd>,
+        customisation_weight: Option<f64>,
+    ) -> Result<(), TrainLanguageModelError> {
+        let customisation_id = customisation_id.as_ref();
+        let mut url = self.language_model_endpoint(customisation_id, Some("train"));
+        {
+            let mut query = url.query_pairs_mut();
+            if let Some(word_type) = word_type_to_add {
+                query.append_pair("word_type_to_add", word_type.id());
+            }
+            if let Some(weight) = customisation_weight {
+                query.append_pair("customization_weight", &weight.to_string());
+            }
+        }
+        let req = Request::new(Method::POST, url);
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(TrainLanguageModelError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(TrainLanguageModelError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(TrainLanguageModelError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::CONFLICT => Err(TrainLanguageModelError::Conflict409(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(TrainLanguageModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(TrainLanguageModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(TrainLanguageModelError::Forbidden403(details)),
+                    status => Err(TrainLanguageModelError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Waits until a custom language model has been trained, checking it with
+    /// [`get_language_model()`] as often as `polling` asks. Call it after
+    /// [`train_language_model()`] or [`upgrade_language_model()`]: a model that has not been
+    /// asked to train stays [`Ready`] or [`Pending`] until the timeout. If training failed,
+    /// [`WaitError::Failed`] carries the service's reason
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `polling` - How often, and for how long, to check the model
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     polling::Polling,
+    /// #     stt::SpeechToText,
+    /// # };
+    /// # use std::time::Duration;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// stt.train_language_model("cust-id", None, None).await?;
+    /// let polling = Polling::new(Duration::from_secs(10), Duration::from_secs(1800));
+    /// let model = stt.wait_until_trained("cust-id", &polling).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`get_language_model()`]: Self::get_language_model()
+    /// [`train_language_model()`]: Self::train_language_model()
+    /// [`upgrade_language_model()`]: Self::upgrade_language_model()
+    /// [`Ready`]: self::TrainingStatus::Ready
+    /// [`Pending`]: self::TrainingStatus::Pending
+    /// [`WaitError::Failed`]: crate::polling::errors::WaitError::Failed
+    pub async fn wait_until_trained(
+        &self,
+        customisation_id: impl AsRef<str>,
+        polling: &Polling,
+    ) -> Result<LanguageModel, WaitError<GetLanguageModelError>> {
+        polling
+            .until(|| async {
+                let model = self.get_language_model(customisation_id.as_ref()).await?;
+                Ok(match model.status {
+                    TrainingStatus::Available => Status::Ready(model),
+                    TrainingStatus::Failed => {
+                        Status::Failed(model.error.unwrap_or_else(|| {
+                            format!("training {} failed", model.customisation_id)
+                        }))
+                    }
+                    _ => Status::Pending,
+                })
+            })
+            .await
+    }
+
+    /// Resets a custom language model, removing all of its words, corpora and grammars so that
+    /// it is as it was when it was created. Its name, description and base model are kept
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// stt.reset_language_model("cust-id").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn reset_language_model(
+        &self,
+        customisation_id: impl AsRef<str>,
+    ) -> Result<(), ResetLanguageModelError> {
+        let customisation_id = customisation_id.as_ref();
+        let req = Request::new(
+            Method::POST,
+            self.language_model_endpoint(customisation_id, Some("reset")),
+        );
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(ResetLanguageModelError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(ResetLanguageModelError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(ResetLanguageModelError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::CONFLICT => Err(ResetLanguageModelError::Conflict409(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(ResetLanguageModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(ResetLanguageModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(ResetLanguageModelError::Forbidden403(details)),
+                    status => Err(ResetLanguageModelError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Starts upgrading a custom language model to the latest version of its base model, after
+    /// which it has the [`Upgrading`] status until it is [`Available`] again. A model must be
+    /// upgraded before it can be used with a new version of its base model
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     polling::Polling,
+    /// #     stt::SpeechToText,
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// stt.upgrade_language_model("cust-id").await?;
+    /// stt.wait_until_trained("cust-id", &Polling::default()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`Upgrading`]: self::TrainingStatus::Upgrading
+    /// [`Available`]: self::TrainingStatus::Available
+    pub async fn upgrade_language_model(
+        &self,
+        customisation_id: impl AsRef<str>,
+    ) -> Result<(), UpgradeLanguageModelError> {
+        let customisation_id = customisation_id.as_ref();
+        let req = Request::new(
+            Method::POST,
+            self.language_model_endpoint(customisation_id, Some("upgrade_model")),
+        );
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(UpgradeLanguageModelError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(UpgradeLanguageModelError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(UpgradeLanguageModelError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::CONFLICT => Err(UpgradeLanguageModelError::Conflict409(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(UpgradeLanguageModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(UpgradeLanguageModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(UpgradeLanguageModelError::Forbidden403(details)),
+                    status => Err(UpgradeLanguageModelError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// The URL of a custom language model, or of `action` on it
+    pub(crate) fn language_model_endpoint(
+        &self,
+        customisation_id: &str,
+        action: Option<&str>,
+    ) -> Url {
+        let mut url = self.service.endpoint("v1/customizations");
+        {
+            let mut segments = url.path_segments_mut().unwrap();
+            segments.push(customisation_id);
+            if let Some(action) = action {
+                segments.push(action);
+            }
+        }
+        url
+    }
+}
@@ -0,0 +1,14 @@
+mod acoustic;
+mod audio;
+mod corpora;
+/// Errors that may be returned in customisation requests
+pub mod errors;
+mod grammars;
+mod language;
+mod words;
+pub use acoustic::*;
+pub use audio::*;
+pub use corpora::*;
+pub use grammars::*;
+pub use language::*;
+pub use words::*;
@@ -0,0 +1,497 @@
+use std::collections::HashMap;
+
+use reqwest::{Method, Request, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::{auth::Authenticator, errors::ServiceErrorDetails, stt::SpeechToText};
+
+use super::errors::{AddWordError, DeleteWordError, GetWordError, ListWordsError};
+
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+/// A word in the vocabulary of a custom language model, added by the user or found in a corpus
+/// or grammar. Only [`word`], [`sounds_like`] and [`display_as`] are sent when adding a word;
+/// the rest are filled in by the service
+///
+/// # Example
+/// ```
+/// # use ibm_watson::stt::customisations::Word;
+/// let word = Word::new("IEEE", &["I. triple E."], Some("IEEE"));
+/// assert_eq!(word.sounds_like, ["I. triple E."]);
+/// ```
+/// [`word`]: Self::word
+/// [`sounds_like`]: Self::sounds_like
+/// [`display_as`]: Self::display_as
+pub struct Word {
+    /// The word, as it is spelled. Use underscores instead of spaces in a word of several tokens,
+    /// such as `New_York`. The maximum length of a word is 49 characters
+    pub word: String,
+    /// How the word is pronounced, as up to five words or syllables that sound like it, such as
+    /// `I. triple E.` for `IEEE`. The service makes its own pronunciation from the spelling of
+    /// the word when there are none
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sounds_like: Vec<String>,
+    /// How to spell the word in transcripts, if not as it is written in [`word`]
+    ///
+    /// [`word`]: Self::word
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_as: Option<String>,
+    /// How many times the word was found in the corpora and grammars of the custom model
+    #[serde(default, skip_serializing)]
+    pub count: u32,
+    /// Where the word came from: the names of the corpora and grammars it was found in, and
+    /// `user` if it was added or changed by the user
+    #[serde(default, skip_serializing)]
+    pub source: Vec<String>,
+    /// The problems the service found with the word, each mapping the element of the word that
+    /// is invalid to why
+    #[serde(default, rename = "error", skip_serializing)]
+    pub errors: Vec<HashMap<String, String>>,
+}
+
+impl Word {
+    /// A word to add to a custom language model
+    ///
+    /// # Parameters
+    ///
+    /// * `word` - The word, as it is spelled
+    /// * `sounds_like` - Words or syllables that sound like the word, or none to let the service
+    ///   work out how it is pronounced
+    /// * `display_as` - How to spell the word in transcripts, or [`None`] to spell it as `word`
+    ///
+    /// [`None`]: std::option::Option::None
+    pub fn new(word: impl Into<String>, sounds_like: &[&str], display_as: Option<&str>) -> Self {
+        Self {
+            word: word.into(),
+            sounds_like: sounds_like.iter().map(|&s| s.to_owned()).collect(),
+            display_as: display_as.map(str::to_owned),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// Which words of a custom language model to list
+pub enum WordType {
+    /// All words
+    All,
+    /// Only the words added or changed by the user
+    User,
+    /// Only the words found in corpora
+    Corpora,
+    /// Only the words found in grammars
+    Grammars,
+}
+
+impl WordType {
+    /// The value of the word type known to IBM Watson
+    pub fn id(&self) -> &str {
+        match self {
+            WordType::All => "all",
+            WordType::User => "user",
+            WordType::Corpora => "corpora",
+            WordType::Grammars => "grammars",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// The order to list the words of a custom language model in
+pub enum WordSort {
+    /// From A to Z
+    Alphabetical,
+    /// From Z to A
+    ReverseAlphabetical,
+    /// The words found most often in corpora and grammars first
+    MostFrequent,
+    /// The words found least often in corpora and grammars first
+    LeastFrequent,
+}
+
+impl WordSort {
+    /// The value of the order known to IBM Watson
+    pub fn id(&self) -> &str {
+        match self {
+            WordSort::Alphabetical => "+alphabetical",
+            WordSort::ReverseAlphabetical => "-alphabetical",
+            WordSort::MostFrequent => "-count",
+            WordSort::LeastFrequent => "+count",
+        }
+    }
+}
+
+impl<A: Authenticator> SpeechToText<'_, A> {
+    /// Adds words to a custom language model, or changes the words it already has. The service
+    /// works out a pronunciation for words that have no [`sounds_like`] in the background; train
+    /// the model once it has finished for the words to be recognised. A custom model can hold
+    /// up to 90,000 words
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `words` - The [`Words`] to add or change
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     stt::{customisations::Word, SpeechToText},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let words = [
+    ///     Word::new("IEEE", &["I. triple E."], None),
+    ///     Word::new("tomato", &["tomatoh", "tomayto"], None),
+    /// ];
+    /// stt.add_words("cust-id", &words).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`sounds_like`]: self::Word::sounds_like
+    /// [`Words`]: self::Word
+    pub async fn add_words(
+        &self,
+        customisation_id: impl AsRef<str>,
+        words: &[Word],
+    ) -> Result<(), AddWordError> {
+        let customisation_id = customisation_id.as_ref();
+        #[derive(Serialize)]
+        struct Body<'a> {
+            words: &'a [Word],
+        }
+        let req = self
+            .service
+            .http()
+            .post(self.word_endpoint(customisation_id, None))
+            .json(&Body { words })
+            .build()
+            .map_err(|e| AddWordError::ConnectionError(e.to_string()))?;
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(AddWordError::ConnectionError)?;
+        match response.status() {
+            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                add_word_error(customisation_id, status, details)
+            }
+        }
+    }
+
+    /// Adds a word to a custom language model, or changes it if the model already has it
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `word` - The [`Word`] to add or change
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     stt::{customisations::Word, SpeechToText},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let word = Word::new("HHonors", &["hilton honours", "H. honours"], Some("HHonors"));
+    /// stt.add_word("cust-id", &word).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`Word`]: self::Word
+    pub async fn add_word(
+        &self,
+        customisation_id: impl AsRef<str>,
+        word: &Word,
+    ) -> Result<(), AddWordError> {
+        let customisation_id = customisation_id.as_ref();
+        #[derive(Serialize)]
+        struct Body<'a> {
+            #[serde(skip_serializing_if = "<[String]>::is_empty")]
+            sounds_like: &'a [String],
+            #[serde(skip_serializing_if = "Option::is_none")]
+            display_as: Option<&'a str>,
+        }
+        let req = self
+            .service
+            .http()
+            .put(self.word_endpoint(customisation_id, Some(&word.word)))
+            .json(&Body {
+                sounds_like: &word.sounds_like,
+                display_as: word.display_as.as_deref(),
+            })
+            .build()
+            .map_err(|e| AddWordError::ConnectionError(e.to_string()))?;
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(AddWordError::ConnectionError)?;
+        match response.status() {
+            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                add_word_error(customisation_id, status, details)
+            }
+        }
+    }
+
+    /// Lists the words of a custom language model
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `word_type` - Which words to list. Defaults to [`All`]
+    /// * `sort` - The order to list the words in. Defaults to [`Alphabetical`]
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     stt::{customisations::{WordSort, WordType}, SpeechToText},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let words = stt
+    ///     .list_words("cust-id", Some(WordType::Corpora), Some(WordSort::MostFrequent))
+    ///     .await?;
+    /// for word in words {
+    ///     println!("{}: {}", word.word, word.count);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`All`]: self::WordType::All
+    /// [`Alphabetical`]: self::WordSort::Alphabetical
+    pub async fn list_words(
+        &self,
+        customisation_id: impl AsRef<str>,
+        word_type: Option<WordType>,
+        sort: Option<WordSort>,
+    ) -> Result<Vec<Word>, ListWordsError> {
+        let customisation_id = customisation_id.as_ref();
+        let mut url = self.word_endpoint(customisation_id, None);
+        {
+            let mut query = url.query_pairs_mut();
+            if let Some(word_type) = word_type {
+                query.append_pair("word_type", word_type.id());
+            }
+            if let Some(sort) = sort {
+                query.append_pair("sort", sort.id());
+            }
+        }
+        let req = Request::new(Method::GET, url);
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(ListWordsError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => {
+                #[derive(Deserialize)]
+                struct Root {
+                    words: Vec<Word>,
+                }
+                let root: Root = response
+                    .json()
+                    .await
+                    .map_err(|e| ListWordsError::ConnectionError(e.to_string()))?;
+                Ok(root.words)
+            }
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(ListWordsError::BadRequest400(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNAUTHORIZED => Err(ListWordsError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(ListWordsError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(ListWordsError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(ListWordsError::Forbidden403(details)),
+                    status => Err(ListWordsError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Gets a word of a custom language model
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `word` - The word, as it is spelled
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let word = stt.get_word("cust-id", "IEEE").await?;
+    /// println!("{:?}", word.sounds_like);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_word(
+        &self,
+        customisation_id: impl AsRef<str>,
+        word: impl AsRef<str>,
+    ) -> Result<Word, GetWordError> {
+        let customisation_id = customisation_id.as_ref();
+        let word = word.as_ref();
+        let req = Request::new(
+            Method::GET,
+            self.word_endpoint(customisation_id, Some(word)),
+        );
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(GetWordError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| GetWordError::ConnectionError(e.to_string())),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => {
+                        Err(GetWordError::BadRequest400(word.to_owned(), details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(GetWordError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(GetWordError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(GetWordError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(GetWordError::Forbidden403(details)),
+                    status => Err(GetWordError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Deletes a word from a custom language model. A word that was found in a corpus or grammar
+    /// is deleted too, but comes back if the corpus or grammar is added again. Train the model
+    /// again for the change to take effect
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `word` - The word, as it is spelled
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// stt.delete_word("cust-id", "IEEE").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_word(
+        &self,
+        customisation_id: impl AsRef<str>,
+        word: impl AsRef<str>,
+    ) -> Result<(), DeleteWordError> {
+        let customisation_id = customisation_id.as_ref();
+        let word = word.as_ref();
+        let req = Request::new(
+            Method::DELETE,
+            self.word_endpoint(customisation_id, Some(word)),
+        );
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(DeleteWordError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK | StatusCode::NO_CONTENT => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => {
+                        Err(DeleteWordError::BadRequest400(word.to_owned(), details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(DeleteWordError::Unauthorised401(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::CONFLICT => Err(DeleteWordError::Conflict409(
+                        customisation_id.to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(DeleteWordError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(DeleteWordError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(DeleteWordError::Forbidden403(details)),
+                    status => Err(DeleteWordError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// The URL of the words of a custom language model, or of one of them
+    fn word_endpoint(&self, customisation_id: &str, word: Option<&str>) -> Url {
+        let mut url = self.language_model_endpoint(customisation_id, Some("words"));
+        if let Some(word) = word {
+            url.path_segments_mut().unwrap().push(word);
+        }
+        url
+    }
+}
+
+/// The error for a failed request to add words, which [`add_words()`] and [`add_word()`] share
+///
+/// [`add_words()`]: crate::stt::SpeechToText::add_words()
+/// [`add_word()`]: crate::stt::SpeechToText::add_word()
+fn add_word_error(
+    customisation_id: &str,
+    status: StatusCode,
+    details: Option<ServiceErrorDetails>,
+) -> Result<(), AddWordError> {
+    match status {
+        StatusCode::BAD_REQUEST => Err(AddWordError::BadRequest400(
+            customisation_id.to_owned(),
+            details,
+        )),
+        StatusCode::UNAUTHORIZED => Err(AddWordError::Unauthorised401(
+            customisation_id.to_owned(),
+            details,
+        )),
+        StatusCode::CONFLICT => Err(AddWordError::Conflict409(
+            customisation_id.to_owned(),
+            details,
+        )),
+        StatusCode::INTERNAL_SERVER_ERROR => Err(AddWordError::InternalServerError500(details)),
+        StatusCode::SERVICE_UNAVAILABLE => Err(AddWordError::ServiceUnavailable503(details)),
+        StatusCode::FORBIDDEN => Err(AddWordError::Forbidden403(details)),
+        status => Err(AddWordError::UnexpectedStatus(status, details)),
+    }
+}
@@ -0,0 +1,202 @@
+#[cfg(not(target_arch = "wasm32"))]
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::{Method, Request, StatusCode};
+use serde::Deserialize;
+
+use super::errors::{RegisterCallbackError, UnregisterCallbackError};
+use crate::{auth::Authenticator, errors::ServiceErrorDetails, stt::SpeechToText};
+
+/// The header carrying the signature of a request the service sends to a callback URL
+pub const CALLBACK_SIGNATURE: &str = "X-Callback-Signature";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+/// Whether registering a callback URL added it to the allowlist
+pub enum CallbackStatus {
+    /// The URL answered the challenge and was registered
+    #[serde(rename = "created")]
+    Created,
+    /// The URL had already been registered
+    #[serde(rename = "already created")]
+    AlreadyCreated,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+/// A callback URL that the service may notify of changes to jobs
+pub struct RegisteredCallback {
+    /// Whether the URL was registered by this request
+    pub status: CallbackStatus,
+    /// The callback URL
+    pub url: String,
+}
+
+impl<A: Authenticator> SpeechToText<'_, A> {
+    /// Adds a URL to the allowlist of URLs that [jobs] may send notifications to. The service
+    /// first checks that the URL is yours with a challenge:
+    ///
+    /// 1. It sends a `GET` request to the URL with a random `challenge_string` query parameter
+    /// 2. The server at the URL must answer within 5 seconds with `200 OK`, a `text/plain` body
+    ///    that is exactly the challenge string, and no other text
+    ///
+    /// With a `user_secret`, the challenge request, and every notification sent to the URL
+    /// later, carries a signature in its [`X-Callback-Signature`] header, which
+    /// [`verify_callback_signature()`] checks. The signature of the challenge is made from the
+    /// challenge string, and that of a notification from its body. A URL only needs to be
+    /// registered once for the credentials of this client
+    ///
+    /// # Parameters
+    ///
+    /// * `callback_url` - An `http` or `https` URL to which notifications are sent
+    /// * `user_secret` - A secret the service signs its requests to the URL with, or [`None`]
+    ///   to leave them unsigned
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let callback = stt
+    ///     .register_callback("https://example.com/watson", Some("my-secret"))
+    ///     .await?;
+    /// println!("{} is {:?}", callback.url, callback.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [jobs]: crate::stt::jobs::JobOptions::callback_url()
+    /// [`X-Callback-Signature`]: self::CALLBACK_SIGNATURE
+    /// [`verify_callback_signature()`]: self::verify_callback_signature()
+    /// [`None`]: std::option::Option::None
+    pub async fn register_callback(
+        &self,
+        callback_url: impl AsRef<str>,
+        user_secret: Option<&str>,
+    ) -> Result<RegisteredCallback, RegisterCallbackError> {
+        let mut url = self.service.endpoint("v1/register_callback");
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("callback_url", callback_url.as_ref());
+            if let Some(user_secret) = user_secret {
+                query.append_pair("user_secret", user_secret);
+            }
+        }
+        let req = Request::new(Method::POST, url);
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(RegisterCallbackError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK | StatusCode::CREATED => response
+                .json()
+                .await
+                .map_err(|e| RegisterCallbackError::ConnectionError(e.to_string())),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(RegisterCallbackError::BadRequest400(
+                        callback_url.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(RegisterCallbackError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => {
+                        Err(RegisterCallbackError::Unauthorised401(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(RegisterCallbackError::Forbidden403(details)),
+                    status => Err(RegisterCallbackError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Removes a URL from the allowlist of callback URLs. Jobs that were created with the URL
+    /// can no longer send notifications to it
+    ///
+    /// # Parameters
+    ///
+    /// * `callback_url` - The URL that was registered with [`register_callback()`]
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// stt.unregister_callback("https://example.com/watson").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`register_callback()`]: Self::register_callback()
+    pub async fn unregister_callback(
+        &self,
+        callback_url: impl AsRef<str>,
+    ) -> Result<(), UnregisterCallbackError> {
+        let mut url = self.service.endpoint("v1/unregister_callback");
+        url.query_pairs_mut()
+            .append_pair("callback_url", callback_url.as_ref());
+        let req = Request::new(Method::POST, url);
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(UnregisterCallbackError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(UnregisterCallbackError::BadRequest400(
+                        callback_url.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(UnregisterCallbackError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => {
+                        Err(UnregisterCallbackError::Unauthorised401(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(UnregisterCallbackError::Forbidden403(details)),
+                    status => Err(UnregisterCallbackError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+}
+
+/// Checks that a request to a callback URL was sent by the service, by comparing the
+/// [`X-Callback-Signature`] header it carries with the HMAC-SHA1 signature of its payload
+///
+/// # Parameters
+///
+/// * `user_secret` - The secret the callback URL was registered with
+/// * `payload` - The `challenge_string` query parameter of a challenge, or the body of a
+///   notification
+/// * `signature` - The value of the request's [`X-Callback-Signature`] header
+///
+/// # Example
+/// ```
+/// # use ibm_watson::stt::jobs::verify_callback_signature;
+/// let challenge = "dGVzdA";
+/// let signature = "ZLetd8WOZHbEl+gus9QWvmTQD3M=";
+/// assert!(verify_callback_signature("my-secret", challenge, signature));
+/// assert!(!verify_callback_signature("other-secret", challenge, signature));
+/// ```
+/// [`X-Callback-Signature`]: self::CALLBACK_SIGNATURE
+#[cfg(not(target_arch = "wasm32"))]
+pub fn verify_callback_signature(
+    user_secret: &str,
+    payload: impl AsRef<[u8]>,
+    signature: &str,
+) -> bool {
+    let Ok(signature) = STANDARD.decode(signature.trim()) else {
+        return false;
+    };
+    let key = ring::hmac::Key::new(
+        ring::hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY,
+        user_secret.as_bytes(),
+    );
+    ring::hmac::verify(&key, payload.as_ref(), &signature).is_ok()
+}
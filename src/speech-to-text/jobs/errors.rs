@@ -0,0 +1,164 @@
+use reqwest::StatusCode;
+use thiserror::Error;
+
+use crate::errors::{describe, ServiceErrorDetails};
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when creating a recognition job
+pub enum CreateJobError {
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid"))]
+    /// A required input parameter is null or a specified input parameter or header value is invalid, such as a callback URL that has not been registered
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The request specified an unacceptable media type."))]
+    /// The request specified an unacceptable media type for the audio
+    UnsupportedMediaType415(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "Too many requests have been sent to the service"))]
+    /// The service throttled the request, and it was not retried any further
+    TooManyRequests429(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error."))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable."))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+    #[error("Connection error: {0}")]
+    /// Some other error occurred in the request
+    ConnectionError(String),
+    #[error("{0}")]
+    /// An option sent as a header, such as the customer ID, is not a valid header value
+    InvalidHeader(String),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when checking all recognition jobs
+pub enum CheckJobsError {
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error."))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable."))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+    #[error("Connection error: {0}")]
+    /// Some other error occurred in the request
+    ConnectionError(String),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when checking a recognition job
+pub enum CheckJobError {
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The job {} does not exist or has expired", .0)))]
+    /// The specified job ID is invalid, or the job's results have expired
+    NotFound404(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error."))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable."))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+    #[error("Connection error: {0}")]
+    /// Some other error occurred in the request
+    ConnectionError(String),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when deleting a recognition job
+pub enum DeleteJobError {
+    #[error("{}", describe(.1, &format!("The job {} cannot be deleted while it is being processed", .0)))]
+    /// The service cannot delete a job until it is complete
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The job {} does not exist or has already been deleted", .0)))]
+    /// The specified job ID is invalid, or the job has already been deleted
+    NotFound404(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error."))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable."))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+    #[error("Connection error: {0}")]
+    /// Some other error occurred in the request
+    ConnectionError(String),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when registering a callback URL
+pub enum RegisterCallbackError {
+    #[error("{}", describe(.1, &format!("The callback URL {} is invalid or did not answer the service's challenge", .0)))]
+    /// The callback URL is invalid, or it did not answer the service's challenge with the
+    /// challenge string in time
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable."))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+    #[error("Connection error: {0}")]
+    /// Some other error occurred in the request
+    ConnectionError(String),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when unregistering a callback URL
+pub enum UnregisterCallbackError {
+    #[error("{}", describe(.1, &format!("The callback URL {} is not registered", .0)))]
+    /// The callback URL was not registered, or is invalid
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable."))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+    #[error("Connection error: {0}")]
+    /// Some other error occurred in the request
+    ConnectionError(String),
+}
@@ -0,0 +1,594 @@
+mod callbacks;
+/// Errors that may be returned in asynchronous recognition requests
+pub mod errors;
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::callbacks::verify_callback_signature;
+pub use self::callbacks::{CallbackStatus, RegisteredCallback, CALLBACK_SIGNATURE};
+
+use bytes::Bytes;
+use reqwest::{
+    header::{HeaderValue, CONTENT_TYPE},
+    Body, Method, Request, StatusCode, Url,
+};
+use serde::Deserialize;
+
+use self::errors::{CheckJobError, CheckJobsError, CreateJobError, DeleteJobError};
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::recognition::{chunked_body, stream_content_type};
+use super::{
+    recognition::{detect_content_type, RecognitionOptions, SpeechRecognitionResults},
+    SpeechToText,
+};
+use crate::{
+    auth::Authenticator,
+    errors::ServiceErrorDetails,
+    polling::{errors::WaitError, Polling, Status},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// A change in the status of a job that the service can notify a callback URL of
+pub enum JobEvent {
+    /// The service has started processing the job
+    Started,
+    /// The job is complete. The results are retrieved with [`check_job()`]
+    ///
+    /// [`check_job()`]: crate::stt::SpeechToText::check_job()
+    Completed,
+    /// The job is complete, and its results are sent with the notification
+    CompletedWithResults,
+    /// The job failed
+    Failed,
+}
+
+impl JobEvent {
+    /// The value of the event known to IBM Watson
+    pub fn id(&self) -> &str {
+        match self {
+            JobEvent::Started => "recognitions.started",
+            JobEvent::Completed => "recognitions.completed",
+            JobEvent::CompletedWithResults => "recognitions.completed_with_results",
+            JobEvent::Failed => "recognitions.failed",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// Options for an asynchronous recognition job, on top of the [`RecognitionOptions`] its audio is
+/// recognised with
+///
+/// # Example
+/// ``` no_run
+/// # use ibm_watson::{
+/// #     auth::IamAuthenticator,
+/// #     stt::{jobs::{JobEvent, JobOptions}, recognition::RecognitionOptions, SpeechToText},
+/// # };
+/// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+/// # let auth = IamAuthenticator::new("api_key").await?;
+/// # let stt = SpeechToText::new(&auth, "service_url")?;
+/// let options = JobOptions::new()
+///     .recognition(RecognitionOptions::new().timestamps(true))
+///     .callback_url("https://example.com/watson")
+///     .events([JobEvent::CompletedWithResults, JobEvent::Failed])
+///     .user_token("meeting-42");
+/// let audio = std::fs::read("meeting.flac")?;
+/// let job = stt.create_job(audio, Some("audio/flac"), &options).await?;
+/// println!("{} is {:?}", job.id, job.status);
+/// # Ok(())
+/// # }
+/// ```
+/// [`RecognitionOptions`]: crate::stt::recognition::RecognitionOptions
+pub struct JobOptions {
+    recognition: RecognitionOptions,
+    callback_url: Option<String>,
+    events: Vec<JobEvent>,
+    user_token: Option<String>,
+    results_ttl: Option<u32>,
+}
+
+impl JobOptions {
+    /// Options that leave everything to the service's defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Recognise the audio of the job with `options`
+    ///
+    /// # Parameters
+    ///
+    /// * `options` - The [`RecognitionOptions`] to recognise with. [`interim_results`] are not
+    ///   sent for jobs
+    ///
+    /// [`RecognitionOptions`]: crate::stt::recognition::RecognitionOptions
+    /// [`interim_results`]: crate::stt::recognition::RecognitionOptions::interim_results()
+    pub fn recognition(mut self, options: RecognitionOptions) -> Self {
+        self.recognition = options;
+        self
+    }
+
+    /// Notify a URL when the status of the job changes, instead of having to check it
+    ///
+    /// # Parameters
+    ///
+    /// * `callback_url` - A URL that has been registered with the service, to which it sends a
+    ///   notification for each of the [`events()`]
+    ///
+    /// [`events()`]: Self::events()
+    pub fn callback_url(mut self, callback_url: impl Into<String>) -> Self {
+        self.callback_url = Some(callback_url.into());
+        self
+    }
+
+    /// Which changes to the status of the job to notify the [`callback_url()`] of. Without a
+    /// callback URL, only [`CompletedWithResults`] may be asked for, and it is then only used to
+    /// keep the results
+    ///
+    /// # Parameters
+    ///
+    /// * `events` - The [`JobEvent`]s to notify. Defaults to [`Started`], [`Completed`] and
+    ///   [`Failed`]
+    ///
+    /// [`callback_url()`]: Self::callback_url()
+    /// [`JobEvent`]: self::JobEvent
+    /// [`Started`]: self::JobEvent::Started
+    /// [`Completed`]: self::JobEvent::Completed
+    /// [`CompletedWithResults`]: self::JobEvent::CompletedWithResults
+    /// [`Failed`]: self::JobEvent::Failed
+    pub fn events(mut self, events: impl IntoIterator<Item = JobEvent>) -> Self {
+        self.events = events.into_iter().collect();
+        self
+    }
+
+    /// A token that the service sends with each notification, to tell jobs apart
+    ///
+    /// # Parameters
+    ///
+    /// * `user_token` - The token, which is also returned with the job
+    pub fn user_token(mut self, user_token: impl Into<String>) -> Self {
+        self.user_token = Some(user_token.into());
+        self
+    }
+
+    /// How long the service keeps the results of the job once it is complete
+    ///
+    /// # Parameters
+    ///
+    /// * `minutes` - The number of minutes to keep the results for. Defaults to a week
+    pub fn results_ttl(mut self, minutes: u32) -> Self {
+        self.results_ttl = Some(minutes);
+        self
+    }
+
+    /// Adds the options that are sent as query parameters to `url`
+    pub(crate) fn append_query(&self, url: &mut Url) {
+        {
+            let mut query = url.query_pairs_mut();
+            if let Some(callback_url) = &self.callback_url {
+                query.append_pair("callback_url", callback_url);
+            }
+            if !self.events.is_empty() {
+                let events: Vec<_> = self.events.iter().map(JobEvent::id).collect();
+                query.append_pair("events", &events.join(","));
+            }
+            if let Some(user_token) = &self.user_token {
+                query.append_pair("user_token", user_token);
+            }
+            if let Some(minutes) = self.results_ttl {
+                query.append_pair("results_ttl", &minutes.to_string());
+            }
+        }
+        self.recognition.append_query(url);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// Where a job is in recognising its audio
+pub enum JobStatus {
+    /// The job is waiting to be processed
+    Waiting,
+    /// The service is recognising the audio of the job
+    Processing,
+    /// The job is complete, and its results are available
+    Completed,
+    /// The job failed
+    Failed,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+struct RecognitionJobs {
+    recognitions: Vec<RecognitionJob>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+/// An asynchronous recognition job
+pub struct RecognitionJob {
+    /// The ID of the job
+    pub id: String,
+    /// Where the job is in recognising its audio
+    pub status: JobStatus,
+    /// When the job was created, in ISO 8601 format
+    pub created: Option<String>,
+    /// When the status of the job last changed, in ISO 8601 format
+    pub updated: Option<String>,
+    /// The URL to check the job at. Only returned when the job is created
+    pub url: Option<String>,
+    /// The [`user_token`] the job was created with
+    ///
+    /// [`user_token`]: self::JobOptions::user_token()
+    pub user_token: Option<String>,
+    /// The results of the job, once it is complete
+    #[serde(default)]
+    pub results: Vec<SpeechRecognitionResults>,
+    /// Warnings about the request that created the job, such as an unknown parameter
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+impl<A: Authenticator> SpeechToText<'_, A> {
+    /// Creates a job that recognises `audio` asynchronously. Unlike [`recognise()`], the request
+    /// returns as soon as the audio has been received, so it suits long audio that would take
+    /// longer to recognise than a connection can stay open. The results are either sent to a
+    /// callback URL or kept until they are retrieved
+    ///
+    /// # Parameters
+    ///
+    /// * `audio` - The audio to recognise, of up to 1 GB. [`create_job_chunked()`] sends audio
+    ///   as it is read instead of holding all of it in memory
+    /// * `content_type` - The MIME type of the audio, such as `audio/flac`, or [`None`] to detect
+    ///   it from the audio with [`detect_content_type()`]
+    /// * `options` - The [`JobOptions`] for the job
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     stt::{jobs::JobOptions, SpeechToText},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let audio = std::fs::read("lecture.mp3")?;
+    /// let job = stt
+    ///     .create_job(audio, None, &JobOptions::new())
+    ///     .await?;
+    /// println!("created job {}", job.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`recognise()`]: Self::recognise()
+    /// [`create_job_chunked()`]: Self::create_job_chunked()
+    /// [`JobOptions`]: self::JobOptions
+    /// [`None`]: std::option::Option::None
+    /// [`detect_content_type()`]: super::recognition::detect_content_type()
+    pub async fn create_job(
+        &self,
+        audio: impl Into<Bytes>,
+        content_type: Option<&str>,
+        options: &JobOptions,
+    ) -> Result<RecognitionJob, CreateJobError> {
+        let audio = audio.into();
+        let content_type = content_type.unwrap_or_else(|| detect_content_type(&audio));
+        self.send_job(audio.into(), content_type, options).await
+    }
+
+    /// Creates a job that recognises `audio` asynchronously, as [`create_job()`] does, but sends
+    /// the audio with chunked transfer encoding as it is read, so even a recording of hundreds of
+    /// MB is never held in memory all at once
+    ///
+    /// # Parameters
+    ///
+    /// * `audio` - A [`Stream`] of chunks of audio, of up to 1 GB in total, such as one made from
+    ///   an [`AsyncRead`] with [`read_chunks()`]. The audio ends when the stream does, and an
+    ///   error reading it fails the request with a [`ConnectionError`]
+    /// * `content_type` - The MIME type of the audio, such as `audio/flac`, or [`None`] to detect
+    ///   it from the first chunk with [`detect_content_type()`]
+    /// * `options` - The [`JobOptions`] for the job
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     stt::{jobs::JobOptions, recognition::read_chunks, SpeechToText},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let audio = read_chunks(tokio::fs::File::open("lecture.mp3").await?);
+    /// let job = stt
+    ///     .create_job_chunked(audio, None, &JobOptions::new())
+    ///     .await?;
+    /// println!("created job {}", job.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`create_job()`]: Self::create_job()
+    /// [`Stream`]: futures_util::Stream
+    /// [`AsyncRead`]: tokio::io::AsyncRead
+    /// [`read_chunks()`]: super::recognition::read_chunks()
+    /// [`ConnectionError`]: self::errors::CreateJobError::ConnectionError
+    /// [`None`]: std::option::Option::None
+    /// [`detect_content_type()`]: super::recognition::detect_content_type()
+    /// [`JobOptions`]: self::JobOptions
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn create_job_chunked<E: std::fmt::Display + Send + 'static>(
+        &self,
+        audio: impl futures_util::Stream<Item = Result<Bytes, E>> + Send + 'static,
+        content_type: Option<&str>,
+        options: &JobOptions,
+    ) -> Result<RecognitionJob, CreateJobError> {
+        let (content_type, audio) = stream_content_type(audio, content_type).await;
+        self.send_job(chunked_body(audio), content_type, options)
+            .await
+    }
+
+    /// Creates a job that recognises the audio sent as `body`, of the MIME type `content_type`
+    async fn send_job(
+        &self,
+        body: Body,
+        content_type: &str,
+        options: &JobOptions,
+    ) -> Result<RecognitionJob, CreateJobError> {
+        let mut url = self.service.endpoint("v1/recognitions");
+        options.append_query(&mut url);
+        let mut req = Request::new(Method::POST, url);
+        let content_type = HeaderValue::from_str(content_type).map_err(|_| {
+            CreateJobError::ConnectionError(format!("{content_type:?} is not a valid MIME type"))
+        })?;
+        req.headers_mut().insert(CONTENT_TYPE, content_type);
+        *req.body_mut() = Some(body);
+        options
+            .recognition
+            .apply_headers(&mut req)
+            .map_err(|e| CreateJobError::InvalidHeader(e.to_string()))?;
+
+        let response = self
+            .service
+            .execute_retrying(req)
+            .await
+            .map_err(CreateJobError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK | StatusCode::CREATED => response
+                .json()
+                .await
+                .map_err(|e| CreateJobError::ConnectionError(e.to_string())),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(CreateJobError::BadRequest400(details)),
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE => {
+                        Err(CreateJobError::UnsupportedMediaType415(details))
+                    }
+                    StatusCode::TOO_MANY_REQUESTS => {
+                        Err(CreateJobError::TooManyRequests429(details))
+                    }
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(CreateJobError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(CreateJobError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(CreateJobError::Unauthorised401(details)),
+                    StatusCode::FORBIDDEN => Err(CreateJobError::Forbidden403(details)),
+                    status => Err(CreateJobError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Checks the status of the latest 100 jobs created with the credentials of this client. The
+    /// jobs are returned without their results, which [`check_job()`] returns
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// for job in stt.check_jobs().await? {
+    ///     println!("{} is {:?}", job.id, job.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`check_job()`]: Self::check_job()
+    pub async fn check_jobs(&self) -> Result<Vec<RecognitionJob>, CheckJobsError> {
+        let req = Request::new(Method::GET, self.service.endpoint("v1/recognitions"));
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(CheckJobsError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => {
+                let jobs: RecognitionJobs = response
+                    .json()
+                    .await
+                    .map_err(|e| CheckJobsError::ConnectionError(e.to_string()))?;
+                Ok(jobs.recognitions)
+            }
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(CheckJobsError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(CheckJobsError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(CheckJobsError::Unauthorised401(details)),
+                    StatusCode::FORBIDDEN => Err(CheckJobsError::Forbidden403(details)),
+                    status => Err(CheckJobsError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Checks the status of a job, which carries its results once it is complete. Results are
+    /// kept until they expire, as set by [`results_ttl()`], or the job is deleted
+    ///
+    /// # Parameters
+    ///
+    /// * `id` - The ID of the job
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::{jobs::JobStatus, SpeechToText}};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let job = stt.check_job("job-id").await?;
+    /// if job.status == JobStatus::Completed {
+    ///     for results in job.results {
+    ///         println!("{}", results.transcript());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`results_ttl()`]: self::JobOptions::results_ttl()
+    pub async fn check_job(&self, id: impl AsRef<str>) -> Result<RecognitionJob, CheckJobError> {
+        let req = Request::new(Method::GET, self.job_endpoint(id.as_ref()));
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(CheckJobError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| CheckJobError::ConnectionError(e.to_string())),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::NOT_FOUND => {
+                        Err(CheckJobError::NotFound404(id.as_ref().to_owned(), details))
+                    }
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(CheckJobError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(CheckJobError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(CheckJobError::Unauthorised401(details)),
+                    StatusCode::FORBIDDEN => Err(CheckJobError::Forbidden403(details)),
+                    status => Err(CheckJobError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Waits until a job is complete, checking it with [`check_job()`] as often as `polling`
+    /// asks, and returns its results. If the job failed, [`WaitError::Failed`] carries its
+    /// warnings
+    ///
+    /// # Parameters
+    ///
+    /// * `id` - The ID of the job
+    /// * `polling` - How often, and for how long, to check the job
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     polling::Polling,
+    /// #     stt::{jobs::JobOptions, SpeechToText},
+    /// # };
+    /// # use std::time::Duration;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let audio = std::fs::read("lecture.flac")?;
+    /// let job = stt.create_job(audio, Some("audio/flac"), &JobOptions::new()).await?;
+    /// let polling = Polling::new(Duration::from_secs(10), Duration::from_secs(3600));
+    /// for results in stt.wait_for_job(&job.id, &polling).await? {
+    ///     println!("{}", results.transcript());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`check_job()`]: Self::check_job()
+    /// [`WaitError::Failed`]: crate::polling::errors::WaitError::Failed
+    pub async fn wait_for_job(
+        &self,
+        id: impl AsRef<str>,
+        polling: &Polling,
+    ) -> Result<Vec<SpeechRecognitionResults>, WaitError<CheckJobError>> {
+        polling
+            .until(|| async {
+                let job = self.check_job(id.as_ref()).await?;
+                Ok(match job.status {
+                    JobStatus::Completed => Status::Ready(job.results),
+                    JobStatus::Failed if job.warnings.is_empty() => {
+                        Status::Failed(format!("job {} failed", job.id))
+                    }
+                    JobStatus::Failed => Status::Failed(job.warnings.join("; ")),
+                    JobStatus::Waiting | JobStatus::Processing => Status::Pending,
+                })
+            })
+            .await
+    }
+
+    /// Deletes a job, along with its results. A job cannot be deleted while it is being
+    /// processed
+    ///
+    /// # Parameters
+    ///
+    /// * `id` - The ID of the job
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// if stt.delete_job("job-id").await.is_ok() {
+    ///     println!("job deleted");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_job(&self, id: impl AsRef<str>) -> Result<(), DeleteJobError> {
+        let req = Request::new(Method::DELETE, self.job_endpoint(id.as_ref()));
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(DeleteJobError::ConnectionError)?;
+        match response.status() {
+            StatusCode::NO_CONTENT => Ok(()),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(DeleteJobError::BadRequest400(
+                        id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::NOT_FOUND => {
+                        Err(DeleteJobError::NotFound404(id.as_ref().to_owned(), details))
+                    }
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(DeleteJobError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(DeleteJobError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(DeleteJobError::Unauthorised401(details)),
+                    StatusCode::FORBIDDEN => Err(DeleteJobError::Forbidden403(details)),
+                    status => Err(DeleteJobError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// The URL of a single job
+    fn job_endpoint(&self, id: &str) -> Url {
+        let mut url = self.service.endpoint("v1/recognitions");
+        url.path_segments_mut().unwrap().push(id);
+        url
+    }
+}
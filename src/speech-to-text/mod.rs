@@ -0,0 +1,332 @@
+use std::sync::Arc;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::transport::Transport;
+use crate::{
+    auth::{Authenticator, IamAuthenticator, RetryPolicy},
+    client::{AuthenticatorRef, ServiceBuilder, ServiceClient},
+    region::Region,
+};
+
+use self::errors::BuildError;
+
+/// Improve recognition of domain-specific speech with custom models
+pub mod customisations;
+/// Errors that may be returned when building a client
+pub mod errors;
+/// Recognise long audio asynchronously
+pub mod jobs;
+/// View information about the models speech can be recognised with
+pub mod models;
+/// Recognise speech in audio
+pub mod recognition;
+
+/// Creates a client used to send requests to your Speech To Text endpoint
+///
+/// A client is cheap to clone: clones share its connection pool and authenticator. A client made
+/// with [`new_shared()`] owns its authenticator, so it is `'static` and, when the authenticator
+/// is, [`Send`] and [`Sync`]
+///
+/// [`new_shared()`]: Self::new_shared()
+/// [`Send`]: std::marker::Send
+/// [`Sync`]: std::marker::Sync
+pub struct SpeechToText<'a, A = IamAuthenticator> {
+    service: ServiceClient<'a, A>,
+}
+
+impl<A> Clone for SpeechToText<'_, A> {
+    fn clone(&self) -> Self {
+        Self {
+            service: self.service.clone(),
+        }
+    }
+}
+
+impl<A: Authenticator> SpeechToText<'static, A> {
+    /// Create a Speech To Text instance that shares ownership of `authenticator`, instead of
+    /// borrowing it like [`new()`]. The client does not borrow anything, so it can be moved into
+    /// spawned tasks or stored in application state
+    ///
+    /// # Parameters
+    /// * `authenticator` - The [`Authenticator`], such as an [`IamAuthenticator`], used to authorise requests
+    /// * `service_url` - The endpoint for your speech to text instance. All Speech To Text
+    ///   requests will be made to this endpoint. Fails with [`InvalidServiceUrl`] if it cannot
+    ///   be parsed
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # use std::sync::Arc;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = Arc::new(IamAuthenticator::new("api_key").await?);
+    /// let stt = SpeechToText::new_shared(auth, "service_url")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`new()`]: Self::new()
+    /// [`InvalidServiceUrl`]: self::errors::BuildError::InvalidServiceUrl
+    /// [`Authenticator`]: crate::auth::Authenticator
+    /// [`IamAuthenticator`]: crate::auth::IamAuthenticator
+    pub fn new_shared(
+        authenticator: Arc<A>,
+        service_url: impl AsRef<str>,
+    ) -> Result<Self, BuildError> {
+        Self::with_service_url(
+            AuthenticatorRef::Shared(authenticator),
+            service_url.as_ref(),
+        )
+    }
+
+    /// Configure a Speech To Text instance that shares ownership of `authenticator`, like
+    /// [`new_shared()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `authenticator` - The [`Authenticator`], such as an [`IamAuthenticator`], used to authorise requests
+    ///
+    /// [`new_shared()`]: Self::new_shared()
+    /// [`Authenticator`]: crate::auth::Authenticator
+    /// [`IamAuthenticator`]: crate::auth::IamAuthenticator
+    pub fn shared_builder(authenticator: Arc<A>) -> SpeechToTextBuilder<'static, A> {
+        SpeechToTextBuilder::new(AuthenticatorRef::Shared(authenticator))
+    }
+}
+
+impl<'a, A: Authenticator> SpeechToText<'a, A> {
+    /// Create a new Speech To Text instance. This instance will be used to make all the requests
+    /// to the speech to text service.
+    ///
+    /// # Parameters
+    /// * `authenticator` - The [`Authenticator`], such as an [`IamAuthenticator`], used to authorise requests
+    /// * `service_url` - The endpoint for your speech to text instance. All Speech To Text
+    ///   requests will be made to this endpoint. Fails with [`InvalidServiceUrl`] if it cannot
+    ///   be parsed
+    ///
+    /// # Examples
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::new("api_key").await?;
+    /// let stt = SpeechToText::new(&auth, "service_url")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`InvalidServiceUrl`]: self::errors::BuildError::InvalidServiceUrl
+    /// [`Authenticator`]: crate::auth::Authenticator
+    /// [`IamAuthenticator`]: crate::auth::IamAuthenticator
+    pub fn new(authenticator: &'a A, service_url: impl AsRef<str>) -> Result<Self, BuildError> {
+        Self::with_service_url(
+            AuthenticatorRef::Borrowed(authenticator),
+            service_url.as_ref(),
+        )
+    }
+
+    /// Configure a Speech To Text instance, such as one in a particular [`Region`]
+    ///
+    /// # Parameters
+    ///
+    /// * `authenticator` - The [`Authenticator`], such as an [`IamAuthenticator`], used to authorise requests
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, region::Region, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::new("api_key").await?;
+    /// let stt = SpeechToText::builder(&auth)
+    ///     .region(Region::London, "instance_id")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Region`]: crate::region::Region
+    /// [`Authenticator`]: crate::auth::Authenticator
+    /// [`IamAuthenticator`]: crate::auth::IamAuthenticator
+    pub fn builder(authenticator: &'a A) -> SpeechToTextBuilder<'a, A> {
+        SpeechToTextBuilder::new(AuthenticatorRef::Borrowed(authenticator))
+    }
+
+    fn with_service_url(
+        authenticator: AuthenticatorRef<'a, A>,
+        service_url: &str,
+    ) -> Result<Self, BuildError> {
+        Ok(Self {
+            service: ServiceClient::new(authenticator, service_url)?,
+        })
+    }
+
+    /// Opt out of IBM using the data sent with every request from this client, such as the audio
+    /// to recognise, to improve its services
+    ///
+    /// # Parameters
+    ///
+    /// * `opt_out` - Whether to send `X-Watson-Learning-Opt-Out: true` with every request
+    ///
+    /// # Examples
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// let mut stt = SpeechToText::new(&auth, "service_url")?;
+    /// stt.set_learning_opt_out(true);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_learning_opt_out(&mut self, opt_out: bool) {
+        self.service.learning_opt_out = opt_out;
+    }
+
+    /// Associate the data sent with every request from this client, such as the audio to
+    /// recognise, with a customer ID, so that all of a customer's data can later be deleted
+    ///
+    /// # Parameters
+    ///
+    /// * `customer_id` - The customer ID to send in the `X-Watson-Metadata` header, or [`None`] to
+    ///   stop sending one
+    ///
+    /// # Examples
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// let mut stt = SpeechToText::new(&auth, "service_url")?;
+    /// stt.set_customer_id(Some("customer-1"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`None`]: std::option::Option::None
+    pub fn set_customer_id(&mut self, customer_id: Option<&str>) {
+        self.service.customer_id = customer_id.map(ToOwned::to_owned);
+    }
+
+    /// Retry recognition requests that the service throttled with a `429 Too Many Requests` or
+    /// `503 Service Unavailable` response, waiting as long as its `Retry-After` header asks. By
+    /// default, requests are not retried
+    ///
+    /// # Parameters
+    ///
+    /// * `retry` - The [`RetryPolicy`] to follow
+    ///
+    /// [`RetryPolicy`]: crate::auth::RetryPolicy
+    pub fn set_retry(&mut self, retry: RetryPolicy) {
+        self.service.retry = retry;
+    }
+
+    /// Send requests through `transport` instead of over the network, such as a
+    /// [`MockTransport`] in tests. Requests still carry every header, including
+    /// `Authorization`, when they reach it
+    ///
+    /// # Parameters
+    ///
+    /// * `transport` - The [`Transport`] to send requests with
+    ///
+    /// [`MockTransport`]: crate::transport::MockTransport
+    /// [`Transport`]: crate::transport::Transport
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_transport(&mut self, transport: impl Transport + 'static) {
+        self.service.transport = Some(Arc::new(transport));
+    }
+}
+
+/// Configures a [`SpeechToText`] client before it is used
+///
+/// [`SpeechToText`]: self::SpeechToText
+pub struct SpeechToTextBuilder<'a, A = IamAuthenticator> {
+    service: ServiceBuilder<'a, A>,
+}
+
+impl<'a, A: Authenticator> SpeechToTextBuilder<'a, A> {
+    /// A builder with nothing but its authenticator set
+    fn new(authenticator: AuthenticatorRef<'a, A>) -> Self {
+        Self {
+            service: ServiceBuilder::new(authenticator),
+        }
+    }
+
+    /// Send requests to the instance at `service_url`, such as a private endpoint or an IBM Cloud
+    /// Pak for Data installation
+    ///
+    /// # Parameters
+    ///
+    /// * `service_url` - The endpoint for your speech to text instance, including its
+    ///   `/instances/{instance_id}` path
+    pub fn service_url(mut self, service_url: impl Into<String>) -> Self {
+        self.service.service_url = Some(service_url.into());
+        self
+    }
+
+    /// Send requests to an instance on IBM Cloud's public endpoint for `region`
+    ///
+    /// # Parameters
+    ///
+    /// * `region` - The [`Region`] the instance was created in
+    /// * `instance_id` - The ID (GUID) of the instance
+    ///
+    /// [`Region`]: crate::region::Region
+    pub fn region(mut self, region: Region, instance_id: impl AsRef<str>) -> Self {
+        self.service.service_url = Some(region.service_url("speech-to-text", instance_id.as_ref()));
+        self
+    }
+
+    /// Opt out of IBM using the data sent to improve its services, like
+    /// [`SpeechToText::set_learning_opt_out()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `opt_out` - Whether to send `X-Watson-Learning-Opt-Out: true` with every request
+    ///
+    /// [`SpeechToText::set_learning_opt_out()`]: self::SpeechToText::set_learning_opt_out()
+    pub fn learning_opt_out(mut self, opt_out: bool) -> Self {
+        self.service.learning_opt_out = opt_out;
+        self
+    }
+
+    /// Associate the data sent with a customer ID, like [`SpeechToText::set_customer_id()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `customer_id` - The customer ID to send in the `X-Watson-Metadata` header
+    ///
+    /// [`SpeechToText::set_customer_id()`]: self::SpeechToText::set_customer_id()
+    pub fn customer_id(mut self, customer_id: impl Into<String>) -> Self {
+        self.service.customer_id = Some(customer_id.into());
+        self
+    }
+
+    /// Retry throttled recognition requests, like [`SpeechToText::set_retry()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `retry` - The [`RetryPolicy`] to follow
+    ///
+    /// [`SpeechToText::set_retry()`]: self::SpeechToText::set_retry()
+    /// [`RetryPolicy`]: crate::auth::RetryPolicy
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.service.retry = retry;
+        self
+    }
+
+    /// Send requests through `transport`, like [`SpeechToText::set_transport()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `transport` - The [`Transport`] to send requests with
+    ///
+    /// [`SpeechToText::set_transport()`]: self::SpeechToText::set_transport()
+    /// [`Transport`]: crate::transport::Transport
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.service.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Creates the client, checking that its service URL is valid
+    pub fn build(self) -> Result<SpeechToText<'a, A>, BuildError> {
+        Ok(SpeechToText {
+            service: self.service.build()?,
+        })
+    }
+}
@@ -0,0 +1,69 @@
+use reqwest::StatusCode;
+use thiserror::Error;
+
+use crate::errors::{describe, ServiceErrorDetails};
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when listing [`SpeechModel`]s
+///
+/// [`SpeechModel`]: crate::stt::models::SpeechModel
+pub enum ListModelsError {
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The request specified an Accept header with an incompatible content type."))]
+    /// The request specified an Accept header with an incompatible content type
+    NotAcceptable406(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The request specified an unacceptable media type."))]
+    /// The request specified an unacceptable media type
+    UnsupportedMediaType415(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error."))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable."))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+}
+
+#[derive(Error, Debug)]
+/// Errors that may be returned when getting information about a particular [`SpeechModel`]
+///
+/// [`SpeechModel`]: crate::stt::models::SpeechModel
+pub enum GetModelError {
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The model {} does not exist", .0)))]
+    /// The specified model name is invalid
+    NotFound404(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The request specified an Accept header with an incompatible content type."))]
+    /// The request specified an Accept header with an incompatible content type
+    NotAcceptable406(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The request specified an unacceptable media type."))]
+    /// The request specified an unacceptable media type
+    UnsupportedMediaType415(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error."))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable."))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+    #[error("{0}")]
+    /// There was an error making the request
+    ConnectionError(String),
+}
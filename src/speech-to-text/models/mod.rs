@@ -0,0 +1,179 @@
+use reqwest::{Method, Request, StatusCode};
+use serde::{Deserialize, Serialize};
+
+/// Errors that may be returned in making model requests
+pub mod errors;
+
+use self::errors::{GetModelError, ListModelsError};
+
+use super::SpeechToText;
+use crate::{auth::Authenticator, errors::ServiceErrorDetails};
+
+#[derive(Clone, Debug, Eq, PartialEq, Default, Serialize, Deserialize)]
+/// A model that speech can be recognised with
+pub struct SpeechModel {
+    /// The name of the model, such as `en-US_Telephony`. Use this as the model identifier in
+    /// all requests
+    pub name: String,
+    /// The language and region of the model (for example, en-US)
+    pub language: String,
+    /// The sampling rate, in Hertz, of the audio the model is for: 8000 for telephony and
+    /// narrowband models, or 16000 for multimedia and broadband models
+    pub rate: u32,
+    /// The URI of the model
+    pub url: String,
+    /// Additional service [features](SupportedFeatures) that are supported with the model
+    #[serde(default)]
+    pub supported_features: SupportedFeatures,
+    /// A textual description of the model
+    pub description: String,
+}
+
+impl SpeechModel {
+    /// Whether the model is for telephony audio, sampled at 8 kHz, rather than multimedia audio
+    /// sampled at 16 kHz or more. Audio sampled at a higher rate than a model's is down-sampled
+    /// to it, but audio sampled at a lower rate cannot be recognised
+    pub fn is_telephony(&self) -> bool {
+        self.rate <= 8000
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Default, Serialize, Deserialize)]
+/// Additional service features that are supported with a model
+pub struct SupportedFeatures {
+    /// Whether the model can be customised with a custom language model
+    pub custom_language_model: bool,
+    /// Whether the model can be customised with a custom acoustic model
+    #[serde(default)]
+    pub custom_acoustic_model: bool,
+    /// Whether the model can label which speaker said each word
+    pub speaker_labels: bool,
+    /// Whether the model can recognise with low latency, returning results sooner
+    #[serde(default)]
+    pub low_latency: bool,
+}
+
+#[derive(Deserialize)]
+struct SpeechModels {
+    models: Vec<SpeechModel>,
+}
+
+impl<A: Authenticator> SpeechToText<'_, A> {
+    /// Lists all models available for use with the service. The information includes the
+    /// [`name`], [`language`], sampling [`rate`] and features of each model. To see information
+    /// about a specific model, use [`get_model()`]
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let telephony: Vec<_> = stt
+    ///     .list_models()
+    ///     .await?
+    ///     .into_iter()
+    ///     .filter(|model| model.language == "en-GB" && model.is_telephony())
+    ///     .collect();
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`name`]: self::SpeechModel::name
+    /// [`language`]: self::SpeechModel::language
+    /// [`rate`]: self::SpeechModel::rate
+    /// [`get_model()`]: Self::get_model()
+    pub async fn list_models(&self) -> Result<Vec<SpeechModel>, ListModelsError> {
+        let req = Request::new(Method::GET, self.service.endpoint("v1/models"));
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(ListModelsError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => {
+                let models: SpeechModels = response
+                    .json()
+                    .await
+                    .map_err(|e| ListModelsError::ConnectionError(e.to_string()))?;
+                Ok(models.models)
+            }
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::NOT_ACCEPTABLE => Err(ListModelsError::NotAcceptable406(details)),
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE => {
+                        Err(ListModelsError::UnsupportedMediaType415(details))
+                    }
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(ListModelsError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(ListModelsError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(ListModelsError::Unauthorised401(details)),
+                    StatusCode::FORBIDDEN => Err(ListModelsError::Forbidden403(details)),
+                    status => Err(ListModelsError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+
+    /// Gets information about a single model, such as to check that it supports a feature
+    /// before recognising with it
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the model, such as `en-US_Multimedia`
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, stt::SpeechToText};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let model = stt.get_model("en-US_Multimedia").await?;
+    /// println!("Speaker labels: {}", model.supported_features.speaker_labels);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_model(&self, name: impl AsRef<str>) -> Result<SpeechModel, GetModelError> {
+        let mut url = self.service.endpoint("v1/models");
+        url.path_segments_mut().unwrap().push(name.as_ref());
+        let req = Request::new(Method::GET, url);
+
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(GetModelError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| GetModelError::ConnectionError(e.to_string())),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::NOT_FOUND => Err(GetModelError::NotFound404(
+                        name.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::NOT_ACCEPTABLE => Err(GetModelError::NotAcceptable406(details)),
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE => {
+                        Err(GetModelError::UnsupportedMediaType415(details))
+                    }
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(GetModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(GetModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(GetModelError::Unauthorised401(details)),
+                    StatusCode::FORBIDDEN => Err(GetModelError::Forbidden403(details)),
+                    status => Err(GetModelError::UnexpectedStatus(status, details)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt::Display;
+
+use bytes::Bytes;
+use futures_util::{stream, Stream, StreamExt, TryStreamExt};
+use reqwest::Body;
+
+use super::{
+    detect_content_type, errors::RecognitionError, RecognitionOptions, SpeechRecognitionResults,
+};
+use crate::{auth::Authenticator, stt::SpeechToText};
+
+/// The content type of `audio`: `content_type` if it is given, or else the one detected from
+/// its first chunk. The chunk read to detect it is still at the start of the audio returned
+pub(crate) async fn stream_content_type<E>(
+    audio: impl Stream<Item = Result<Bytes, E>>,
+    content_type: Option<&str>,
+) -> (&str, impl Stream<Item = Result<Bytes, E>>) {
+    let mut audio = Box::pin(audio);
+    let mut first = None;
+    let content_type = match content_type {
+        Some(content_type) => content_type,
+        None => {
+            first = audio.next().await;
+            match &first {
+                Some(Ok(chunk)) => detect_content_type(chunk),
+                _ => detect_content_type(&[]),
+            }
+        }
+    };
+    (content_type, stream::iter(first).chain(audio))
+}
+
+/// A request body that sends each chunk of `audio` as it is read, with chunked transfer encoding
+pub(crate) fn chunked_body<E: Display>(
+    audio: impl Stream<Item = Result<Bytes, E>> + Send + 'static,
+) -> Body {
+    Body::from(hyper::Body::wrap_stream(
+        audio.map_err(|e| format!("the audio could not be read: {e}")),
+    ))
+}
+
+impl<A: Authenticator> SpeechToText<'_, A> {
+    /// Recognises the speech in `audio` as [`recognise()`] does, but sends the audio over HTTP
+    /// with chunked transfer encoding as it is read, so it is never held in memory all at once.
+    /// The service still waits for all of the audio before returning any results, and a request
+    /// can carry up to 100 MB of it. [`create_job_chunked()`] takes up to 1 GB, and
+    /// [`recognise_stream()`] has no limit
+    ///
+    /// # Parameters
+    ///
+    /// * `audio` - A [`Stream`] of chunks of audio, such as one made from an [`AsyncRead`] with
+    ///   [`read_chunks()`]. The audio ends when the stream does, and an error reading it fails
+    ///   the request with a [`ConnectionError`]
+    /// * `content_type` - The MIME type of the audio, such as `audio/flac`, `audio/wav` or
+    ///   `audio/l16;rate=16000`, or [`None`] to detect it from the first chunk with
+    ///   [`detect_content_type()`]
+    /// * `options` - The [`RecognitionOptions`] to recognise with
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     stt::{
+    /// #         recognition::{read_chunks, RecognitionOptions},
+    /// #         SpeechToText,
+    /// #     },
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let audio = read_chunks(tokio::fs::File::open("interview.flac").await?);
+    /// let results = stt
+    ///     .recognise_chunked(audio, None, &RecognitionOptions::new())
+    ///     .await?;
+    /// println!("{}", results.transcript());
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`recognise()`]: Self::recognise()
+    /// [`create_job_chunked()`]: Self::create_job_chunked()
+    /// [`recognise_stream()`]: Self::recognise_stream()
+    /// [`Stream`]: futures_util::Stream
+    /// [`AsyncRead`]: tokio::io::AsyncRead
+    /// [`read_chunks()`]: super::read_chunks()
+    /// [`ConnectionError`]: super::errors::RecognitionError::ConnectionError
+    /// [`None`]: std::option::Option::None
+    /// [`detect_content_type()`]: super::detect_content_type()
+    /// [`RecognitionOptions`]: super::RecognitionOptions
+    pub async fn recognise_chunked<E: Display + Send + 'static>(
+        &self,
+        audio: impl Stream<Item = Result<Bytes, E>> + Send + 'static,
+        content_type: Option<&str>,
+        options: &RecognitionOptions,
+    ) -> Result<SpeechRecognitionResults, RecognitionError> {
+        let (content_type, audio) = stream_content_type(audio, content_type).await;
+        self.send_recognition(chunked_body(audio), content_type, options)
+            .await
+    }
+}
@@ -0,0 +1,57 @@
+use reqwest::StatusCode;
+use thiserror::Error;
+
+use crate::errors::{describe, ServiceErrorDetails};
+
+#[derive(Error, Debug)]
+/// Errors that may be returned in speech recognition requests
+pub enum RecognitionError {
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid"))]
+    /// A required input parameter is null or a specified input parameter or header value is invalid, such as audio that does not match its content type
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The specified model or custom model does not exist"))]
+    /// The specified model or custom model does not exist. The message is Model '{model}' not found
+    NotFound404(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The request specified an incompatible content type"))]
+    /// The request specified an Accept header with an incompatible content type
+    NotAcceptable406(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The connection was closed because no audio was received"))]
+    /// The service closed the connection because it received no audio for longer than the inactivity timeout
+    RequestTimeout408(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The audio is too large for a single request"))]
+    /// The audio is larger than the 100 MB a single request can carry
+    PayloadTooLarge413(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The request specified an unacceptable media type."))]
+    /// The request specified an unacceptable media type for the audio
+    UnsupportedMediaType415(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "Too many requests have been sent to the service"))]
+    /// The service throttled the request, and it was not retried any further
+    TooManyRequests429(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error."))]
+    /// The service experienced an internal error
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable."))]
+    /// The service is currently unavailable
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
+    #[error("Connection error: {0}")]
+    /// Some other error occurred in the request
+    ConnectionError(String),
+    #[error("{0}")]
+    /// An option sent as a header, such as the customer ID, is not a valid header value
+    InvalidHeader(String),
+    #[error("Could not read the audio: {0}")]
+    /// The audio being streamed to the service could not be read
+    AudioError(String),
+    #[error("The service reported an error: {0}")]
+    /// The service reported an error while recognising over a WebSocket
+    ServiceError(String),
+}
@@ -0,0 +1,822 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod chunked;
+/// Errors that may be returned in speech recognition requests
+pub mod errors;
+mod sniff;
+#[cfg(not(target_arch = "wasm32"))]
+mod websocket;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use self::chunked::{chunked_body, stream_content_type};
+pub use self::sniff::detect_content_type;
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::websocket::{read_chunks, RecognitionEvent};
+
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use reqwest::{
+    header::{HeaderValue, CONTENT_TYPE},
+    multipart::{Form, Part},
+    Body, Method, Request, StatusCode, Url,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use self::errors::RecognitionError;
+
+use super::SpeechToText;
+use crate::{
+    auth::Authenticator,
+    client::{metadata_header, LEARNING_OPT_OUT, WATSON_METADATA},
+    errors::ServiceErrorDetails,
+};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// Options for a recognition request. Anything left unset uses the service's default
+///
+/// # Example
+/// ``` no_run
+/// # use ibm_watson::{
+/// #     auth::IamAuthenticator,
+/// #     stt::{recognition::RecognitionOptions, SpeechToText},
+/// # };
+/// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+/// # let auth = IamAuthenticator::new("api_key").await?;
+/// # let stt = SpeechToText::new(&auth, "service_url")?;
+/// let options = RecognitionOptions::new()
+///     .model("en-GB_Telephony")
+///     .timestamps(true);
+/// let audio = std::fs::read("call.flac")?;
+/// let results = stt.recognise(audio, Some("audio/flac"), &options).await?;
+/// println!("{}", results.transcript());
+/// # Ok(())
+/// # }
+/// ```
+pub struct RecognitionOptions {
+    model: Option<String>,
+    language_customisation_id: Option<String>,
+    acoustic_customisation_id: Option<String>,
+    base_model_version: Option<String>,
+    customisation_weight: Option<f64>,
+    grammar_name: Option<String>,
+    inactivity_timeout: Option<i32>,
+    timestamps: Option<bool>,
+    speaker_labels: Option<bool>,
+    smart_formatting: Option<bool>,
+    redaction: Option<bool>,
+    keywords: Option<(Vec<String>, f64)>,
+    max_alternatives: Option<u32>,
+    word_alternatives_threshold: Option<f64>,
+    word_confidence: Option<bool>,
+    profanity_filter: Option<bool>,
+    interim_results: Option<bool>,
+    learning_opt_out: Option<bool>,
+    customer_id: Option<String>,
+}
+
+impl RecognitionOptions {
+    /// Options that leave everything to the service's defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Recognise the audio with `model`, such as `en-US_Multimedia`. Defaults to
+    /// `en-US_BroadbandModel`
+    ///
+    /// # Parameters
+    ///
+    /// * `model` - The name of the model. The model's sampling rate should match that of the
+    ///   audio: telephony and narrowband models are for 8 kHz audio, multimedia and broadband
+    ///   ones for 16 kHz or more
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Recognise with a custom language model
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of a custom language model that is
+    ///   based on the model of the request. You must make the request with credentials for the
+    ///   instance of the service that owns the custom model
+    pub fn language_customisation_id(mut self, customisation_id: impl Into<String>) -> Self {
+        self.language_customisation_id = Some(customisation_id.into());
+        self
+    }
+
+    /// Recognise with a custom acoustic model
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of a custom acoustic model that is
+    ///   based on the model of the request. You must make the request with credentials for the
+    ///   instance of the service that owns the custom model
+    pub fn acoustic_customisation_id(mut self, customisation_id: impl Into<String>) -> Self {
+        self.acoustic_customisation_id = Some(customisation_id.into());
+        self
+    }
+
+    /// Recognise with a particular version of the model, such as the one custom models were
+    /// last trained against, instead of the latest
+    ///
+    /// # Parameters
+    ///
+    /// * `version` - The version of the base model
+    pub fn base_model_version(mut self, version: impl Into<String>) -> Self {
+        self.base_model_version = Some(version.into());
+        self
+    }
+
+    /// How much weight to give words from the custom language model over words from the base
+    /// model
+    ///
+    /// # Parameters
+    ///
+    /// * `weight` - A weight between `0.0` and `1.0`. Defaults to the weight the custom model was
+    ///   trained with, or `0.3`
+    pub fn customisation_weight(mut self, weight: f64) -> Self {
+        self.customisation_weight = Some(weight);
+        self
+    }
+
+    /// Only recognise the phrases a grammar of the custom language model describes. The custom
+    /// model must be set with [`language_customisation_id()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `grammar_name` - The name of a grammar that was added to the custom model with
+    ///   [`add_grammar()`]
+    ///
+    /// [`language_customisation_id()`]: Self::language_customisation_id()
+    /// [`add_grammar()`]: super::SpeechToText::add_grammar()
+    pub fn grammar_name(mut self, grammar_name: impl Into<String>) -> Self {
+        self.grammar_name = Some(grammar_name.into());
+        self
+    }
+
+    /// How long the service waits for speech before it closes the connection
+    ///
+    /// # Parameters
+    ///
+    /// * `seconds` - The number of seconds of silence or noise after which the request fails
+    ///   with [`RequestTimeout408`], or `-1` for no timeout. Defaults to 30
+    ///
+    /// [`RequestTimeout408`]: self::errors::RecognitionError::RequestTimeout408
+    pub fn inactivity_timeout(mut self, seconds: i32) -> Self {
+        self.inactivity_timeout = Some(seconds);
+        self
+    }
+
+    /// Return when each word starts and ends in the audio
+    ///
+    /// # Parameters
+    ///
+    /// * `timestamps` - Whether to fill the [`timestamps`] of every alternative. Off by default
+    ///
+    /// [`timestamps`]: self::SpeechRecognitionAlternative::timestamps
+    pub fn timestamps(mut self, timestamps: bool) -> Self {
+        self.timestamps = Some(timestamps);
+        self
+    }
+
+    /// Label which speaker said each word, for audio with more than one speaker. Turning speaker
+    /// labels on also turns on [`timestamps()`], which the labels are matched to
+    ///
+    /// # Parameters
+    ///
+    /// * `speaker_labels` - Whether to fill the [`speaker_labels`] of the results. Off by
+    ///   default
+    ///
+    /// [`timestamps()`]: Self::timestamps()
+    /// [`speaker_labels`]: self::SpeechRecognitionResults::speaker_labels
+    pub fn speaker_labels(mut self, speaker_labels: bool) -> Self {
+        self.speaker_labels = Some(speaker_labels);
+        self
+    }
+
+    /// Write dates, times, numbers, currency amounts, phone numbers and internet addresses in
+    /// transcripts the way they are usually written, such as `$3.50` for "three dollars fifty
+    /// cents". Only US English, Japanese and Spanish models support smart formatting
+    ///
+    /// # Parameters
+    ///
+    /// * `smart_formatting` - Whether to format transcripts. Off by default
+    pub fn smart_formatting(mut self, smart_formatting: bool) -> Self {
+        self.smart_formatting = Some(smart_formatting);
+        self
+    }
+
+    /// Mask numbers of three or more digits in transcripts with `X`, such as credit card
+    /// numbers, to keep sensitive information out of them. Redaction turns on
+    /// [`smart_formatting()`], and the service then returns a single alternative and no interim
+    /// results. Only US English, Japanese and Korean models support redaction
+    ///
+    /// # Parameters
+    ///
+    /// * `redaction` - Whether to redact numbers. Off by default
+    ///
+    /// [`smart_formatting()`]: Self::smart_formatting()
+    pub fn redaction(mut self, redaction: bool) -> Self {
+        self.redaction = Some(redaction);
+        self
+    }
+
+    /// Spot keywords in the audio, filling in where each was said in the [`keywords_result`] of
+    /// the final results. A keyword may be a phrase of several words
+    ///
+    /// # Parameters
+    ///
+    /// * `keywords` - The keywords to spot, up to 1,000 of them
+    /// * `threshold` - How confident the service must be, between `0.0` and `1.0`, that a keyword
+    ///   was said to report it
+    ///
+    /// [`keywords_result`]: self::SpeechRecognitionResult::keywords_result
+    pub fn keywords(mut self, keywords: &[&str], threshold: f64) -> Self {
+        let keywords = keywords.iter().map(|&keyword| keyword.to_owned()).collect();
+        self.keywords = Some((keywords, threshold));
+        self
+    }
+
+    /// How many [`alternatives`] to return for each utterance
+    ///
+    /// # Parameters
+    ///
+    /// * `max_alternatives` - The most alternatives to return. Defaults to 1
+    ///
+    /// [`alternatives`]: self::SpeechRecognitionResult::alternatives
+    pub fn max_alternatives(mut self, max_alternatives: u32) -> Self {
+        self.max_alternatives = Some(max_alternatives);
+        self
+    }
+
+    /// Return the other words that might have been said in place of each word, in the
+    /// [`word_alternatives`] of the final results
+    ///
+    /// # Parameters
+    ///
+    /// * `threshold` - How confident the service must be, between `0.0` and `1.0`, that a word
+    ///   was said to return it as an alternative
+    ///
+    /// [`word_alternatives`]: self::SpeechRecognitionResult::word_alternatives
+    pub fn word_alternatives_threshold(mut self, threshold: f64) -> Self {
+        self.word_alternatives_threshold = Some(threshold);
+        self
+    }
+
+    /// Return how confident the service is in each word of the best alternative
+    ///
+    /// # Parameters
+    ///
+    /// * `word_confidence` - Whether to fill the [`word_confidence`] of the best alternative.
+    ///   Off by default
+    ///
+    /// [`word_confidence`]: self::SpeechRecognitionAlternative::word_confidence
+    pub fn word_confidence(mut self, word_confidence: bool) -> Self {
+        self.word_confidence = Some(word_confidence);
+        self
+    }
+
+    /// Whether to censor profanity in transcripts, replacing it with asterisks. The service
+    /// censors US English and Japanese transcripts by default; the filter has no effect on
+    /// other languages
+    ///
+    /// # Parameters
+    ///
+    /// * `profanity_filter` - Whether to censor profanity. Turn it off for verbatim transcripts
+    pub fn profanity_filter(mut self, profanity_filter: bool) -> Self {
+        self.profanity_filter = Some(profanity_filter);
+        self
+    }
+
+    /// Send results while the audio is still being recognised, which may change before they are
+    /// final. Only recognition over a WebSocket, such as [`recognise_websocket()`], can send
+    /// interim results
+    ///
+    /// # Parameters
+    ///
+    /// * `interim_results` - Whether to send interim results. Off by default
+    ///
+    /// [`recognise_websocket()`]: super::SpeechToText::recognise_websocket()
+    pub fn interim_results(mut self, interim_results: bool) -> Self {
+        self.interim_results = Some(interim_results);
+        self
+    }
+
+    /// Whether IBM may use the audio of this request to improve its services, overriding the
+    /// client's [`set_learning_opt_out()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `opt_out` - Whether to send `X-Watson-Learning-Opt-Out: true`
+    ///
+    /// [`set_learning_opt_out()`]: super::SpeechToText::set_learning_opt_out()
+    pub fn learning_opt_out(mut self, opt_out: bool) -> Self {
+        self.learning_opt_out = Some(opt_out);
+        self
+    }
+
+    /// Associate the audio of this request with a customer ID, in place of the client's
+    /// [`set_customer_id()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `customer_id` - The customer ID to send in the `X-Watson-Metadata` header
+    ///
+    /// [`set_customer_id()`]: super::SpeechToText::set_customer_id()
+    pub fn customer_id(mut self, customer_id: impl Into<String>) -> Self {
+        self.customer_id = Some(customer_id.into());
+        self
+    }
+
+    /// Adds the options that are sent as headers to `req`
+    pub(crate) fn apply_headers(&self, req: &mut Request) -> Result<(), RecognitionError> {
+        if let Some(opt_out) = self.learning_opt_out {
+            let value = if opt_out { "true" } else { "false" };
+            req.headers_mut()
+                .insert(LEARNING_OPT_OUT, HeaderValue::from_static(value));
+        }
+        if let Some(customer_id) = &self.customer_id {
+            let value = metadata_header(customer_id).map_err(RecognitionError::InvalidHeader)?;
+            req.headers_mut().insert(WATSON_METADATA, value);
+        }
+        Ok(())
+    }
+
+    /// Adds the options that choose the models to recognise with to `url`. These are sent as
+    /// query parameters, whether recognising over HTTP or a WebSocket
+    pub(crate) fn append_model_query(&self, url: &mut Url) {
+        let mut query = url.query_pairs_mut();
+        if let Some(model) = &self.model {
+            query.append_pair("model", model);
+        }
+        if let Some(customisation_id) = &self.language_customisation_id {
+            query.append_pair("language_customization_id", customisation_id);
+        }
+        if let Some(customisation_id) = &self.acoustic_customisation_id {
+            query.append_pair("acoustic_customization_id", customisation_id);
+        }
+        if let Some(version) = &self.base_model_version {
+            query.append_pair("base_model_version", version);
+        }
+    }
+
+    /// The options that change how audio is recognised, which are sent as query parameters over
+    /// HTTP and in the start message over a WebSocket
+    pub(crate) fn parameters(&self) -> Vec<(&'static str, Value)> {
+        let mut parameters = Vec::new();
+        if let Some(weight) = self.customisation_weight {
+            parameters.push(("customization_weight", Value::from(weight)));
+        }
+        if let Some(grammar_name) = &self.grammar_name {
+            parameters.push(("grammar_name", Value::from(grammar_name.as_str())));
+        }
+        if let Some(seconds) = self.inactivity_timeout {
+            parameters.push(("inactivity_timeout", Value::from(seconds)));
+        }
+        if let Some(timestamps) = self.timestamps {
+            parameters.push(("timestamps", Value::from(timestamps)));
+        }
+        if let Some(speaker_labels) = self.speaker_labels {
+            parameters.push(("speaker_labels", Value::from(speaker_labels)));
+        }
+        if let Some(smart_formatting) = self.smart_formatting {
+            parameters.push(("smart_formatting", Value::from(smart_formatting)));
+        }
+        if let Some(redaction) = self.redaction {
+            parameters.push(("redaction", Value::from(redaction)));
+        }
+        if let Some((keywords, threshold)) = &self.keywords {
+            parameters.push(("keywords", Value::from(keywords.clone())));
+            parameters.push(("keywords_threshold", Value::from(*threshold)));
+        }
+        if let Some(max_alternatives) = self.max_alternatives {
+            parameters.push(("max_alternatives", Value::from(max_alternatives)));
+        }
+        if let Some(threshold) = self.word_alternatives_threshold {
+            parameters.push(("word_alternatives_threshold", Value::from(threshold)));
+        }
+        if let Some(word_confidence) = self.word_confidence {
+            parameters.push(("word_confidence", Value::from(word_confidence)));
+        }
+        if let Some(profanity_filter) = self.profanity_filter {
+            parameters.push(("profanity_filter", Value::from(profanity_filter)));
+        }
+        parameters
+    }
+
+    /// Adds the options that are sent as query parameters over HTTP to `url`
+    pub(crate) fn append_query(&self, url: &mut Url) {
+        self.append_model_query(url);
+        let mut query = url.query_pairs_mut();
+        for (name, value) in self.parameters() {
+            query.append_pair(name, &query_value(&value));
+        }
+    }
+}
+
+/// `value` as a query parameter, with lists separated by commas
+fn query_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Array(values) => values.iter().map(query_value).collect::<Vec<_>>().join(","),
+        value => value.to_string(),
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+/// The results of recognising speech in some audio
+pub struct SpeechRecognitionResults {
+    /// The index of the first result in [`results`]. Results sent over a WebSocket may replace
+    /// interim results from this index onwards
+    ///
+    /// [`results`]: Self::results
+    #[serde(default)]
+    pub result_index: usize,
+    /// The results, one for each utterance in the audio, in the order they were spoken
+    #[serde(default)]
+    pub results: Vec<SpeechRecognitionResult>,
+    /// Who spoke each word of the results, if [`speaker_labels`] were requested
+    ///
+    /// [`speaker_labels`]: self::RecognitionOptions::speaker_labels()
+    #[serde(default)]
+    pub speaker_labels: Vec<SpeakerLabel>,
+    /// Warnings about the request, such as an unknown parameter. The request still succeeded
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+impl SpeechRecognitionResults {
+    /// The transcript of the final results, joining the most likely alternative of each
+    pub fn transcript(&self) -> String {
+        self.results
+            .iter()
+            .filter(|result| result.final_results)
+            .filter_map(|result| result.alternatives.first())
+            .map(|alternative| alternative.transcript.trim())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The speaker of a word of the results, from the [`speaker_labels`] that start when it does
+    ///
+    /// # Parameters
+    ///
+    /// * `word` - The timestamp of the word, from an alternative of the results
+    ///
+    /// [`speaker_labels`]: Self::speaker_labels
+    pub fn speaker_of(&self, word: &WordTimestamp) -> Option<u32> {
+        self.speaker_labels
+            .iter()
+            .find(|label| label.from == word.start)
+            .map(|label| label.speaker)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+/// What was recognised in a single utterance
+pub struct SpeechRecognitionResult {
+    /// Whether the result is final, as it always is over HTTP. Interim results sent over a
+    /// WebSocket may still change
+    #[serde(rename = "final")]
+    pub final_results: bool,
+    /// What might have been said, the most likely first
+    pub alternatives: Vec<SpeechRecognitionAlternative>,
+    /// Why the utterance ended, if the request split the transcript at phrase ends or the audio
+    /// ended
+    pub end_of_utterance: Option<EndOfUtterance>,
+    /// Where each of the [`keywords`] requested was spotted in the utterance. Keywords that were
+    /// not spotted are left out
+    ///
+    /// [`keywords`]: self::RecognitionOptions::keywords()
+    #[serde(default)]
+    pub keywords_result: HashMap<String, Vec<KeywordResult>>,
+    /// The words that might have been said at each point of the utterance, if a
+    /// [`word_alternatives_threshold`] was set
+    ///
+    /// [`word_alternatives_threshold`]: self::RecognitionOptions::word_alternatives_threshold()
+    #[serde(default)]
+    pub word_alternatives: Vec<WordAlternatives>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+/// One of the transcripts that might have been said in an utterance
+pub struct SpeechRecognitionAlternative {
+    /// The transcript
+    pub transcript: String,
+    /// How confident the service is in the transcript, between `0.0` and `1.0`. Only the best
+    /// alternative of a final result has a confidence
+    pub confidence: Option<f64>,
+    /// When each word of the transcript starts and ends, if [`timestamps`] were requested
+    ///
+    /// [`timestamps`]: self::RecognitionOptions::timestamps()
+    #[serde(default)]
+    pub timestamps: Vec<WordTimestamp>,
+    /// How confident the service is in each word of the transcript, if [`word_confidence`] was
+    /// requested. Only the best alternative of a final result has word confidences
+    ///
+    /// [`word_confidence`]: self::RecognitionOptions::word_confidence()
+    #[serde(default)]
+    pub word_confidence: Vec<WordConfidence>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+/// Where a keyword was spotted in the audio
+pub struct KeywordResult {
+    /// The keyword as it was said, which may differ in case or spacing from the keyword requested
+    pub normalized_text: String,
+    /// When the keyword starts, in seconds from the start of the audio
+    pub start_time: f64,
+    /// When the keyword ends, in seconds from the start of the audio
+    pub end_time: f64,
+    /// How confident the service is that the keyword was said, between `0.0` and `1.0`
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "(String, f64, f64)")]
+/// When a word was spoken in the audio
+pub struct WordTimestamp {
+    /// The word
+    pub word: String,
+    /// When the word starts, in seconds from the start of the audio
+    pub start: f64,
+    /// When the word ends, in seconds from the start of the audio
+    pub end: f64,
+}
+
+impl From<(String, f64, f64)> for WordTimestamp {
+    fn from((word, start, end): (String, f64, f64)) -> Self {
+        Self { word, start, end }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+/// Who spoke a word of the results, by the time the word was spoken
+pub struct SpeakerLabel {
+    /// When the word starts, in seconds from the start of the audio
+    pub from: f64,
+    /// When the word ends, in seconds from the start of the audio
+    pub to: f64,
+    /// The speaker, numbered from `0` in the order the service told them apart
+    pub speaker: u32,
+    /// How confident the service is in the speaker, between `0.0` and `1.0`
+    pub confidence: f64,
+    /// Whether the label is final. Labels sent over a WebSocket with interim results may change
+    /// until one of them is
+    #[serde(rename = "final")]
+    pub final_results: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "(String, f64)")]
+/// How confident the service is in a word of a transcript
+pub struct WordConfidence {
+    /// The word
+    pub word: String,
+    /// How confident the service is in the word, between `0.0` and `1.0`
+    pub confidence: f64,
+}
+
+impl From<(String, f64)> for WordConfidence {
+    fn from((word, confidence): (String, f64)) -> Self {
+        Self { word, confidence }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+/// The words that might have been said at a point of an utterance
+pub struct WordAlternatives {
+    /// When the words start, in seconds from the start of the audio
+    pub start_time: f64,
+    /// When the words end, in seconds from the start of the audio
+    pub end_time: f64,
+    /// The words, the most likely first
+    pub alternatives: Vec<WordAlternative>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+/// A word that might have been said
+pub struct WordAlternative {
+    /// The word
+    pub word: String,
+    /// How confident the service is that the word was said, between `0.0` and `1.0`
+    pub confidence: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// Why an utterance ended
+pub enum EndOfUtterance {
+    /// The audio ended
+    EndOfData,
+    /// The speaker paused long enough to end a phrase
+    FullStop,
+    /// The utterance was cut short, such as by the maximum length of an utterance
+    Reset,
+    /// The speaker was silent for longer than the end of phrase silence time
+    Silence,
+}
+
+impl<A: Authenticator> SpeechToText<'_, A> {
+    /// Recognises the speech in `audio`, sent in the body of a single request without a session.
+    /// A request can carry up to 100 MB of audio, and the service waits for all of it before
+    /// returning any results. [`recognise_chunked()`] sends the audio as it is read instead of
+    /// holding it in memory, and [`recognise_stream()`] takes longer audio
+    ///
+    /// # Parameters
+    ///
+    /// * `audio` - The audio to recognise
+    /// * `content_type` - The MIME type of the audio, such as `audio/flac`, `audio/wav` or
+    ///   `audio/l16;rate=16000`, or [`None`] to detect it from the audio with
+    ///   [`detect_content_type()`]. Formats without a header, such as `audio/l16` and
+    ///   `audio/mulaw`, must be given, along with their sampling rate
+    /// * `options` - The [`RecognitionOptions`] to recognise with
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     stt::{recognition::RecognitionOptions, SpeechToText},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let audio = std::fs::read("hello.wav")?;
+    /// let results = stt
+    ///     .recognise(audio, None, &RecognitionOptions::new())
+    ///     .await?;
+    /// for result in results.results {
+    ///     println!("{:?}", result.alternatives.first());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`RecognitionOptions`]: self::RecognitionOptions
+    /// [`recognise_chunked()`]: Self::recognise_chunked()
+    /// [`recognise_stream()`]: Self::recognise_stream()
+    /// [`None`]: std::option::Option::None
+    /// [`detect_content_type()`]: self::detect_content_type()
+    pub async fn recognise(
+        &self,
+        audio: impl Into<Bytes>,
+        content_type: Option<&str>,
+        options: &RecognitionOptions,
+    ) -> Result<SpeechRecognitionResults, RecognitionError> {
+        let audio = audio.into();
+        let content_type = content_type.unwrap_or_else(|| detect_content_type(&audio));
+        self.send_recognition(audio.into(), content_type, options)
+            .await
+    }
+
+    /// Recognises the speech in the audio sent as `body`, of the MIME type `content_type`
+    async fn send_recognition(
+        &self,
+        body: Body,
+        content_type: &str,
+        options: &RecognitionOptions,
+    ) -> Result<SpeechRecognitionResults, RecognitionError> {
+        let mut url = self.service.endpoint("v1/recognize");
+        options.append_query(&mut url);
+        let mut req = Request::new(Method::POST, url);
+        let content_type = HeaderValue::from_str(content_type).map_err(|_| {
+            RecognitionError::ConnectionError(format!("{content_type:?} is not a valid MIME type"))
+        })?;
+        req.headers_mut().insert(CONTENT_TYPE, content_type);
+        *req.body_mut() = Some(body);
+        options.apply_headers(&mut req)?;
+
+        let response = self
+            .service
+            .execute_retrying(req)
+            .await
+            .map_err(RecognitionError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| RecognitionError::ConnectionError(e.to_string())),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                Err(status_error(status, details))
+            }
+        }
+    }
+
+    /// Recognises the speech in several clips of audio with a single multipart request, with
+    /// the same options for all of them. The service recognises the clips in order as though
+    /// they were one recording, and returns the results for all of them together. All of the
+    /// clips must be in the same format, and together carry up to 100 MB of audio
+    ///
+    /// # Parameters
+    ///
+    /// * `clips` - The clips of audio to recognise, in order
+    /// * `content_type` - The MIME type of every clip, such as `audio/flac`, `audio/wav` or
+    ///   `audio/l16;rate=16000`, or [`None`] to detect it from the first clip with
+    ///   [`detect_content_type()`]
+    /// * `options` - The [`RecognitionOptions`] to recognise with
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     stt::{recognition::RecognitionOptions, SpeechToText},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let clips = [std::fs::read("question.wav")?, std::fs::read("answer.wav")?];
+    /// let results = stt
+    ///     .recognise_multipart(clips, Some("audio/wav"), &RecognitionOptions::new())
+    ///     .await?;
+    /// println!("{}", results.transcript());
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`RecognitionOptions`]: self::RecognitionOptions
+    /// [`None`]: std::option::Option::None
+    /// [`detect_content_type()`]: self::detect_content_type()
+    pub async fn recognise_multipart(
+        &self,
+        clips: impl IntoIterator<Item = impl Into<Bytes>>,
+        content_type: Option<&str>,
+        options: &RecognitionOptions,
+    ) -> Result<SpeechRecognitionResults, RecognitionError> {
+        let clips: Vec<Bytes> = clips.into_iter().map(Into::into).collect();
+        let content_type = content_type.unwrap_or_else(|| {
+            detect_content_type(clips.first().map(|clip| &clip[..]).unwrap_or_default())
+        });
+        let metadata = json!({
+            "part_content_type": content_type,
+            "data_parts_count": clips.len(),
+        });
+        let metadata = Part::text(metadata.to_string())
+            .mime_str("application/json")
+            .map_err(|e| RecognitionError::ConnectionError(e.to_string()))?;
+        let mut form = Form::new().part("metadata", metadata);
+        for clip in clips {
+            // a part made from `Bytes` sends them as they are, rather than copying them
+            #[cfg(not(target_arch = "wasm32"))]
+            let upload = {
+                let len = clip.len() as u64;
+                Part::stream_with_length(clip, len)
+            };
+            #[cfg(target_arch = "wasm32")]
+            let upload = Part::stream(clip);
+            let upload = upload.mime_str(content_type).map_err(|_| {
+                RecognitionError::ConnectionError(format!(
+                    "{content_type:?} is not a valid MIME type"
+                ))
+            })?;
+            form = form.part("upload", upload);
+        }
+
+        let mut url = self.service.endpoint("v1/recognize");
+        options.append_query(&mut url);
+        let mut req = self
+            .service
+            .http()
+            .post(url)
+            .multipart(form)
+            .build()
+            .map_err(|e| RecognitionError::ConnectionError(e.to_string()))?;
+        options.apply_headers(&mut req)?;
+
+        let response = self
+            .service
+            .execute_retrying(req)
+            .await
+            .map_err(RecognitionError::ConnectionError)?;
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| RecognitionError::ConnectionError(e.to_string())),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                Err(status_error(status, details))
+            }
+        }
+    }
+}
+
+/// The error for an unsuccessful recognition response
+pub(crate) fn status_error(
+    status: StatusCode,
+    details: Option<ServiceErrorDetails>,
+) -> RecognitionError {
+    match status {
+        StatusCode::BAD_REQUEST => RecognitionError::BadRequest400(details),
+        StatusCode::NOT_FOUND => RecognitionError::NotFound404(details),
+        StatusCode::NOT_ACCEPTABLE => RecognitionError::NotAcceptable406(details),
+        StatusCode::REQUEST_TIMEOUT => RecognitionError::RequestTimeout408(details),
+        StatusCode::PAYLOAD_TOO_LARGE => RecognitionError::PayloadTooLarge413(details),
+        StatusCode::UNSUPPORTED_MEDIA_TYPE => RecognitionError::UnsupportedMediaType415(details),
+        StatusCode::TOO_MANY_REQUESTS => RecognitionError::TooManyRequests429(details),
+        StatusCode::INTERNAL_SERVER_ERROR => RecognitionError::InternalServerError500(details),
+        StatusCode::SERVICE_UNAVAILABLE => RecognitionError::ServiceUnavailable503(details),
+        StatusCode::UNAUTHORIZED => RecognitionError::Unauthorised401(details),
+        StatusCode::FORBIDDEN => RecognitionError::Forbidden403(details),
+        status => RecognitionError::UnexpectedStatus(status, details),
+    }
+}
@@ -0,0 +1,28 @@
+/// Works out the MIME type of `audio` from the magic bytes its container starts with, to pass
+/// as the content type of a recognition request. WAV, FLAC, Ogg, MP3 and WebM audio are
+/// recognised; anything else is `application/octet-stream`, for which the service detects the
+/// format and sampling rate itself. Audio without a header, such as `audio/l16` or
+/// `audio/mulaw`, cannot be told apart this way and needs its content type given
+///
+/// # Parameters
+///
+/// * `audio` - The audio, or at least its first few bytes
+///
+/// # Example
+/// ```
+/// # use ibm_watson::stt::recognition::detect_content_type;
+/// assert_eq!(detect_content_type(b"fLaC\0\0\0\x22"), "audio/flac");
+/// assert_eq!(detect_content_type(b"\x01\x02"), "application/octet-stream");
+/// ```
+pub fn detect_content_type(audio: &[u8]) -> &'static str {
+    match audio {
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'A', b'V', b'E', ..] => "audio/wav",
+        [b'f', b'L', b'a', b'C', ..] => "audio/flac",
+        [b'O', b'g', b'g', b'S', ..] => "audio/ogg",
+        [b'I', b'D', b'3', ..] => "audio/mp3",
+        // an MPEG audio frame header, of layer I, II or III rather than AAC
+        [0xff, second, ..] if second & 0xe0 == 0xe0 && second & 0x06 != 0 => "audio/mp3",
+        [0x1a, 0x45, 0xdf, 0xa3, ..] => "audio/webm",
+        _ => "application/octet-stream",
+    }
+}
@@ -0,0 +1,332 @@
+use std::{collections::VecDeque, fmt::Display, future::ready, pin::pin};
+
+use bytes::{Bytes, BytesMut};
+use futures_util::{stream, Stream, StreamExt};
+use reqwest::{Method, Request};
+use serde_json::{Map, Value};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use super::{
+    errors::RecognitionError, status_error, stream_content_type, RecognitionOptions,
+    SpeechRecognitionResults,
+};
+use crate::{
+    auth::Authenticator,
+    errors::ServiceErrorDetails,
+    stt::SpeechToText,
+    websocket::{Handshake, Message, Sender, WebSocket},
+};
+
+/// How much audio [`read_chunks()`] sends in each message
+///
+/// [`read_chunks()`]: self::read_chunks()
+const CHUNK_SIZE: usize = 8 << 10;
+
+#[derive(Debug, Clone, PartialEq)]
+/// An event received while recognising speech over a WebSocket
+pub enum RecognitionEvent {
+    /// Results for the audio received so far. Interim results, which are not
+    /// [`final_results`], are replaced by the next results from their [`result_index`] onwards
+    ///
+    /// [`final_results`]: super::SpeechRecognitionResult::final_results
+    /// [`result_index`]: super::SpeechRecognitionResults::result_index
+    Results(SpeechRecognitionResults),
+    /// A warning about the request, such as an unknown parameter
+    Warning(String),
+}
+
+impl RecognitionOptions {
+    /// The message that starts recognising audio of `content_type` over a WebSocket
+    fn start_message(&self, content_type: &str) -> String {
+        let mut message = Map::new();
+        message.insert("action".to_owned(), Value::from("start"));
+        message.insert("content-type".to_owned(), Value::from(content_type));
+        if let Some(interim_results) = self.interim_results {
+            message.insert("interim_results".to_owned(), Value::from(interim_results));
+        }
+        for (name, value) in self.parameters() {
+            message.insert(name.to_owned(), value);
+        }
+        Value::Object(message).to_string()
+    }
+}
+
+impl<A: Authenticator> SpeechToText<'_, A> {
+    /// Recognises speech over the WebSocket interface while `audio` is still being sent, returning
+    /// a [`Stream`] of [`RecognitionEvent`]s. This suits live audio, such as from a microphone or
+    /// a call: with [`interim_results`], the service sends what it has heard so far as it hears
+    /// it. The stream ends once the service has sent the final results for all of the audio, or
+    /// after the first error
+    ///
+    /// # Parameters
+    ///
+    /// * `audio` - A [`Stream`] of chunks of audio, such as one made from an [`AsyncRead`] with
+    ///   [`read_chunks()`]. The audio ends when the stream does
+    /// * `content_type` - The MIME type of the audio, such as `audio/ogg;codecs=opus` or
+    ///   `audio/l16;rate=16000`, or [`None`] to detect it from the first chunk with
+    ///   [`detect_content_type()`], which waits for that chunk before connecting
+    /// * `options` - The [`RecognitionOptions`] to recognise with
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     stt::{
+    /// #         recognition::{read_chunks, RecognitionEvent, RecognitionOptions},
+    /// #         SpeechToText,
+    /// #     },
+    /// # };
+    /// # use futures_util::StreamExt;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let audio = read_chunks(tokio::fs::File::open("call.ogg").await?);
+    /// let options = RecognitionOptions::new().interim_results(true);
+    /// let mut events = Box::pin(
+    ///     stt.recognise_websocket(audio, Some("audio/ogg;codecs=opus"), &options)
+    ///         .await?,
+    /// );
+    /// while let Some(event) = events.next().await {
+    ///     if let RecognitionEvent::Results(results) = event? {
+    ///         println!("{}", results.transcript());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`Stream`]: futures_util::Stream
+    /// [`RecognitionEvent`]: self::RecognitionEvent
+    /// [`interim_results`]: super::RecognitionOptions::interim_results()
+    /// [`AsyncRead`]: tokio::io::AsyncRead
+    /// [`read_chunks()`]: self::read_chunks()
+    /// [`RecognitionOptions`]: super::RecognitionOptions
+    /// [`None`]: std::option::Option::None
+    /// [`detect_content_type()`]: super::detect_content_type()
+    pub async fn recognise_websocket<E: Display>(
+        &self,
+        audio: impl Stream<Item = Result<Bytes, E>>,
+        content_type: Option<&str>,
+        options: &RecognitionOptions,
+    ) -> Result<impl Stream<Item = Result<RecognitionEvent, RecognitionError>>, RecognitionError>
+    {
+        let (content_type, audio) = stream_content_type(audio, content_type).await;
+
+        let mut url = self.service.endpoint("v1/recognize");
+        options.append_model_query(&mut url);
+        let mut req = Request::new(Method::GET, url);
+        options.apply_headers(&mut req)?;
+        let mut socket = match self
+            .service
+            .connect_websocket(req)
+            .await
+            .map_err(RecognitionError::ConnectionError)?
+        {
+            Handshake::Connected(socket) => socket,
+            Handshake::Rejected(response) => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                return Err(status_error(status, details));
+            }
+        };
+        socket
+            .send_text(&options.start_message(content_type))
+            .await
+            .map_err(RecognitionError::ConnectionError)?;
+
+        let upload = stream::once(send_audio(socket.sender(), audio))
+            .filter_map(|sent| ready(sent.err().map(Err)));
+        let state = (Some(socket), VecDeque::new(), 0);
+        let events = stream::unfold(
+            state,
+            |(mut socket, mut pending, mut listening)| async move {
+                loop {
+                    if let Some(event) = pending.pop_front() {
+                        return Some((Ok(event), (socket, pending, listening)));
+                    }
+                    match next_events(socket.as_mut()?, &mut listening).await? {
+                        Ok(events) => pending.extend(events),
+                        // nothing more is read after an error
+                        Err(e) => return Some((Err(e), (None, pending, listening))),
+                    }
+                }
+            },
+        );
+        // nothing more is returned after an error, whether sending or receiving
+        let events = Box::pin(stream::select(upload, events));
+        Ok(stream::unfold(Some(events), |events| async move {
+            let mut events = events?;
+            let event = events.next().await?;
+            let events = event.is_ok().then_some(events);
+            Some((event, events))
+        }))
+    }
+
+    /// Recognises the speech in `audio` as [`recognise()`] does, but streams the audio to the
+    /// service as it is read instead of holding all of it in memory first. Use it for long
+    /// recordings, of hundreds of MB or more: the audio is sent in chunks over the WebSocket
+    /// interface, which has no limit on its size, and the final results for all of it are
+    /// returned once the service has them
+    ///
+    /// # Parameters
+    ///
+    /// * `audio` - A [`Stream`] of chunks of audio, such as one made from an [`AsyncRead`] with
+    ///   [`read_chunks()`]. The audio ends when the stream does
+    /// * `content_type` - The MIME type of the audio, such as `audio/flac`, `audio/wav` or
+    ///   `audio/l16;rate=16000`, or [`None`] to detect it from the first chunk with
+    ///   [`detect_content_type()`]
+    /// * `options` - The [`RecognitionOptions`] to recognise with
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     stt::{
+    /// #         recognition::{read_chunks, RecognitionOptions},
+    /// #         SpeechToText,
+    /// #     },
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let stt = SpeechToText::new(&auth, "service_url")?;
+    /// let audio = read_chunks(tokio::fs::File::open("meeting.flac").await?);
+    /// let results = stt
+    ///     .recognise_stream(audio, None, &RecognitionOptions::new())
+    ///     .await?;
+    /// println!("{}", results.transcript());
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`recognise()`]: Self::recognise()
+    /// [`Stream`]: futures_util::Stream
+    /// [`AsyncRead`]: tokio::io::AsyncRead
+    /// [`read_chunks()`]: self::read_chunks()
+    /// [`RecognitionOptions`]: super::RecognitionOptions
+    /// [`None`]: std::option::Option::None
+    /// [`detect_content_type()`]: super::detect_content_type()
+    pub async fn recognise_stream<E: Display>(
+        &self,
+        audio: impl Stream<Item = Result<Bytes, E>>,
+        content_type: Option<&str>,
+        options: &RecognitionOptions,
+    ) -> Result<SpeechRecognitionResults, RecognitionError> {
+        let events = self
+            .recognise_websocket(audio, content_type, options)
+            .await?;
+        let mut events = pin!(events);
+        let mut results = SpeechRecognitionResults::default();
+        while let Some(event) = events.next().await {
+            match event? {
+                RecognitionEvent::Results(update) => {
+                    // later results replace any interim ones from their index onwards
+                    results.results.truncate(update.result_index);
+                    results.results.extend(update.results);
+                    // interim speaker labels are sent again, possibly revised, with each update
+                    results.speaker_labels.retain(|label| label.final_results);
+                    results.speaker_labels.extend(update.speaker_labels);
+                    results.warnings.extend(update.warnings);
+                }
+                RecognitionEvent::Warning(warning) => results.warnings.push(warning),
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// Splits `reader` into chunks of audio to stream to [`recognise_websocket()`] or
+/// [`recognise_chunked()`], such as from a file or from a pipe that a recorder writes to. The
+/// stream ends when `reader` does
+///
+/// # Parameters
+///
+/// * `reader` - The [`AsyncRead`] to read the audio from
+///
+/// [`recognise_websocket()`]: crate::stt::SpeechToText::recognise_websocket()
+/// [`recognise_chunked()`]: crate::stt::SpeechToText::recognise_chunked()
+/// [`AsyncRead`]: tokio::io::AsyncRead
+pub fn read_chunks(
+    reader: impl AsyncRead + Unpin,
+) -> impl Stream<Item = Result<Bytes, std::io::Error>> {
+    stream::unfold(Some(reader), |reader| async move {
+        let mut reader = reader?;
+        let mut chunk = BytesMut::with_capacity(CHUNK_SIZE);
+        match reader.read_buf(&mut chunk).await {
+            Ok(0) => None,
+            Ok(_) => Some((Ok(chunk.freeze()), Some(reader))),
+            Err(e) => Some((Err(e), None)),
+        }
+    })
+}
+
+/// Sends every chunk of `audio`, then the message that ends it
+async fn send_audio<E: Display>(
+    sender: Sender,
+    audio: impl Stream<Item = Result<Bytes, E>>,
+) -> Result<(), RecognitionError> {
+    let mut audio = pin!(audio);
+    while let Some(chunk) = audio.next().await {
+        match chunk {
+            // the service takes an empty message to be the end of the audio
+            Ok(chunk) if chunk.is_empty() => {}
+            Ok(chunk) => sender
+                .send_binary(&chunk)
+                .await
+                .map_err(RecognitionError::ConnectionError)?,
+            Err(e) => {
+                // the service would otherwise wait for the rest of the audio
+                let _ = sender.close().await;
+                return Err(RecognitionError::AudioError(e.to_string()));
+            }
+        }
+    }
+    sender
+        .send_text(r#"{"action":"stop"}"#)
+        .await
+        .map_err(RecognitionError::ConnectionError)
+}
+
+/// Receives the next message and the events it makes, returning [`None`] once the service has
+/// closed the connection. The service says it is listening once after the start message, and
+/// again once it has sent the results for all of the audio, after which the connection is closed
+async fn next_events(
+    socket: &mut WebSocket,
+    listening: &mut u8,
+) -> Option<Result<Vec<RecognitionEvent>, RecognitionError>> {
+    let text = match socket.receive().await {
+        Ok(Some(Message::Text(text))) => text,
+        Ok(Some(Message::Binary(_))) => return Some(Ok(Vec::new())),
+        Ok(None) => return None,
+        Err(e) => return Some(Err(RecognitionError::ConnectionError(e))),
+    };
+    let Ok(mut message) = serde_json::from_str::<Map<String, Value>>(&text) else {
+        // ignore messages this version does not know about
+        return Some(Ok(Vec::new()));
+    };
+    if let Some(error) = message.remove("error") {
+        let _ = socket.close().await;
+        let error = error
+            .as_str()
+            .map_or_else(|| error.to_string(), ToOwned::to_owned);
+        return Some(Err(RecognitionError::ServiceError(error)));
+    }
+    if message.get("state").and_then(Value::as_str) == Some("listening") {
+        *listening += 1;
+        if *listening > 1 {
+            if let Err(e) = socket.close().await {
+                return Some(Err(RecognitionError::ConnectionError(e)));
+            }
+        }
+        return Some(Ok(Vec::new()));
+    }
+    let mut results: SpeechRecognitionResults = match serde_json::from_value(Value::Object(message))
+    {
+        Ok(results) => results,
+        Err(e) => return Some(Err(RecognitionError::ConnectionError(e.to_string()))),
+    };
+    let mut events: Vec<_> = std::mem::take(&mut results.warnings)
+        .into_iter()
+        .map(RecognitionEvent::Warning)
+        .collect();
+    if !results.results.is_empty() {
+        events.push(RecognitionEvent::Results(results));
+    }
+    Some(Ok(events))
+}
@@ -1,2 +1,9 @@
 mod auth;
-
+// the mock server in these tests only speaks HTTP/1.1
+#[cfg(all(any(feature = "tts", feature = "stt"), not(feature = "http2")))]
+#[cfg_attr(not(all(feature = "tts", feature = "stt")), allow(dead_code))]
+mod server;
+#[cfg(all(feature = "stt", not(feature = "http2")))]
+mod stt;
+#[cfg(all(feature = "tts", not(feature = "http2")))]
+mod tts;
@@ -0,0 +1,151 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    thread::{self, JoinHandle},
+};
+
+/// Reads the request line, headers and body of an HTTP/1.1 request
+pub(super) fn read_request(reader: &mut impl BufRead) -> String {
+    let mut request = String::new();
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+        request.push_str(&line);
+        if line == "\r\n" {
+            break;
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+    request.push_str(&String::from_utf8_lossy(&body));
+    request
+}
+
+/// Reads a body sent with chunked transfer encoding, once its headers have been read
+pub(super) fn read_chunked(reader: &mut impl BufRead) -> Vec<u8> {
+    let mut body = Vec::new();
+    loop {
+        let mut size = String::new();
+        reader.read_line(&mut size).unwrap();
+        let size = usize::from_str_radix(size.trim(), 16).unwrap();
+        let mut chunk = vec![0; size + 2];
+        reader.read_exact(&mut chunk).unwrap();
+        if size == 0 {
+            return body;
+        }
+        body.extend_from_slice(&chunk[..size]);
+    }
+}
+
+/// Accepts a single connection and hands it to `handler`, returning the URL of the server and a
+/// handle resolving to the result of `handler`
+pub(super) fn serve_with<T: Send + 'static>(
+    handler: impl FnOnce(BufReader<TcpStream>, TcpStream) -> T + Send + 'static,
+) -> (String, JoinHandle<T>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/instances/abc", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        handler(BufReader::new(stream.try_clone().unwrap()), stream)
+    });
+    (url, handle)
+}
+
+/// Serves a single HTTP/1.1 request with `response`, returning the URL of the server and a handle
+/// resolving to the request line, headers and body it received
+pub(super) fn serve(response: impl Into<String>) -> (String, JoinHandle<String>) {
+    let response = response.into();
+    serve_with(move |mut reader, mut stream| {
+        let request = read_request(&mut reader);
+        stream.write_all(response.as_bytes()).unwrap();
+        request
+    })
+}
+
+/// Answers every request on every connection with the response `handler` makes for it,
+/// returning the URL of the server
+pub(super) fn serve_all(handler: impl Fn(String) -> String + Send + Sync + 'static) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/instances/abc", listener.local_addr().unwrap());
+    let handler = std::sync::Arc::new(handler);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let handler = handler.clone();
+            thread::spawn(move || {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                while !reader.fill_buf().unwrap().is_empty() {
+                    let response = handler(read_request(&mut reader));
+                    stream.write_all(response.as_bytes()).unwrap();
+                }
+            });
+        }
+    });
+    url
+}
+
+/// A `200 OK` response carrying the JSON `body`
+pub(super) fn json_response(body: &str) -> String {
+    json_response_with("200 OK", body)
+}
+
+/// A response with `status` carrying the JSON `body`
+pub(super) fn json_response_with(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// Completes the WebSocket handshake the client started with `request`
+pub(super) fn accept_websocket(request: &str, stream: &mut TcpStream) {
+    let key = request
+        .lines()
+        .find_map(|line| line.strip_prefix("sec-websocket-key: "))
+        .unwrap();
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\r\n",
+        crate::websocket::accept_key(key)
+    )
+    .unwrap();
+}
+
+/// Reads the opcode and unmasked payload of a frame sent by the client
+pub(super) fn read_frame(reader: &mut impl Read) -> (u8, Vec<u8>) {
+    let mut head = [0; 2];
+    reader.read_exact(&mut head).unwrap();
+    let len = match head[1] & 0x7f {
+        126 => {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len).unwrap();
+            u16::from_be_bytes(len) as usize
+        }
+        len => len as usize,
+    };
+    let mut mask = [0; 4];
+    reader.read_exact(&mut mask).unwrap();
+    let mut payload = vec![0; len];
+    reader.read_exact(&mut payload).unwrap();
+    crate::websocket::apply_mask(&mut payload, mask);
+    (head[0] & 0x0f, payload)
+}
+
+/// Writes an unmasked frame, as a server does
+pub(super) fn write_frame(stream: &mut impl Write, opcode: u8, payload: &[u8]) {
+    match payload.len() {
+        len @ 0..=125 => stream.write_all(&[0x80 | opcode, len as u8]).unwrap(),
+        len => {
+            stream.write_all(&[0x80 | opcode, 126]).unwrap();
+            stream.write_all(&(len as u16).to_be_bytes()).unwrap();
+        }
+    }
+    stream.write_all(payload).unwrap();
+}
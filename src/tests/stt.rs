@@ -0,0 +1,1261 @@
+use super::server::{json_response, json_response_with, serve, serve_all};
+use crate::{
+    auth::NoAuthAuthenticator,
+    stt::{
+        recognition::{errors::RecognitionError, EndOfUtterance, RecognitionOptions},
+        SpeechToText,
+    },
+};
+
+#[tokio::test]
+async fn recognise_post() {
+    let (url, request) = serve(json_response(
+        r#"{"result_index":0,"results":[{"final":true,"end_of_utterance":"end_of_data","alternatives":[{"transcript":"hello world ","confidence":0.96,"timestamps":[["hello",0.1,0.42],["world",0.42,0.9]]},{"transcript":"yellow world "}]}]}"#,
+    ));
+    let auth = NoAuthAuthenticator;
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let options = RecognitionOptions::new()
+        .model("en-GB_Multimedia")
+        .language_customisation_id("lang")
+        .customisation_weight(0.5)
+        .timestamps(true)
+        .learning_opt_out(true);
+    let results = stt
+        .recognise(&b"fLaC"[..], Some("audio/flac"), &options)
+        .await
+        .unwrap();
+    assert_eq!(results.transcript(), "hello world");
+    let result = &results.results[0];
+    assert!(result.final_results);
+    assert_eq!(result.end_of_utterance, Some(EndOfUtterance::EndOfData));
+    assert_eq!(result.alternatives[0].confidence, Some(0.96));
+    assert_eq!(result.alternatives[0].timestamps[1].word, "world");
+    assert_eq!(result.alternatives[0].timestamps[1].end, 0.9);
+    assert_eq!(result.alternatives[1].confidence, None);
+    assert!(result.alternatives[1].timestamps.is_empty());
+
+    let request = request.join().unwrap();
+    assert!(request.starts_with(
+        "POST /instances/abc/v1/recognize?model=en-GB_Multimedia&language_customization_id=lang&customization_weight=0.5&timestamps=true "
+    ));
+    assert!(request.contains("content-type: audio/flac\r\n"));
+    assert!(request.contains("x-watson-learning-opt-out: true\r\n"));
+    assert!(request.ends_with("fLaC"));
+}
+
+#[tokio::test]
+async fn recognise_detected_content_type() {
+    let response = json_response(r#"{"result_index":0,"results":[]}"#);
+    let auth = NoAuthAuthenticator;
+    for (audio, content_type) in [
+        (&b"RIFF\0\0\0\0WAVE"[..], "audio/wav"),
+        (b"\x07\x07\x07\x07", "application/octet-stream"),
+    ] {
+        let (url, request) = serve(response.clone());
+        let stt = SpeechToText::new(&auth, &url).unwrap();
+        stt.recognise(audio, None, &RecognitionOptions::new())
+            .await
+            .unwrap();
+        assert!(request
+            .join()
+            .unwrap()
+            .contains(&format!("content-type: {content_type}\r\n")));
+    }
+}
+
+#[tokio::test]
+async fn recognise_errors() {
+    let (url, _) = serve(json_response_with(
+        "415 Unsupported Media Type",
+        r#"{"code":415,"error":"Unable to transcode data stream audio/wav"}"#,
+    ));
+    let auth = NoAuthAuthenticator;
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt
+        .recognise(&b"RIFF"[..], Some("audio/wav"), &RecognitionOptions::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        RecognitionError::UnsupportedMediaType415(Some(_))
+    ));
+
+    let err = stt
+        .recognise(
+            &b"RIFF"[..],
+            Some("audio/\nwav"),
+            &RecognitionOptions::new(),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RecognitionError::ConnectionError(_)));
+
+    let err = stt
+        .recognise(
+            &b"RIFF"[..],
+            Some("audio/wav"),
+            &RecognitionOptions::new().customer_id("customer\n1"),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RecognitionError::InvalidHeader(_)));
+
+    let (url, _) = serve(json_response_with(
+        "403 Forbidden",
+        r#"{"code":403,"error":"Forbidden"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt
+        .recognise(&b"RIFF"[..], Some("audio/wav"), &RecognitionOptions::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RecognitionError::Forbidden403(Some(_))));
+
+    let (url, _) = serve(json_response_with(
+        "409 Conflict",
+        r#"{"code":409,"error":"Conflict"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt
+        .recognise(&b"RIFF"[..], Some("audio/wav"), &RecognitionOptions::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        RecognitionError::UnexpectedStatus(reqwest::StatusCode::CONFLICT, Some(_))
+    ));
+}
+
+#[tokio::test]
+async fn recognise_websocket() {
+    use super::server::{accept_websocket, read_frame, read_request, serve_with, write_frame};
+    use crate::stt::recognition::{read_chunks, RecognitionEvent};
+    use futures_util::StreamExt;
+
+    let (url, request) = serve_with(|mut reader, mut stream| {
+        let request = read_request(&mut reader);
+        accept_websocket(&request, &mut stream);
+        let mut received = vec![read_frame(&mut reader)];
+        write_frame(&mut stream, 0x1, br#"{"state":"listening"}"#);
+        while received.last().unwrap().0 != 0x1 || received.len() == 1 {
+            received.push(read_frame(&mut reader));
+        }
+        for message in [
+            &br#"{"warnings":["Unknown arguments: colour."]}"#[..],
+            br#"{"result_index":0,"results":[{"final":false,"alternatives":[{"transcript":"hell"}]}]}"#,
+            br#"{"result_index":0,"results":[{"final":true,"alternatives":[{"transcript":"hello ","confidence":0.9}]}]}"#,
+            br#"{"state":"listening"}"#,
+        ] {
+            write_frame(&mut stream, 0x1, message);
+        }
+        received.push(read_frame(&mut reader));
+        write_frame(&mut stream, 0x8, &1000u16.to_be_bytes());
+        (request, received)
+    });
+    let auth = NoAuthAuthenticator;
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let options = RecognitionOptions::new()
+        .model("en-GB_Telephony")
+        .inactivity_timeout(-1)
+        .interim_results(true);
+    let audio = read_chunks(&b"OggS-audio"[..]);
+    let events: Vec<_> = stt
+        .recognise_websocket(audio, Some("audio/ogg;codecs=opus"), &options)
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(events.len(), 3);
+    assert_eq!(
+        events[0],
+        RecognitionEvent::Warning("Unknown arguments: colour.".to_owned())
+    );
+    let RecognitionEvent::Results(interim) = &events[1] else {
+        panic!("expected results, got {:?}", events[1]);
+    };
+    assert!(!interim.results[0].final_results);
+    let RecognitionEvent::Results(results) = &events[2] else {
+        panic!("expected results, got {:?}", events[2]);
+    };
+    assert_eq!(results.transcript(), "hello");
+
+    let (request, received) = request.join().unwrap();
+    assert!(request.starts_with("GET /instances/abc/v1/recognize?model=en-GB_Telephony "));
+    let start: serde_json::Value = serde_json::from_slice(&received[0].1).unwrap();
+    assert_eq!(
+        start,
+        serde_json::json!({
+            "action": "start",
+            "content-type": "audio/ogg;codecs=opus",
+            "interim_results": true,
+            "inactivity_timeout": -1,
+        })
+    );
+    let audio: Vec<u8> = received[1..received.len() - 2]
+        .iter()
+        .flat_map(|(opcode, payload)| {
+            assert_eq!(*opcode, 0x2);
+            payload.clone()
+        })
+        .collect();
+    assert_eq!(audio, b"OggS-audio");
+    assert_eq!(
+        received[received.len() - 2],
+        (0x1, br#"{"action":"stop"}"#.to_vec())
+    );
+    assert_eq!(received[received.len() - 1].0, 0x8);
+}
+
+#[tokio::test]
+async fn recognise_websocket_error() {
+    use super::server::{accept_websocket, read_frame, read_request, serve_with, write_frame};
+    use futures_util::{stream, StreamExt};
+
+    let (url, handle) = serve_with(|mut reader, mut stream| {
+        let request = read_request(&mut reader);
+        accept_websocket(&request, &mut stream);
+        read_frame(&mut reader);
+        write_frame(&mut stream, 0x1, br#"{"error":"Session timed out."}"#);
+        read_frame(&mut reader);
+    });
+    let auth = NoAuthAuthenticator;
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let audio = stream::pending::<Result<bytes::Bytes, std::io::Error>>();
+    let events: Vec<_> = stt
+        .recognise_websocket(audio, Some("audio/flac"), &RecognitionOptions::new())
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert!(matches!(
+        &events[..],
+        [Err(RecognitionError::ServiceError(error))] if error == "Session timed out."
+    ));
+    handle.join().unwrap();
+}
+
+#[tokio::test]
+async fn recognise_websocket_transport() {
+    use crate::transport::MockTransport;
+    use futures_util::stream;
+    use reqwest::StatusCode;
+    use std::sync::{Arc, Mutex};
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let requests = seen.clone();
+    let auth = NoAuthAuthenticator;
+    let stt = SpeechToText::builder(&auth)
+        .service_url("https://example.com/instances/abc")
+        .transport(MockTransport::new(move |request| {
+            requests.lock().unwrap().push((
+                request.url().path().to_owned(),
+                request.headers()["upgrade"].to_str().unwrap().to_owned(),
+            ));
+            MockTransport::response(
+                StatusCode::BAD_REQUEST,
+                "application/json",
+                r#"{"code":400,"error":"Model nope not found"}"#,
+            )
+        }))
+        .build()
+        .unwrap();
+    let audio = stream::pending::<Result<bytes::Bytes, std::io::Error>>();
+    let error = stt
+        .recognise_websocket(audio, Some("audio/flac"), &RecognitionOptions::new())
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(error, RecognitionError::BadRequest400(_)));
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [(
+            "/instances/abc/v1/recognize".to_owned(),
+            "websocket".to_owned()
+        )]
+    );
+}
+
+#[tokio::test]
+async fn create_job() {
+    use crate::stt::jobs::{JobEvent, JobOptions, JobStatus};
+    let (url, request) = serve(json_response_with(
+        "201 Created",
+        r#"{"id":"4bd734c0","status":"waiting","url":"https://example.com/v1/recognitions/4bd734c0","created":"2016-08-17T19:15:17.926Z"}"#,
+    ));
+    let auth = NoAuthAuthenticator;
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let options = JobOptions::new()
+        .recognition(RecognitionOptions::new().model("en-US_Multimedia"))
+        .callback_url("https://example.com/callback")
+        .events([JobEvent::CompletedWithResults, JobEvent::Failed])
+        .user_token("job1")
+        .results_ttl(60);
+    let job = stt
+        .create_job(&b"ID3"[..], Some("audio/mp3"), &options)
+        .await
+        .unwrap();
+    assert_eq!(job.id, "4bd734c0");
+    assert_eq!(job.status, JobStatus::Waiting);
+    assert!(job.results.is_empty());
+
+    let request = request.join().unwrap();
+    assert!(request.starts_with(
+        "POST /instances/abc/v1/recognitions?callback_url=https%3A%2F%2Fexample.com%2Fcallback&events=recognitions.completed_with_results%2Crecognitions.failed&user_token=job1&results_ttl=60&model=en-US_Multimedia "
+    ));
+    assert!(request.contains("content-type: audio/mp3\r\n"));
+    assert!(request.ends_with("ID3"));
+}
+
+#[tokio::test]
+async fn check_and_delete_jobs() {
+    use crate::{
+        polling::{errors::WaitError, Polling},
+        stt::jobs::{errors::CheckJobError, JobStatus},
+    };
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+    let polling = Polling::new(Duration::from_millis(1), Duration::from_millis(50));
+    let auth = NoAuthAuthenticator;
+
+    let (url, _) = serve(json_response(
+        r#"{"recognitions":[{"id":"a","status":"completed","created":"2016-08-17T19:15:17.926Z","updated":"2016-08-17T19:16:01.103Z"},{"id":"b","status":"processing"}]}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let jobs = stt.check_jobs().await.unwrap();
+    let statuses: Vec<_> = jobs
+        .iter()
+        .map(|job| (job.id.as_str(), job.status))
+        .collect();
+    assert_eq!(
+        statuses,
+        [("a", JobStatus::Completed), ("b", JobStatus::Processing)]
+    );
+
+    let checks = std::sync::Arc::new(AtomicUsize::new(0));
+    let counter = checks.clone();
+    let url = serve_all(move |request| {
+        assert!(request.starts_with("GET /instances/abc/v1/recognitions/job1 "));
+        match counter.fetch_add(1, Ordering::SeqCst) {
+            0 => json_response(r#"{"id":"job1","status":"waiting"}"#),
+            1 => json_response(r#"{"id":"job1","status":"processing"}"#),
+            _ => json_response(
+                r#"{"id":"job1","status":"completed","results":[{"result_index":0,"results":[{"final":true,"alternatives":[{"transcript":"so long "}]}]}]}"#,
+            ),
+        }
+    });
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let results = stt.wait_for_job("job1", &polling).await.unwrap();
+    assert_eq!(results[0].transcript(), "so long");
+    assert_eq!(checks.load(Ordering::SeqCst), 3);
+
+    let url = serve_all(|_| {
+        json_response(r#"{"id":"job1","status":"failed","warnings":["Audio is corrupt."]}"#)
+    });
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt.wait_for_job("job1", &polling).await.unwrap_err();
+    assert!(matches!(err, WaitError::Failed(reason) if reason == "Audio is corrupt."));
+
+    // the time spent in slow checks counts towards the timeout
+    let checks = std::sync::Arc::new(AtomicUsize::new(0));
+    let counter = checks.clone();
+    let url = serve_all(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(30));
+        json_response(r#"{"id":"job1","status":"processing"}"#)
+    });
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt.wait_for_job("job1", &polling).await.unwrap_err();
+    assert!(matches!(err, WaitError::TimedOut(timeout) if timeout == Duration::from_millis(50)));
+    assert!(checks.load(Ordering::SeqCst) <= 3);
+
+    let (url, _) = serve(json_response_with(
+        "404 Not Found",
+        r#"{"code":404,"error":"Not Found"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt.wait_for_job("gone", &polling).await.unwrap_err();
+    assert!(matches!(
+        err,
+        WaitError::Request(CheckJobError::NotFound404(id, Some(_))) if id == "gone"
+    ));
+
+    let (url, request) = serve("HTTP/1.1 204 No Content\r\n\r\n");
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    stt.delete_job("job1").await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("DELETE /instances/abc/v1/recognitions/job1 "));
+}
+
+#[tokio::test]
+async fn register_callbacks() {
+    use crate::stt::jobs::{
+        errors::RegisterCallbackError, verify_callback_signature, CallbackStatus,
+    };
+    let auth = NoAuthAuthenticator;
+
+    let (url, request) = serve(json_response_with(
+        "201 Created",
+        r#"{"status":"created","url":"https://example.com/hook"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let callback = stt
+        .register_callback("https://example.com/hook", Some("s3cret"))
+        .await
+        .unwrap();
+    assert_eq!(callback.status, CallbackStatus::Created);
+    let request = request.join().unwrap();
+    assert!(request.starts_with(
+        "POST /instances/abc/v1/register_callback?callback_url=https%3A%2F%2Fexample.com%2Fhook&user_secret=s3cret "
+    ));
+
+    let (url, _) = serve(json_response(
+        r#"{"status":"already created","url":"https://example.com/hook"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let callback = stt
+        .register_callback("https://example.com/hook", None)
+        .await
+        .unwrap();
+    assert_eq!(callback.status, CallbackStatus::AlreadyCreated);
+
+    let (url, _) = serve(json_response_with(
+        "400 Bad Request",
+        r#"{"code":400,"error":"Failed to verify the callback URL"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt
+        .register_callback("https://example.com/other", None)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        RegisterCallbackError::BadRequest400(url, Some(_)) if url == "https://example.com/other"
+    ));
+
+    let (url, request) = serve(json_response("{}"));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    stt.unregister_callback("https://example.com/hook")
+        .await
+        .unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with(
+        "POST /instances/abc/v1/unregister_callback?callback_url=https%3A%2F%2Fexample.com%2Fhook "
+    ));
+
+    let body = r#"{"id":"job1","event":"recognitions.completed"}"#;
+    let signature = "eOE21Jt2c9+ovlDf64UL482dgOc=";
+    assert!(verify_callback_signature("s3cret", body, signature));
+    assert!(!verify_callback_signature("s3cret", "tampered", signature));
+    assert!(!verify_callback_signature("s3cret", body, "not base64!"));
+}
+
+#[tokio::test]
+async fn list_and_get_models() {
+    use crate::stt::models::errors::GetModelError;
+    let model = |name: &str, rate: u32| {
+        format!(
+            r#"{{"name":"{name}","language":"en-GB","rate":{rate},"url":"","supported_features":{{"custom_language_model":true,"custom_acoustic_model":true,"speaker_labels":true,"low_latency":true}},"description":""}}"#
+        )
+    };
+    let auth = NoAuthAuthenticator;
+
+    let (url, request) = serve(json_response(&format!(
+        r#"{{"models":[{},{}]}}"#,
+        model("en-GB_Telephony", 8000),
+        model("en-GB_Multimedia", 16000)
+    )));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let models = stt.list_models().await.unwrap();
+    let telephony: Vec<_> = models
+        .iter()
+        .map(|model| (model.name.as_str(), model.is_telephony()))
+        .collect();
+    assert_eq!(
+        telephony,
+        [("en-GB_Telephony", true), ("en-GB_Multimedia", false)]
+    );
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("GET /instances/abc/v1/models "));
+
+    let (url, request) = serve(json_response(&model("en-GB_Multimedia", 16000)));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let model = stt.get_model("en-GB_Multimedia").await.unwrap();
+    assert_eq!(model.rate, 16000);
+    assert!(model.supported_features.low_latency);
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("GET /instances/abc/v1/models/en-GB_Multimedia "));
+
+    let (url, _) = serve(json_response_with(
+        "404 Not Found",
+        r#"{"code":404,"error":"Model en-GB_Narrow not found"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt.get_model("en-GB_Narrow").await.unwrap_err();
+    assert!(matches!(err, GetModelError::NotFound404(name, Some(_)) if name == "en-GB_Narrow"));
+}
+
+#[tokio::test]
+async fn manage_language_models() {
+    use crate::{
+        polling::{errors::WaitError, Polling},
+        stt::customisations::{
+            errors::{GetLanguageModelError, TrainLanguageModelError},
+            TrainingStatus, WordTypeToAdd,
+        },
+    };
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+    let model = |status: &str| {
+        format!(
+            r#"{{"customization_id":"cust1","created":"2016-06-01T18:51:37.291Z","language":"en-GB","versions":["en-GB_Multimedia.v2023-01-01"],"owner":"owner","name":"medical","description":"Drug names","base_model_name":"en-GB_Multimedia","status":"{status}","progress":0}}"#
+        )
+    };
+    let polling = Polling::new(Duration::from_millis(1), Duration::from_millis(50));
+    let auth = NoAuthAuthenticator;
+
+    let (url, request) = serve(json_response_with(
+        "201 Created",
+        r#"{"customization_id":"cust1"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let id = stt
+        .create_language_model("medical", "en-GB_Multimedia", None, Some("Drug names"))
+        .await
+        .unwrap();
+    assert_eq!(id, "cust1");
+    let request = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/customizations "));
+    assert!(request.ends_with(
+        r#"{"name":"medical","base_model_name":"en-GB_Multimedia","description":"Drug names"}"#
+    ));
+
+    let (url, request) = serve(json_response(&format!(
+        r#"{{"customizations":[{}]}}"#,
+        model("ready")
+    )));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let models = stt.list_language_models(Some("en-GB")).await.unwrap();
+    assert_eq!(models[0].customisation_id, "cust1");
+    assert_eq!(models[0].status, TrainingStatus::Ready);
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("GET /instances/abc/v1/customizations?language=en-GB "));
+
+    let (url, request) = serve(json_response("{}"));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    stt.train_language_model("cust1", Some(WordTypeToAdd::User), Some(0.3))
+        .await
+        .unwrap();
+    assert!(request.join().unwrap().starts_with(
+        "POST /instances/abc/v1/customizations/cust1/train?word_type_to_add=user&customization_weight=0.3 "
+    ));
+
+    let (url, _) = serve(json_response_with(
+        "409 Conflict",
+        r#"{"code":409,"error":"The model is being trained"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt
+        .train_language_model("cust1", None, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, TrainLanguageModelError::Conflict409(id, Some(_)) if id == "cust1"));
+
+    let checks = std::sync::Arc::new(AtomicUsize::new(0));
+    let counter = checks.clone();
+    let url = serve_all(move |request| {
+        assert!(request.starts_with("GET /instances/abc/v1/customizations/cust1 "));
+        match counter.fetch_add(1, Ordering::SeqCst) {
+            0 => json_response(&model("training")),
+            _ => json_response(&model("available")),
+        }
+    });
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let trained = stt.wait_until_trained("cust1", &polling).await.unwrap();
+    assert_eq!(trained.status, TrainingStatus::Available);
+    assert_eq!(checks.load(Ordering::SeqCst), 2);
+
+    let url = serve_all(move |_| json_response(&model("failed")));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt.wait_until_trained("cust1", &polling).await.unwrap_err();
+    assert!(matches!(err, WaitError::Failed(_)));
+
+    let (url, _) = serve(json_response_with(
+        "401 Unauthorized",
+        r#"{"code":401,"error":"Invalid customization_id"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt.wait_until_trained("other", &polling).await.unwrap_err();
+    assert!(matches!(
+        err,
+        WaitError::Request(GetLanguageModelError::Unauthorised401(id, Some(_))) if id == "other"
+    ));
+
+    for (action, path) in [("reset", "reset"), ("upgrade", "upgrade_model")] {
+        let (url, request) = serve(json_response("{}"));
+        let stt = SpeechToText::new(&auth, &url).unwrap();
+        match action {
+            "reset" => stt.reset_language_model("cust1").await.unwrap(),
+            _ => stt.upgrade_language_model("cust1").await.unwrap(),
+        }
+        assert!(request.join().unwrap().starts_with(&format!(
+            "POST /instances/abc/v1/customizations/cust1/{path} "
+        )));
+    }
+
+    let (url, request) = serve(json_response("{}"));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    stt.delete_language_model("cust1").await.unwrap();
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("DELETE /instances/abc/v1/customizations/cust1 "));
+}
+
+#[tokio::test]
+async fn manage_corpora() {
+    use crate::stt::customisations::{errors::GetCorpusError, AnalysisStatus};
+    let auth = NoAuthAuthenticator;
+
+    let (url, request) = serve(json_response_with("201 Created", "{}"));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    stt.add_corpus("cust1", "drug notes", "Take two ibuprofen.", true)
+        .await
+        .unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with(
+        "POST /instances/abc/v1/customizations/cust1/corpora/drug%20notes?allow_overwrite=true "
+    ));
+    assert!(request.contains(
+        "name=\"corpus_file\"; filename=\"drug notes.txt\"\r\nContent-Type: text/plain\r\n\r\nTake two ibuprofen.\r\n"
+    ));
+
+    let (url, request) = serve(json_response(
+        r#"{"corpora":[{"name":"notes","total_words":5,"out_of_vocabulary_words":1,"status":"analyzed"},{"name":"more","total_words":0,"out_of_vocabulary_words":0,"status":"being_processed"}]}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let corpora = stt.list_corpora("cust1").await.unwrap();
+    let statuses: Vec<_> = corpora
+        .iter()
+        .map(|corpus| (corpus.name.as_str(), corpus.status))
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            ("notes", AnalysisStatus::Analysed),
+            ("more", AnalysisStatus::BeingProcessed)
+        ]
+    );
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("GET /instances/abc/v1/customizations/cust1/corpora "));
+
+    let (url, _) = serve(json_response(
+        r#"{"name":"notes","total_words":0,"out_of_vocabulary_words":0,"status":"undetermined","error":"Analysis of corpus 'notes' failed."}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let corpus = stt.get_corpus("cust1", "notes").await.unwrap();
+    assert_eq!(corpus.status, AnalysisStatus::Undetermined);
+    assert!(corpus.error.is_some());
+
+    let (url, _) = serve(json_response_with(
+        "400 Bad Request",
+        r#"{"code":400,"error":"Invalid value for corpus name 'gone'"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt.get_corpus("cust1", "gone").await.unwrap_err();
+    assert!(matches!(err, GetCorpusError::BadRequest400(name, Some(_)) if name == "gone"));
+
+    let (url, request) = serve(json_response("{}"));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    stt.delete_corpus("cust1", "notes").await.unwrap();
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("DELETE /instances/abc/v1/customizations/cust1/corpora/notes "));
+}
+
+#[tokio::test]
+async fn manage_words() {
+    use crate::stt::customisations::{
+        errors::{AddWordError, GetWordError},
+        Word, WordSort, WordType,
+    };
+    let auth = NoAuthAuthenticator;
+
+    let (url, request) = serve(json_response_with("201 Created", "{}"));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let words = [
+        Word::new("IEEE", &["I. triple E."], None),
+        Word::new("hhonors", &[], Some("HHonors")),
+    ];
+    stt.add_words("cust1", &words).await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/customizations/cust1/words "));
+    assert!(request.ends_with(
+        r#"{"words":[{"word":"IEEE","sounds_like":["I. triple E."]},{"word":"hhonors","display_as":"HHonors"}]}"#
+    ));
+
+    let (url, request) = serve(json_response_with("201 Created", "{}"));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let word = Word::new("New_York", &["new york"], Some("New York"));
+    stt.add_word("cust1", &word).await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("PUT /instances/abc/v1/customizations/cust1/words/New_York "));
+    assert!(request.ends_with(r#"{"sounds_like":["new york"],"display_as":"New York"}"#));
+
+    let (url, _) = serve(json_response_with(
+        "409 Conflict",
+        r#"{"code":409,"error":"The model is being trained"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt.add_word("cust1", &word).await.unwrap_err();
+    assert!(matches!(err, AddWordError::Conflict409(id, Some(_)) if id == "cust1"));
+
+    let (url, request) = serve(json_response(
+        r#"{"words":[{"word":"ibuprofen","sounds_like":["eye bew profen"],"display_as":"ibuprofen","count":4,"source":["notes"]},{"word":"tomato","sounds_like":["tomatoh"],"display_as":"tomato","count":0,"source":["user"],"error":[{"tomatoh":"Numbers are not allowed in sounds-like"}]}]}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let words = stt
+        .list_words(
+            "cust1",
+            Some(WordType::Corpora),
+            Some(WordSort::MostFrequent),
+        )
+        .await
+        .unwrap();
+    assert_eq!(words[0].count, 4);
+    assert_eq!(words[0].source, ["notes"]);
+    assert!(words[0].errors.is_empty());
+    assert_eq!(words[1].errors.len(), 1);
+    assert!(request.join().unwrap().starts_with(
+        "GET /instances/abc/v1/customizations/cust1/words?word_type=corpora&sort=-count "
+    ));
+
+    let (url, _) = serve(json_response_with(
+        "400 Bad Request",
+        r#"{"code":400,"error":"Word 'gone' not found"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt.get_word("cust1", "gone").await.unwrap_err();
+    assert!(matches!(err, GetWordError::BadRequest400(word, Some(_)) if word == "gone"));
+
+    let (url, request) = serve(json_response("{}"));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    stt.delete_word("cust1", "New York").await.unwrap();
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("DELETE /instances/abc/v1/customizations/cust1/words/New%20York "));
+}
+
+#[tokio::test]
+async fn manage_grammars() {
+    use crate::stt::customisations::{errors::DeleteGrammarError, AnalysisStatus, GrammarFormat};
+    let auth = NoAuthAuthenticator;
+    let grammar = "#ABNF 1.0 ISO-8859-1;\nlanguage en-GB;\nroot $yesno;\n$yesno = yes | no;";
+
+    let (url, request) = serve(json_response_with("201 Created", "{}"));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    stt.add_grammar("cust1", "yes-no", grammar, GrammarFormat::Abnf, false)
+        .await
+        .unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/customizations/cust1/grammars/yes-no "));
+    assert!(request.contains("content-type: application/srgs\r\n"));
+    assert!(request.ends_with(grammar));
+
+    let (url, request) = serve(json_response(
+        r#"{"grammars":[{"name":"yes-no","out_of_vocabulary_words":0,"status":"analyzed"},{"name":"menu","out_of_vocabulary_words":0,"status":"undetermined","error":"Invalid grammar"}]}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let grammars = stt.list_grammars("cust1").await.unwrap();
+    assert_eq!(grammars[0].status, AnalysisStatus::Analysed);
+    assert_eq!(grammars[1].error.as_deref(), Some("Invalid grammar"));
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("GET /instances/abc/v1/customizations/cust1/grammars "));
+
+    let (url, request) = serve(json_response(
+        r#"{"name":"yes-no","out_of_vocabulary_words":0,"status":"being_processed"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let grammar = stt.get_grammar("cust1", "yes-no").await.unwrap();
+    assert_eq!(grammar.status, AnalysisStatus::BeingProcessed);
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("GET /instances/abc/v1/customizations/cust1/grammars/yes-no "));
+
+    let (url, _) = serve(json_response_with(
+        "400 Bad Request",
+        r#"{"code":400,"error":"Grammar 'menu' not found"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt.delete_grammar("cust1", "menu").await.unwrap_err();
+    assert!(matches!(err, DeleteGrammarError::BadRequest400(name, Some(_)) if name == "menu"));
+
+    let (url, request) = serve(json_response(r#"{"result_index":0,"results":[]}"#));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let options = RecognitionOptions::new()
+        .language_customisation_id("cust1")
+        .grammar_name("yes-no");
+    stt.recognise(&b"RIFF"[..], Some("audio/wav"), &options)
+        .await
+        .unwrap();
+    assert!(request.join().unwrap().starts_with(
+        "POST /instances/abc/v1/recognize?language_customization_id=cust1&grammar_name=yes-no "
+    ));
+}
+
+#[tokio::test]
+async fn manage_acoustic_models() {
+    use crate::stt::customisations::{errors::GetAcousticModelError, TrainingStatus};
+    let auth = NoAuthAuthenticator;
+
+    let (url, request) = serve(json_response_with(
+        "201 Created",
+        r#"{"customization_id":"ac1"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let id = stt
+        .create_acoustic_model("call centre", "en-GB_Telephony", None)
+        .await
+        .unwrap();
+    assert_eq!(id, "ac1");
+    let request = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/acoustic_customizations "));
+    assert!(request.ends_with(r#"{"name":"call centre","base_model_name":"en-GB_Telephony"}"#));
+
+    let (url, request) = serve(json_response(
+        r#"{"customizations":[{"customization_id":"ac1","language":"en-GB","name":"call centre","base_model_name":"en-GB_Telephony","status":"training","progress":0}]}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let models = stt.list_acoustic_models(None).await.unwrap();
+    assert_eq!(models[0].status, TrainingStatus::Training);
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("GET /instances/abc/v1/acoustic_customizations "));
+
+    let (url, _) = serve(json_response_with(
+        "401 Unauthorized",
+        r#"{"code":401,"error":"Invalid customization_id"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt.get_acoustic_model("other").await.unwrap_err();
+    assert!(matches!(err, GetAcousticModelError::Unauthorised401(id, Some(_)) if id == "other"));
+
+    let (url, request) = serve(json_response("{}"));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    stt.train_acoustic_model("ac1", Some("lang1"), Some(false))
+        .await
+        .unwrap();
+    assert!(request.join().unwrap().starts_with(
+        "POST /instances/abc/v1/acoustic_customizations/ac1/train?custom_language_model_id=lang1&strict=false "
+    ));
+
+    let (url, request) = serve(json_response("{}"));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    stt.upgrade_acoustic_model("ac1", None, true).await.unwrap();
+    assert!(request.join().unwrap().starts_with(
+        "POST /instances/abc/v1/acoustic_customizations/ac1/upgrade_model?force=true "
+    ));
+
+    let (url, request) = serve(json_response("{}"));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    stt.reset_acoustic_model("ac1").await.unwrap();
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("POST /instances/abc/v1/acoustic_customizations/ac1/reset "));
+
+    let (url, request) = serve(json_response("{}"));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    stt.delete_acoustic_model("ac1").await.unwrap();
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("DELETE /instances/abc/v1/acoustic_customizations/ac1 "));
+}
+
+#[tokio::test]
+async fn manage_audio_resources() {
+    use crate::stt::customisations::{errors::AddAudioError, AudioResourceType, AudioStatus};
+    let auth = NoAuthAuthenticator;
+
+    let (url, request) = serve(json_response_with("201 Created", "{}"));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    stt.add_audio(
+        "ac1",
+        "calls",
+        &b"PK"[..],
+        "application/zip",
+        Some("audio/l16;rate=8000"),
+        true,
+    )
+    .await
+    .unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with(
+        "POST /instances/abc/v1/acoustic_customizations/ac1/audio/calls?allow_overwrite=true "
+    ));
+    assert!(request.contains("content-type: application/zip\r\n"));
+    assert!(request.contains("contained-content-type: audio/l16;rate=8000\r\n"));
+    assert!(request.ends_with("PK"));
+
+    let (url, _) = serve(json_response_with(
+        "415 Unsupported Media Type",
+        r#"{"code":415,"error":"Unsupported content type"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let err = stt
+        .add_audio("ac1", "notes", &b"hi"[..], "text/plain", None, false)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        AddAudioError::UnsupportedMediaType415(Some(_))
+    ));
+
+    let (url, request) = serve(json_response(
+        r#"{"total_minutes_of_audio":11.5,"audio":[{"duration":690,"name":"calls","details":{"type":"archive","compression":"zip"},"status":"ok"}]}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let resources = stt.list_audio("ac1").await.unwrap();
+    assert_eq!(resources.total_minutes_of_audio, 11.5);
+    assert_eq!(
+        resources.audio[0].details.resource_type,
+        AudioResourceType::Archive
+    );
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("GET /instances/abc/v1/acoustic_customizations/ac1/audio "));
+
+    let (url, _) = serve(json_response(
+        r#"{"container":{"duration":690,"name":"calls","details":{"type":"archive","compression":"zip"},"status":"ok"},"audio":[{"duration":300,"name":"call1.wav","details":{"type":"audio","codec":"pcm_s16le","frequency":8000},"status":"ok"},{"duration":0,"name":"call2.wav","details":{"type":"undetermined"},"status":"invalid"}]}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let listing = stt.get_audio("ac1", "calls").await.unwrap();
+    assert!(listing.resource.is_none());
+    assert_eq!(listing.container.unwrap().duration, 690);
+    let statuses: Vec<_> = listing.files.iter().map(|file| file.status).collect();
+    assert_eq!(statuses, [AudioStatus::Ok, AudioStatus::Invalid]);
+    assert_eq!(listing.files[0].details.frequency, Some(8000));
+
+    let (url, _) = serve(json_response(
+        r#"{"duration":131,"name":"hello","details":{"type":"audio","codec":"pcm_s16le","frequency":16000},"status":"being_processed"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let listing = stt.get_audio("ac1", "hello").await.unwrap();
+    let resource = listing.resource.unwrap();
+    assert_eq!(resource.status, AudioStatus::BeingProcessed);
+    assert!(listing.container.is_none() && listing.files.is_empty());
+
+    let (url, request) = serve(json_response("{}"));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    stt.delete_audio("ac1", "hello").await.unwrap();
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("DELETE /instances/abc/v1/acoustic_customizations/ac1/audio/hello "));
+}
+
+#[tokio::test]
+async fn recognise_speaker_labels() {
+    let (url, request) = serve(json_response(
+        r#"{"result_index":0,"results":[{"final":true,"alternatives":[{"transcript":"hello hi ","timestamps":[["hello",0.1,0.5],["hi",0.9,1.2]]}]}],"speaker_labels":[{"from":0.1,"to":0.5,"speaker":0,"confidence":0.81,"final":true},{"from":0.9,"to":1.2,"speaker":1,"confidence":0.62,"final":true}]}"#,
+    ));
+    let auth = NoAuthAuthenticator;
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let options = RecognitionOptions::new().speaker_labels(true);
+    let results = stt
+        .recognise(&b"RIFF"[..], Some("audio/wav"), &options)
+        .await
+        .unwrap();
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("POST /instances/abc/v1/recognize?speaker_labels=true "));
+    assert_eq!(results.speaker_labels.len(), 2);
+    assert!(results.speaker_labels[1].final_results);
+    let speakers: Vec<_> = results.results[0].alternatives[0]
+        .timestamps
+        .iter()
+        .map(|word| (word.word.as_str(), results.speaker_of(word)))
+        .collect();
+    assert_eq!(speakers, [("hello", Some(0)), ("hi", Some(1))]);
+}
+
+#[tokio::test]
+async fn recognise_formatted() {
+    let (url, request) = serve(json_response(
+        r#"{"result_index":0,"results":[{"final":true,"alternatives":[{"transcript":"my card is XXXXXXXXXXXX1234 and it expires on 03/27 "}]}]}"#,
+    ));
+    let auth = NoAuthAuthenticator;
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let options = RecognitionOptions::new()
+        .smart_formatting(true)
+        .redaction(true);
+    let results = stt
+        .recognise(&b"RIFF"[..], Some("audio/wav"), &options)
+        .await
+        .unwrap();
+    assert!(results.transcript().contains("XXXXXXXXXXXX1234"));
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("POST /instances/abc/v1/recognize?smart_formatting=true&redaction=true "));
+}
+
+#[tokio::test]
+async fn recognise_keywords() {
+    let (url, request) = serve(json_response(
+        r#"{"result_index":0,"results":[{"final":true,"alternatives":[{"transcript":"the colour of the sky "}],"keywords_result":{"colour":[{"normalized_text":"colour","start_time":0.3,"end_time":0.7,"confidence":0.98}]}}]}"#,
+    ));
+    let auth = NoAuthAuthenticator;
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let options = RecognitionOptions::new().keywords(&["colour", "sea shore"], 0.5);
+    let results = stt
+        .recognise(&b"RIFF"[..], Some("audio/wav"), &options)
+        .await
+        .unwrap();
+    let keywords = &results.results[0].keywords_result;
+    assert_eq!(keywords.len(), 1);
+    assert_eq!(keywords["colour"][0].start_time, 0.3);
+    assert!(request.join().unwrap().starts_with(
+        "POST /instances/abc/v1/recognize?keywords=colour%2Csea+shore&keywords_threshold=0.5 "
+    ));
+}
+
+#[tokio::test]
+async fn recognise_alternatives() {
+    let (url, request) = serve(json_response(
+        r#"{"result_index":0,"results":[{"final":true,"alternatives":[{"transcript":"red sky ","confidence":0.9,"word_confidence":[["red",0.84],["sky",0.97]]},{"transcript":"read sky "}],"word_alternatives":[{"start_time":0.1,"end_time":0.4,"alternatives":[{"word":"red","confidence":0.84},{"word":"read","confidence":0.12}]}]}]}"#,
+    ));
+    let auth = NoAuthAuthenticator;
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let options = RecognitionOptions::new()
+        .max_alternatives(2)
+        .word_alternatives_threshold(0.1)
+        .word_confidence(true);
+    let results = stt
+        .recognise(&b"RIFF"[..], Some("audio/wav"), &options)
+        .await
+        .unwrap();
+    let result = &results.results[0];
+    assert_eq!(result.alternatives.len(), 2);
+    assert_eq!(result.alternatives[0].word_confidence[1].word, "sky");
+    assert!(result.alternatives[1].word_confidence.is_empty());
+    let words: Vec<_> = result.word_alternatives[0]
+        .alternatives
+        .iter()
+        .map(|alternative| alternative.word.as_str())
+        .collect();
+    assert_eq!(words, ["red", "read"]);
+    assert!(request.join().unwrap().starts_with(
+        "POST /instances/abc/v1/recognize?max_alternatives=2&word_alternatives_threshold=0.1&word_confidence=true "
+    ));
+}
+
+#[tokio::test]
+async fn recognise_unfiltered() {
+    let (url, request) = serve(json_response(
+        r#"{"result_index":0,"results":[{"final":true,"alternatives":[{"transcript":"oh darn "}]}]}"#,
+    ));
+    let auth = NoAuthAuthenticator;
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let options = RecognitionOptions::new()
+        .model("en-US_Multimedia")
+        .profanity_filter(false);
+    let results = stt
+        .recognise(&b"RIFF"[..], Some("audio/wav"), &options)
+        .await
+        .unwrap();
+    assert_eq!(results.transcript(), "oh darn");
+    assert!(request.join().unwrap().starts_with(
+        "POST /instances/abc/v1/recognize?model=en-US_Multimedia&profanity_filter=false "
+    ));
+}
+
+#[tokio::test]
+async fn recognise_stream() {
+    use super::server::{accept_websocket, read_frame, read_request, serve_with, write_frame};
+    use crate::stt::recognition::read_chunks;
+
+    let (url, audio) = serve_with(|mut reader, mut stream| {
+        let request = read_request(&mut reader);
+        accept_websocket(&request, &mut stream);
+        let (_, start) = read_frame(&mut reader);
+        write_frame(&mut stream, 0x1, br#"{"state":"listening"}"#);
+        let mut audio = Vec::new();
+        while let (0x2, chunk) = read_frame(&mut reader) {
+            audio.extend(chunk);
+        }
+        for message in [
+            &br#"{"result_index":0,"results":[{"final":true,"alternatives":[{"transcript":"first part "}]}],"speaker_labels":[{"from":0.1,"to":0.5,"speaker":0,"confidence":0.5,"final":false}]}"#[..],
+            br#"{"warnings":["Unknown arguments: colour."]}"#,
+            br#"{"result_index":1,"results":[{"final":true,"alternatives":[{"transcript":"second part "}]}],"speaker_labels":[{"from":0.1,"to":0.5,"speaker":1,"confidence":0.8,"final":true}]}"#,
+            br#"{"state":"listening"}"#,
+        ] {
+            write_frame(&mut stream, 0x1, message);
+        }
+        read_frame(&mut reader);
+        write_frame(&mut stream, 0x8, &1000u16.to_be_bytes());
+        (start, audio)
+    });
+    let auth = NoAuthAuthenticator;
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let mut recording = b"OggS".to_vec();
+    recording.resize(20 << 10, 7);
+    let results = stt
+        .recognise_stream(
+            read_chunks(&recording[..]),
+            None,
+            &RecognitionOptions::new(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(results.transcript(), "first part second part");
+    assert_eq!(results.warnings, ["Unknown arguments: colour."]);
+    // the interim label is replaced by the final one rather than kept alongside it
+    assert_eq!(results.speaker_labels.len(), 1);
+    assert_eq!(results.speaker_labels[0].speaker, 1);
+    let (start, audio) = audio.join().unwrap();
+    let start: serde_json::Value = serde_json::from_slice(&start).unwrap();
+    assert_eq!(start["content-type"], "audio/ogg");
+    assert_eq!(audio, recording);
+}
+
+#[tokio::test]
+async fn recognise_chunked() {
+    use super::server::{read_chunked, read_request, serve_with};
+    use crate::stt::jobs::JobOptions;
+    use bytes::Bytes;
+    use futures_util::stream;
+    use std::io::Write;
+    let serve_chunked = |response: String| {
+        serve_with(move |mut reader, mut stream| {
+            let request = read_request(&mut reader);
+            let body = read_chunked(&mut reader);
+            stream.write_all(response.as_bytes()).unwrap();
+            (request, body)
+        })
+    };
+    let audio = || {
+        stream::iter(["fLaC", "-1", "-2"].map(|chunk| Ok::<_, std::io::Error>(Bytes::from(chunk))))
+    };
+    let auth = NoAuthAuthenticator;
+
+    let (url, request) = serve_chunked(json_response(
+        r#"{"result_index":0,"results":[{"final":true,"alternatives":[{"transcript":"hello "}]}]}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let results = stt
+        .recognise_chunked(audio(), None, &RecognitionOptions::new())
+        .await
+        .unwrap();
+    assert_eq!(results.transcript(), "hello");
+    let (request, body) = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/recognize?"));
+    assert!(request.contains("transfer-encoding: chunked\r\n"));
+    assert!(request.contains("content-type: audio/flac\r\n"));
+    assert_eq!(body, b"fLaC-1-2");
+
+    let (url, request) = serve_chunked(json_response_with(
+        "201 Created",
+        r#"{"id":"4bd734c0","status":"waiting","created":"2016-08-17T19:15:17.926Z"}"#,
+    ));
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let job = stt
+        .create_job_chunked(audio(), Some("audio/flac"), &JobOptions::new())
+        .await
+        .unwrap();
+    assert_eq!(job.id, "4bd734c0");
+    let (request, body) = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/recognitions?"));
+    assert!(request.contains("transfer-encoding: chunked\r\n"));
+    assert_eq!(body, b"fLaC-1-2");
+}
+
+#[tokio::test]
+async fn recognise_multipart() {
+    let (url, request) = serve(json_response(
+        r#"{"result_index":0,"results":[{"final":true,"alternatives":[{"transcript":"how are you "}]},{"final":true,"alternatives":[{"transcript":"fine thanks "}]}]}"#,
+    ));
+    let auth = NoAuthAuthenticator;
+    let stt = SpeechToText::new(&auth, &url).unwrap();
+    let options = RecognitionOptions::new().timestamps(false);
+    let results = stt
+        .recognise_multipart(
+            [&b"fLaC-1"[..], &b"fLaC-2"[..]],
+            Some("audio/flac"),
+            &options,
+        )
+        .await
+        .unwrap();
+    assert_eq!(results.transcript(), "how are you fine thanks");
+
+    let request = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/recognize?timestamps=false "));
+    assert!(request.contains("content-type: multipart/form-data; boundary="));
+    assert!(request.contains(
+        "name=\"metadata\"\r\nContent-Type: application/json\r\n\r\n{\"data_parts_count\":2,\"part_content_type\":\"audio/flac\"}\r\n"
+    ));
+    let uploads: Vec<_> = request
+        .split("name=\"upload\"\r\nContent-Type: audio/flac\r\n\r\n")
+        .skip(1)
+        .map(|part| part.split("\r\n").next().unwrap())
+        .collect();
+    assert_eq!(uploads, ["fLaC-1", "fLaC-2"]);
+}
+
+#[test]
+fn detect_content_types() {
+    use crate::stt::recognition::detect_content_type;
+    let wav = b"RIFF\x24\x08\0\0WAVEfmt ";
+    let mp3 = [0xff, 0xfb, 0x90, 0x64];
+    let aac = [0xff, 0xf1, 0x50, 0x80];
+    let webm = [0x1a, 0x45, 0xdf, 0xa3, 0x9f];
+    let detected: Vec<_> = [
+        &wav[..],
+        b"fLaC\0\0\0\x22",
+        b"OggS\0\x02",
+        b"ID3\x04\0",
+        &mp3,
+        &aac,
+        &webm,
+        b"RIFF\x24\x08\0\0AVI ",
+        b"",
+    ]
+    .into_iter()
+    .map(detect_content_type)
+    .collect();
+    assert_eq!(
+        detected,
+        [
+            "audio/wav",
+            "audio/flac",
+            "audio/ogg",
+            "audio/mp3",
+            "audio/mp3",
+            "application/octet-stream",
+            "audio/webm",
+            "application/octet-stream",
+            "application/octet-stream",
+        ]
+    );
+}
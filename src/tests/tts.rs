@@ -0,0 +1,1580 @@
+use std::io::{Read, Write};
+
+use super::server::{
+    json_response, json_response_with, read_request, serve, serve_all, serve_with,
+};
+use crate::{
+    auth::NoAuthAuthenticator,
+    tts::{
+        synthesis::{AudioFormat, SpellOutMode, SynthesisOptions},
+        TextToSpeech,
+    },
+};
+
+#[tokio::test]
+async fn synthesise_post() {
+    let (url, request) =
+        serve("HTTP/1.1 200 OK\r\nContent-Type: audio/wav\r\nContent-Length: 4\r\n\r\nRIFF");
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let audio = tts
+        .synthesise(
+            "<speak>Hello & goodbye</speak>",
+            Some(AudioFormat::AudioWav { sample_rate: None }),
+            Some("custom-id"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(&audio[..], b"RIFF");
+    assert_eq!(audio.content_type(), "audio/wav");
+
+    let request = request.join().unwrap();
+    assert!(request.starts_with(
+        "POST /instances/abc/v1/synthesize?voice=en-US_MichaelV3Voice&customization_id=custom-id "
+    ));
+    assert!(request.contains("accept: audio/wav;rate=22050\r\n"));
+    assert!(request.ends_with(r#"{"text":"<speak>Hello & goodbye</speak>"}"#));
+}
+
+#[tokio::test]
+async fn synthesise_stream() {
+    use futures_util::StreamExt;
+    let (url, request) = serve(
+        "HTTP/1.1 200 OK\r\nContent-Type: audio/ogg\r\nTransfer-Encoding: chunked\r\n\r\n\
+         4\r\nOggS\r\n5\r\n-more\r\n0\r\n\r\n",
+    );
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let audio = tts
+        .synthesise_stream("Hello", &SynthesisOptions::new())
+        .await
+        .unwrap();
+    let chunks: Vec<_> = audio.map(Result::unwrap).collect().await;
+    assert_eq!(chunks.concat(), b"OggS-more");
+    request.join().unwrap();
+}
+
+#[tokio::test]
+async fn synthesise_websocket() {
+    use crate::{
+        tts::synthesis::{MarkReached, SynthesisEvent, TimingEvent},
+        websocket::{accept_key, apply_mask},
+    };
+    use futures_util::StreamExt;
+
+    let (url, request) = serve_with(|mut reader, mut stream| {
+        let request = read_request(&mut reader);
+        let key = request
+            .lines()
+            .find_map(|line| line.strip_prefix("sec-websocket-key: "))
+            .unwrap();
+        write!(
+            stream,
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+             Sec-WebSocket-Accept: {}\r\n\r\n",
+            accept_key(key)
+        )
+        .unwrap();
+
+        let mut head = [0; 2];
+        reader.read_exact(&mut head).unwrap();
+        assert_eq!(head[0], 0x81);
+        let len = match head[1] & 0x7f {
+            126 => {
+                let mut len = [0; 2];
+                reader.read_exact(&mut len).unwrap();
+                u16::from_be_bytes(len) as usize
+            }
+            len => len as usize,
+        };
+        let mut mask = [0; 4];
+        reader.read_exact(&mut mask).unwrap();
+        let mut text = vec![0; len];
+        reader.read_exact(&mut text).unwrap();
+        apply_mask(&mut text, mask);
+
+        for (opcode, payload) in [
+            (
+                0x1,
+                &br#"{"binary_streams":[{"content_type":"audio/ogg;codecs=opus"}]}"#[..],
+            ),
+            (0x2, b"OggS"),
+            (0x1, br#"{"words":[["Hello",0.0,0.5],["there",0.5,0.75]]}"#),
+            (0x1, br#"{"marks":[["greeted",0.75]]}"#),
+            (0x8, &1000u16.to_be_bytes()),
+        ] {
+            stream
+                .write_all(&[0x80 | opcode, payload.len() as u8])
+                .unwrap();
+            stream.write_all(payload).unwrap();
+        }
+        (request, String::from_utf8(text).unwrap())
+    });
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let events: Vec<_> = tts
+        .synthesise_websocket("Hello there", &SynthesisOptions::new().rate_percentage(-10))
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(
+        events,
+        [
+            SynthesisEvent::ContentType("audio/ogg;codecs=opus".to_owned()),
+            SynthesisEvent::Audio("OggS".into()),
+            SynthesisEvent::Word(TimingEvent {
+                word: "Hello".to_owned(),
+                start: 0.0,
+                end: 0.5,
+            }),
+            SynthesisEvent::Word(TimingEvent {
+                word: "there".to_owned(),
+                start: 0.5,
+                end: 0.75,
+            }),
+            SynthesisEvent::Mark(MarkReached {
+                name: "greeted".to_owned(),
+                time: 0.75,
+            }),
+        ]
+    );
+
+    let (request, text) = request.join().unwrap();
+    assert!(request.starts_with(
+        "GET /instances/abc/v1/synthesize?voice=en-US_MichaelV3Voice&rate_percentage=-10 "
+    ));
+    assert_eq!(
+        text,
+        r#"{"text":"Hello there","accept":"audio/ogg;codecs=opus;rate=48000","timings":["words"]}"#
+    );
+}
+
+#[test]
+fn audio_format_ids() {
+    use crate::tts::synthesis::AudioEndianness;
+    let l16 = AudioFormat::AudioL16 {
+        sample_rate: 8000,
+        endianness: None,
+    };
+    assert_eq!(l16.id(), "audio/l16;rate=8000");
+    assert_eq!(
+        l16.rate(16000)
+            .endianness(AudioEndianness::LittleEndian)
+            .id(),
+        "audio/l16;rate=16000;endianness=little-endian"
+    );
+    assert_eq!(
+        AudioFormat::AudioWebmCodecsVorbis { sample_rate: None }
+            .rate(44100)
+            .id(),
+        "audio/webm;codecs=vorbis;rate=44100"
+    );
+    assert_eq!(
+        AudioFormat::AudioMulaw { sample_rate: 8000 }.id(),
+        "audio/mulaw;rate=8000"
+    );
+    assert_eq!(AudioFormat::AudioWebm.rate(22050), AudioFormat::AudioWebm);
+    assert_eq!(
+        AudioFormat::AudioMp3 { sample_rate: None }.endianness(AudioEndianness::BigEndian),
+        AudioFormat::AudioMp3 { sample_rate: None }
+    );
+}
+
+#[test]
+fn ssml_builder() {
+    use crate::tts::ssml::{Element, ExpressiveStyle, PhonemeAlphabet, Speak};
+    use std::time::Duration;
+    let ssml = Speak::new()
+        .element(
+            Element::express_as(ExpressiveStyle::Cheerful)
+                .text("Say \"hi\" to <Tom> & ")
+                .element(Element::phoneme(PhonemeAlphabet::Ipa, "təˈmɑːtəʊ").text("tomato")),
+        )
+        .element(Element::pause_for(Duration::from_millis(1500)))
+        .element(Element::mark("end"))
+        .element(
+            Element::prosody()
+                .rate("+10%")
+                .pitch("low")
+                .rate("-10%")
+                .text("it's"),
+        );
+    assert_eq!(
+        String::from(ssml),
+        concat!(
+            r#"<speak version="1.0"><express-as style="cheerful">Say &quot;hi&quot; to &lt;Tom&gt; &amp; "#,
+            r#"<phoneme alphabet="ipa" ph="təˈmɑːtəʊ">tomato</phoneme></express-as>"#,
+            r#"<break time="1500ms"/><mark name="end"/>"#,
+            r#"<prosody rate="-10%" pitch="low">it&apos;s</prosody></speak>"#
+        )
+    );
+}
+
+#[test]
+fn validate_phonemes() {
+    use crate::tts::{
+        customisations::Word,
+        ssml::{errors::PhonemeError, PhonemeAlphabet},
+    };
+    assert_eq!(PhonemeAlphabet::Ibm.validate(".1Sa.0ke"), Ok(()));
+    assert_eq!(
+        PhonemeAlphabet::Ibm.validate("  "),
+        Err(PhonemeError::Empty)
+    );
+    assert_eq!(
+        PhonemeAlphabet::Ibm.validate("1s1a"),
+        Err(PhonemeError::MisplacedStress(2))
+    );
+    assert_eq!(
+        PhonemeAlphabet::Ibm.validate("ta..ke"),
+        Err(PhonemeError::EmptySyllable(3))
+    );
+    assert_eq!(
+        PhonemeAlphabet::Ibm.validate("ta.1"),
+        Err(PhonemeError::MisplacedStress(3))
+    );
+    assert_eq!(
+        PhonemeAlphabet::Ibm.validate("ta\"ke"),
+        Err(PhonemeError::InvalidCharacter {
+            character: '"',
+            position: 2
+        })
+    );
+
+    assert_eq!(PhonemeAlphabet::Ipa.validate("ˈtʃiːz.bɜːɡɚ"), Ok(()));
+    assert_eq!(
+        PhonemeAlphabet::Ipa.validate("t1a"),
+        Err(PhonemeError::LooksLikeSpr {
+            character: '1',
+            position: 1
+        })
+    );
+    assert_eq!(
+        PhonemeAlphabet::Ipa.validate("ta<"),
+        Err(PhonemeError::InvalidCharacter {
+            character: '<',
+            position: 2
+        })
+    );
+
+    let word = |translation: &str| Word::new("word", translation, None);
+    assert_eq!(word("sounds like this").validate_translation(), Ok(()));
+    assert_eq!(
+        word(r#"<phoneme alphabet='ibm' ph="1wxrd"></phoneme>"#).validate_translation(),
+        Ok(())
+    );
+    assert_eq!(
+        word(r#"<phoneme ph="wɜːd"></phoneme>"#).validate_translation(),
+        Ok(())
+    );
+    assert_eq!(
+        word(r#"<phoneme alphabet="ibm" ph="wɜːd"></phoneme>"#).validate_translation(),
+        Err(PhonemeError::LooksLikeIpa {
+            character: 'ɜ',
+            position: 1
+        })
+    );
+    assert_eq!(
+        word(r#"<phoneme alphabet="x-sampa" ph="w3:d"></phoneme>"#).validate_translation(),
+        Err(PhonemeError::UnknownAlphabet("x-sampa".to_owned()))
+    );
+}
+
+#[tokio::test]
+async fn synthesise_with_options() {
+    let (url, request) = serve("HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n");
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let options = SynthesisOptions::new()
+        .customisation_id("custom-id")
+        .rate_percentage(-20)
+        .pitch_percentage(5)
+        .spell_out_mode(SpellOutMode::Pairs);
+    tts.synthesise_with("Hello", &options).await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with(
+        "POST /instances/abc/v1/synthesize?voice=en-US_MichaelV3Voice&customization_id=custom-id\
+         &rate_percentage=-20&pitch_percentage=5&spell_out_mode=pairs "
+    ));
+    assert!(!request.contains("accept: audio"));
+}
+
+#[tokio::test]
+async fn list_custom_models() {
+    use crate::tts::customisations::Language;
+    let body = r#"{"customizations":[{"customization_id":"abc-123","name":"names","language":"en-GB","owner":"me","created":"2023-01-01T00:00:00.000Z","last_modified":"2023-01-02T00:00:00.000Z","description":"people"}]}"#;
+    let (url, request) = serve(json_response(body));
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let models = tts.list_custom_models(Some(Language::EnGb)).await.unwrap();
+    assert_eq!(models.len(), 1);
+    assert_eq!(models[0].customisation_id, "abc-123");
+    assert_eq!(models[0].language.as_deref(), Some("en-GB"));
+    assert_eq!(models[0].description.as_deref(), Some("people"));
+    assert!(models[0].words.is_none());
+    let request = request.join().unwrap();
+    assert!(request.starts_with("GET /instances/abc/v1/customizations?language=en-GB "));
+}
+
+#[tokio::test]
+async fn create_custom_model() {
+    let (url, request) = serve(json_response(r#"{"customization_id":"abc-123"}"#));
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let id = tts
+        .create_custom_model("names", None, None::<&str>)
+        .await
+        .unwrap();
+    assert_eq!(id, "abc-123");
+    let request = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/customizations "));
+    assert!(request.ends_with(r#"{"name":"names","language":"en-US"}"#));
+}
+
+#[tokio::test]
+async fn get_custom_model() {
+    let (url, request) = serve(json_response(
+        r#"{"customization_id":"abc-123","name":"names","words":[{"word":"IEEE","translation":"I triple E"}],"prompts":[]}"#,
+    ));
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let model = tts.get_custom_model("abc-123").await.unwrap();
+    assert_eq!(model.name, "names");
+    let words = model.words.unwrap();
+    assert_eq!(words[0].word, "IEEE");
+    assert_eq!(words[0].translation, "I triple E");
+    let request = request.join().unwrap();
+    assert!(request.starts_with("GET /instances/abc/v1/customizations/abc-123 "));
+}
+
+#[tokio::test]
+async fn update_custom_model() {
+    use crate::tts::customisations::{errors::UpdateModelError, Word};
+    let (url, request) = serve("HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n");
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let words = [Word {
+        word: "NCAA".to_owned(),
+        translation: "N C double A".to_owned(),
+        part_of_speech: None,
+    }];
+    tts.update_custom_model("abc-123", Some("renamed"), None, Some(&words))
+        .await
+        .unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/customizations/abc-123 "));
+    assert!(request
+        .ends_with(r#"{"name":"renamed","words":[{"word":"NCAA","translation":"N C double A"}]}"#));
+
+    let (url, _) = serve("HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\n\r\n");
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let error = tts
+        .update_custom_model("abc-123", None, Some("new"), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, UpdateModelError::Unauthorised401(id, None) if id == "abc-123"));
+}
+
+#[tokio::test]
+async fn delete_custom_model() {
+    use crate::tts::customisations::errors::DeleteModelError;
+    let (url, request) = serve("HTTP/1.1 204 No Content\r\n\r\n");
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    tts.delete_custom_model("abc-123").await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("DELETE /instances/abc/v1/customizations/abc-123 "));
+
+    let (url, _) = serve("HTTP/1.1 405 Method Not Allowed\r\nContent-Length: 0\r\n\r\n");
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let error = tts.delete_custom_model("abc-123").await.unwrap_err();
+    assert!(matches!(error, DeleteModelError::MethodNotAllowed405(id, None) if id == "abc-123"));
+}
+
+#[tokio::test]
+async fn add_custom_words() {
+    use crate::tts::customisations::{PartOfSpeech, Word};
+    let (url, request) = serve("HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n");
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let words = [
+        Word::new("IEEE", "I triple E", None),
+        Word::new("東京", "トウキョウ", Some(PartOfSpeech::Mesi)),
+    ];
+    tts.add_custom_words("abc-123", &words).await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/customizations/abc-123/words "));
+    assert!(request.ends_with(concat!(
+        r#"{"words":[{"word":"IEEE","translation":"I triple E"},"#,
+        r#"{"word":"東京","translation":"トウキョウ","part_of_speech":"Mesi"}]}"#
+    )));
+}
+
+#[tokio::test]
+async fn custom_word() {
+    use crate::tts::customisations::{PartOfSpeech, Word};
+    let auth = NoAuthAuthenticator;
+
+    let (url, request) = serve("HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n");
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let word = Word::new("TCP/IP", "T C P I P", None);
+    tts.add_custom_word("abc-123", &word).await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("PUT /instances/abc/v1/customizations/abc-123/words/TCP%2FIP "));
+    assert!(request.ends_with(r#"{"translation":"T C P I P"}"#));
+
+    let (url, request) = serve(json_response(r#"{"translation":"T C P I P"}"#));
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let word = tts.get_custom_word("abc-123", "TCP/IP").await.unwrap();
+    assert_eq!(word, Word::new("TCP/IP", "T C P I P", None));
+    let request = request.join().unwrap();
+    assert!(request.starts_with("GET /instances/abc/v1/customizations/abc-123/words/TCP%2FIP "));
+
+    let (url, _) = serve(json_response(
+        r#"{"translation":"トウキョウ","part_of_speech":"Koyu"}"#,
+    ));
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let word = tts.get_custom_word("abc-123", "東京").await.unwrap();
+    assert_eq!(word.part_of_speech, Some(PartOfSpeech::Koyu));
+
+    let (url, request) = serve(json_response(
+        r#"{"words":[{"word":"TCP/IP","translation":"T C P I P"}]}"#,
+    ));
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let words = tts.list_custom_words("abc-123").await.unwrap();
+    assert_eq!(words[0].word, "TCP/IP");
+    let request = request.join().unwrap();
+    assert!(request.starts_with("GET /instances/abc/v1/customizations/abc-123/words "));
+
+    let (url, request) = serve("HTTP/1.1 204 No Content\r\n\r\n");
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    tts.delete_custom_word("abc-123", "TCP/IP").await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("DELETE /instances/abc/v1/customizations/abc-123/words/TCP%2FIP "));
+}
+
+#[tokio::test]
+async fn get_pronunciation() {
+    use crate::tts::{pronunciation::PhonemeFormat, voices::WatsonVoice};
+    let (url, request) = serve(json_response(r#"{"pronunciation":".ˈtə.ˈme.ɾo.ʊ"}"#));
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let pronunciation = tts
+        .get_pronunciation(
+            "tomato",
+            Some(WatsonVoice::EnGbCharlotteV3),
+            Some(PhonemeFormat::IPA),
+            Some("abc-123"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(pronunciation.pronunciation, ".ˈtə.ˈme.ɾo.ʊ");
+    let request = request.join().unwrap();
+    assert!(request.starts_with(
+        "GET /instances/abc/v1/pronunciation?text=tomato&format=ipa\
+         &voice=en-GB_CharlotteV3Voice&customization_id=abc-123 "
+    ));
+
+    let (url, _) = serve("HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\n\r\n");
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    assert!(tts
+        .get_pronunciation("tomato", None, None, None::<&str>)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn speaker_models() {
+    let auth = NoAuthAuthenticator;
+
+    let (url, request) = serve(json_response(
+        r#"{"speakers":[{"speaker_id":"56367f89","name":"speaker_one"}]}"#,
+    ));
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let speakers = tts.list_speaker_models().await.unwrap();
+    assert_eq!(speakers[0].speaker_id, "56367f89");
+    assert_eq!(speakers[0].name, "speaker_one");
+    let request = request.join().unwrap();
+    assert!(request.starts_with("GET /instances/abc/v1/speakers "));
+
+    let path = std::env::temp_dir().join("ibm-watson-enrollment.wav");
+    std::fs::write(&path, b"RIFF").unwrap();
+    let (url, request) = serve(json_response_with(
+        "201 Created",
+        r#"{"speaker_id":"56367f89"}"#,
+    ));
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let speaker_id = tts
+        .create_speaker_model("speaker one", &path)
+        .await
+        .unwrap();
+    assert_eq!(speaker_id, "56367f89");
+    let request = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/speakers?speaker_name=speaker+one "));
+    assert!(request.contains("content-type: audio/wav\r\n"));
+    assert!(request.ends_with("\r\n\r\nRIFF"));
+}
+
+#[tokio::test]
+async fn get_and_delete_speaker_model() {
+    use crate::tts::customisations::PromptStatus;
+    let auth = NoAuthAuthenticator;
+
+    let (url, request) = serve(json_response(
+        r#"{"customizations":[{"customization_id":"abc-123","prompts":[{"prompt":"Hello","prompt_id":"hello","status":"available"}]}]}"#,
+    ));
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let models = tts.get_speaker_model("56367f89").await.unwrap();
+    assert_eq!(models[0].customisation_id, "abc-123");
+    assert_eq!(models[0].prompts[0].prompt_id, "hello");
+    assert_eq!(models[0].prompts[0].status, Some(PromptStatus::Available));
+    let request = request.join().unwrap();
+    assert!(request.starts_with("GET /instances/abc/v1/speakers/56367f89 "));
+
+    let (url, request) = serve("HTTP/1.1 204 No Content\r\n\r\n");
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    tts.delete_speaker_model("56367f89").await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("DELETE /instances/abc/v1/speakers/56367f89 "));
+}
+
+#[tokio::test]
+async fn add_custom_prompt() {
+    use crate::tts::customisations::{Prompt, PromptStatus};
+    let path = std::env::temp_dir().join("ibm-watson-prompt.wav");
+    std::fs::write(&path, b"RIFF").unwrap();
+    let (url, request) = serve(json_response_with(
+        "201 Created",
+        r#"{"prompt":"Hello","prompt_id":"hello","status":"processing"}"#,
+    ));
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let prompt = Prompt {
+        prompt: "Hello".to_owned(),
+        prompt_id: "hello".to_owned(),
+        speaker_id: Some("56367f89".to_owned()),
+        ..Default::default()
+    };
+    let added = tts
+        .add_custom_prompt("abc-123", &prompt, &path)
+        .await
+        .unwrap();
+    assert_eq!(added.status, Some(PromptStatus::Processing));
+
+    let request = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/customizations/abc-123/prompts/hello "));
+    assert!(request.contains("content-type: multipart/form-data; boundary="));
+    assert!(request.contains(concat!(
+        "Content-Disposition: form-data; name=\"metadata\"\r\n",
+        "Content-Type: application/json\r\n\r\n",
+        r#"{"prompt_text":"Hello","speaker_id":"56367f89"}"#
+    )));
+    assert!(request.contains(concat!(
+        "Content-Disposition: form-data; name=\"file\"; filename=\"ibm-watson-prompt.wav\"\r\n",
+        "Content-Type: audio/wav\r\n\r\nRIFF"
+    )));
+}
+
+#[tokio::test]
+async fn custom_prompts() {
+    use crate::tts::customisations::PromptStatus;
+    let auth = NoAuthAuthenticator;
+
+    let (url, request) = serve(json_response(
+        r#"{"prompts":[{"prompt":"Hello","prompt_id":"hello","status":"failed","error":"audio too long"}]}"#,
+    ));
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let prompts = tts.list_custom_prompts("abc-123").await.unwrap();
+    assert_eq!(prompts[0].status, Some(PromptStatus::Failed));
+    assert_eq!(prompts[0].error.as_deref(), Some("audio too long"));
+    let request = request.join().unwrap();
+    assert!(request.starts_with("GET /instances/abc/v1/customizations/abc-123/prompts "));
+
+    let (url, request) = serve(json_response(
+        r#"{"prompt":"Hello","prompt_id":"hello","status":"available","speaker_id":"56367f89"}"#,
+    ));
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let prompt = tts.get_custom_prompt("abc-123", "hello").await.unwrap();
+    assert_eq!(prompt.status, Some(PromptStatus::Available));
+    assert_eq!(prompt.speaker_id.as_deref(), Some("56367f89"));
+    let request = request.join().unwrap();
+    assert!(request.starts_with("GET /instances/abc/v1/customizations/abc-123/prompts/hello "));
+
+    let (url, request) = serve("HTTP/1.1 204 No Content\r\n\r\n");
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    tts.delete_custom_prompt("abc-123", "hello").await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("DELETE /instances/abc/v1/customizations/abc-123/prompts/hello "));
+}
+
+#[tokio::test]
+async fn synthesise_content_type() {
+    use crate::tts::synthesis::errors::SynthesisError;
+    let auth = NoAuthAuthenticator;
+    let mp3 = SynthesisOptions::new().format(AudioFormat::AudioMp3 { sample_rate: None });
+
+    let (url, _) =
+        serve("HTTP/1.1 200 OK\r\nContent-Type: audio/mpeg\r\nContent-Length: 3\r\n\r\nID3");
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let audio = tts.synthesise_with("Hello", &mp3).await.unwrap();
+    assert_eq!(audio.content_type(), "audio/mpeg");
+
+    let (url, _) = serve(
+        "HTTP/1.1 200 OK\r\nContent-Type: audio/ogg; codecs=opus\r\nContent-Length: 4\r\n\r\nOggS",
+    );
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let error = tts.synthesise_with("Hello", &mp3).await.unwrap_err();
+    assert!(matches!(
+        error,
+        SynthesisError::ContentTypeMismatch { requested, received }
+            if requested == "audio/mp3;rate=22050" && received == "audio/ogg; codecs=opus"
+    ));
+}
+
+#[tokio::test]
+async fn list_voices_for_language() {
+    use crate::tts::customisations::Language;
+    let voice = |name: &str, language: &str| {
+        format!(
+            r#"{{"url":"","gender":"female","name":"{name}","language":"{language}","description":"","customizable":true,"supported_features":{{"custom_pronunciation":true,"voice_transformation":false}}}}"#
+        )
+    };
+    let body = format!(
+        r#"{{"voices":[{},{},{}]}}"#,
+        voice("en-GB_KateV3Voice", "en-GB"),
+        voice("en-US_AllisonV3Voice", "en-US"),
+        voice("en-GB_CharlotteV3Voice", "en-GB"),
+    );
+    let (url, request) = serve(json_response(&body));
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let voices = tts.list_voices_for_language(Language::EnGb).await.unwrap();
+    let names: Vec<_> = voices.iter().map(|voice| voice.name.as_str()).collect();
+    assert_eq!(names, ["en-GB_KateV3Voice", "en-GB_CharlotteV3Voice"]);
+    let request = request.join().unwrap();
+    assert!(request.starts_with("GET /instances/abc/v1/voices "));
+}
+
+#[tokio::test]
+async fn get_voice_with_customisation() {
+    use crate::tts::voices::WatsonVoice;
+    let (url, request) = serve(json_response(
+        r#"{"url":"","gender":"female","name":"en-GB_KateV3Voice","language":"en-GB","description":"","customizable":true,"supported_features":{"custom_pronunciation":true,"voice_transformation":false},"customization":{"customization_id":"abc-123","name":"names"}}"#,
+    ));
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let voice = tts
+        .get_voice(WatsonVoice::EnGbKateV3, Some("abc-123"))
+        .await
+        .unwrap();
+    assert!(voice.supports_custom_models());
+    assert!(!voice.supports_voice_transformation());
+    assert_eq!(voice.customisation.unwrap().customisation_id, "abc-123");
+    let request = request.join().unwrap();
+    assert!(request
+        .starts_with("GET /instances/abc/v1/voices/en-GB_KateV3Voice?customization_id=abc-123 "));
+}
+
+#[tokio::test]
+async fn synthesise_with_voice_id() {
+    let (url, request) =
+        serve("HTTP/1.1 200 OK\r\nContent-Type: audio/wav\r\nContent-Length: 4\r\n\r\nRIFF");
+    let auth = NoAuthAuthenticator;
+    let mut tts = TextToSpeech::new(&auth, &url).unwrap();
+    tts.set_voice("en-US_EllieNatural");
+    tts.synthesise("Hello", None, None).await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/synthesize?voice=en-US_EllieNatural "));
+
+    let (url, request) = serve(json_response(
+        r#"{"url":"","gender":"female","name":"en-US_EllieNatural","language":"en-US","description":"","customizable":false,"supported_features":{"custom_pronunciation":false,"voice_transformation":false}}"#,
+    ));
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let voice = tts.get_voice("en-US_EllieNatural", None).await.unwrap();
+    assert_eq!(voice.name, "en-US_EllieNatural");
+    let request = request.join().unwrap();
+    assert!(request.starts_with("GET /instances/abc/v1/voices/en-US_EllieNatural "));
+}
+
+#[tokio::test]
+async fn synthesise_to_file() {
+    let (url, request) =
+        serve("HTTP/1.1 200 OK\r\nContent-Type: audio/wav\r\nContent-Length: 8\r\n\r\nRIFFdata");
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let options = SynthesisOptions::new().format(AudioFormat::AudioWav { sample_rate: None });
+    let path = std::env::temp_dir().join(format!("ibm-watson-speech-{}", std::process::id()));
+    let path = tts
+        .synthesise_to_file("Hello", &options, &path)
+        .await
+        .unwrap();
+    assert_eq!(path.extension().unwrap(), "wav");
+    assert_eq!(std::fs::read(&path).unwrap(), b"RIFFdata");
+    std::fs::remove_file(&path).unwrap();
+    request.join().unwrap();
+}
+
+#[tokio::test]
+async fn delete_labeled_data() {
+    let (url, request) = serve("HTTP/1.1 204 No Content\r\n\r\n");
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    tts.delete_labeled_data("customer 1").await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("DELETE /instances/abc/v1/user_data?customer_id=customer+1 "));
+}
+
+#[tokio::test]
+async fn learning_opt_out() {
+    let response = "HTTP/1.1 200 OK\r\nContent-Type: audio/wav\r\nContent-Length: 4\r\n\r\nRIFF";
+    let auth = NoAuthAuthenticator;
+    let (url, request) = serve(response);
+    let mut tts = TextToSpeech::new(&auth, &url).unwrap();
+    tts.set_learning_opt_out(true);
+    tts.synthesise("Hello", None, None).await.unwrap();
+    assert!(request
+        .join()
+        .unwrap()
+        .contains("x-watson-learning-opt-out: true\r\n"));
+
+    let (url, request) = serve(response);
+    let mut tts = TextToSpeech::new(&auth, &url).unwrap();
+    tts.set_learning_opt_out(true);
+    let options = SynthesisOptions::new().learning_opt_out(false);
+    tts.synthesise_with("Hello", &options).await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.contains("x-watson-learning-opt-out: false\r\n"));
+    assert!(!request.contains("x-watson-learning-opt-out: true"));
+
+    let (url, request) = serve(json_response(r#"{"voices":[]}"#));
+    let mut tts = TextToSpeech::new(&auth, &url).unwrap();
+    tts.set_learning_opt_out(true);
+    tts.list_voices().await.unwrap();
+    assert!(request
+        .join()
+        .unwrap()
+        .contains("x-watson-learning-opt-out: true\r\n"));
+}
+
+#[tokio::test]
+async fn customer_id_metadata() {
+    let response = "HTTP/1.1 200 OK\r\nContent-Type: audio/wav\r\nContent-Length: 4\r\n\r\nRIFF";
+    let auth = NoAuthAuthenticator;
+    let (url, request) = serve(response);
+    let mut tts = TextToSpeech::new(&auth, &url).unwrap();
+    tts.set_customer_id(Some("customer-1"));
+    tts.synthesise("Hello", None, None).await.unwrap();
+    assert!(request
+        .join()
+        .unwrap()
+        .contains("x-watson-metadata: customer_id=customer-1\r\n"));
+
+    let (url, request) = serve(response);
+    let mut tts = TextToSpeech::new(&auth, &url).unwrap();
+    tts.set_customer_id(Some("customer-1"));
+    let options = SynthesisOptions::new().customer_id("customer-2");
+    tts.synthesise_with("Hello", &options).await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.contains("x-watson-metadata: customer_id=customer-2\r\n"));
+    assert!(!request.contains("customer-1"));
+
+    // the request is never sent, so the server is not needed
+    let options = SynthesisOptions::new().customer_id("customer\n3");
+    assert!(matches!(
+        tts.synthesise_with("Hello", &options).await,
+        Err(crate::tts::synthesis::errors::SynthesisError::InvalidHeader(_))
+    ));
+}
+
+#[tokio::test]
+async fn builder() {
+    use crate::{region::Region, tts::errors::BuildError};
+    assert_eq!(
+        Region::Frankfurt.service_url("text-to-speech", "abc"),
+        "https://api.eu-de.text-to-speech.watson.cloud.ibm.com/instances/abc"
+    );
+    let auth = NoAuthAuthenticator;
+    assert_eq!(
+        TextToSpeech::builder(&auth).build().err(),
+        Some(BuildError::MissingServiceUrl)
+    );
+    assert_eq!(
+        TextToSpeech::builder(&auth)
+            .service_url("not a url")
+            .build()
+            .err(),
+        Some(BuildError::InvalidServiceUrl("not a url".to_owned()))
+    );
+    assert_eq!(
+        TextToSpeech::new(&auth, "not a url").err(),
+        Some(BuildError::InvalidServiceUrl("not a url".to_owned()))
+    );
+
+    let (url, request) =
+        serve("HTTP/1.1 200 OK\r\nContent-Type: audio/wav\r\nContent-Length: 4\r\n\r\nRIFF");
+    let tts = TextToSpeech::builder(&auth)
+        .service_url(url)
+        .voice("en-US_EllieNatural")
+        .learning_opt_out(true)
+        .customer_id("customer-1")
+        .build()
+        .unwrap();
+    tts.synthesise("Hello", None, None).await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/synthesize?voice=en-US_EllieNatural "));
+    assert!(request.contains("x-watson-learning-opt-out: true\r\n"));
+    assert!(request.contains("x-watson-metadata: customer_id=customer-1\r\n"));
+}
+
+#[tokio::test]
+async fn synthesise_batch() {
+    // echo the text back as the audio
+    let url = serve_all(|request| {
+        let text = request.rsplit_once(r#"{"text":""#).unwrap().1;
+        match text.trim_end_matches(r#""}"#) {
+            "fail" => "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\n\r\n".to_owned(),
+            text => format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: audio/wav\r\nContent-Length: {}\r\n\r\n{text}",
+                text.len()
+            ),
+        }
+    });
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let texts = ["one", "two", "fail", "three", "four"];
+    let results = tts
+        .synthesise_batch(texts, &SynthesisOptions::new(), 2)
+        .await;
+    assert_eq!(results.len(), 5);
+    for (text, result) in texts.iter().zip(results) {
+        match *text {
+            "fail" => assert!(matches!(
+                result,
+                Err(crate::tts::synthesis::errors::SynthesisError::InternalServerError500(None))
+            )),
+            text => assert_eq!(&result.unwrap()[..], text.as_bytes()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn retry_throttled_requests() {
+    use crate::auth::RetryPolicy;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    let requests = std::sync::Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    let url = serve_all(move |_| match counter.fetch_add(1, Ordering::SeqCst) {
+        0 => "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\n\r\n"
+            .to_owned(),
+        1 => "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n".to_owned(),
+        _ => json_response(r#"{"voices":[]}"#),
+    });
+    let auth = NoAuthAuthenticator;
+    let mut tts = TextToSpeech::new(&auth, &url).unwrap();
+    assert!(matches!(
+        tts.list_voices().await,
+        Err(crate::tts::voices::errors::ListVoicesError::TooManyRequests429(None))
+    ));
+    tts.set_retry(RetryPolicy::new(2).backoff(
+        std::time::Duration::from_millis(1),
+        std::time::Duration::from_millis(10),
+    ));
+    assert!(tts.list_voices().await.unwrap().is_empty());
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn service_error_details() {
+    use crate::{
+        errors::ServiceErrorDetails,
+        tts::{synthesis::errors::SynthesisError, voices::errors::ListVoicesError},
+    };
+    use reqwest::StatusCode;
+    let body =
+        r#"{"code":404,"error":"Model 'en-US_Unknown' not found","code_description":"Not Found"}"#;
+    let (url, _) = serve(format!(
+        "HTTP/1.1 404 Not Found\r\nX-Global-Transaction-Id: 6b1b0a1f\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    ));
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let error = tts.synthesise("Hello", None, None).await.unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "404: Model 'en-US_Unknown' not found (transaction ID 6b1b0a1f)"
+    );
+    let SynthesisError::NotFound404(Some(details)) = error else {
+        panic!("unexpected error {error:?}");
+    };
+    assert_eq!(
+        details,
+        ServiceErrorDetails {
+            code: 404,
+            error: "Model 'en-US_Unknown' not found".to_owned(),
+            code_description: Some("Not Found".to_owned()),
+            transaction_id: Some("6b1b0a1f".to_owned()),
+        }
+    );
+
+    let (url, _) = serve("HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n");
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let error = tts.list_voices().await.unwrap_err();
+    assert_eq!(error.to_string(), "The service is currently unavailable.");
+
+    let (url, _) = serve(json_response_with(
+        "401 Unauthorized",
+        r#"{"code":401,"error":"Unauthorized"}"#,
+    ));
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let error = tts.synthesise("Hello", None, None).await.unwrap_err();
+    assert!(matches!(error, SynthesisError::Unauthorised401(Some(_))));
+
+    let (url, _) = serve(json_response_with(
+        "403 Forbidden",
+        r#"{"code":403,"error":"Forbidden"}"#,
+    ));
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let error = tts.list_voices().await.unwrap_err();
+    assert_eq!(error.to_string(), "403: Forbidden");
+    assert!(matches!(error, ListVoicesError::Forbidden403(Some(_))));
+
+    let (url, _) = serve(json_response_with(
+        "418 I'm a teapot",
+        r#"{"code":418,"error":"I'm a teapot"}"#,
+    ));
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let error = tts.synthesise("Hello", None, None).await.unwrap_err();
+    let SynthesisError::UnexpectedStatus(StatusCode::IM_A_TEAPOT, Some(details)) = error else {
+        panic!("unexpected error {error:?}");
+    };
+    assert_eq!(details.error, "I'm a teapot");
+
+    let (url, _) = serve("HTTP/1.1 418 I'm a teapot\r\nContent-Length: 0\r\n\r\n");
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let error = tts.list_voices().await.unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "The service responded with an unexpected status 418 I'm a teapot"
+    );
+}
+
+#[tokio::test]
+async fn voices_if_changed() {
+    use crate::tts::voices::errors::{GetVoiceError, ListVoicesError};
+    let body = r#"{"voices":[]}"#;
+    let (url, request) = serve(format!(
+        "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    ));
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let voices = tts.list_voices_if_changed(None).await.unwrap();
+    assert_eq!(voices.etag.as_deref(), Some("\"v1\""));
+    assert!(!request.join().unwrap().contains("if-none-match"));
+
+    let (url, request) = serve("HTTP/1.1 304 Not Modified\r\n\r\n");
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let error = tts
+        .list_voices_if_changed(voices.etag.as_deref())
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ListVoicesError::NotModified304));
+    assert!(request
+        .join()
+        .unwrap()
+        .contains("if-none-match: \"v1\"\r\n"));
+
+    let (url, request) = serve("HTTP/1.1 304 Not Modified\r\n\r\n");
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let error = tts
+        .get_voice_if_changed("en-GB_KateV3Voice", None, Some("\"v2\""))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, GetVoiceError::NotModified304));
+    assert!(request
+        .join()
+        .unwrap()
+        .contains("if-none-match: \"v2\"\r\n"));
+}
+
+#[tokio::test]
+async fn split_long_text() {
+    use crate::tts::synthesis::{errors::SynthesisError, AudioFormat};
+    use std::sync::{Arc, Mutex};
+    // echo the text back as the audio, remembering every request
+    let texts = Arc::new(Mutex::new(Vec::new()));
+    let received = texts.clone();
+    let url = serve_all(move |request| {
+        let text = request.rsplit_once(r#"{"text":""#).unwrap().1;
+        let text = text.trim_end_matches(r#""}"#).to_owned();
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: audio/mpeg\r\nContent-Length: {}\r\n\r\n{text}",
+            text.len()
+        );
+        received.lock().unwrap().push(text);
+        response
+    });
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let options = SynthesisOptions::new()
+        .format(AudioFormat::AudioMp3 { sample_rate: None })
+        .split_long_text(true);
+
+    let text = "This is one of many sentences. ".repeat(400);
+    let audio = tts.synthesise_with(&text, &options).await.unwrap();
+    assert_eq!(&audio[..], text.as_bytes());
+    let sent = std::mem::take(&mut *texts.lock().unwrap());
+    assert_eq!(sent.len(), 3);
+    assert!(sent.iter().all(|chunk| chunk.len() <= 5000));
+    assert!(sent.iter().all(|chunk| chunk.starts_with("This")));
+
+    let ssml = format!("<speak>{}</speak>", "<s>Say this.</s> ".repeat(800));
+    let audio = tts.synthesise_with(&ssml, &options).await.unwrap();
+    let sent = std::mem::take(&mut *texts.lock().unwrap());
+    assert_eq!(sent.len(), 3);
+    assert_eq!(audio.len(), sent.iter().map(String::len).sum::<usize>());
+    for chunk in &sent {
+        assert!(chunk.len() <= 5000);
+        assert!(chunk.starts_with("<speak><s>") && chunk.ends_with("</speak>"));
+    }
+
+    let short = tts.synthesise_with("Hey there", &options).await.unwrap();
+    assert_eq!(&short[..], b"Hey there");
+    assert_eq!(texts.lock().unwrap().len(), 1);
+
+    let wav = options
+        .clone()
+        .format(AudioFormat::AudioWav { sample_rate: None });
+    assert!(matches!(
+        tts.synthesise_with(&text, &wav).await,
+        Err(SynthesisError::UnsplittableFormat(_))
+    ));
+    // joined Ogg streams would only play up to the end of the first chunk in many players
+    let default = SynthesisOptions::new().split_long_text(true);
+    let error = tts.synthesise_with(&text, &default).await.unwrap_err();
+    let SynthesisError::UnsplittableFormat(format) = error else {
+        panic!("unexpected error {error:?}");
+    };
+    assert!(format.starts_with("audio/ogg;codecs=opus"));
+    assert_eq!(texts.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn repair_wav_headers() {
+    let mut streamed = b"RIFF\x7f\x7f\x7f\x7fWAVEfmt \x10\x00\x00\x00".to_vec();
+    streamed.extend_from_slice(&[1, 0, 1, 0, 0x40, 0x1f, 0, 0, 0x40, 0x1f, 0, 0, 2, 0, 16, 0]);
+    streamed.extend_from_slice(b"data\x7f\x7f\x7f\x7f\x01\x00\x02\x00\x03\x00");
+    // the placeholder lengths are kept to ASCII, as the mock server only sends strings
+    let respond = |wav: &[u8]| {
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: audio/wav\r\nContent-Length: {}\r\n\r\n{}",
+            wav.len(),
+            std::str::from_utf8(wav).unwrap()
+        )
+    };
+    let check = |wav: &[u8]| {
+        assert_eq!(wav.len(), streamed.len());
+        assert_eq!(wav[4..8], 42u32.to_le_bytes());
+        assert_eq!(wav[40..44], 6u32.to_le_bytes());
+        assert_eq!(wav[44..], streamed[44..]);
+    };
+    let auth = NoAuthAuthenticator;
+    let options = SynthesisOptions::new().format(AudioFormat::AudioWav { sample_rate: None });
+
+    let (url, request) = serve(respond(&streamed));
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    check(&tts.synthesise_with("Hello", &options).await.unwrap());
+    request.join().unwrap();
+
+    let (url, request) = serve(respond(&streamed));
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let path = std::env::temp_dir().join(format!("ibm-watson-wav-{}.wav", std::process::id()));
+    let path = tts
+        .synthesise_to_file("Hello", &options, &path)
+        .await
+        .unwrap();
+    check(&std::fs::read(&path).unwrap());
+    std::fs::remove_file(&path).unwrap();
+    request.join().unwrap();
+}
+
+#[test]
+fn voice_without_supported_features() {
+    let voice: crate::tts::voices::Voice = serde_json::from_str(
+        r#"{"url":"","gender":"female","name":"en-US_EllieNatural","language":"en-US","description":"","customizable":false}"#,
+    )
+    .unwrap();
+    assert!(!voice.supports_custom_models());
+    assert!(!voice.supports_voice_transformation());
+}
+
+#[tokio::test]
+async fn speaking_style() {
+    use crate::tts::{ssml::ExpressiveStyle, voices::WatsonVoice};
+    let response = "HTTP/1.1 200 OK\r\nContent-Type: audio/wav\r\nContent-Length: 4\r\n\r\nRIFF";
+    let auth = NoAuthAuthenticator;
+    let options = SynthesisOptions::new().speaking_style(ExpressiveStyle::Cheerful);
+
+    let (url, request) = serve(response);
+    let mut tts = TextToSpeech::new(&auth, &url).unwrap();
+    tts.set_voice(WatsonVoice::EnUsEmmaExpressive);
+    tts.synthesise_with("Fish & chips", &options).await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/synthesize?voice=en-US_EmmaExpressive "));
+    assert!(request.ends_with(
+        r#"{"text":"<speak version=\"1.0\"><express-as style=\"cheerful\">Fish &amp; chips</express-as></speak>"}"#
+    ));
+
+    let (url, request) = serve(response);
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    tts.synthesise_with("<speak>Hello <break/>there</speak>", &options)
+        .await
+        .unwrap();
+    let request = request.join().unwrap();
+    assert!(request.ends_with(
+        r#"{"text":"<speak><express-as style=\"cheerful\">Hello <break/>there</express-as></speak>"}"#
+    ));
+}
+
+#[tokio::test]
+async fn synthesis_voice() {
+    use crate::tts::{synthesis::SpellOutMode, voices::WatsonVoice};
+    let response = "HTTP/1.1 200 OK\r\nContent-Type: audio/wav\r\nContent-Length: 4\r\n\r\nRIFF";
+    let auth = NoAuthAuthenticator;
+
+    let (url, request) = serve(response);
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let options = SynthesisOptions::new()
+        .voice(WatsonVoice::EnGbCharlotteV3)
+        .customisation_id("cust")
+        .spell_out_mode(SpellOutMode::Pairs);
+    tts.synthesise_with("Hey there", &options).await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/synthesize?voice=en-GB_CharlotteV3Voice&customization_id=cust&spell_out_mode=pairs "));
+
+    let (url, request) = serve(response);
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    tts.synthesise_with("Hey there", &SynthesisOptions::new())
+        .await
+        .unwrap();
+    let request = request.join().unwrap();
+    assert!(request.starts_with("POST /instances/abc/v1/synthesize?voice=en-US_MichaelV3Voice "));
+}
+
+#[tokio::test]
+async fn list_expressive_voices() {
+    let voice = |name: &str| {
+        format!(
+            r#"{{"url":"","gender":"female","name":"{name}","language":"en-US","description":"","customizable":true,"supported_features":{{"custom_pronunciation":true,"voice_transformation":false}}}}"#
+        )
+    };
+    let (url, _) = serve(json_response(&format!(
+        r#"{{"voices":[{},{}]}}"#,
+        voice("en-US_EmmaExpressive"),
+        voice("en-US_EmilyV3Voice")
+    )));
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let voices = tts.list_expressive_voices().await.unwrap();
+    let names: Vec<_> = voices.iter().map(|voice| voice.name.as_str()).collect();
+    assert_eq!(names, ["en-US_EmmaExpressive"]);
+    assert!(crate::tts::voices::WatsonVoice::EnUsEmmaExpressive.is_expressive());
+    assert!(!crate::tts::voices::WatsonVoice::EnUsEmilyV3.is_expressive());
+}
+
+#[tokio::test]
+async fn mock_transport() {
+    use crate::{auth::BearerTokenAuthenticator, transport::MockTransport};
+    use reqwest::{header::AUTHORIZATION, StatusCode};
+    use std::sync::{Arc, Mutex};
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let requests = seen.clone();
+    let mock = MockTransport::new(move |request| {
+        let authorization = request.headers()[AUTHORIZATION]
+            .to_str()
+            .unwrap()
+            .to_owned();
+        requests
+            .lock()
+            .unwrap()
+            .push((request.url().to_string(), authorization));
+        match request.url().path() {
+            "/instances/abc/v1/synthesize" => {
+                MockTransport::response(StatusCode::OK, "audio/wav", "RIFF")
+            }
+            _ => MockTransport::response(
+                StatusCode::BAD_REQUEST,
+                "application/json",
+                r#"{"code":400,"error":"Model 'nope' not found"}"#,
+            ),
+        }
+    });
+    let auth = BearerTokenAuthenticator::new("token");
+    let tts = TextToSpeech::builder(&auth)
+        .service_url("https://example.com/instances/abc")
+        .learning_opt_out(true)
+        .transport(mock)
+        .build()
+        .unwrap();
+    let audio = tts.synthesise("Hello", None, None).await.unwrap();
+    assert_eq!(&audio[..], b"RIFF");
+    assert_eq!(audio.content_type(), "audio/wav");
+    let error = tts.get_voice("nope", None).await.unwrap_err();
+    assert_eq!(error.to_string(), "400: Model 'nope' not found");
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [
+            (
+                "https://example.com/instances/abc/v1/synthesize?voice=en-US_MichaelV3Voice"
+                    .to_owned(),
+                "Bearer token".to_owned()
+            ),
+            (
+                "https://example.com/instances/abc/v1/voices/nope".to_owned(),
+                "Bearer token".to_owned()
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn custom_headers() {
+    use reqwest::header::{HeaderName, HeaderValue};
+    let (url, request) =
+        serve("HTTP/1.1 200 OK\r\nContent-Type: audio/wav\r\nContent-Length: 4\r\n\r\nRIFF");
+    let auth = NoAuthAuthenticator;
+    let mut tts = TextToSpeech::new(&auth, &url).unwrap();
+    tts.set_learning_opt_out(true);
+    let trace = HeaderName::from_static("x-trace");
+    let options = SynthesisOptions::new()
+        .header(trace.clone(), HeaderValue::from_static("a"))
+        .header(trace, HeaderValue::from_static("b"))
+        .header(
+            HeaderName::from_static("x-watson-learning-opt-out"),
+            HeaderValue::from_static("false"),
+        );
+    tts.synthesise_with("Hello", &options).await.unwrap();
+    let request = request.join().unwrap();
+    assert!(request.contains("x-trace: a\r\nx-trace: b\r\n"));
+    assert!(request.contains("x-watson-learning-opt-out: false\r\n"));
+    assert!(!request.contains("x-watson-learning-opt-out: true"));
+}
+
+#[tokio::test]
+async fn synthesis_progress() {
+    use crate::tts::synthesis::Progress;
+    let (url, request) =
+        serve("HTTP/1.1 200 OK\r\nContent-Type: audio/mpeg\r\nContent-Length: 8\r\n\r\nID3audio");
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let mut reports = Vec::new();
+    let audio = tts
+        .synthesise_with_progress("Hello", &SynthesisOptions::new(), |progress| {
+            reports.push(progress)
+        })
+        .await
+        .unwrap();
+    assert_eq!(&audio[..], b"ID3audio");
+    assert_eq!(
+        reports.last(),
+        Some(&Progress {
+            bytes_received: 8,
+            total_hint: Some(8)
+        })
+    );
+    assert!(reports
+        .windows(2)
+        .all(|pair| pair[0].bytes_received < pair[1].bytes_received));
+    request.join().unwrap();
+}
+
+#[cfg(feature = "pcm")]
+#[tokio::test]
+async fn decode_pcm() {
+    use crate::{
+        transport::MockTransport,
+        tts::{
+            pcm::{errors::PcmError, to_wav, Pcm},
+            synthesis::Audio,
+        },
+    };
+    use reqwest::StatusCode;
+
+    async fn synthesise(content_type: &'static str, body: Vec<u8>) -> Audio {
+        let auth = NoAuthAuthenticator;
+        let tts = TextToSpeech::builder(&auth)
+            .service_url("https://example.com")
+            .transport(MockTransport::new(move |_| {
+                MockTransport::response(StatusCode::OK, content_type, body.clone())
+            }))
+            .build()
+            .unwrap();
+        tts.synthesise("Hello", None, None).await.unwrap()
+    }
+
+    let l16 = synthesise("audio/l16;rate=8000", vec![0x00, 0x01, 0xff, 0x7f]).await;
+    let pcm = Pcm::decode(&l16).unwrap();
+    assert_eq!(pcm.sample_rate, 8000);
+    assert_eq!(pcm.samples, [256, 32767]);
+    let big = synthesise(
+        "audio/l16;rate=8000;endianness=big-endian",
+        vec![0x00, 0x01, 0xff, 0x7f],
+    )
+    .await;
+    assert_eq!(Pcm::decode(&big).unwrap().samples, [1, -129]);
+    assert_eq!(Pcm::decode(&l16).unwrap().resample(16000).samples.len(), 4);
+
+    let mulaw = synthesise("audio/mulaw;rate=8000", vec![0xff, 0x00, 0x80]).await;
+    assert_eq!(Pcm::decode(&mulaw).unwrap().samples, [0, -32124, 32124]);
+    let alaw = synthesise("audio/alaw", vec![0xd5, 0x55]).await;
+    let pcm = Pcm::decode(&alaw).unwrap();
+    assert_eq!((pcm.sample_rate, pcm.samples), (8000, vec![8, -8]));
+
+    let wav = to_wav(&l16, Some(16000)).unwrap();
+    assert_eq!(&wav[..4], b"RIFF");
+    assert_eq!(wav[24..28], 16000u32.to_le_bytes());
+    let decoded = Pcm::decode(&synthesise("audio/wav", wav).await).unwrap();
+    assert_eq!(decoded, Pcm::decode(&l16).unwrap().resample(16000));
+
+    let ogg = synthesise("audio/ogg;codecs=opus", b"OggS".to_vec()).await;
+    assert!(matches!(
+        Pcm::decode(&ogg),
+        Err(PcmError::UnsupportedFormat(_))
+    ));
+    let raw = synthesise("audio/l16", vec![0, 0]).await;
+    assert!(matches!(
+        Pcm::decode(&raw),
+        Err(PcmError::MissingSampleRate(_))
+    ));
+    let mut crafted = b"RIFF\0\0\0\0WAVEjunk".to_vec();
+    crafted.extend_from_slice(&u32::MAX.to_le_bytes());
+    let crafted = synthesise("audio/wav", crafted).await;
+    assert!(matches!(
+        Pcm::decode(&crafted),
+        Err(PcmError::InvalidAudio { .. })
+    ));
+}
+
+#[tokio::test]
+async fn synthesise_samples() {
+    use crate::{
+        transport::MockTransport,
+        tts::synthesis::{errors::SynthesisError, AudioEndianness},
+    };
+    use reqwest::StatusCode;
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::builder(&auth)
+        .service_url("https://example.com")
+        .transport(MockTransport::new(|request| {
+            let accept = request.headers()["accept"].to_str().unwrap().to_owned();
+            MockTransport::response(StatusCode::OK, &accept, vec![0x01, 0x00, 0xff, 0xff, 0x7f])
+        }))
+        .build()
+        .unwrap();
+    let format = AudioFormat::AudioL16 {
+        sample_rate: 16000,
+        endianness: None,
+    };
+    let options = SynthesisOptions::new().format(format);
+    let samples = tts.synthesise_samples("Hello", &options).await.unwrap();
+    assert_eq!(samples, [1, -1]);
+
+    let options = SynthesisOptions::new().format(format.endianness(AudioEndianness::BigEndian));
+    let samples = tts.synthesise_samples("Hello", &options).await.unwrap();
+    assert_eq!(samples, [256, -1]);
+
+    assert!(matches!(
+        tts.synthesise_samples("Hello", &SynthesisOptions::new())
+            .await,
+        Err(SynthesisError::NotLinear16(_))
+    ));
+}
+
+#[tokio::test]
+async fn wait_until_ready() {
+    use crate::tts::{
+        customisations::PromptStatus,
+        polling::{errors::WaitError, Polling},
+    };
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+    let polling = Polling::new(Duration::from_millis(1), Duration::from_millis(50));
+    let auth = NoAuthAuthenticator;
+
+    let checks = std::sync::Arc::new(AtomicUsize::new(0));
+    let counter = checks.clone();
+    let url = serve_all(move |request| {
+        assert!(request.starts_with("GET /instances/abc/v1/customizations/cust/prompts/hello "));
+        let status = match counter.fetch_add(1, Ordering::SeqCst) {
+            0 | 1 => "processing",
+            _ => "available",
+        };
+        json_response(&format!(
+            r#"{{"prompt":"Hello","prompt_id":"hello","status":"{status}"}}"#
+        ))
+    });
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let prompt = tts
+        .wait_until_prompt_ready("cust", "hello", &polling)
+        .await
+        .unwrap();
+    assert_eq!(prompt.status, Some(PromptStatus::Available));
+    assert_eq!(checks.load(Ordering::SeqCst), 3);
+
+    let url = serve_all(|_| {
+        json_response(
+            r#"{"customizations":[{"customization_id":"cust","prompts":[{"prompt":"Hi","prompt_id":"hi","status":"available"},{"prompt":"Bye","prompt_id":"bye","status":"failed","error":"The audio is too long"}]}]}"#,
+        )
+    });
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let err = tts
+        .wait_until_speaker_model_ready("speaker", &polling)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WaitError::Failed(reason) if reason == "The audio is too long"));
+
+    let url = serve_all(|_| {
+        json_response(
+            r#"{"customizations":[{"customization_id":"cust","prompts":[{"prompt":"Hi","prompt_id":"hi","status":"processing"}]}]}"#,
+        )
+    });
+    let tts = TextToSpeech::new(&auth, &url).unwrap();
+    let err = tts
+        .wait_until_speaker_model_ready("speaker", &polling)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WaitError::TimedOut(timeout) if timeout == Duration::from_millis(50)));
+}
+
+#[tokio::test]
+async fn shared_client() {
+    use crate::auth::IamAuthenticator;
+    use std::sync::Arc;
+    fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+    assert_shareable::<TextToSpeech<'static, IamAuthenticator>>();
+
+    let url = serve_all(|request| {
+        assert!(
+            request.starts_with("POST /instances/abc/v1/synthesize?voice=en-US_MichaelV3Voice ")
+        );
+        "HTTP/1.1 200 OK\r\nContent-Type: audio/wav\r\nContent-Length: 4\r\n\r\nRIFF".to_owned()
+    });
+    let tts = TextToSpeech::new_shared(Arc::new(NoAuthAuthenticator), &url).unwrap();
+    let tasks: Vec<_> = ["Hello", "Goodbye"]
+        .into_iter()
+        .map(|text| {
+            let tts = tts.clone();
+            tokio::spawn(async move { tts.synthesise(text, None, None).await })
+        })
+        .collect();
+    for task in tasks {
+        assert_eq!(&task.await.unwrap().unwrap()[..], b"RIFF");
+    }
+
+    let tts = TextToSpeech::shared_builder(Arc::new(NoAuthAuthenticator))
+        .service_url(url)
+        .build()
+        .unwrap();
+    let audio = tokio::spawn(async move { tts.synthesise("Hey", None, None).await });
+    assert_eq!(&audio.await.unwrap().unwrap()[..], b"RIFF");
+}
+
+#[cfg(feature = "compression")]
+#[tokio::test]
+async fn compressed_responses() {
+    use crate::transport::MockTransport;
+    use reqwest::{
+        header::{HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE},
+        Response, StatusCode,
+    };
+    use std::sync::{Arc, Mutex};
+    // the voices of `list_voices_for_language`, compressed with gzip using dynamic codes
+    const VOICES: &[u8] = b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\xcd\x51\x31\x0e\xc2\x30\x0c\xfc\x4b\xe6\x32\xb1\x75\x03\x06\x06\xc6\xaa\x5d\x10\x8a\x4c\xea\x94\x48\x89\x13\x39\x09\x03\x55\xff\x4e\x4a\x2b\x36\x24\xc6\x6e\xb6\xcf\x77\xba\xd3\x8d\xe2\xe9\x8d\xc2\x28\xea\xeb\x28\x32\x5b\x51\x0b\x51\x89\x01\xa9\x47\x2e\xb3\x46\x07\x16\xcb\x85\xc0\x61\xd9\x91\x76\xe7\xa3\xbc\x40\xc2\x6e\xdf\xcd\xc4\x02\x59\xa0\x21\xc3\xf0\x85\xcb\xa9\xc7\xa8\xd8\x84\x64\x3c\x2d\x82\x2a\xc7\xe4\x9d\x79\xc1\xbd\xa8\xd5\x89\x33\x56\x22\xe6\x10\x3c\x27\xec\xa5\x46\x48\x99\x67\x13\xe3\xfa\x29\x03\x7b\xca\xa4\x0c\x2c\x1a\x0b\xe3\x63\x55\x26\x06\x8a\xda\xb3\x5b\x31\x0d\x36\xe2\x34\x55\x7f\xfa\x6f\x1b\x79\xb0\xd6\x44\x4f\xbf\x22\xb4\xcd\xc6\x23\x94\x0a\x4e\x0f\x60\xeb\xd3\x06\x7b\xb8\x4d\x6f\x6b\x60\x8a\x99\x54\x02\x00\x00";
+    // `{"customizations":[]}` as a zlib stream using fixed codes
+    const MODELS: &[u8] = b"\x78\xda\xab\x56\x4a\x2e\x2d\x2e\xc9\xcf\xcd\xac\x4a\x2c\xc9\xcc\xcf\x2b\x56\xb2\x8a\x8e\xad\x05\x00\x5c\x38\x08\x3b";
+    let compressed = |encoding: &str, body: &'static [u8]| {
+        let response = http::Response::builder()
+            .header(CONTENT_TYPE, "application/json")
+            .header(CONTENT_ENCODING, encoding)
+            .body(body)
+            .unwrap();
+        Response::from(response)
+    };
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let requests = seen.clone();
+    let mock = MockTransport::new(move |request| {
+        let accept_encoding = request.headers().get(ACCEPT_ENCODING).cloned();
+        requests.lock().unwrap().push(accept_encoding);
+        match request.url().path() {
+            "/v1/voices" => compressed("gzip", VOICES),
+            "/v1/customizations" => compressed("deflate", MODELS),
+            _ => MockTransport::response(StatusCode::OK, "audio/wav", "RIFF"),
+        }
+    });
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::builder(&auth)
+        .service_url("https://example.com")
+        .transport(mock)
+        .build()
+        .unwrap();
+    let voices = tts.list_voices().await.unwrap();
+    assert_eq!(voices.len(), 3);
+    assert_eq!(voices[2].name, "en-GB_CharlotteV3Voice");
+    assert!(tts.list_custom_models(None).await.unwrap().is_empty());
+    tts.synthesise(
+        "Hello",
+        Some(AudioFormat::AudioWav { sample_rate: None }),
+        None,
+    )
+    .await
+    .unwrap();
+    // audio is already compressed, so only JSON responses are
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [
+            Some(HeaderValue::from_static("gzip, deflate")),
+            Some(HeaderValue::from_static("gzip, deflate")),
+            None,
+        ]
+    );
+}
+
+#[cfg(all(feature = "playback", unix))]
+#[tokio::test]
+async fn play_audio() {
+    use crate::{
+        transport::MockTransport,
+        tts::playback::{errors::PlaybackError, Player},
+    };
+    use reqwest::StatusCode;
+    let auth = NoAuthAuthenticator;
+    let tts = TextToSpeech::builder(&auth)
+        .service_url("https://example.com")
+        .transport(MockTransport::new(|_| {
+            MockTransport::response(StatusCode::OK, "audio/ogg;codecs=opus", "OggS audio")
+        }))
+        .build()
+        .unwrap();
+    let audio = tts.synthesise("Hello", None, None).await.unwrap();
+    let path = std::env::temp_dir().join(format!("ibm-watson-played-{}", std::process::id()));
+    // a player that records what it is given instead of playing it
+    Player::new("sh")
+        .args(["-c", "cat > \"$0\"", path.to_str().unwrap()])
+        .play(&audio)
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"OggS audio");
+    std::fs::remove_file(&path).unwrap();
+
+    let error = Player::new("false").play(&audio).await.unwrap_err();
+    assert!(matches!(error, PlaybackError::PlayerFailed { program, .. } if program == "false"));
+    let error = Player::new("/nonexistent/player")
+        .play(&audio)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, PlaybackError::PlayerNotStarted { .. }));
+}
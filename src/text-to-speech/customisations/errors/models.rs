@@ -1,19 +1,31 @@
+use reqwest::StatusCode;
 use thiserror::Error;
 
+use crate::errors::{describe, ServiceErrorDetails};
+
 #[derive(Error, Debug)]
 pub enum CreateModelError {
     /// There was an error establishing the connection
     #[error("{0}")]
     ConnectionError(String),
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
     /// A required input parameter is null or a specified input parameter or header value is invalid or not supported
-    BadRequest400,
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
     /// The service is currently unavailable
-    #[error("The service is currently unavailable")]
-    ServiceUnavailable503,
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
     /// The service experienced an internal error
-    #[error("The service experienced an internal error")]
-    InternalServerError500,
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
 
 #[derive(Error, Debug)]
@@ -21,15 +33,24 @@ pub enum ListModelError {
     /// There was an error establishing the connection
     #[error("{0}")]
     ConnectionError(String),
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
     /// A required input parameter is null or a specified input parameter or header value is invalid or not supported
-    BadRequest400,
-    #[error("The service is currently unavailable")]
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
     /// The service is currently unavailable
-    ServiceUnavailable503,
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
     /// The service experienced an internal error
-    #[error("The service experienced an internal error")]
-    InternalServerError500,
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
 
 #[derive(Error, Debug)]
@@ -37,18 +58,24 @@ pub enum UpdateModelError {
     /// There was an error establishing the connection
     #[error("{0}")]
     ConnectionError(String),
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
     /// A required input parameter is null or a specified input parameter or header value is invalid or not supported
-    BadRequest400,
-    #[error("The service is currently unavailable")]
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
     /// The service is currently unavailable
-    ServiceUnavailable503,
-    #[error("The service experienced an internal error")]
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
     /// The service experienced an internal error
-    InternalServerError500,
-    #[error("The specified customisation_id {0} is invalid for the requesting credentials")]
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
     /// The specified customisation_id is invalid for the requesting credentials
-    Unauthorised401(String),
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
 
 #[derive(Error, Debug)]
@@ -56,21 +83,27 @@ pub enum GetModelError {
     /// There was an error establishing the connection
     #[error("{0}")]
     ConnectionError(String),
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
+    #[error("{}", describe(.1, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
     /// A required input parameter is null or a specified input parameter or header value is invalid or not supported
-    BadRequest400(String),
-    #[error("The service is currently unavailable")]
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
     /// The service is currently unavailable
-    ServiceUnavailable503,
-    #[error("The service experienced an internal error")]
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
     /// The service experienced an internal error
-    InternalServerError500,
+    InternalServerError500(Option<ServiceErrorDetails>),
     /// "The specified customisation_id is invalid for the requesting credentials
-    #[error("The specified customisation_id {0} is invalid for the requesting credentials")]
-    Unauthorised401(String),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    Unauthorised401(String, Option<ServiceErrorDetails>),
     #[error(" The requested resource has not been modified since the time specified by the If-Modified-Since header, as documented in the HTTP specification.")]
     /// The requested resource has not been modified since the time specified by the If-Modified-Since header, as documented in the HTTP specification
     NotModified304,
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
 
 #[derive(Error, Debug)]
@@ -78,16 +111,26 @@ pub enum DeleteModelError {
     /// There was an error establishing the connection
     #[error("{0}")]
     ConnectionError(String),
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
+    #[error("{}", describe(.1, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
     /// A required input parameter is null or a specified input parameter or header value is invalid or not supported
-    BadRequest400(String),
-    #[error("The service is currently unavailable")]
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
     /// The service is currently unavailable
-    ServiceUnavailable503,
-    #[error("The service experienced an internal error")]
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
     /// The service experienced an internal error
-    InternalServerError500,
-    #[error("The specified customisation_id {0} is invalid for the requesting credentials")]
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
     /// The specified customisation_id is invalid for the requesting credentials
-    Unauthorised401(String),
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The custom model {} cannot be deleted with this method", .0)))]
+    /// The custom model cannot be deleted with this method, such as when it was created with a
+    /// newer version of the service
+    MethodNotAllowed405(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
@@ -1,19 +1,31 @@
+use reqwest::StatusCode;
 use thiserror::Error;
 
+use crate::errors::{describe, ServiceErrorDetails};
+
 #[derive(Error, Debug)]
 pub enum ListPromptsError {
     /// There was an error establishing the connection
     #[error("{0}")]
     ConnectionError(String),
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
     /// A required input parameter is null or a specified input parameter or header value is invalid or not supported
-    BadRequest400,
-    #[error("The service is currently unavailable")]
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
     /// The service is currently unavailable
-    ServiceUnavailable503,
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
     /// The service experienced an internal error
-    #[error("The service experienced an internal error")]
-    InternalServerError500,
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    InternalServerError500(Option<ServiceErrorDetails>),
+    /// The specified customisation_id is invalid for the requesting credentials
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
 
 #[derive(Error, Debug)]
@@ -21,7 +33,7 @@ pub enum AddPromptError {
     /// There was an error establishing the connection
     #[error("{0}")]
     ConnectionError(String),
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
     /// The request failed: Possible failure causes include:
     ///
     /// * The prompt name exceeds the 49-character limit or includes characters that are not alphanumeric or underscores
@@ -31,22 +43,28 @@ pub enum AddPromptError {
     /// * The service cannot align the text and the audio because differences between the two are too great
     /// * The request attempts to add a prompt to a custom model that is defined for a language other than US English
     /// * Invalid service credentials were passed with the request
-    BadRequest400,
-    #[error("The service is currently unavailable")]
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
     /// The service is currently unavailable
-    ServiceUnavailable503,
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
     /// The service experienced an internal error
-    #[error("The service experienced an internal error")]
-    InternalServerError500,
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    InternalServerError500(Option<ServiceErrorDetails>),
     /// There was an error reading the file
     #[error("There was an error reading the file: {0}")]
     FileReadError(String),
     /// The request passed an unacceptable media type with the Content-Type header. The header must pass a value of multipart/form-data
-    #[error("The request passed an unacceptable media type with the Content-Type header. The header must pass a value of multipart/form-data")]
-    UnsupportedMediaType415,
+    #[error("{}", describe(.0, "The request passed an unacceptable media type with the Content-Type header. The header must pass a value of multipart/form-data"))]
+    UnsupportedMediaType415(Option<ServiceErrorDetails>),
     /// The specified customisation_id is invalid for the requesting credentials
-    #[error("The specified customisation_id  {0} is invalid for the requesting credentials")]
-    Unauthorised401(String),
+    #[error("{}", describe(.1, &format!("The specified customisation_id  {} is invalid for the requesting credentials", .0)))]
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
 
 #[derive(Error, Debug)]
@@ -54,18 +72,24 @@ pub enum GetPromptError {
     /// There was an error establishing the connection
     #[error("{0}")]
     ConnectionError(String),
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
+    #[error("{}", describe(.1, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
     /// A required input parameter is null or a specified input parameter or header value is invalid or not supported
-    BadRequest400(String),
-    #[error("The service is currently unavailable")]
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
     /// The service is currently unavailable
-    ServiceUnavailable503,
-    #[error("The service experienced an internal error")]
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
     /// The service experienced an internal error
-    InternalServerError500,
+    InternalServerError500(Option<ServiceErrorDetails>),
     /// "The specified customisation_id is invalid for the requesting credentials
-    #[error("The specified customisation_id {0} is invalid for the requesting credentials")]
-    Unauthorised401(String),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
 
 #[derive(Error, Debug)]
@@ -74,15 +98,21 @@ pub enum DeletePromptError {
     #[error("{0}")]
     ConnectionError(String),
     /// A required input parameter is null or a specified input parameter or header value is invalid or not supported
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
-    BadRequest400(String),
-    #[error("The service is currently unavailable")]
+    #[error("{}", describe(.1, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
     /// The service is currently unavailable
-    ServiceUnavailable503,
-    #[error("The service experienced an internal error")]
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
     /// The service experienced an internal error
-    InternalServerError500,
-    #[error("The specified customisation_id {0} or prompt_id {1} is invalid for the requesting credentials")]
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.2, &format!("The specified customisation_id {} or prompt_id {} is invalid for the requesting credentials", .0, .1)))]
     /// The specified customisation_id is invalid for the requesting credentials
-    Unauthorised401(String, String),
+    Unauthorised401(String, String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
@@ -1,18 +1,27 @@
+use reqwest::StatusCode;
 use thiserror::Error;
+
+use crate::errors::{describe, ServiceErrorDetails};
 #[derive(Error, Debug)]
 pub enum AddWordError {
     /// A required input parameter is null or a specified input parameter or header value is invalid or not supported
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
-    BadRequest400,
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
+    BadRequest400(Option<ServiceErrorDetails>),
     /// The specified customisation_id is invalid for the requesting credentials
-    #[error("The specified customisation_id {0} is invalid for the requesting credentials")]
-    Unauthorised401(String),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
     /// The service experienced an internal error
-    #[error("The service experienced an internal error")]
-    InternalServerError500,
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    InternalServerError500(Option<ServiceErrorDetails>),
     /// The service is currently unavailable
-    #[error("The service is currently unavailable.")]
-    ServiceUnavailable503,
+    #[error("{}", describe(.0, "The service is currently unavailable."))]
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
     /// There was an error establishing the connection
     #[error("{0}")]
     ConnectionError(String),
@@ -24,17 +33,23 @@ pub enum ListWordsError {
     #[error("{0}")]
     ConnectionError(String),
     /// A required input parameter is null or a specified input parameter or header value is invalid or not supported
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
-    BadRequest400,
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
     /// The service is currently unavailable
-    #[error("The service is currently unavailable")]
-    ServiceUnavailable503,
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
     /// The service experienced an internal error
-    #[error("The service experienced an internal error")]
-    InternalServerError500,
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    InternalServerError500(Option<ServiceErrorDetails>),
     /// The specified customisation_id is invalid for the requesting credentials
-    #[error("The specified customisation_id {0} is invalid for the requesting credentials")]
-    Unauthorised401(String),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
 
 #[derive(Error, Debug)]
@@ -42,17 +57,23 @@ pub enum GetWordError {
     /// There was an error establishing the connection
     #[error("{0}")]
     ConnectionError(String),
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
-    BadRequest400,
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
     /// The service is currently unavailable
-    #[error("The service is currently unavailable")]
-    ServiceUnavailable503,
-    #[error("The service experienced an internal error")]
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
     /// The service experienced an internal error
-    InternalServerError500,
-    #[error("The specified customisation_id {0} is invalid for the requesting credentials")]
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
     /// The specified customisation_id is invalid for the requesting credentials
-    Unauthorised401(String),
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
 
 #[derive(Error, Debug)]
@@ -61,15 +82,21 @@ pub enum DeleteWordError {
     #[error("{0}")]
     ConnectionError(String),
     /// A required input parameter is null or a specified input parameter or header value is invalid or not supported
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
-    BadRequest400(String),
-    #[error("The service is currently unavailable")]
+    #[error("{}", describe(.1, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
     /// The service is currently unavailable
-    ServiceUnavailable503,
-    #[error("The service experienced an internal error")]
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
     /// The service experienced an internal error
-    InternalServerError500,
-    #[error("The specified customisation_id {0} is invalid for the requesting credentials")]
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
     /// The specified customisation_id is invalid for the requesting credentials
-    Unauthorised401(String),
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
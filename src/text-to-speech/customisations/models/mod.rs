@@ -1,8 +1,10 @@
 use std::borrow::Cow;
 
-use reqwest::{Method, Request, StatusCode, Url, Version};
+use reqwest::{Method, Request, StatusCode};
 use serde::{Deserialize, Serialize};
 
+use crate::auth::Authenticator;
+use crate::errors::ServiceErrorDetails;
 use crate::tts::TextToSpeech;
 
 use super::{
@@ -12,9 +14,14 @@ use super::{
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
-/// Defines a custom model
-pub struct Model {
-    /// the customisation id (guid) of the custom model. the create a custom model method returns only this field. it does not not return the other fields of this object.
+/// Defines a custom model. [`list_custom_models()`] returns only its metadata, leaving [`words`]
+/// and [`prompts`] empty
+///
+/// [`list_custom_models()`]: crate::tts::TextToSpeech::list_custom_models()
+/// [`words`]: Self::words
+/// [`prompts`]: Self::prompts
+pub struct CustomModel {
+    /// the customisation id (guid) of the custom model.
     #[serde(rename = "customization_id")]
     pub customisation_id: String,
     /// the name of the custom model.
@@ -43,6 +50,12 @@ pub struct Model {
     pub prompts: Option<Vec<Prompt>>,
 }
 
+#[deprecated(note = "renamed to `CustomModel`")]
+/// The former name of [`CustomModel`]
+///
+/// [`CustomModel`]: self::CustomModel
+pub type Model = CustomModel;
+
 #[non_exhaustive]
 #[derive(Default)]
 /// The language of the new custom model
@@ -115,8 +128,8 @@ impl Language {
     }
 }
 
-impl TextToSpeech<'_> {
-    /// Creates a new empty custom model. You must specify a name for the new custom model. You can optionally specify the language and a description for the new model. The model is owned by the instance of the service whose credentials are used to create it
+impl<A: Authenticator> TextToSpeech<'_, A> {
+    /// Creates a new empty custom model. You must specify a name for the new custom model. You can optionally specify the language and a description for the new model. The model is owned by the instance of the service whose credentials are used to create it. Returns the customisation ID (GUID) of the new model, which the other customisation methods take
     ///
     /// # Parameters
     ///
@@ -132,9 +145,9 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
-    /// let model = tts.create_custom_model("new model", None, Some("example")).await?;
-    /// println!("model: {:#?}", model);
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// let customisation_id = tts.create_custom_model("new model", None, Some("example")).await?;
+    /// println!("created: {customisation_id}");
     /// # Ok(())
     /// # }
     /// ```
@@ -145,48 +158,59 @@ impl TextToSpeech<'_> {
         name: impl AsRef<str>,
         language: Option<Language>,
         description: Option<impl AsRef<str>>,
-    ) -> Result<Model, CreateModelError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path("v1/customizations");
-        #[derive(Serialize, Deserialize)]
+    ) -> Result<String, CreateModelError> {
+        let url = self.service.endpoint("v1/customizations");
+        #[derive(Serialize)]
         struct FormBody<'a> {
             name: &'a str,
             language: &'a str,
-            description: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            description: Option<&'a str>,
         }
-        let name = name.as_ref();
-        let language = language.unwrap_or_default().id().to_owned();
-        let description = match description {
-            Some(s) => s.as_ref().to_owned(),
-            None => String::default(),
-        };
+        let language = language.unwrap_or_default().id();
         let form_body = FormBody {
-            name,
+            name: name.as_ref(),
             language: &language,
-            description: &description,
+            description: description.as_ref().map(AsRef::as_ref),
         };
-        let client = self.get_client();
-        let response = client
+        let req = self
+            .service
+            .http()
             .post(url)
             .json(&form_body)
-            .version(if cfg!(feature = "http2") {
-                Version::HTTP_2
-            } else {
-                Version::default()
-            })
-            .send()
-            .await
+            .build()
             .map_err(|e| CreateModelError::ConnectionError(e.to_string()))?;
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(CreateModelError::ConnectionError)?;
         match response.status() {
-            StatusCode::OK => {
-                let root: Model = response.json().await.unwrap();
-                Ok(root)
+            StatusCode::OK | StatusCode::CREATED => {
+                #[derive(Deserialize)]
+                struct Root {
+                    customization_id: String,
+                }
+                let root: Root = response
+                    .json()
+                    .await
+                    .map_err(|e| CreateModelError::ConnectionError(e.to_string()))?;
+                Ok(root.customization_id)
             }
-            StatusCode::BAD_REQUEST => Err(CreateModelError::BadRequest400),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(CreateModelError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(CreateModelError::ServiceUnavailable503),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(CreateModelError::BadRequest400(details)),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(CreateModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(CreateModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(CreateModelError::Unauthorised401(details)),
+                    StatusCode::FORBIDDEN => Err(CreateModelError::Forbidden403(details)),
+                    status => Err(CreateModelError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
@@ -205,7 +229,7 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// let models = tts.list_custom_models(None).await?;
     /// println!("found: {:#?} models", models.len());
     /// # Ok(())
@@ -217,35 +241,45 @@ impl TextToSpeech<'_> {
     pub async fn list_custom_models(
         &self,
         language: Option<Language>,
-    ) -> Result<Vec<Model>, ListModelError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path("v1/customizations");
-        url.set_query(Some(&language.unwrap_or_default().id()));
-        let mut req = Request::new(Method::GET, url);
-
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
+    ) -> Result<Vec<CustomModel>, ListModelError> {
+        let mut url = self.service.endpoint("v1/customizations");
+        if let Some(language) = language {
+            url.query_pairs_mut()
+                .append_pair("language", &language.id());
         }
+        let req = Request::new(Method::GET, url);
 
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| ListModelError::ConnectionError(e.to_string()))?;
+            .map_err(ListModelError::ConnectionError)?;
         match response.status() {
             StatusCode::OK => {
                 #[derive(Deserialize, Serialize)]
                 struct Root {
-                    customizations: Vec<Model>,
+                    customizations: Vec<CustomModel>,
                 }
-                let root: Root = response.json().await.unwrap();
+                let root: Root = response
+                    .json()
+                    .await
+                    .map_err(|e| ListModelError::ConnectionError(e.to_string()))?;
                 Ok(root.customizations)
             }
-            StatusCode::BAD_REQUEST => Err(ListModelError::BadRequest400),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(ListModelError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(ListModelError::ServiceUnavailable503),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(ListModelError::BadRequest400(details)),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(ListModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(ListModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(ListModelError::Unauthorised401(details)),
+                    StatusCode::FORBIDDEN => Err(ListModelError::Forbidden403(details)),
+                    status => Err(ListModelError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
@@ -272,13 +306,13 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// tts.update_custom_model("cust-id", Some("foo"), None, None).await?;
     /// # Ok(())
     /// # }
     /// ```
-    /// [`name`]: crate::tts::customisations::Model::name
-    /// [`description`]: crate::tts::customisations::Model::description
+    /// [`name`]: crate::tts::customisations::CustomModel::name
+    /// [`description`]: crate::tts::customisations::CustomModel::description
     /// [`Word`]: crate::tts::customisations::Word
     pub async fn update_custom_model(
         &self,
@@ -287,53 +321,54 @@ impl TextToSpeech<'_> {
         description: Option<&str>,
         words: Option<&[Word]>,
     ) -> Result<(), UpdateModelError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path(&format!("v1/customizations/{}", customisation_id.as_ref()));
-        #[derive(Deserialize, Serialize)]
-        struct Foo<'a> {
+        let url = self
+            .service
+            .endpoint(&format!("v1/customizations/{}", customisation_id.as_ref()));
+        #[derive(Serialize)]
+        struct Body<'a> {
             #[serde(skip_serializing_if = "Option::is_none")]
             name: Option<&'a str>,
             #[serde(skip_serializing_if = "Option::is_none")]
             description: Option<&'a str>,
             #[serde(skip_serializing_if = "Option::is_none")]
-            words: Option<Vec<Word>>,
+            words: Option<&'a [Word]>,
         }
-        impl<'a> Foo<'a> {
-            fn new(
-                name: Option<&'a str>,
-                description: Option<&'a str>,
-                words: Option<&'a [Word]>,
-            ) -> Self {
-                Self {
-                    name,
-                    description,
-                    words: words.map(|f| f.to_owned()),
-                }
-            }
-        }
-        let data = Foo::new(name, description, words);
-        let client = self.get_client();
-        let response = client
+        let body = Body {
+            name,
+            description,
+            words,
+        };
+        let req = self
+            .service
+            .http()
             .post(url)
-            .json(&data)
-            .version(if cfg!(feature = "http2") {
-                Version::HTTP_2
-            } else {
-                Version::default()
-            })
-            .send()
-            .await
+            .json(&body)
+            .build()
             .map_err(|e| UpdateModelError::ConnectionError(e.to_string()))?;
+        let response = self
+            .service
+            .execute(req)
+            .await
+            .map_err(UpdateModelError::ConnectionError)?;
         match response.status() {
             StatusCode::OK => Ok(()),
-            StatusCode::BAD_REQUEST => Err(UpdateModelError::BadRequest400),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(UpdateModelError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(UpdateModelError::ServiceUnavailable503),
-            StatusCode::UNAUTHORIZED => Err(UpdateModelError::Unauthorised401(
-                customisation_id.as_ref().to_owned(),
-            )),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(UpdateModelError::BadRequest400(details)),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(UpdateModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(UpdateModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(UpdateModelError::Unauthorised401(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::FORBIDDEN => Err(UpdateModelError::Forbidden403(details)),
+                    status => Err(UpdateModelError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
@@ -352,7 +387,7 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// let model = tts.get_custom_model("cust-id").await?;
     /// println!("{:#?}", model);
     /// # Ok(())
@@ -363,36 +398,43 @@ impl TextToSpeech<'_> {
     pub async fn get_custom_model(
         &self,
         customisation_id: impl AsRef<str>,
-    ) -> Result<Model, GetModelError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path(&format!("v1/customizations/{}", customisation_id.as_ref()));
-        let mut req = Request::new(Method::GET, url);
+    ) -> Result<CustomModel, GetModelError> {
+        let url = self
+            .service
+            .endpoint(&format!("v1/customizations/{}", customisation_id.as_ref()));
+        let req = Request::new(Method::GET, url);
 
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
-        }
-
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| GetModelError::ConnectionError(e.to_string()))?;
+            .map_err(GetModelError::ConnectionError)?;
         match response.status() {
-            StatusCode::OK => {
-                let root: Model = response.json().await.unwrap();
-                Ok(root)
-            }
-            StatusCode::BAD_REQUEST => Err(GetModelError::BadRequest400(
-                customisation_id.as_ref().to_owned(),
-            )),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(GetModelError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(GetModelError::ServiceUnavailable503),
-            StatusCode::NOT_MODIFIED => Err(GetModelError::NotModified304),
-            StatusCode::UNAUTHORIZED => Err(GetModelError::Unauthorised401(
-                customisation_id.as_ref().to_owned(),
-            )),
+            StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| GetModelError::ConnectionError(e.to_string())),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(GetModelError::BadRequest400(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(GetModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(GetModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::NOT_MODIFIED => Err(GetModelError::NotModified304),
+                    StatusCode::UNAUTHORIZED => Err(GetModelError::Unauthorised401(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::FORBIDDEN => Err(GetModelError::Forbidden403(details)),
+                    status => Err(GetModelError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
@@ -411,7 +453,7 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// if tts.delete_custom_model("cust-id").await.is_ok() {
     ///     println!("model deleted");
     /// }
@@ -424,31 +466,42 @@ impl TextToSpeech<'_> {
         &self,
         customisation_id: impl AsRef<str>,
     ) -> Result<(), DeleteModelError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path(&format!("v1/customizations/{}", customisation_id.as_ref()));
-        let mut req = Request::new(Method::DELETE, url);
+        let url = self
+            .service
+            .endpoint(&format!("v1/customizations/{}", customisation_id.as_ref()));
+        let req = Request::new(Method::DELETE, url);
 
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
-        }
-
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| DeleteModelError::ConnectionError(e.to_string()))?;
+            .map_err(DeleteModelError::ConnectionError)?;
         match response.status() {
             StatusCode::NO_CONTENT => Ok(()),
-            StatusCode::BAD_REQUEST => Err(DeleteModelError::BadRequest400(
-                customisation_id.as_ref().to_owned(),
-            )),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(DeleteModelError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(DeleteModelError::ServiceUnavailable503),
-            StatusCode::UNAUTHORIZED => Err(DeleteModelError::Unauthorised401(
-                customisation_id.as_ref().to_owned(),
-            )),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(DeleteModelError::BadRequest400(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(DeleteModelError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(DeleteModelError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(DeleteModelError::Unauthorised401(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::METHOD_NOT_ALLOWED => Err(DeleteModelError::MethodNotAllowed405(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::FORBIDDEN => Err(DeleteModelError::Forbidden403(details)),
+                    status => Err(DeleteModelError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
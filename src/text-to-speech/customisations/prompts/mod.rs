@@ -1,31 +1,20 @@
+#[cfg(not(target_arch = "wasm32"))]
 use std::path::Path;
 
-use reqwest::{
-    header::{HeaderValue, CONTENT_TYPE},
-    multipart::{Form, Part},
-    Method, Request, StatusCode, Url, Version,
-};
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::multipart::{Form, Part};
+use reqwest::{Method, Request, StatusCode, Url};
 use serde::{Deserialize, Serialize};
-use tokio::io::{AsyncReadExt, BufReader};
 
-use crate::tts::TextToSpeech;
+use crate::auth::Authenticator;
+use crate::errors::ServiceErrorDetails;
+use crate::tts::{
+    polling::{errors::WaitError, Polling, Status},
+    TextToSpeech,
+};
 
 use super::errors::{AddPromptError, DeletePromptError, GetPromptError, ListPromptsError};
 
-#[derive(Clone, Debug, Eq, PartialEq, Default, Serialize, Deserialize)]
-struct OuterPrompt {
-    #[serde(rename = "prompt")]
-    pub prompt: String,
-    #[serde(rename = "prompt_id")]
-    pub prompt_id: String,
-    #[serde(rename = "status", skip_serializing_if = "Option::is_none")]
-    pub status: Option<String>,
-    #[serde(rename = "error", skip_serializing_if = "Option::is_none")]
-    pub error: Option<String>,
-    #[serde(rename = "speaker_id", skip_serializing_if = "Option::is_none")]
-    pub speaker_id: Option<String>,
-}
-
 #[derive(Clone, Debug, Eq, PartialEq, Default, Serialize, Deserialize)]
 /// Defines information about the prompt in a custom model
 pub struct Prompt {
@@ -34,6 +23,11 @@ pub struct Prompt {
     #[serde(rename = "prompt_id")]
     /// The user-specified identifier (name) of the prompt
     pub prompt_id: String,
+    /// The status of the prompt. Poll [`get_custom_prompt()`] until it is no longer processing,
+    /// or wait for it with [`wait_until_prompt_ready()`]
+    ///
+    /// [`get_custom_prompt()`]: crate::tts::TextToSpeech::get_custom_prompt()
+    /// [`wait_until_prompt_ready()`]: crate::tts::TextToSpeech::wait_until_prompt_ready()
     #[serde(rename = "status", skip_serializing_if = "Option::is_none")]
     pub status: Option<PromptStatus>,
     /// If the status of the prompt is failed, an error message that describes the reason for the failure. The field is omitted if no error occurred
@@ -45,6 +39,7 @@ pub struct Prompt {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 /// The status of the prompt:
 pub enum PromptStatus {
     /// The service received the request to add the prompt and is analyzing the validity of the prompt.
@@ -55,28 +50,7 @@ pub enum PromptStatus {
     Failed,
 }
 
-impl From<OuterPrompt> for Prompt {
-    fn from(prompt: OuterPrompt) -> Self {
-        let status = match prompt.status {
-            Some(ref val) => match val.as_str() {
-                "processing" => Some(PromptStatus::Processing),
-                "available" => Some(PromptStatus::Available),
-                "failed" => Some(PromptStatus::Failed),
-                _ => unreachable!(),
-            },
-            None => None,
-        };
-        Self {
-            prompt: prompt.prompt,
-            prompt_id: prompt.prompt_id,
-            status,
-            error: prompt.error,
-            speaker_id: prompt.speaker_id,
-        }
-    }
-}
-
-impl TextToSpeech<'_> {
+impl<A: Authenticator> TextToSpeech<'_, A> {
     /// Lists information about all custom prompts that are defined for a custom [`model`]. The information includes the [`prompt ID`], [`prompt text`], [`status`], and
     /// optional [`speaker ID`] for each prompt of the custom model. You must use credentials for the instance of the service that owns the custom model.
     /// The same information about all of the prompts for a custom model is also provided by [`get_custom_model()`]. That method provides complete details about a specified
@@ -95,61 +69,69 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// let prompts = tts.list_custom_prompts("word").await?;
     /// println!("{:#?}", prompts);
     /// # Ok(())
     /// # }
     /// ```
     ///
-    /// [`model`]: crate::tts::customisations::Model
+    /// [`model`]: crate::tts::customisations::CustomModel
     /// [`prompt ID`]: crate::tts::customisations::Prompt::prompt_id
     /// [`prompt text`]: crate::tts::customisations::Prompt::prompt
     /// [`status`]: crate::tts::customisations::Prompt::status
     /// [`speaker ID`]: crate::tts::customisations::Prompt::speaker_id
     /// [`get_custom_model()`]: crate::tts::TextToSpeech::get_custom_model()
-    /// [`language`]: crate::tts::customisations::Model::language
-    /// [`owner`]: crate::tts::customisations::Model::owner
-    /// [`custom words`]: crate::tts::customisations::Model::words
+    /// [`language`]: crate::tts::customisations::CustomModel::language
+    /// [`owner`]: crate::tts::customisations::CustomModel::owner
+    /// [`custom words`]: crate::tts::customisations::CustomModel::words
     ///
     pub async fn list_custom_prompts(
         &self,
         customisation_id: impl AsRef<str>,
     ) -> Result<Vec<Prompt>, ListPromptsError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path(&format!(
-            "v1/customizations/{}/prompts",
-            customisation_id.as_ref()
-        ));
-        let mut req = Request::new(Method::GET, url);
-
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
-        }
+        let url = self.prompt_endpoint(customisation_id.as_ref(), None);
+        let req = Request::new(Method::GET, url);
 
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| ListPromptsError::ConnectionError(e.to_string()))?;
+            .map_err(ListPromptsError::ConnectionError)?;
         match response.status() {
             StatusCode::OK => {
                 #[derive(Deserialize, Serialize)]
                 struct Root {
                     prompts: Vec<Prompt>,
                 }
-                let root: Root = response.json().await.unwrap();
+                let root: Root = response
+                    .json()
+                    .await
+                    .map_err(|e| ListPromptsError::ConnectionError(e.to_string()))?;
                 Ok(root.prompts)
             }
-            StatusCode::BAD_REQUEST => Err(ListPromptsError::BadRequest400),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(ListPromptsError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(ListPromptsError::ServiceUnavailable503),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(ListPromptsError::BadRequest400(details)),
+                    StatusCode::UNAUTHORIZED => Err(ListPromptsError::Unauthorised401(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(ListPromptsError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(ListPromptsError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(ListPromptsError::Forbidden403(details)),
+                    status => Err(ListPromptsError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
     /// Adds a custom prompt to a custom [`model`]. A prompt is defined by the text that is to be spoken, the audio for that text, a unique user-specified ID for the prompt, and an optional speaker ID. The information is used to generate prosodic data that is not visible to the user. This data is used by the service to produce the synthesized audio upon request. You must use credentials for the instance of the service that owns a custom model to add a prompt to it. You can add a maximum of 1000 custom prompts to a single custom model
     ///
     /// # Parameters
@@ -169,7 +151,7 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// let file_path = std::path::Path::new("/home/user/audio.wav");
     /// let prompt = Prompt {
     ///     prompt: String::from("foo"),
@@ -181,15 +163,15 @@ impl TextToSpeech<'_> {
     /// # }
     /// ```
     ///
-    /// [`model`]: crate::tts::customisations::Model
+    /// [`model`]: crate::tts::customisations::CustomModel
     /// [`prompt ID`]: crate::tts::customisations::Prompt::prompt_id
     /// [`prompt text`]: crate::tts::customisations::Prompt::prompt
     /// [`status`]: crate::tts::customisations::Prompt::status
     /// [`speaker ID`]: crate::tts::customisations::Prompt::speaker_id
     /// [`get_custom_model()`]: crate::tts::TextToSpeech::get_custom_model()
-    /// [`language`]: crate::tts::customisations::Model::language
-    /// [`owner`]: crate::tts::customisations::Model::owner
-    /// [`custom words`]: crate::tts::customisations::Model::words
+    /// [`language`]: crate::tts::customisations::CustomModel::language
+    /// [`owner`]: crate::tts::customisations::CustomModel::owner
+    /// [`custom words`]: crate::tts::customisations::CustomModel::words
     ///
     pub async fn add_custom_prompt(
         &self,
@@ -197,68 +179,75 @@ impl TextToSpeech<'_> {
         prompt: &Prompt,
         audio_file: impl AsRef<Path>,
     ) -> Result<Prompt, AddPromptError> {
-        let audio_file = audio_file.as_ref().to_owned();
-        let name = audio_file.clone();
-        let f_name = name.file_name();
-        let file_name = f_name
-            .ok_or_else(|| AddPromptError::FileReadError("Could not read file".to_owned()))?;
-
-        let file_name = file_name.to_string_lossy();
-        let file_name = file_name.to_string();
-        let file = tokio::fs::OpenOptions::new()
-            .read(true)
-            .open(&audio_file)
-            .await
-            .map_err(|e| AddPromptError::FileReadError(e.to_string()))?;
-        let mut buf_reader = BufReader::new(file);
-        let mut buffer = Vec::new();
-        buf_reader
-            .read_to_end(&mut buffer)
+        let audio_file = audio_file.as_ref();
+        let file_name = audio_file
+            .file_name()
+            .ok_or_else(|| AddPromptError::FileReadError("Could not read file".to_owned()))?
+            .to_string_lossy()
+            .into_owned();
+        let buffer = tokio::fs::read(audio_file)
             .await
             .map_err(|e| AddPromptError::FileReadError(e.to_string()))?;
 
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path(&format!(
-            "v1/customizations/{}/prompts/{}",
-            customisation_id.as_ref(),
-            prompt.prompt_id
-        ));
-        let forms;
-        let form = Form::new()
-            .text("prompt_text", prompt.prompt.to_owned())
-            .part("file", Part::bytes(buffer).file_name(file_name));
+        #[derive(Serialize)]
+        struct Metadata<'a> {
+            prompt_text: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            speaker_id: Option<&'a str>,
+        }
+        let metadata = serde_json::to_string(&Metadata {
+            prompt_text: &prompt.prompt,
+            speaker_id: prompt.speaker_id.as_deref(),
+        })
+        .map_err(|e| AddPromptError::ConnectionError(e.to_string()))?;
+        let metadata = Part::text(metadata)
+            .mime_str("application/json")
+            .map_err(|e| AddPromptError::ConnectionError(e.to_string()))?;
+        let file = Part::bytes(buffer)
+            .file_name(file_name)
+            .mime_str("audio/wav")
+            .map_err(|e| AddPromptError::ConnectionError(e.to_string()))?;
+        let form = Form::new().part("metadata", metadata).part("file", file);
 
-        if let Some(speaker) = &prompt.speaker_id {
-            forms = form.text("speaker_id", speaker.to_owned());
-        } else {
-            forms = form;
-        };
-        let client = self.get_client();
-        let response = client
+        let url = self.prompt_endpoint(customisation_id.as_ref(), Some(&prompt.prompt_id));
+        let req = self
+            .service
+            .http()
             .post(url)
-            .header(
-                CONTENT_TYPE,
-                HeaderValue::from_static("multipart/form-data"),
-            )
-            .version(if cfg!(feature = "http2") {
-                Version::HTTP_2
-            } else {
-                Version::default()
-            })
-            .multipart(forms)
-            .send()
+            .multipart(form)
+            .build()
+            .map_err(|e| AddPromptError::ConnectionError(e.to_string()))?;
+        let response = self
+            .service
+            .execute(req)
             .await
-            .unwrap();
+            .map_err(AddPromptError::ConnectionError)?;
         match response.status() {
-            StatusCode::CREATED => Ok(response.json().await.unwrap()),
-            StatusCode::BAD_REQUEST => Err(AddPromptError::BadRequest400),
-            StatusCode::UNAUTHORIZED => Err(AddPromptError::Unauthorised401(
-                customisation_id.as_ref().to_string(),
-            )),
-            StatusCode::UNSUPPORTED_MEDIA_TYPE => Err(AddPromptError::UnsupportedMediaType415),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(AddPromptError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(AddPromptError::ServiceUnavailable503),
-            _ => unreachable!(),
+            StatusCode::CREATED | StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| AddPromptError::ConnectionError(e.to_string())),
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(AddPromptError::BadRequest400(details)),
+                    StatusCode::UNAUTHORIZED => Err(AddPromptError::Unauthorised401(
+                        customisation_id.as_ref().to_string(),
+                        details,
+                    )),
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE => {
+                        Err(AddPromptError::UnsupportedMediaType415(details))
+                    }
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(AddPromptError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(AddPromptError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(AddPromptError::Forbidden403(details)),
+                    status => Err(AddPromptError::UnexpectedStatus(status, details)),
+                }
+            }
         }
     }
 
@@ -278,7 +267,7 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// let prompt = tts.get_custom_prompt("cust-id", "prompt_id").await?;
     /// println!("{:#?}", prompt);
     /// # Ok(())
@@ -289,42 +278,96 @@ impl TextToSpeech<'_> {
         customisation_id: impl AsRef<str>,
         prompt_id: impl AsRef<str>,
     ) -> Result<Prompt, GetPromptError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path(&format!(
-            "v1/customizations/{}/prompts/{}",
-            customisation_id.as_ref(),
-            prompt_id.as_ref()
-        ));
-        let mut req = Request::new(Method::GET, url);
-
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
-        }
+        let url = self.prompt_endpoint(customisation_id.as_ref(), Some(prompt_id.as_ref()));
+        let req = Request::new(Method::GET, url);
 
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| GetPromptError::ConnectionError(e.to_string()))?;
+            .map_err(GetPromptError::ConnectionError)?;
         match response.status() {
-            StatusCode::OK => {
-                let root: Prompt = response.json().await.unwrap();
-                Ok(root)
-            }
-            StatusCode::BAD_REQUEST => Err(GetPromptError::BadRequest400(
-                customisation_id.as_ref().to_owned(),
-            )),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(GetPromptError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(GetPromptError::ServiceUnavailable503),
-            StatusCode::UNAUTHORIZED => Err(GetPromptError::Unauthorised401(
-                customisation_id.as_ref().to_owned(),
-            )),
+            StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| GetPromptError::ConnectionError(e.to_string())),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(GetPromptError::BadRequest400(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(GetPromptError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(GetPromptError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(GetPromptError::Unauthorised401(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::FORBIDDEN => Err(GetPromptError::Forbidden403(details)),
+                    status => Err(GetPromptError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
 
+    /// Waits until the service has finished processing a custom prompt, checking it with
+    /// [`get_custom_prompt()`] as often as `polling` asks. The prompt is returned once it is
+    /// available; if its validation failed, [`WaitError::Failed`] carries the service's reason
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of the custom model. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `prompt_id` - The identifier (name) of the prompt
+    /// * `polling` - How often, and for how long, to check the prompt
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     polling::Polling,
+    /// #     tts::TextToSpeech,
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// let prompt = tts
+    ///     .wait_until_prompt_ready("cust-id", "prompt_id", &Polling::default())
+    ///     .await?;
+    /// println!("{:#?}", prompt);
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`get_custom_prompt()`]: Self::get_custom_prompt()
+    /// [`WaitError::Failed`]: crate::polling::errors::WaitError::Failed
+    pub async fn wait_until_prompt_ready(
+        &self,
+        customisation_id: impl AsRef<str>,
+        prompt_id: impl AsRef<str>,
+        polling: &Polling,
+    ) -> Result<Prompt, WaitError<GetPromptError>> {
+        polling
+            .until(|| async {
+                let prompt = self
+                    .get_custom_prompt(customisation_id.as_ref(), prompt_id.as_ref())
+                    .await?;
+                Ok(match prompt.status {
+                    Some(PromptStatus::Available) => Status::Ready(prompt),
+                    Some(PromptStatus::Failed) => Status::Failed(
+                        prompt
+                            .error
+                            .unwrap_or_else(|| format!("prompt {} failed", prompt.prompt_id)),
+                    ),
+                    Some(PromptStatus::Processing) | None => Status::Pending,
+                })
+            })
+            .await
+    }
+
     /// Deletes an existing custom prompt from a custom [`model`]. The service deletes the prompt with the specified ID. You must use credentials for the instance of the service that owns the custom model from which the prompt is to be deleted
     ///
     /// # Parameters
@@ -340,50 +383,62 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// if tts.delete_custom_prompt("cust-id", "prompt-id").await.is_ok() {
     ///     println!("prompt deleted");
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    /// [`model`]: crate::tts::customisations::Model
+    /// [`model`]: crate::tts::customisations::CustomModel
     pub async fn delete_custom_prompt(
         &self,
         customisation_id: impl AsRef<str>,
         prompt_id: impl AsRef<str>,
     ) -> Result<(), DeletePromptError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path(&format!(
-            "v1/customizations/{}/prompts/{}",
-            customisation_id.as_ref(),
-            prompt_id.as_ref()
-        ));
-        let mut req = Request::new(Method::DELETE, url);
-
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
-        }
+        let url = self.prompt_endpoint(customisation_id.as_ref(), Some(prompt_id.as_ref()));
+        let req = Request::new(Method::DELETE, url);
 
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| DeletePromptError::ConnectionError(e.to_string()))?;
+            .map_err(DeletePromptError::ConnectionError)?;
         match response.status() {
             StatusCode::NO_CONTENT => Ok(()),
-            StatusCode::BAD_REQUEST => Err(DeletePromptError::BadRequest400(
-                customisation_id.as_ref().to_owned(),
-            )),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(DeletePromptError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(DeletePromptError::ServiceUnavailable503),
-            StatusCode::UNAUTHORIZED => Err(DeletePromptError::Unauthorised401(
-                customisation_id.as_ref().to_owned(),
-                prompt_id.as_ref().to_string(),
-            )),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(DeletePromptError::BadRequest400(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(DeletePromptError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(DeletePromptError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(DeletePromptError::Unauthorised401(
+                        customisation_id.as_ref().to_owned(),
+                        prompt_id.as_ref().to_string(),
+                        details,
+                    )),
+                    StatusCode::FORBIDDEN => Err(DeletePromptError::Forbidden403(details)),
+                    status => Err(DeletePromptError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
+
+    /// The URL of the prompts of a custom model, or of a single prompt in it
+    fn prompt_endpoint(&self, customisation_id: &str, prompt_id: Option<&str>) -> Url {
+        let mut url = self
+            .service
+            .endpoint(&format!("v1/customizations/{customisation_id}/prompts"));
+        if let Some(prompt_id) = prompt_id {
+            url.path_segments_mut().unwrap().push(prompt_id);
+        }
+        url
+    }
 }
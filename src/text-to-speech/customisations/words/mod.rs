@@ -1,17 +1,19 @@
-use reqwest::{
-    header::{HeaderValue, CONTENT_TYPE},
-    Body, Method, Request, StatusCode, Url, Version,
-};
+use reqwest::{Method, Request, StatusCode, Url};
 use serde::{Deserialize, Serialize};
 
-use crate::tts::TextToSpeech;
+use crate::auth::Authenticator;
+use crate::errors::ServiceErrorDetails;
+use crate::tts::{
+    ssml::{errors::PhonemeError, PhonemeAlphabet},
+    TextToSpeech,
+};
 
 use super::errors::{AddWordError, DeleteWordError, GetWordError, ListWordsError};
 
 #[derive(Clone, Debug, Eq, PartialEq, Default, Serialize, Deserialize)]
 /// Defines words and their translations to be used in custom [`models`]
 ///
-/// [`models`]: crate::tts::customisations::Model
+/// [`models`]: crate::tts::customisations::CustomModel
 pub struct Word {
     /// the word for the custom model. the maximum length of a word is 49 characters.
     #[serde(rename = "word")]
@@ -21,10 +23,153 @@ pub struct Word {
     pub translation: String,
     /// japanese only. the part of speech for the word. the service uses the value to produce the correct intonation for the word. you can create only a single entry, with or without a single part of speech, for any word; you cannot create multiple entries with different parts of speech for the same word.
     #[serde(rename = "part_of_speech", skip_serializing_if = "Option::is_none")]
-    pub part_of_speech: Option<String>,
+    pub part_of_speech: Option<PartOfSpeech>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+/// The part of speech of a Japanese [`word`], which the service uses to give it the right
+/// intonation. Custom words for Japanese custom models must have one
+///
+/// # Example
+/// ```
+/// # use ibm_watson::tts::customisations::{PartOfSpeech, Word};
+/// let word = Word::new("東京", "トウキョウ", Some(PartOfSpeech::Koyu));
+/// assert_eq!(PartOfSpeech::Koyu.id(), "Koyu");
+/// ```
+/// [`word`]: self::Word
+pub enum PartOfSpeech {
+    /// Verbs (動詞, doushi)
+    Dosi,
+    /// Adverbs (副詞, fukushi)
+    Fuku,
+    /// Inflections (語尾, gobi)
+    Gobi,
+    /// Other parts of speech (他, hoka)
+    Hoka,
+    /// Auxiliary verbs (助動詞, jodoushi)
+    Jodo,
+    /// Postpositions (助詞, joshi)
+    Josi,
+    /// Interjections (感動詞, kandoushi)
+    Kato,
+    /// Adjectival nouns (形容動詞, keiyoudoushi)
+    Kedo,
+    /// Adjectives (形容詞, keiyoushi)
+    Keyo,
+    /// Symbols (記号, kigou)
+    Kigo,
+    /// Proper nouns (固有名詞, koyuu meishi)
+    Koyu,
+    /// Common nouns (名詞, meishi)
+    Mesi,
+    /// Adnominals (連体詞, rentaishi)
+    Reta,
+    /// Suffixes (接尾辞, setsubiji)
+    Stbi,
+    /// Prefixes (接頭辞, settouji)
+    Stto,
+    /// Conjunctions (接続詞, setsuzokushi)
+    Stzo,
+    /// Numbers (数字, suuji)
+    Suji,
+}
+
+impl PartOfSpeech {
+    /// The value of the part of speech for the service
+    pub fn id(&self) -> &'static str {
+        match self {
+            PartOfSpeech::Dosi => "Dosi",
+            PartOfSpeech::Fuku => "Fuku",
+            PartOfSpeech::Gobi => "Gobi",
+            PartOfSpeech::Hoka => "Hoka",
+            PartOfSpeech::Jodo => "Jodo",
+            PartOfSpeech::Josi => "Josi",
+            PartOfSpeech::Kato => "Kato",
+            PartOfSpeech::Kedo => "Kedo",
+            PartOfSpeech::Keyo => "Keyo",
+            PartOfSpeech::Kigo => "Kigo",
+            PartOfSpeech::Koyu => "Koyu",
+            PartOfSpeech::Mesi => "Mesi",
+            PartOfSpeech::Reta => "Reta",
+            PartOfSpeech::Stbi => "Stbi",
+            PartOfSpeech::Stto => "Stto",
+            PartOfSpeech::Stzo => "Stzo",
+            PartOfSpeech::Suji => "Suji",
+        }
+    }
+}
+
+impl Word {
+    /// A word and its translation
+    ///
+    /// # Parameters
+    ///
+    /// * `word` - The word for the custom model
+    /// * `translation` - The phonetic or sounds-like translation for the word
+    /// * `part_of_speech` - Japanese only. The [`part of speech`] for the word
+    ///
+    /// [`part of speech`]: self::PartOfSpeech
+    pub fn new(
+        word: impl Into<String>,
+        translation: impl Into<String>,
+        part_of_speech: Option<PartOfSpeech>,
+    ) -> Self {
+        Self {
+            word: word.into(),
+            translation: translation.into(),
+            part_of_speech,
+        }
+    }
+
+    /// Checks the pronunciation of a phonetic translation, written as
+    /// `<phoneme alphabet="ibm" ph="...">`, offline with [`PhonemeAlphabet::validate()`]. A
+    /// sounds-like translation, one without a `<phoneme>` element, is always valid
+    ///
+    /// # Example
+    /// ```
+    /// # use ibm_watson::tts::customisations::Word;
+    /// let word = Word::new("IEEE", r#"<phoneme alphabet="ibm" ph="1Y.0tri.1pxl.1i"></phoneme>"#, None);
+    /// assert!(word.validate_translation().is_ok());
+    /// let word = Word::new("IEEE", r#"<phoneme alphabet="ipa" ph="1aɪ"></phoneme>"#, None);
+    /// assert!(word.validate_translation().is_err());
+    /// ```
+    /// [`PhonemeAlphabet::validate()`]: crate::tts::ssml::PhonemeAlphabet::validate()
+    pub fn validate_translation(&self) -> Result<(), PhonemeError> {
+        let Some(start) = self.translation.find("<phoneme") else {
+            return Ok(());
+        };
+        let tag = &self.translation[start..];
+        let tag = &tag[..tag.find('>').unwrap_or(tag.len())];
+        let alphabet = match attribute(tag, "alphabet") {
+            None | Some("ipa") => PhonemeAlphabet::Ipa,
+            Some("ibm") => PhonemeAlphabet::Ibm,
+            Some(other) => return Err(PhonemeError::UnknownAlphabet(other.to_owned())),
+        };
+        alphabet.validate(attribute(tag, "ph").unwrap_or_default())
+    }
 }
 
-impl TextToSpeech<'_> {
+/// The value of the attribute `name` of an XML start tag
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = tag;
+    while let Some(at) = rest.find(name) {
+        let before = rest[..at].chars().next_back();
+        let after = rest[at + name.len()..].trim_start();
+        rest = &rest[at + name.len()..];
+        if !before.is_some_and(char::is_whitespace) {
+            continue;
+        }
+        let Some(value) = after.strip_prefix('=').map(str::trim_start) else {
+            continue;
+        };
+        let quote = value.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+        let value = &value[1..];
+        return value.find(quote).map(|end| &value[..end]);
+    }
+    None
+}
+
+impl<A: Authenticator> TextToSpeech<'_, A> {
     /// Adds one or more words and their translations to the specified custom [`model`]. Adding a new translation for a word that already exists in a custom model overwrites the word's existing translation. A custom model can contain no more than 20,000 entries. You must use credentials for the instance of the service that owns a model to add words to it.
     ///
     /// # Parameters
@@ -41,22 +186,19 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
-    /// let word = Word {
-    ///     word: String::default(),
-    ///     translation: String::default(),
-    ///     part_of_speech: Option::default()
-    /// };
-    /// let mut words = vec![];
-    /// words.push(word);
-    /// if let Ok(_) = tts.add_custom_words("word", &words).await {
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// let words = [
+    ///     Word::new("IEEE", "I triple E", None),
+    ///     Word::new("NCAA", "N C double A", None),
+    /// ];
+    /// if let Ok(_) = tts.add_custom_words("customisation_id", &words).await {
     ///     println!("word(s) added!");
     /// }
     /// # Ok(())
     /// # }
     /// ```
     ///
-    /// [`model`]: crate::tts::customisations::Model
+    /// [`model`]: crate::tts::customisations::CustomModel
     /// [`Words`]: self::Word
     /// [`AddWordError`]: super::errors::AddWordError
     pub async fn add_custom_words(
@@ -64,44 +206,43 @@ impl TextToSpeech<'_> {
         customisation_id: impl AsRef<str>,
         words: &[Word],
     ) -> Result<(), AddWordError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        Self::set_words_path(&mut url, &customisation_id);
-        #[derive(Serialize, Deserialize)]
-        struct FormBody {
-            words: Vec<Word>,
-        }
-        impl FormBody {
-            fn new(words: &[Word]) -> Self {
-                Self {
-                    words: words.to_owned(),
-                }
-            }
-        }
-        let body = serde_json::to_string(&FormBody::new(words)).unwrap();
-        let mut req = Request::new(Method::POST, url);
-        req.headers_mut()
-            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-        *req.body_mut() = Some(Body::from(body));
-
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
+        let url = self.words_endpoint(customisation_id.as_ref(), None);
+        #[derive(Serialize)]
+        struct FormBody<'a> {
+            words: &'a [Word],
         }
+        let req = self
+            .service
+            .http()
+            .post(url)
+            .json(&FormBody { words })
+            .build()
+            .map_err(|e| AddWordError::ConnectionError(e.to_string()))?;
 
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| AddWordError::ConnectionError(e.to_string()))?;
+            .map_err(AddWordError::ConnectionError)?;
         match response.status() {
             StatusCode::OK => Ok(()),
-            StatusCode::BAD_REQUEST => Err(AddWordError::BadRequest400),
-            StatusCode::UNAUTHORIZED => Err(AddWordError::Unauthorised401(
-                customisation_id.as_ref().to_owned(),
-            )),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(AddWordError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(AddWordError::ServiceUnavailable503),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(AddWordError::BadRequest400(details)),
+                    StatusCode::UNAUTHORIZED => Err(AddWordError::Unauthorised401(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(AddWordError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(AddWordError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(AddWordError::Forbidden403(details)),
+                    status => Err(AddWordError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
@@ -121,49 +262,56 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// let words = tts.list_custom_words("customisation_id").await?;
     /// # Ok(())
     /// # }
     /// ```
     ///
-    /// [`model`]: crate::tts::customisations::Model
+    /// [`model`]: crate::tts::customisations::CustomModel
     /// [`Words`]: self::Word
     /// [`Error`]: super::errors::ListWordsError
     pub async fn list_custom_words(
         &self,
         customisation_id: impl AsRef<str>,
     ) -> Result<Vec<Word>, ListWordsError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        Self::set_words_path(&mut url, &customisation_id);
-        let mut req = Request::new(Method::GET, url);
-
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
-        }
+        let url = self.words_endpoint(customisation_id.as_ref(), None);
+        let req = Request::new(Method::GET, url);
 
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| ListWordsError::ConnectionError(e.to_string()))?;
+            .map_err(ListWordsError::ConnectionError)?;
         match response.status() {
             StatusCode::OK => {
                 #[derive(Deserialize, Serialize)]
                 struct Root {
                     words: Vec<Word>,
                 }
-                let root: Root = response.json().await.unwrap();
+                let root: Root = response
+                    .json()
+                    .await
+                    .map_err(|e| ListWordsError::ConnectionError(e.to_string()))?;
                 Ok(root.words)
             }
-            StatusCode::BAD_REQUEST => Err(ListWordsError::BadRequest400),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(ListWordsError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(ListWordsError::ServiceUnavailable503),
-            StatusCode::UNAUTHORIZED => Err(ListWordsError::Unauthorised401(
-                customisation_id.as_ref().to_owned(),
-            )),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(ListWordsError::BadRequest400(details)),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(ListWordsError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(ListWordsError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(ListWordsError::Unauthorised401(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::FORBIDDEN => Err(ListWordsError::Forbidden403(details)),
+                    status => Err(ListWordsError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
@@ -173,7 +321,7 @@ impl TextToSpeech<'_> {
     /// # Parameters
     ///
     /// * `customisation_id` -  The customisation ID (GUID) of the custom [`model`]. You must make the request with credentials for the instance of the service that owns the custom model
-    /// * `word` -  The customisation ID (GUID) of the custom [`model`]. You must make the request with credentials for the instance of the service that owns the custom model
+    /// * `word` - The [`word`] to add, along with its translation
     ///
     /// # Example
     /// ``` no_run
@@ -184,7 +332,7 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// let word = Word {
     ///     word: String::default(),
     ///     translation: String::default(),
@@ -196,60 +344,54 @@ impl TextToSpeech<'_> {
     /// ```
     /// [`Words`]: self::Word
     /// [`word`]: self::Word
-    /// [`model`]: crate::tts::customisations::Model
+    /// [`model`]: crate::tts::customisations::CustomModel
     pub async fn add_custom_word(
         &self,
         customisation_id: impl AsRef<str>,
         word: &Word,
     ) -> Result<(), AddWordError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path(&format!(
-            "v1/customizations/{}/words/{}",
-            customisation_id.as_ref(),
-            &word.word
-        ));
-        #[derive(Serialize, Deserialize)]
-        struct FormBody {
-            /// the phonetic or sounds-like translation for the word. a phonetic translation is based on the ssml format for representing the phonetic string of a word either as an ipa or ibm spr translation. the arabic, chinese, dutch, australian english, and korean languages support only ipa. a sounds-like translation consists of one or more words that, when combined, sound like the word. the maximum length of a translation is 499 characters.
-            #[serde(rename = "translation")]
-            translation: String,
-            /// japanese only. the part of speech for the word. the service uses the value to produce the correct intonation for the word. you can create only a single entry, with or without a single part of speech, for any word; you cannot create multiple entries with different parts of speech for the same word.
-            #[serde(rename = "part_of_speech", skip_serializing_if = "Option::is_none")]
-            part_of_speech: Option<String>,
-        }
-        impl FormBody {
-            fn new(words: &Word) -> Self {
-                Self {
-                    translation: words.translation.clone(),
-                    part_of_speech: words.part_of_speech.clone(),
-                }
-            }
-        }
-        let body = serde_json::to_string(&FormBody::new(word)).unwrap();
-        let mut req = Request::new(Method::PUT, url);
-        req.headers_mut()
-            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-        *req.body_mut() = Some(Body::from(body));
-
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
+        let url = self.words_endpoint(customisation_id.as_ref(), Some(&word.word));
+        #[derive(Serialize)]
+        struct FormBody<'a> {
+            translation: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            part_of_speech: Option<PartOfSpeech>,
         }
+        let req = self
+            .service
+            .http()
+            .put(url)
+            .json(&FormBody {
+                translation: &word.translation,
+                part_of_speech: word.part_of_speech,
+            })
+            .build()
+            .map_err(|e| AddWordError::ConnectionError(e.to_string()))?;
 
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| AddWordError::ConnectionError(e.to_string()))?;
+            .map_err(AddWordError::ConnectionError)?;
         match response.status() {
             StatusCode::OK => Ok(()),
-            StatusCode::BAD_REQUEST => Err(AddWordError::BadRequest400),
-            StatusCode::UNAUTHORIZED => Err(AddWordError::Unauthorised401(
-                customisation_id.as_ref().to_owned(),
-            )),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(AddWordError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(AddWordError::ServiceUnavailable503),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(AddWordError::BadRequest400(details)),
+                    StatusCode::UNAUTHORIZED => Err(AddWordError::Unauthorised401(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(AddWordError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(AddWordError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(AddWordError::Forbidden403(details)),
+                    status => Err(AddWordError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
@@ -270,7 +412,7 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// let word = tts.get_custom_word("customisation_id", "foo").await?;
     /// println!("{:#?}", word);
     /// # Ok(())
@@ -278,42 +420,55 @@ impl TextToSpeech<'_> {
     /// ```
     /// [`Words`]: self::Word
     /// [`word`]: self::Word
-    /// [`model`]: crate::tts::customisations::Model
+    /// [`model`]: crate::tts::customisations::CustomModel
     pub async fn get_custom_word(
         &self,
         customisation_id: impl AsRef<str>,
         word: impl AsRef<str>,
     ) -> Result<Word, GetWordError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path(&format!(
-            "v1/customizations/{}/words/{}",
-            customisation_id.as_ref(),
-            word.as_ref()
-        ));
-        let mut req = Request::new(Method::GET, url);
-
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
-        }
+        let url = self.words_endpoint(customisation_id.as_ref(), Some(word.as_ref()));
+        let req = Request::new(Method::GET, url);
 
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| GetWordError::ConnectionError(e.to_string()))?;
+            .map_err(GetWordError::ConnectionError)?;
         match response.status() {
             StatusCode::OK => {
-                let root: Word = response.json().await.unwrap();
-                Ok(root)
+                // the service returns only the translation, not the word itself
+                #[derive(Deserialize)]
+                struct Translation {
+                    translation: String,
+                    part_of_speech: Option<PartOfSpeech>,
+                }
+                let root: Translation = response
+                    .json()
+                    .await
+                    .map_err(|e| GetWordError::ConnectionError(e.to_string()))?;
+                Ok(Word {
+                    word: word.as_ref().to_owned(),
+                    translation: root.translation,
+                    part_of_speech: root.part_of_speech,
+                })
             }
-            StatusCode::BAD_REQUEST => Err(GetWordError::BadRequest400),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(GetWordError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(GetWordError::ServiceUnavailable503),
-            StatusCode::UNAUTHORIZED => Err(GetWordError::Unauthorised401(
-                customisation_id.as_ref().to_owned(),
-            )),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(GetWordError::BadRequest400(details)),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(GetWordError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(GetWordError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(GetWordError::Unauthorised401(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::FORBIDDEN => Err(GetWordError::Forbidden403(details)),
+                    status => Err(GetWordError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
@@ -322,7 +477,7 @@ impl TextToSpeech<'_> {
     /// # Parameters
     ///
     /// * `customisation_id` - The customisation ID (GUID) of the custom [`model`]. You must make the request with credentials for the instance of the service that owns the custom model
-    /// * `word` - The word that is to be queried from the custom [`model`]
+    /// * `word` - The word that is to be deleted from the custom [`model`]
     ///
     /// # Example
     /// ``` no_run
@@ -333,7 +488,7 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// if tts.delete_custom_word("customisation_id", "foo").await.is_ok() {
     ///     println!("word deleted");
     /// }
@@ -342,49 +497,55 @@ impl TextToSpeech<'_> {
     /// ```
     /// [`Words`]: self::Word
     /// [`word`]: self::Word
-    /// [`model`]: crate::tts::customisations::Model
+    /// [`model`]: crate::tts::customisations::CustomModel
     pub async fn delete_custom_word(
         &self,
         customisation_id: impl AsRef<str>,
         word: impl AsRef<str>,
     ) -> Result<(), DeleteWordError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path(&format!(
-            "v1/customizations/{}/words/{}",
-            customisation_id.as_ref(),
-            word.as_ref()
-        ));
-        let mut req = Request::new(Method::DELETE, url);
+        let url = self.words_endpoint(customisation_id.as_ref(), Some(word.as_ref()));
+        let req = Request::new(Method::DELETE, url);
 
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
-        }
-
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| DeleteWordError::ConnectionError(e.to_string()))?;
+            .map_err(DeleteWordError::ConnectionError)?;
         match response.status() {
             StatusCode::NO_CONTENT => Ok(()),
-            StatusCode::BAD_REQUEST => Err(DeleteWordError::BadRequest400(
-                customisation_id.as_ref().to_owned(),
-            )),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(DeleteWordError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(DeleteWordError::ServiceUnavailable503),
-            StatusCode::UNAUTHORIZED => Err(DeleteWordError::Unauthorised401(
-                customisation_id.as_ref().to_owned(),
-            )),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(DeleteWordError::BadRequest400(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(DeleteWordError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(DeleteWordError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(DeleteWordError::Unauthorised401(
+                        customisation_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::FORBIDDEN => Err(DeleteWordError::Forbidden403(details)),
+                    status => Err(DeleteWordError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
 
-    fn set_words_path(uri: &mut Url, customisation_id: impl AsRef<str>) {
-        uri.set_path(&format!(
-            "v1/customizations/{}/words",
-            customisation_id.as_ref()
-        ));
+    /// The URL of the words of a custom model, or of a single `word` in it. The word is
+    /// percent-encoded, so it may contain characters such as `/`
+    fn words_endpoint(&self, customisation_id: &str, word: Option<&str>) -> Url {
+        let mut url = self
+            .service
+            .endpoint(&format!("v1/customizations/{customisation_id}/words"));
+        if let Some(word) = word {
+            url.path_segments_mut().unwrap().push(word);
+        }
+        url
     }
 }
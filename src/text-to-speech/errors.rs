@@ -0,0 +1 @@
+pub use crate::client::BuildError;
@@ -1,19 +1,37 @@
-use reqwest::{
-    header::{HeaderMap, HeaderValue, AUTHORIZATION},
-    Client, ClientBuilder,
-};
+use std::sync::Arc;
 
-use crate::auth::IamAuthenticator;
+pub(crate) use crate::client::{metadata_header, LEARNING_OPT_OUT, WATSON_METADATA};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::transport::Transport;
+use crate::{
+    auth::{Authenticator, IamAuthenticator, RetryPolicy},
+    client::{AuthenticatorRef, ServiceBuilder, ServiceClient},
+    region::Region,
+};
 
-use self::voices::WatsonVoice;
+use self::{errors::BuildError, voices::VoiceId};
 
 /// Manage custom Prompts, Words, Models
 pub mod customisations;
+/// Errors that may be returned when building a client
+pub mod errors;
+/// Wait for resources that are processed in the background
+pub use crate::polling;
+/// Decode uncompressed synthesised audio to PCM samples and convert it to WAV
+#[cfg(feature = "pcm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pcm")))]
+pub mod pcm;
+/// Play synthesised audio
+#[cfg(all(feature = "playback", not(target_arch = "wasm32")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "playback")))]
+pub mod playback;
 /// Manage word pronunciation
 pub mod pronunciation;
 #[path = "speaker-models/mod.rs"]
 /// Manage speaker models
 pub mod speaker_models;
+/// Build SSML documents to synthesise
+pub mod ssml;
 /// Synthesise text to speech
 pub mod synthesis;
 #[path = "user-data/mod.rs"]
@@ -23,20 +41,96 @@ pub mod user_data;
 pub mod voices;
 
 /// Creates a client used to send requests to your Text To Speech endpoint
-pub struct TextToSpeech<'a> {
-    service_url: &'a str,
-    voice: WatsonVoice,
-    client: Client,
+///
+/// A client is cheap to clone: clones share its connection pool and authenticator. A client made
+/// with [`new_shared()`] owns its authenticator, so it is `'static` and, when the authenticator
+/// is, [`Send`] and [`Sync`]. It can be kept in the state of a web server and used from many
+/// handlers at once
+///
+/// [`new_shared()`]: Self::new_shared()
+/// [`Send`]: std::marker::Send
+/// [`Sync`]: std::marker::Sync
+pub struct TextToSpeech<'a, A = IamAuthenticator> {
+    voice: VoiceId,
+    service: ServiceClient<'a, A>,
+}
+
+impl<A> Clone for TextToSpeech<'_, A> {
+    fn clone(&self) -> Self {
+        Self {
+            voice: self.voice.clone(),
+            service: self.service.clone(),
+        }
+    }
 }
 
-impl<'a> TextToSpeech<'a> {
+impl<A: Authenticator> TextToSpeech<'static, A> {
+    /// Create a Text To Speech instance that shares ownership of `authenticator`, instead of
+    /// borrowing it like [`new()`]. The client does not borrow anything, so it can be moved into
+    /// spawned tasks or stored in application state
+    ///
+    /// # Parameters
+    /// * `authenticator` - The [`Authenticator`], such as an [`IamAuthenticator`], used to authorise requests
+    /// * `service_url` - The endpoint for your text to speech instance. All Text To Speech
+    ///   requests will be made to this endpoint. Fails with [`InvalidServiceUrl`] if it cannot
+    ///   be parsed
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, tts::TextToSpeech};
+    /// # use std::sync::Arc;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = Arc::new(IamAuthenticator::new("api_key").await?);
+    /// let tts = TextToSpeech::new_shared(auth, "service_url")?;
+    /// let handles: Vec<_> = ["Hello", "Goodbye"]
+    ///     .into_iter()
+    ///     .map(|text| {
+    ///         let tts = tts.clone();
+    ///         tokio::spawn(async move { tts.synthesise(text, None, None).await })
+    ///     })
+    ///     .collect();
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`new()`]: Self::new()
+    /// [`InvalidServiceUrl`]: self::errors::BuildError::InvalidServiceUrl
+    /// [`Authenticator`]: super::auth::Authenticator
+    /// [`IamAuthenticator`]: super::auth::IamAuthenticator
+    pub fn new_shared(
+        authenticator: Arc<A>,
+        service_url: impl AsRef<str>,
+    ) -> Result<Self, BuildError> {
+        Self::with_service_url(
+            AuthenticatorRef::Shared(authenticator),
+            service_url.as_ref(),
+        )
+    }
+
+    /// Configure a Text To Speech instance that shares ownership of `authenticator`, like
+    /// [`new_shared()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `authenticator` - The [`Authenticator`], such as an [`IamAuthenticator`], used to authorise requests
+    ///
+    /// [`new_shared()`]: Self::new_shared()
+    /// [`Authenticator`]: super::auth::Authenticator
+    /// [`IamAuthenticator`]: super::auth::IamAuthenticator
+    pub fn shared_builder(authenticator: Arc<A>) -> TextToSpeechBuilder<'static, A> {
+        TextToSpeechBuilder::new(AuthenticatorRef::Shared(authenticator))
+    }
+}
+
+impl<'a, A: Authenticator> TextToSpeech<'a, A> {
     /// Create a new Text To Speech instance. This instance will be used to make all the requests
     /// to the text to speech service.
     ///
     /// # Parameters
-    /// * `authenticator` - The [`IamAuthenticator`] containing your IAM Access Token
+    /// * `authenticator` - The [`Authenticator`], such as an [`IamAuthenticator`], used to authorise requests
     /// * `service_url` - The endpoint for your text to speech instance. All Text To Speech
-    /// requests will be made to this endpoint
+    ///   requests will be made to this endpoint. Fails with [`InvalidServiceUrl`] if it cannot
+    ///   be parsed
     ///
     /// # Examples
     /// ``` no_run
@@ -46,38 +140,63 @@ impl<'a> TextToSpeech<'a> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// let auth = IamAuthenticator::new("api_key").await?;
-    /// let tts = TextToSpeech::new(&auth, "service_url");
+    /// let tts = TextToSpeech::new(&auth, "service_url")?;
     /// let voice = tts.get_voice(WatsonVoice::EnGbCharlotteV3, None).await?;
     /// # Ok(())
     /// # }
     /// ```
     ///
+    /// [`InvalidServiceUrl`]: self::errors::BuildError::InvalidServiceUrl
+    /// [`Authenticator`]: super::auth::Authenticator
     /// [`IamAuthenticator`]: super::auth::IamAuthenticator
-    pub fn new(authenticator: &'a IamAuthenticator, service_url: &'a str) -> Self {
-        let client = ClientBuilder::new();
-        let default_headers = Self::default_headers(authenticator.token_response().access_token());
-        let client = client.default_headers(default_headers);
-
-        #[cfg(feature = "http2")]
-        let client = ClientBuilder::use_rustls_tls(client);
-
-        #[cfg(feature = "http2")]
-        let client = client.http2_prior_knowledge();
+    pub fn new(authenticator: &'a A, service_url: impl AsRef<str>) -> Result<Self, BuildError> {
+        Self::with_service_url(
+            AuthenticatorRef::Borrowed(authenticator),
+            service_url.as_ref(),
+        )
+    }
 
-        let client = client.build().unwrap();
+    /// Configure a Text To Speech instance, such as one in a particular [`Region`]
+    ///
+    /// # Parameters
+    ///
+    /// * `authenticator` - The [`Authenticator`], such as an [`IamAuthenticator`], used to authorise requests
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, region::Region, tts::TextToSpeech};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = IamAuthenticator::new("api_key").await?;
+    /// let tts = TextToSpeech::builder(&auth)
+    ///     .region(Region::Frankfurt, "instance_id")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Region`]: crate::region::Region
+    /// [`Authenticator`]: super::auth::Authenticator
+    /// [`IamAuthenticator`]: super::auth::IamAuthenticator
+    pub fn builder(authenticator: &'a A) -> TextToSpeechBuilder<'a, A> {
+        TextToSpeechBuilder::new(AuthenticatorRef::Borrowed(authenticator))
+    }
 
-        Self {
-            service_url,
-            voice: WatsonVoice::default(),
-            client,
-        }
+    fn with_service_url(
+        authenticator: AuthenticatorRef<'a, A>,
+        service_url: &str,
+    ) -> Result<Self, BuildError> {
+        Ok(Self {
+            voice: VoiceId::default(),
+            service: ServiceClient::new(authenticator, service_url)?,
+        })
     }
 
     /// Change the default voice to use for Text To Speech requests
     ///
     /// # Parameters
     ///
-    /// * `voice` - Use this [`voice`] in place of the [`default`] one
+    /// * `voice` - Use this [`voice`] in place of the [`default`] one. Any voice the service
+    ///   offers can be given by its [`VoiceId`]
     ///
     /// # Examples
     /// ``` no_run
@@ -87,27 +206,237 @@ impl<'a> TextToSpeech<'a> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// let mut tts = TextToSpeech::new(&auth, "service_url");
+    /// let mut tts = TextToSpeech::new(&auth, "service_url")?;
     /// tts.set_voice(WatsonVoice::EnGbCharlotteV3);
+    /// tts.set_voice("en-US_AllisonExpressive");
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// [`voice`]: self::voices::WatsonVoice
     /// [`default`]: self::voices::WatsonVoice::EnUsMichaelV3
-    pub fn set_voice(&mut self, voice: WatsonVoice) {
-        self.voice = voice;
+    /// [`VoiceId`]: self::voices::VoiceId
+    pub fn set_voice(&mut self, voice: impl Into<VoiceId>) {
+        self.voice = voice.into();
+    }
+
+    /// Opt out of IBM using the data sent with every request from this client, such as the text
+    /// to synthesise, to improve its services. Individual synthesis requests can override this
+    /// with [`SynthesisOptions::learning_opt_out()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `opt_out` - Whether to send `X-Watson-Learning-Opt-Out: true` with every request
+    ///
+    /// # Examples
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, tts::TextToSpeech};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// let mut tts = TextToSpeech::new(&auth, "service_url")?;
+    /// tts.set_learning_opt_out(true);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`SynthesisOptions::learning_opt_out()`]: self::synthesis::SynthesisOptions::learning_opt_out()
+    pub fn set_learning_opt_out(&mut self, opt_out: bool) {
+        self.service.learning_opt_out = opt_out;
+    }
+
+    /// Associate the data sent with every request from this client, such as the text to
+    /// synthesise, with a customer ID. All of a customer's data can then be deleted with
+    /// [`delete_labeled_data()`]. Individual synthesis requests can use another ID with
+    /// [`SynthesisOptions::customer_id()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `customer_id` - The customer ID to send in the `X-Watson-Metadata` header, or [`None`] to
+    ///   stop sending one
+    ///
+    /// # Examples
+    /// ``` no_run
+    /// # use ibm_watson::{auth::IamAuthenticator, tts::TextToSpeech};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// let mut tts = TextToSpeech::new(&auth, "service_url")?;
+    /// tts.set_customer_id(Some("customer-1"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`delete_labeled_data()`]: Self::delete_labeled_data()
+    /// [`SynthesisOptions::customer_id()`]: self::synthesis::SynthesisOptions::customer_id()
+    pub fn set_customer_id(&mut self, customer_id: Option<&str>) {
+        self.service.customer_id = customer_id.map(ToOwned::to_owned);
     }
 
-    pub(crate) fn get_client(&self) -> Client {
-        self.client.clone()
+    /// Retry synthesis and voice requests that the service throttled with a
+    /// `429 Too Many Requests` or `503 Service Unavailable` response, waiting as long as its
+    /// `Retry-After` header asks. By default, these requests are not retried
+    ///
+    /// # Parameters
+    ///
+    /// * `retry` - The [`RetryPolicy`] to follow
+    ///
+    /// # Examples
+    /// ``` no_run
+    /// # use ibm_watson::{auth::{IamAuthenticator, RetryPolicy}, tts::TextToSpeech};
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// let mut tts = TextToSpeech::new(&auth, "service_url")?;
+    /// tts.set_retry(RetryPolicy::new(5));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`RetryPolicy`]: crate::auth::RetryPolicy
+    pub fn set_retry(&mut self, retry: RetryPolicy) {
+        self.service.retry = retry;
+    }
+
+    /// Send requests through `transport` instead of over the network, such as a
+    /// [`MockTransport`] in tests. Requests still carry every header, including
+    /// `Authorization`, when they reach it
+    ///
+    /// # Parameters
+    ///
+    /// * `transport` - The [`Transport`] to send requests with
+    ///
+    /// # Examples
+    /// ```
+    /// # use ibm_watson::{auth::NoAuthAuthenticator, transport::MockTransport, tts::TextToSpeech};
+    /// # use reqwest::StatusCode;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let auth = NoAuthAuthenticator;
+    /// let mut tts = TextToSpeech::new(&auth, "https://example.com")?;
+    /// tts.set_transport(MockTransport::new(|_| {
+    ///     MockTransport::response(StatusCode::OK, "application/json", r#"{"voices":[]}"#)
+    /// }));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`MockTransport`]: crate::transport::MockTransport
+    /// [`Transport`]: crate::transport::Transport
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_transport(&mut self, transport: impl Transport + 'static) {
+        self.service.transport = Some(Arc::new(transport));
+    }
+}
+
+/// Configures a [`TextToSpeech`] client before it is used
+///
+/// [`TextToSpeech`]: self::TextToSpeech
+pub struct TextToSpeechBuilder<'a, A = IamAuthenticator> {
+    service: ServiceBuilder<'a, A>,
+    voice: Option<VoiceId>,
+}
+
+impl<'a, A: Authenticator> TextToSpeechBuilder<'a, A> {
+    /// A builder with nothing but its authenticator set
+    fn new(authenticator: AuthenticatorRef<'a, A>) -> Self {
+        Self {
+            service: ServiceBuilder::new(authenticator),
+            voice: None,
+        }
+    }
+
+    /// Send requests to the instance at `service_url`, such as a private endpoint or an IBM Cloud
+    /// Pak for Data installation
+    ///
+    /// # Parameters
+    ///
+    /// * `service_url` - The endpoint for your text to speech instance, including its
+    ///   `/instances/{instance_id}` path
+    pub fn service_url(mut self, service_url: impl Into<String>) -> Self {
+        self.service.service_url = Some(service_url.into());
+        self
+    }
+
+    /// Send requests to an instance on IBM Cloud's public endpoint for `region`
+    ///
+    /// # Parameters
+    ///
+    /// * `region` - The [`Region`] the instance was created in
+    /// * `instance_id` - The ID (GUID) of the instance
+    ///
+    /// [`Region`]: crate::region::Region
+    pub fn region(mut self, region: Region, instance_id: impl AsRef<str>) -> Self {
+        self.service.service_url = Some(region.service_url("text-to-speech", instance_id.as_ref()));
+        self
+    }
+
+    /// Use `voice` instead of the [`default`] one, like [`TextToSpeech::set_voice()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `voice` - The voice to synthesise with
+    ///
+    /// [`default`]: self::voices::WatsonVoice::EnUsMichaelV3
+    /// [`TextToSpeech::set_voice()`]: self::TextToSpeech::set_voice()
+    pub fn voice(mut self, voice: impl Into<VoiceId>) -> Self {
+        self.voice = Some(voice.into());
+        self
+    }
+
+    /// Opt out of IBM using the data sent to improve its services, like
+    /// [`TextToSpeech::set_learning_opt_out()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `opt_out` - Whether to send `X-Watson-Learning-Opt-Out: true` with every request
+    ///
+    /// [`TextToSpeech::set_learning_opt_out()`]: self::TextToSpeech::set_learning_opt_out()
+    pub fn learning_opt_out(mut self, opt_out: bool) -> Self {
+        self.service.learning_opt_out = opt_out;
+        self
+    }
+
+    /// Associate the data sent with a customer ID, like [`TextToSpeech::set_customer_id()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `customer_id` - The customer ID to send in the `X-Watson-Metadata` header
+    ///
+    /// [`TextToSpeech::set_customer_id()`]: self::TextToSpeech::set_customer_id()
+    pub fn customer_id(mut self, customer_id: impl Into<String>) -> Self {
+        self.service.customer_id = Some(customer_id.into());
+        self
+    }
+
+    /// Retry throttled synthesis and voice requests, like [`TextToSpeech::set_retry()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `retry` - The [`RetryPolicy`] to follow
+    ///
+    /// [`TextToSpeech::set_retry()`]: self::TextToSpeech::set_retry()
+    /// [`RetryPolicy`]: crate::auth::RetryPolicy
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.service.retry = retry;
+        self
+    }
+
+    /// Send requests through `transport`, like [`TextToSpeech::set_transport()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `transport` - The [`Transport`] to send requests with
+    ///
+    /// [`TextToSpeech::set_transport()`]: self::TextToSpeech::set_transport()
+    /// [`Transport`]: crate::transport::Transport
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.service.transport = Some(Arc::new(transport));
+        self
     }
 
-    fn default_headers(token: &str) -> HeaderMap<HeaderValue> {
-        let mut headers = HeaderMap::new();
-        let mut auth_value = HeaderValue::from_str(&format!("Bearer {}", token)).unwrap();
-        auth_value.set_sensitive(true);
-        headers.insert(AUTHORIZATION, auth_value);
-        headers
+    /// Creates the client, checking that its service URL is valid
+    pub fn build(self) -> Result<TextToSpeech<'a, A>, BuildError> {
+        Ok(TextToSpeech {
+            voice: self.voice.unwrap_or_default(),
+            service: self.service.build()?,
+        })
     }
 }
@@ -0,0 +1,22 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+/// Errors that may be returned when decoding synthesised audio
+pub enum PcmError {
+    #[error("Audio as {0} is compressed, so it cannot be decoded to PCM")]
+    /// The audio is compressed, such as the default Ogg Opus or MP3, which needs a codec this
+    /// crate does not include. Request `audio/l16`, `audio/wav`, `audio/mulaw`, `audio/alaw` or
+    /// `audio/basic` instead
+    UnsupportedFormat(String),
+    #[error("The MIME type {0} does not give the sample rate of the audio")]
+    /// Raw audio has no header, so its sample rate must come from its MIME type
+    MissingSampleRate(String),
+    #[error("The audio is not valid {format}: {reason}")]
+    /// The audio does not hold what its MIME type says it does
+    InvalidAudio {
+        /// The format the audio was expected to be in
+        format: String,
+        /// What is wrong with it
+        reason: String,
+    },
+}
@@ -0,0 +1,304 @@
+/// Errors that may be returned when decoding audio
+pub mod errors;
+
+use self::errors::PcmError;
+
+use super::synthesis::{linear16, Audio};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// Mono 16-bit PCM audio, decoded from synthesised [`Audio`]. Convert audio to PCM to hand it to
+/// a device or library that only accepts samples, or to WAV at another sample rate with
+/// [`to_wav()`]
+///
+/// Only audio that is not compressed can be decoded: `audio/l16`, `audio/wav`, `audio/mulaw`,
+/// `audio/alaw` and `audio/basic`. Compressed formats fail with [`UnsupportedFormat`], including
+/// `audio/ogg;codecs=opus`, which the service returns unless another [`AudioFormat`] is asked
+/// for, as well as MP3, FLAC and WebM. Synthesise such audio as [`AudioL16`] or [`AudioWav`] to
+/// decode it
+///
+/// # Example
+/// ``` no_run
+/// # use ibm_watson::{
+/// #     auth::IamAuthenticator,
+/// #     tts::{pcm::Pcm, synthesis::AudioFormat, TextToSpeech},
+/// # };
+/// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+/// # let auth = IamAuthenticator::new("api_key").await?;
+/// # let tts = TextToSpeech::new(&auth, "service_url")?;
+/// let format = AudioFormat::AudioMulaw { sample_rate: 8000 };
+/// let audio = tts.synthesise("Hey there", Some(format), None).await?;
+/// let wav = Pcm::decode(&audio)?.resample(16000).to_wav();
+/// # Ok(())
+/// # }
+/// ```
+/// [`Audio`]: crate::tts::synthesis::Audio
+/// [`to_wav()`]: Self::to_wav()
+/// [`UnsupportedFormat`]: self::errors::PcmError::UnsupportedFormat
+/// [`AudioFormat`]: crate::tts::synthesis::AudioFormat
+/// [`AudioL16`]: crate::tts::synthesis::AudioFormat::AudioL16
+/// [`AudioWav`]: crate::tts::synthesis::AudioFormat::AudioWav
+pub struct Pcm {
+    /// Samples per second
+    pub sample_rate: u32,
+    /// The samples, in the order they are played
+    pub samples: Vec<i16>,
+}
+
+impl Pcm {
+    /// Decodes `audio` into samples, reading its format from its MIME type
+    ///
+    /// # Parameters
+    ///
+    /// * `audio` - The synthesised [`Audio`] to decode
+    ///
+    /// [`Audio`]: crate::tts::synthesis::Audio
+    pub fn decode(audio: &Audio) -> Result<Self, PcmError> {
+        let content_type = audio.content_type();
+        let media_type = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        let rate = || {
+            parameter(content_type, "rate")
+                .and_then(|rate| rate.parse().ok())
+                .ok_or_else(|| PcmError::MissingSampleRate(content_type.to_owned()))
+        };
+        match media_type.as_str() {
+            "audio/l16" => {
+                let big_endian = parameter(content_type, "endianness") == Some("big-endian");
+                Ok(Self {
+                    sample_rate: rate()?,
+                    samples: linear16(audio, big_endian),
+                })
+            }
+            "audio/mulaw" => Ok(Self {
+                sample_rate: rate()?,
+                samples: audio.iter().map(|&byte| mulaw(byte)).collect(),
+            }),
+            // the service sends a-law audio at 8kHz unless asked otherwise
+            "audio/alaw" => Ok(Self {
+                sample_rate: rate().unwrap_or(8000),
+                samples: audio.iter().map(|&byte| alaw(byte)).collect(),
+            }),
+            "audio/wav" | "audio/wave" | "audio/x-wav" => decode_wav(audio),
+            "audio/basic" => decode_au(audio),
+            _ => Err(PcmError::UnsupportedFormat(content_type.to_owned())),
+        }
+    }
+
+    /// The audio at another sample rate, interpolating between samples
+    ///
+    /// # Parameters
+    ///
+    /// * `sample_rate` - The samples per second wanted, such as `16000`
+    pub fn resample(&self, sample_rate: u32) -> Self {
+        if sample_rate == self.sample_rate || self.sample_rate == 0 || self.samples.is_empty() {
+            return Self {
+                sample_rate,
+                samples: self.samples.clone(),
+            };
+        }
+        let step = f64::from(self.sample_rate) / f64::from(sample_rate);
+        let len = (self.samples.len() as f64 / step).round() as usize;
+        let last = self.samples.len() - 1;
+        let samples = (0..len)
+            .map(|i| {
+                let position = i as f64 * step;
+                let index = (position as usize).min(last);
+                let from = f64::from(self.samples[index]);
+                let to = f64::from(self.samples[(index + 1).min(last)]);
+                (from + (to - from) * position.fract()).round() as i16
+            })
+            .collect();
+        Self {
+            sample_rate,
+            samples,
+        }
+    }
+
+    /// The audio as a mono 16-bit WAV file
+    pub fn to_wav(&self) -> Vec<u8> {
+        let data_len = saturate(self.samples.len() * 2);
+        let mut wav = Vec::with_capacity(44 + self.samples.len() * 2);
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&data_len.saturating_add(36).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        // uncompressed PCM, one channel
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&self.sample_rate.to_le_bytes());
+        wav.extend_from_slice(&self.sample_rate.saturating_mul(2).to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        for sample in &self.samples {
+            wav.extend_from_slice(&sample.to_le_bytes());
+        }
+        wav
+    }
+}
+
+/// Decodes uncompressed `audio` and converts it to a mono 16-bit WAV file
+///
+/// # Parameters
+///
+/// * `audio` - The synthesised [`Audio`] to convert
+/// * `sample_rate` - The sample rate of the WAV file, or [`None`] to keep that of `audio`
+///
+/// [`Audio`]: crate::tts::synthesis::Audio
+/// [`None`]: std::option::Option::None
+pub fn to_wav(audio: &Audio, sample_rate: Option<u32>) -> Result<Vec<u8>, PcmError> {
+    let pcm = Pcm::decode(audio)?;
+    Ok(match sample_rate {
+        Some(sample_rate) => pcm.resample(sample_rate).to_wav(),
+        None => pcm.to_wav(),
+    })
+}
+
+/// The value of the parameter `name` of a MIME type
+fn parameter<'a>(content_type: &'a str, name: &str) -> Option<&'a str> {
+    content_type.split(';').skip(1).find_map(|part| {
+        let (key, value) = part.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then(|| value.trim().trim_matches('"'))
+    })
+}
+
+/// Expands a G.711 mu-law byte to a 16-bit sample
+fn mulaw(byte: u8) -> i16 {
+    let byte = !byte;
+    let exponent = (byte >> 4) & 0x07;
+    let mantissa = i32::from(byte & 0x0f);
+    let magnitude = (((mantissa << 3) + 0x84) << exponent) - 0x84;
+    let sample = if byte & 0x80 != 0 {
+        -magnitude
+    } else {
+        magnitude
+    };
+    sample as i16
+}
+
+/// Expands a G.711 a-law byte to a 16-bit sample
+fn alaw(byte: u8) -> i16 {
+    let byte = byte ^ 0x55;
+    let exponent = (byte >> 4) & 0x07;
+    let mantissa = i32::from(byte & 0x0f) << 4;
+    let magnitude = match exponent {
+        0 => mantissa + 8,
+        _ => (mantissa + 0x108) << (exponent - 1),
+    };
+    let sample = if byte & 0x80 != 0 {
+        magnitude
+    } else {
+        -magnitude
+    };
+    sample as i16
+}
+
+fn invalid(format: &str, reason: &str) -> PcmError {
+    PcmError::InvalidAudio {
+        format: format.to_owned(),
+        reason: reason.to_owned(),
+    }
+}
+
+/// Decodes a WAV file of 16-bit PCM, mixing any channels down to one
+fn decode_wav(wav: &[u8]) -> Result<Pcm, PcmError> {
+    if wav.len() < 12 || &wav[..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+        return Err(invalid("WAV", "it has no RIFF header"));
+    }
+    let mut format = None;
+    let mut offset = 12;
+    while offset + 8 <= wav.len() {
+        let id = &wav[offset..offset + 4];
+        let size = u32::from_le_bytes([
+            wav[offset + 4],
+            wav[offset + 5],
+            wav[offset + 6],
+            wav[offset + 7],
+        ]) as usize;
+        let body = &wav[offset + 8..];
+        match id {
+            b"fmt " if body.len() >= 16 => {
+                let tag = u16::from_le_bytes([body[0], body[1]]);
+                let channels = u16::from_le_bytes([body[2], body[3]]);
+                let rate = u32::from_le_bytes([body[4], body[5], body[6], body[7]]);
+                let bits = u16::from_le_bytes([body[14], body[15]]);
+                if tag != 1 || bits != 16 || channels == 0 {
+                    return Err(invalid("WAV", "only 16-bit PCM can be decoded"));
+                }
+                format = Some((channels, rate));
+            }
+            b"data" => {
+                let (channels, sample_rate) =
+                    format.ok_or_else(|| invalid("WAV", "the data comes before its format"))?;
+                // streamed audio may not give the real length of its data
+                let data = &body[..size.min(body.len())];
+                return Ok(Pcm {
+                    sample_rate,
+                    samples: mix_down(linear16(data, false), channels),
+                });
+            }
+            _ => {}
+        }
+        // a chunk size near `u32::MAX` would overflow on 32-bit targets
+        match 8usize
+            .checked_add(size)
+            .and_then(|len| len.checked_add(size % 2))
+            .and_then(|len| offset.checked_add(len))
+        {
+            Some(next) => offset = next,
+            None => break,
+        }
+    }
+    Err(invalid("WAV", "it has no data"))
+}
+
+/// Decodes a Sun/NeXT `.au` file of mu-law or 16-bit PCM
+fn decode_au(au: &[u8]) -> Result<Pcm, PcmError> {
+    let word = |at: usize| {
+        au.get(at..at + 4)
+            .map(|word| u32::from_be_bytes([word[0], word[1], word[2], word[3]]))
+    };
+    let (Some(offset), Some(encoding), Some(sample_rate), Some(channels)) =
+        (word(4), word(12), word(16), word(20))
+    else {
+        return Err(invalid("AU", "its header is too short"));
+    };
+    if &au[..4] != b".snd" || channels == 0 {
+        return Err(invalid("AU", "it has no .snd header"));
+    }
+    let data = au.get(offset as usize..).unwrap_or_default();
+    let samples = match encoding {
+        1 => data.iter().map(|&byte| mulaw(byte)).collect(),
+        3 => linear16(data, true),
+        _ => return Err(invalid("AU", "only mu-law and 16-bit PCM can be decoded")),
+    };
+    Ok(Pcm {
+        sample_rate,
+        samples: mix_down(samples, channels as u16),
+    })
+}
+
+/// Averages interleaved samples of `channels` channels into one
+fn mix_down(samples: Vec<i16>, channels: u16) -> Vec<i16> {
+    if channels <= 1 {
+        return samples;
+    }
+    samples
+        .chunks(channels as usize)
+        .map(|frame| {
+            let sum: i32 = frame.iter().map(|&sample| i32::from(sample)).sum();
+            (sum / frame.len() as i32) as i16
+        })
+        .collect()
+}
+
+fn saturate(len: usize) -> u32 {
+    u32::try_from(len).unwrap_or(u32::MAX)
+}
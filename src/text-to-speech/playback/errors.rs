@@ -0,0 +1,24 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+/// Errors that may be returned when playing synthesised audio
+pub enum PlaybackError {
+    #[error("The audio player {program} could not be started: {reason}")]
+    /// The player program could not be run, usually because it is not installed or not on the
+    /// `PATH`
+    PlayerNotStarted {
+        /// The program that was run
+        program: String,
+        /// Why it could not be run
+        reason: String,
+    },
+    #[error("The audio player {program} failed: {status}")]
+    /// The player exited unsuccessfully, such as when it could not decode the audio or open an
+    /// output device
+    PlayerFailed {
+        /// The program that was run
+        program: String,
+        /// How it exited
+        status: String,
+    },
+}
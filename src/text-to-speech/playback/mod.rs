@@ -0,0 +1,156 @@
+/// Errors that may be returned when playing audio
+pub mod errors;
+
+use std::{
+    ffi::{OsStr, OsString},
+    io::{ErrorKind, Write},
+    process::{Command, Stdio},
+};
+
+use self::errors::PlaybackError;
+
+use super::synthesis::Audio;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Plays synthesised [`Audio`] by piping it into an audio player program, which reads it from
+/// its standard input. By default that is [FFmpeg]'s `ffplay`, which plays every format the
+/// service synthesises, including the default `audio/ogg;codecs=opus`, on Linux, macOS and
+/// Windows
+///
+/// # Example
+/// ``` no_run
+/// # use ibm_watson::{
+/// #     auth::IamAuthenticator,
+/// #     tts::{playback::Player, TextToSpeech},
+/// # };
+/// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+/// # let auth = IamAuthenticator::new("api_key").await?;
+/// # let tts = TextToSpeech::new(&auth, "service_url")?;
+/// let audio = tts.synthesise("Hey there", None, None).await?;
+/// // play the audio with mpv instead of ffplay
+/// Player::new("mpv").args(["--no-video", "-"]).play(&audio).await?;
+/// # Ok(())
+/// # }
+/// ```
+/// [`Audio`]: crate::tts::synthesis::Audio
+/// [FFmpeg]: https://ffmpeg.org
+pub struct Player {
+    program: OsString,
+    args: Vec<OsString>,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self::new("ffplay").args(["-nodisp", "-autoexit", "-loglevel", "error", "-i", "pipe:0"])
+    }
+}
+
+impl Player {
+    /// A player that runs `program` with no arguments
+    ///
+    /// # Parameters
+    ///
+    /// * `program` - The name of, or path to, a program that plays audio from its standard input
+    pub fn new(program: impl AsRef<OsStr>) -> Self {
+        Self {
+            program: program.as_ref().to_owned(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Pass `args` to the program, after any added before
+    ///
+    /// # Parameters
+    ///
+    /// * `args` - The arguments to pass, such as those telling it to read from standard input
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.args
+            .extend(args.into_iter().map(|arg| arg.as_ref().to_owned()));
+        self
+    }
+
+    /// Plays `audio`, resolving once the player has finished. The player runs on a blocking
+    /// thread of the current Tokio runtime
+    ///
+    /// # Parameters
+    ///
+    /// * `audio` - The synthesised [`Audio`] to play
+    ///
+    /// [`Audio`]: crate::tts::synthesis::Audio
+    pub async fn play(&self, audio: &Audio) -> Result<(), PlaybackError> {
+        let player = self.clone();
+        let audio = audio.clone().into_bytes();
+        tokio::task::spawn_blocking(move || player.play_blocking(&audio))
+            .await
+            .map_err(|e| PlaybackError::PlayerFailed {
+                program: self.program.to_string_lossy().into_owned(),
+                status: e.to_string(),
+            })?
+    }
+
+    fn play_blocking(&self, audio: &[u8]) -> Result<(), PlaybackError> {
+        let program = self.program.to_string_lossy().into_owned();
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .map_err(|e| PlaybackError::PlayerNotStarted {
+                program: program.clone(),
+                reason: e.to_string(),
+            })?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // a player that stops reading early reports why through its exit status
+            match stdin.write_all(audio) {
+                Err(e) if e.kind() != ErrorKind::BrokenPipe => {
+                    let _ = child.kill();
+                    return Err(PlaybackError::PlayerFailed {
+                        program,
+                        status: e.to_string(),
+                    });
+                }
+                _ => {}
+            }
+        }
+        let status = child.wait().map_err(|e| PlaybackError::PlayerFailed {
+            program: program.clone(),
+            status: e.to_string(),
+        })?;
+        match status.success() {
+            true => Ok(()),
+            false => Err(PlaybackError::PlayerFailed {
+                program,
+                status: status.to_string(),
+            }),
+        }
+    }
+}
+
+/// Plays `audio` with the default [`Player`], resolving once it has finished
+///
+/// # Parameters
+///
+/// * `audio` - The synthesised [`Audio`] to play
+///
+/// # Example
+/// ``` no_run
+/// # use ibm_watson::{
+/// #     auth::IamAuthenticator,
+/// #     tts::{playback::play, TextToSpeech},
+/// # };
+/// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+/// # let auth = IamAuthenticator::new("api_key").await?;
+/// # let tts = TextToSpeech::new(&auth, "service_url")?;
+/// play(&tts.synthesise("Hey there", None, None).await?).await?;
+/// # Ok(())
+/// # }
+/// ```
+/// [`Player`]: self::Player
+/// [`Audio`]: crate::tts::synthesis::Audio
+pub async fn play(audio: &Audio) -> Result<(), PlaybackError> {
+    Player::default().play(audio).await
+}
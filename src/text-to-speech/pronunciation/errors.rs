@@ -1,31 +1,43 @@
+use reqwest::StatusCode;
 use thiserror::Error;
 
+use crate::errors::{describe, ServiceErrorDetails};
+
 #[derive(Error, Debug)]
 /// Errors that may be returned in pronunciation requests
 pub enum PronunciationError {
     /// The requested resource has not been modified since the time specified by the If-Modified-Since header, as documented in the HTTP specification
     #[error("The requested resource has not been modified since the time specified by the If-Modified-Since header, as documented in the HTTP specification")]
     NotModified304,
-    #[error("The request specified an Accept header with an incompatible content type.")]
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The request specified an Accept header with an incompatible content type."))]
     /// The request specified an Accept header with an incompatible content type
-    NotAcceptable406,
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
+    NotAcceptable406(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
     /// A required input parameter is null or a specified input parameter or header value is invalid or not supported
-    BadRequest400,
-    #[error("The specified customisation_id: {0} is invalid for the requesting credentials")]
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id: {} is invalid for the requesting credentials", .0)))]
     /// The specified customisation_id is invalid for the requesting credentials
-    Unuathorised401(String),
-    #[error("The specified voice does not exist")]
+    Unuathorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The specified voice does not exist"))]
     /// The specified voice does not exist or, for IBM Cloud Pak for Data, the voice parameter was not specified but the default voice is not installed. The message is Model '{voice}' not found
-    NotFound404,
+    NotFound404(Option<ServiceErrorDetails>),
     //    #[error("The request specified an unacceptable media type.")]
     //    UnsupportedMediaType415,
-    #[error("The service experienced an internal error.")]
+    #[error("{}", describe(.0, "The service experienced an internal error."))]
     /// The service experienced an internal error.
-    InternalServerError500,
+    InternalServerError500(Option<ServiceErrorDetails>),
     /// The service is currently unavailable
-    #[error("The service is currently unavailable.")]
-    ServiceUnavailable503,
+    #[error("{}", describe(.0, "The service is currently unavailable."))]
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
     #[error("{0}")]
     /// There was an error making the request
     ConnectionError(String),
@@ -1,10 +1,11 @@
-use reqwest::{Method, Request, StatusCode, Url, Version};
+use reqwest::{Method, Request, StatusCode};
 use serde::{Deserialize, Serialize};
 pub mod errors;
 
 use self::errors::PronunciationError;
 
 use super::{voices::WatsonVoice, TextToSpeech};
+use crate::{auth::Authenticator, errors::ServiceErrorDetails};
 #[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
 /// Holds the pronunciation of some text
 pub struct Pronunciation {
@@ -13,7 +14,7 @@ pub struct Pronunciation {
     ///
     /// [`voice`]: super::voices::WatsonVoice
     /// [`format`]: self::PhonemeFormat
-    /// [`model`]: crate::tts::customisations::Model
+    /// [`model`]: crate::tts::customisations::CustomModel
     pub pronunciation: String,
 }
 
@@ -39,7 +40,7 @@ impl PhonemeFormat {
     }
 }
 
-impl TextToSpeech<'_> {
+impl<A: Authenticator> TextToSpeech<'_, A> {
     /// Gets the phonetic [`Pronunciation`] for the specified word. You can request the pronunciation for a specific [`format`]. You can also request the pronunciation for a specific [`voice`] to see the default translation for the language of that voice or for a specific custom [`model`] to see the translation for that model.
     ///
     /// # Parameters
@@ -57,7 +58,7 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// let customisation_id = Some("cust-id");
     /// let pronunciation = tts.get_pronunciation("word", None, None, customisation_id).await?;
     /// println!("{:#?}", pronunciation);
@@ -70,7 +71,7 @@ impl TextToSpeech<'_> {
     /// [`default`]: super::voices::WatsonVoice::EnUsMichaelV3
     /// [`PhonemeFormat`]: self::PhonemeFormat
     /// [`format`]: self::PhonemeFormat
-    /// [`model`]: crate::tts::customisations::Model
+    /// [`model`]: crate::tts::customisations::CustomModel
     /// [`Pronunciation`]: self::Pronunciation
     pub async fn get_pronunciation(
         &self,
@@ -79,9 +80,7 @@ impl TextToSpeech<'_> {
         format: Option<PhonemeFormat>,
         customisation_id: Option<impl AsRef<str>>,
     ) -> Result<Pronunciation, PronunciationError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path("v1/pronunciation");
-
+        let mut url = self.service.endpoint("v1/pronunciation");
         url.query_pairs_mut()
             .append_pair("text", text.as_ref())
             .append_pair("format", format.unwrap_or_default().id())
@@ -89,39 +88,51 @@ impl TextToSpeech<'_> {
                 "voice",
                 match &voice {
                     Some(voice) => voice.id(),
-                    None => self.voice.id(),
+                    None => self.voice.as_str(),
                 },
             );
         if let Some(c_id) = &customisation_id {
             url.query_pairs_mut()
                 .append_pair("customization_id", c_id.as_ref());
         }
-        let mut req = Request::new(Method::GET, url);
-
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
-        }
+        let req = Request::new(Method::GET, url);
 
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| PronunciationError::ConnectionError(e.to_string()))?;
+            .map_err(PronunciationError::ConnectionError)?;
         match response.status() {
-            StatusCode::OK => {
-                let root: Pronunciation = response.json().await.unwrap();
-                Ok(root)
-            }
-            StatusCode::NOT_ACCEPTABLE => Err(PronunciationError::NotAcceptable406),
-            StatusCode::UNAUTHORIZED => Err(PronunciationError::Unuathorised401(
-                customisation_id.unwrap().as_ref().to_string(),
-            )),
-            StatusCode::NOT_FOUND => Err(PronunciationError::NotFound404),
-            StatusCode::SERVICE_UNAVAILABLE => Err(PronunciationError::ServiceUnavailable503),
-            StatusCode::BAD_REQUEST => Err(PronunciationError::BadRequest400),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(PronunciationError::InternalServerError500),
+            StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| PronunciationError::ConnectionError(e.to_string())),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::NOT_MODIFIED => Err(PronunciationError::NotModified304),
+                    StatusCode::NOT_ACCEPTABLE => {
+                        Err(PronunciationError::NotAcceptable406(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(PronunciationError::Unuathorised401(
+                        customisation_id
+                            .as_ref()
+                            .map(|id| id.as_ref().to_owned())
+                            .unwrap_or_default(),
+                        details,
+                    )),
+                    StatusCode::NOT_FOUND => Err(PronunciationError::NotFound404(details)),
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(PronunciationError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::BAD_REQUEST => Err(PronunciationError::BadRequest400(details)),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(PronunciationError::InternalServerError500(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(PronunciationError::Unauthorised401(details)),
+                    StatusCode::FORBIDDEN => Err(PronunciationError::Forbidden403(details)),
+                    status => Err(PronunciationError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
@@ -1,39 +1,66 @@
+use reqwest::StatusCode;
 use thiserror::Error;
+
+use crate::errors::{describe, ServiceErrorDetails};
 #[derive(Debug, Error)]
 pub enum ListSpeakersError {
-    #[error("The service experienced an internal error.")]
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error."))]
     /// The service experienced an internal error
-    InternalServerError500, // 500
-    #[error("The service is currently unavailable.")]
+    InternalServerError500(Option<ServiceErrorDetails>), // 500
+    #[error("{}", describe(.0, "The service is currently unavailable."))]
     /// The service is currently unavailable
-    ServiceUnavailable503, // 503
+    ServiceUnavailable503(Option<ServiceErrorDetails>), // 503
     #[error("{0}")]
     /// There was an error making the request
     ConnectionError(String),
     /// The request failed. Possible failure causes include. Invalid service credentials were passed with the request
-    #[error("The request failed. Possible failure causes include. Invalid service credentials were passed with the request")]
-    BadRequest400,
+    #[error("{}", describe(.0, "The request failed. Possible failure causes include. Invalid service credentials were passed with the request"))]
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
 
 #[derive(Debug, Error)]
 pub enum CreateSpeakerError {
-    #[error("")]
-    BadRequest400,
-    #[error("The service is currently unavailable")]
+    /// There was an error establishing the connection
+    #[error("{0}")]
+    ConnectionError(String),
+    #[error("{}", describe(.0, "The speaker name is invalid or already in use, or the enrollment audio could not be processed"))]
+    /// The request failed. Possible failure causes include:
+    ///
+    /// * The speaker name is longer than 49 characters, includes characters that are not alphanumeric or underscores, or is already defined for the service instance
+    /// * The audio has a media type other than audio/wav or a sampling rate of less than 16 kHz
+    /// * The audio is longer than 1 minute
+    /// * The service cannot process the audio for any reason (for example, the audio is corrupt)
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
     /// The service is currently unavailable
-    ServiceUnavailable503,
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
     /// The service experienced an internal error
-    #[error("The service experienced an internal error")]
-    InternalServerError500,
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    InternalServerError500(Option<ServiceErrorDetails>),
     /// There was an error reading the file
     #[error("There was an error reading the file: {0}")]
     FileReadError(String),
-    /// The request passed an unacceptable media type with the Content-Type header. The header must pass a value of multipart/form-data
-    #[error("The request passed an unacceptable media type with the Content-Type header. The header must pass a value of multipart/form-data")]
-    UnsupportedMediaType415,
-    /// The specified customisation_id is invalid for the requesting credentials
-    #[error("The specified customisation_id  {0} is invalid for the requesting credentials")]
-    Unauthorised401(String),
+    /// The request passed an unacceptable media type with the Content-Type header. The audio must be in WAV format
+    #[error("{}", describe(.0, "The request passed an unacceptable media type with the Content-Type header. The audio must be in WAV format"))]
+    UnsupportedMediaType415(Option<ServiceErrorDetails>),
+    /// Invalid service credentials were passed with the request to create the named speaker
+    #[error("{}", describe(.1, &format!("Invalid service credentials were passed with the request to create speaker {}", .0)))]
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
 
 #[derive(Debug, Error)]
@@ -41,20 +68,26 @@ pub enum GetSpeakerError {
     /// There was an error establishing the connection
     #[error("{0}")]
     ConnectionError(String),
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
-    BadRequest400,
-    #[error("The service is currently unavailable")]
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
     /// The service is currently unavailable
-    ServiceUnavailable503,
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
     /// The service experienced an internal error
-    #[error("The service experienced an internal error")]
-    InternalServerError500,
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
+    InternalServerError500(Option<ServiceErrorDetails>),
     /// The specified speaker_id is invalid for the requesting credentials")]
-    #[error("The specified speaker_id: {0} is invalid for the requesting credentials")]
-    Unauthorised401(String),
+    #[error("{}", describe(.1, &format!("The specified speaker_id: {} is invalid for the requesting credentials", .0)))]
+    Unauthorised401(String, Option<ServiceErrorDetails>),
     /// The requested resource has not been modified since the time specified by the If-Modified-Since header, as documented in the HTTP specification
     #[error("The requested resource has not been modified since the time specified by the If-Modified-Since header, as documented in the HTTP specification")]
     NotModified304,
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
 
 #[derive(Error, Debug)]
@@ -63,15 +96,21 @@ pub enum DeleteSpeakerError {
     #[error("{0}")]
     ConnectionError(String),
     /// A required input parameter is null or a specified input parameter or header value is invalid or not supported
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported")]
-    BadRequest400(String),
-    #[error("The service is currently unavailable")]
+    #[error("{}", describe(.1, "A required input parameter is null or a specified input parameter or header value is invalid or not supported"))]
+    BadRequest400(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
     /// The service is currently unavailable
-    ServiceUnavailable503,
-    #[error("The service experienced an internal error")]
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
     /// The service experienced an internal error
-    InternalServerError500,
-    #[error("The specified speaker_id {0} is invalid for the requesting credentials")]
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified speaker_id {} is invalid for the requesting credentials", .0)))]
     /// The specified speaker_id is invalid for the requesting credentials
-    Unauthorised401(String),
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
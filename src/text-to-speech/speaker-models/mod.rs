@@ -1,14 +1,19 @@
+#[cfg(not(target_arch = "wasm32"))]
 use std::path::Path;
 
 use reqwest::{
     header::{HeaderValue, CONTENT_TYPE},
-    Body, Method, Request, StatusCode, Url, Version,
+    Body, Method, Request, StatusCode, Url,
 };
 use serde::{Deserialize, Serialize};
-use tokio::io::{AsyncReadExt, BufReader};
 
 pub mod errors;
-use super::{customisations::Prompt, TextToSpeech};
+use super::{
+    customisations::{Prompt, PromptStatus},
+    polling::{errors::WaitError, Polling, Status},
+    TextToSpeech,
+};
+use crate::{auth::Authenticator, errors::ServiceErrorDetails};
 use errors::*;
 #[derive(Deserialize, Serialize, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
 /// Information about all speaker models for the service instance
@@ -20,14 +25,16 @@ pub struct Speaker {
 }
 
 #[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
-/// Provides information about the prompts that are defined for a specified speaker in the custom models that are owned by a specified service instance
+/// Provides information about the prompts that are defined for a specified speaker in one of the custom models that are owned by a specified service instance
 pub struct SpeakerCustomModel {
     #[serde(rename = "customization_id")]
+    /// The customisation ID (GUID) of a custom model for which the speaker has defined one or more prompts
     pub customisation_id: String,
+    /// The prompts that the speaker has defined for the custom model
     pub prompts: Vec<Prompt>,
 }
 
-impl TextToSpeech<'_> {
+impl<A: Authenticator> TextToSpeech<'_, A> {
     /// Lists information about all speaker models that are defined for a service instance. The information includes the speaker ID and speaker name of each defined speaker. You must use credentials for the instance of a service to list its speakers. Speaker models and the custom prompts with which they are used are supported only for use with US English custom models and voices.
     ///
     /// # Example
@@ -39,49 +46,52 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// let speakers = tts.list_speaker_models().await?;
     /// println!("Speakers count: {}", speakers.len());
     /// # Ok(())
     /// # }
     /// ```
     pub async fn list_speaker_models(&self) -> Result<Vec<Speaker>, ListSpeakersError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        Self::set_speakers_path(&mut url);
-        let mut req = Request::new(Method::GET, url);
-
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
-        }
+        let url = self.service.endpoint("v1/speakers");
+        let req = Request::new(Method::GET, url);
 
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| ListSpeakersError::ConnectionError(e.to_string()))?;
+            .map_err(ListSpeakersError::ConnectionError)?;
         match response.status() {
             StatusCode::OK => {
                 #[derive(Deserialize)]
                 struct Root {
-                    voices: Vec<Speaker>,
+                    speakers: Vec<Speaker>,
                 }
-                let root: Root = response.json().await.unwrap();
-
-                Ok(root.voices)
+                let root: Root = response
+                    .json()
+                    .await
+                    .map_err(|e| ListSpeakersError::ConnectionError(e.to_string()))?;
+                Ok(root.speakers)
             }
-            StatusCode::BAD_REQUEST => Err(ListSpeakersError::BadRequest400),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(ListSpeakersError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(ListSpeakersError::ServiceUnavailable503),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(ListSpeakersError::BadRequest400(details)),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(ListSpeakersError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(ListSpeakersError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(ListSpeakersError::Unauthorised401(details)),
+                    StatusCode::FORBIDDEN => Err(ListSpeakersError::Forbidden403(details)),
+                    status => Err(ListSpeakersError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
 
-    fn set_speakers_path(uri: &mut Url) {
-        uri.set_path("v1/speakers");
-    }
-
+    #[cfg(not(target_arch = "wasm32"))]
     /// Creates a new speaker model, which is an optional enrollment token for users who are to add prompts to custom models. A speaker model contains information about a user's voice. The service extracts this information from a WAV audio sample that you pass as the body of the request. Associating a speaker model with a prompt is optional, but the information that is extracted from the speaker model helps the service learn about the speaker's voice
     ///
     /// # Parameters
@@ -107,7 +117,7 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// let file_path = std::path::Path::new("/home/user/audio.wav");
     /// let speaker_id = tts.create_speaker_model("speaker_one", &file_path).await?;
     /// println!("created speaker: {}", speaker_id);
@@ -120,51 +130,59 @@ impl TextToSpeech<'_> {
         speaker_name: impl AsRef<str>,
         audio_file: impl AsRef<Path>,
     ) -> Result<String, CreateSpeakerError> {
-        let wav_file = audio_file.as_ref();
-        let file = tokio::fs::OpenOptions::new()
-            .read(true)
-            .open(&wav_file)
+        let buffer = tokio::fs::read(audio_file.as_ref())
             .await
             .map_err(|e| CreateSpeakerError::FileReadError(e.to_string()))?;
 
-        let mut buf_reader = BufReader::new(file);
-        let mut buffer = Vec::new();
-        buf_reader
-            .read_to_end(&mut buffer)
-            .await
-            .map_err(|e| CreateSpeakerError::FileReadError(e.to_string()))?;
-
-        let mut url = Url::parse(self.service_url).unwrap();
-        Self::set_speakers_path(&mut url);
-        url.set_query(Some(&format!("speaker_name={}", speaker_name.as_ref())));
-        let body = Body::from(buffer);
-        let client = self.get_client();
-        let response = client
+        let mut url = self.service.endpoint("v1/speakers");
+        url.query_pairs_mut()
+            .append_pair("speaker_name", speaker_name.as_ref());
+        let req = self
+            .service
+            .http()
             .post(url)
             .header(CONTENT_TYPE, HeaderValue::from_static("audio/wav"))
-            .version(if cfg!(feature = "http2") {
-                Version::HTTP_2
-            } else {
-                Version::default()
-            })
-            .body(body)
-            .send()
+            .body(Body::from(buffer))
+            .build()
+            .map_err(|e| CreateSpeakerError::ConnectionError(e.to_string()))?;
+        let response = self
+            .service
+            .execute(req)
             .await
-            .unwrap();
+            .map_err(CreateSpeakerError::ConnectionError)?;
         match response.status() {
-            StatusCode::CREATED => {
+            StatusCode::CREATED | StatusCode::OK => {
                 #[derive(Deserialize)]
-                struct Foo {
+                struct Root {
                     speaker_id: String,
                 }
-                let response: Foo = response.json().await.unwrap();
-                Ok(response.speaker_id)
+                let root: Root = response
+                    .json()
+                    .await
+                    .map_err(|e| CreateSpeakerError::ConnectionError(e.to_string()))?;
+                Ok(root.speaker_id)
+            }
+            _ => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(CreateSpeakerError::BadRequest400(details)),
+                    StatusCode::UNAUTHORIZED => Err(CreateSpeakerError::Unauthorised401(
+                        speaker_name.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE => {
+                        Err(CreateSpeakerError::UnsupportedMediaType415(details))
+                    }
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(CreateSpeakerError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(CreateSpeakerError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(CreateSpeakerError::Forbidden403(details)),
+                    status => Err(CreateSpeakerError::UnexpectedStatus(status, details)),
+                }
             }
-            StatusCode::BAD_REQUEST => Err(CreateSpeakerError::BadRequest400),
-            StatusCode::UNSUPPORTED_MEDIA_TYPE => Err(CreateSpeakerError::UnsupportedMediaType415),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(CreateSpeakerError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(CreateSpeakerError::ServiceUnavailable503),
-            _ => unreachable!(),
         }
     }
 
@@ -182,49 +200,117 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
-    /// let speaker = tts.get_speaker_model("speaker_id").await?;
-    /// println!("Speaker: {:#?}", speaker);
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// for model in tts.get_speaker_model("speaker_id").await? {
+    ///     println!("{}: {} prompt(s)", model.customisation_id, model.prompts.len());
+    /// }
     /// # Ok(())
     /// # }
     /// ```
     pub async fn get_speaker_model(
         &self,
         speaker_id: impl AsRef<str>,
-    ) -> Result<SpeakerCustomModel, GetSpeakerError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path(&format!("v1/speakers/{}", speaker_id.as_ref()));
-        let mut req = Request::new(Method::GET, url);
+    ) -> Result<Vec<SpeakerCustomModel>, GetSpeakerError> {
+        let url = self.speaker_endpoint(speaker_id.as_ref());
+        let req = Request::new(Method::GET, url);
 
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
-        }
-
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| GetSpeakerError::ConnectionError(e.to_string()))?;
-        assert_eq!(response.status(), 200);
+            .map_err(GetSpeakerError::ConnectionError)?;
         match response.status() {
             StatusCode::OK => {
-                let root: SpeakerCustomModel = response.json().await.unwrap();
-
-                Ok(root)
+                #[derive(Deserialize)]
+                struct Root {
+                    customizations: Vec<SpeakerCustomModel>,
+                }
+                let root: Root = response
+                    .json()
+                    .await
+                    .map_err(|e| GetSpeakerError::ConnectionError(e.to_string()))?;
+                Ok(root.customizations)
             }
-            StatusCode::BAD_REQUEST => Err(GetSpeakerError::BadRequest400),
-            StatusCode::UNAUTHORIZED => Err(GetSpeakerError::Unauthorised401(
-                speaker_id.as_ref().to_owned(),
-            )),
-            StatusCode::NOT_MODIFIED => Err(GetSpeakerError::NotModified304),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(GetSpeakerError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(GetSpeakerError::ServiceUnavailable503),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(GetSpeakerError::BadRequest400(details)),
+                    StatusCode::UNAUTHORIZED => Err(GetSpeakerError::Unauthorised401(
+                        speaker_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::NOT_MODIFIED => Err(GetSpeakerError::NotModified304),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(GetSpeakerError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(GetSpeakerError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(GetSpeakerError::Forbidden403(details)),
+                    status => Err(GetSpeakerError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
 
+    /// Waits until the service has finished processing every prompt that a speaker has defined,
+    /// checking them with [`get_speaker_model()`] as often as `polling` asks. The speaker's custom
+    /// models are returned once all of their prompts are available; if the validation of any
+    /// prompt failed, [`WaitError::Failed`] carries the service's reason
+    ///
+    /// # Parameters
+    ///
+    /// * `speaker_id` - The speaker ID (GUID) of the speaker model. You must make the request with service credentials for the instance of the service that owns the speaker model
+    /// * `polling` - How often, and for how long, to check the speaker's prompts
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     polling::Polling,
+    /// #     tts::TextToSpeech,
+    /// # };
+    /// # use std::time::Duration;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// let polling = Polling::new(Duration::from_secs(2), Duration::from_secs(120));
+    /// let models = tts.wait_until_speaker_model_ready("speaker_id", &polling).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`get_speaker_model()`]: Self::get_speaker_model()
+    /// [`WaitError::Failed`]: crate::polling::errors::WaitError::Failed
+    pub async fn wait_until_speaker_model_ready(
+        &self,
+        speaker_id: impl AsRef<str>,
+        polling: &Polling,
+    ) -> Result<Vec<SpeakerCustomModel>, WaitError<GetSpeakerError>> {
+        polling
+            .until(|| async {
+                let models = self.get_speaker_model(speaker_id.as_ref()).await?;
+                let mut prompts = models.iter().flat_map(|model| &model.prompts);
+                if let Some(failed) = prompts
+                    .clone()
+                    .find(|prompt| prompt.status == Some(PromptStatus::Failed))
+                {
+                    return Ok(Status::Failed(
+                        failed
+                            .error
+                            .clone()
+                            .unwrap_or_else(|| format!("prompt {} failed", failed.prompt_id)),
+                    ));
+                }
+                Ok(
+                    match prompts.all(|prompt| prompt.status == Some(PromptStatus::Available)) {
+                        true => Status::Ready(models),
+                        false => Status::Pending,
+                    },
+                )
+            })
+            .await
+    }
+
     /// Deletes an existing speaker model from the service instance. The service deletes the enrolled speaker with the specified speaker ID. You must use credentials for the instance of the service that owns a speaker model to delete the speaker
     /// # Parameters
     ///
@@ -238,44 +324,55 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// if tts.delete_speaker_model("speaker-id").await.is_ok() {
     ///     println!("speaker deleted");
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    /// [`model`]: crate::tts::customisations::Model
+    /// [`model`]: crate::tts::customisations::CustomModel
     pub async fn delete_speaker_model(
         &self,
         speaker_id: impl AsRef<str>,
     ) -> Result<(), DeleteSpeakerError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path(&format!("v1/speakers/{}", speaker_id.as_ref()));
-        let mut req = Request::new(Method::DELETE, url);
-
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
-        }
+        let url = self.speaker_endpoint(speaker_id.as_ref());
+        let req = Request::new(Method::DELETE, url);
 
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| DeleteSpeakerError::ConnectionError(e.to_string()))?;
+            .map_err(DeleteSpeakerError::ConnectionError)?;
         match response.status() {
             StatusCode::NO_CONTENT => Ok(()),
-            StatusCode::BAD_REQUEST => Err(DeleteSpeakerError::BadRequest400(
-                speaker_id.as_ref().to_owned(),
-            )),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(DeleteSpeakerError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(DeleteSpeakerError::ServiceUnavailable503),
-            StatusCode::UNAUTHORIZED => Err(DeleteSpeakerError::Unauthorised401(
-                speaker_id.as_ref().to_owned(),
-            )),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(DeleteSpeakerError::BadRequest400(
+                        speaker_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(DeleteSpeakerError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(DeleteSpeakerError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(DeleteSpeakerError::Unauthorised401(
+                        speaker_id.as_ref().to_owned(),
+                        details,
+                    )),
+                    StatusCode::FORBIDDEN => Err(DeleteSpeakerError::Forbidden403(details)),
+                    status => Err(DeleteSpeakerError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
+
+    fn speaker_endpoint(&self, speaker_id: &str) -> Url {
+        let mut url = self.service.endpoint("v1/speakers");
+        url.path_segments_mut().unwrap().push(speaker_id);
+        url
+    }
 }
@@ -0,0 +1,43 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+/// Errors found when checking a pronunciation before it is sent to the service. Positions are
+/// byte offsets into the pronunciation
+pub enum PhonemeError {
+    /// The pronunciation has no symbols
+    #[error("The pronunciation is empty")]
+    Empty,
+    /// A character that no pronunciation in the alphabet uses
+    #[error("'{character}' at {position} is not a phonetic symbol")]
+    InvalidCharacter {
+        /// The character
+        character: char,
+        /// Where the character is
+        position: usize,
+    },
+    /// A character of the International Phonetic Alphabet in an IBM SPR pronunciation
+    #[error("'{character}' at {position} looks like IPA, but IBM SPR is written in ASCII")]
+    LooksLikeIpa {
+        /// The character
+        character: char,
+        /// Where the character is
+        position: usize,
+    },
+    /// A digit or uppercase letter of IBM SPR in an IPA pronunciation
+    #[error("'{character}' at {position} looks like IBM SPR; IPA marks stress with ˈ and ˌ and has no uppercase ASCII letters")]
+    LooksLikeSpr {
+        /// The character
+        character: char,
+        /// Where the character is
+        position: usize,
+    },
+    /// A stress mark that is not at the start of a syllable, or marks a syllable with no symbols
+    #[error("The stress mark at {0} must come before the symbols of a syllable")]
+    MisplacedStress(usize),
+    /// A `<phoneme>` element named an alphabet other than `ipa` or `ibm`
+    #[error("The alphabet {0} is neither ipa nor ibm")]
+    UnknownAlphabet(String),
+    /// Two syllable boundaries with nothing between them
+    #[error("The syllable ending at {0} has no symbols")]
+    EmptySyllable(usize),
+}
@@ -0,0 +1,463 @@
+use std::{fmt::Display, time::Duration};
+
+use self::errors::PhonemeError;
+
+/// Errors that may be returned when checking a pronunciation
+pub mod errors;
+mod phonemes;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// An SSML document, the `<speak>` element that wraps everything to be synthesised. Text added
+/// to the document is escaped, so it always produces valid SSML
+///
+/// # Example
+/// ```
+/// # use ibm_watson::tts::ssml::{BreakStrength, Element, Speak};
+/// let ssml = Speak::new()
+///     .text("Your code is")
+///     .element(Element::say_as("letters").text("A1B"))
+///     .element(Element::pause(BreakStrength::Medium))
+///     .element(Element::prosody().rate("slow").text("Goodbye & good luck"));
+/// assert_eq!(
+///     ssml.to_string(),
+///     r#"<speak version="1.0">Your code is<say-as interpret-as="letters">A1B</say-as><break strength="medium"/><prosody rate="slow">Goodbye &amp; good luck</prosody></speak>"#
+/// );
+/// ```
+pub struct Speak {
+    children: Vec<Node>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Node {
+    Text(String),
+    Element(Element),
+}
+
+impl Speak {
+    /// Create an empty document
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `text`, which is escaped
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The text to speak
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.children.push(Node::Text(text.into()));
+        self
+    }
+
+    /// Append an [`Element`]
+    ///
+    /// # Parameters
+    ///
+    /// * `element` - The element to append
+    ///
+    /// [`Element`]: self::Element
+    pub fn element(mut self, element: Element) -> Self {
+        self.children.push(Node::Element(element));
+        self
+    }
+}
+
+impl Display for Speak {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, r#"<speak version="1.0">"#)?;
+        for child in &self.children {
+            child.fmt(f)?;
+        }
+        write!(f, "</speak>")
+    }
+}
+
+impl From<Speak> for String {
+    fn from(speak: Speak) -> Self {
+        speak.to_string()
+    }
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Node::Text(text) => write!(f, "{}", escape(text)),
+            Node::Element(element) => element.fmt(f),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// An SSML element inside a [`Speak`] document, created with one of the constructors below.
+/// Elements that can hold content, such as [`prosody()`], take text and other elements with
+/// [`text()`] and [`element()`]
+///
+/// [`Speak`]: self::Speak
+/// [`prosody()`]: Self::prosody()
+/// [`text()`]: Self::text()
+/// [`element()`]: Self::element()
+pub struct Element {
+    name: &'static str,
+    attributes: Vec<(&'static str, String)>,
+    children: Vec<Node>,
+}
+
+impl Element {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            attributes: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn attribute(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        let value = value.into();
+        match self
+            .attributes
+            .iter_mut()
+            .find(|(existing, _)| *existing == name)
+        {
+            Some((_, existing)) => *existing = value,
+            None => self.attributes.push((name, value)),
+        }
+        self
+    }
+
+    /// A `<prosody>` element, which changes the rate or pitch of its content. Set them with
+    /// [`rate()`] and [`pitch()`]
+    ///
+    /// [`rate()`]: Self::rate()
+    /// [`pitch()`]: Self::pitch()
+    pub fn prosody() -> Self {
+        Self::new("prosody")
+    }
+
+    /// Set the speaking rate of a `<prosody>` element
+    ///
+    /// # Parameters
+    ///
+    /// * `rate` - A relative change such as `+10%`, or one of `x-slow`, `slow`, `medium`, `fast`,
+    ///   `x-fast` and `default`
+    pub fn rate(self, rate: impl Into<String>) -> Self {
+        self.attribute("rate", rate)
+    }
+
+    /// Set the pitch of a `<prosody>` element
+    ///
+    /// # Parameters
+    ///
+    /// * `pitch` - A relative change such as `-5%`, or one of `x-low`, `low`, `medium`, `high`,
+    ///   `x-high` and `default`
+    pub fn pitch(self, pitch: impl Into<String>) -> Self {
+        self.attribute("pitch", pitch)
+    }
+
+    /// A `<break>` element, which inserts a pause of the given strength
+    ///
+    /// # Parameters
+    ///
+    /// * `strength` - How long a pause to insert
+    pub fn pause(strength: BreakStrength) -> Self {
+        Self::new("break").attribute("strength", strength.id())
+    }
+
+    /// A `<break>` element, which inserts a pause of the given length
+    ///
+    /// # Parameters
+    ///
+    /// * `time` - The length of the pause, rounded to the millisecond
+    pub fn pause_for(time: Duration) -> Self {
+        Self::new("break").attribute("time", format!("{}ms", time.as_millis()))
+    }
+
+    /// A `<say-as>` element, which tells the service how to read out its content
+    ///
+    /// # Parameters
+    ///
+    /// * `interpret_as` - How to interpret the content, such as `letters`, `digits`, `cardinal`,
+    ///   `ordinal`, `date` or `telephone`
+    pub fn say_as(interpret_as: impl Into<String>) -> Self {
+        Self::new("say-as").attribute("interpret-as", interpret_as)
+    }
+
+    /// Set the format of a `<say-as>` element, such as `mdy` for a date
+    ///
+    /// # Parameters
+    ///
+    /// * `format` - The format of the content
+    pub fn format(self, format: impl Into<String>) -> Self {
+        self.attribute("format", format)
+    }
+
+    /// A `<phoneme>` element, which gives the pronunciation of its content
+    ///
+    /// # Parameters
+    ///
+    /// * `alphabet` - The [`PhonemeAlphabet`] the pronunciation is written in
+    /// * `ph` - The pronunciation, which can be checked with [`PhonemeAlphabet::validate()`]
+    ///
+    /// [`PhonemeAlphabet`]: self::PhonemeAlphabet
+    /// [`PhonemeAlphabet::validate()`]: self::PhonemeAlphabet::validate()
+    pub fn phoneme(alphabet: PhonemeAlphabet, ph: impl Into<String>) -> Self {
+        Self::new("phoneme")
+            .attribute("alphabet", alphabet.id())
+            .attribute("ph", ph)
+    }
+
+    /// A `<sub>` element, which speaks `alias` in place of its content, such as for an
+    /// abbreviation
+    ///
+    /// # Parameters
+    ///
+    /// * `alias` - The text to speak instead
+    pub fn sub(alias: impl Into<String>) -> Self {
+        Self::new("sub").attribute("alias", alias)
+    }
+
+    /// An `<emphasis>` element, which stresses its content
+    ///
+    /// # Parameters
+    ///
+    /// * `level` - How strongly to stress the content
+    pub fn emphasis(level: EmphasisLevel) -> Self {
+        Self::new("emphasis").attribute("level", level.id())
+    }
+
+    /// An `<express-as>` element, which speaks its content in a particular style. Only some
+    /// neural voices support expressive styles
+    ///
+    /// # Parameters
+    ///
+    /// * `style` - The style to speak in
+    pub fn express_as(style: ExpressiveStyle) -> Self {
+        Self::new("express-as").attribute("style", style.id())
+    }
+
+    /// A `<mark>` element, whose position is reported while synthesising over a WebSocket
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - Identifies the mark
+    pub fn mark(name: impl Into<String>) -> Self {
+        Self::new("mark").attribute("name", name)
+    }
+
+    /// A `<p>` element, marking its content as a paragraph
+    pub fn paragraph() -> Self {
+        Self::new("p")
+    }
+
+    /// An `<s>` element, marking its content as a sentence
+    pub fn sentence() -> Self {
+        Self::new("s")
+    }
+
+    /// Append `text`, which is escaped
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The text to speak
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.children.push(Node::Text(text.into()));
+        self
+    }
+
+    /// Append a nested element
+    ///
+    /// # Parameters
+    ///
+    /// * `element` - The element to append
+    pub fn element(mut self, element: Element) -> Self {
+        self.children.push(Node::Element(element));
+        self
+    }
+}
+
+impl Display for Element {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{}", self.name)?;
+        for (name, value) in &self.attributes {
+            write!(f, r#" {}="{}""#, name, escape(value))?;
+        }
+        if self.children.is_empty() {
+            return write!(f, "/>");
+        }
+        write!(f, ">")?;
+        for child in &self.children {
+            child.fmt(f)?;
+        }
+        write!(f, "</{}>", self.name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The strength of a pause inserted with [`Element::pause()`]
+///
+/// [`Element::pause()`]: self::Element::pause()
+pub enum BreakStrength {
+    /// No pause, such as to suppress one that would otherwise be inserted
+    None,
+    /// Extra weak
+    XWeak,
+    /// Weak
+    Weak,
+    /// Medium
+    Medium,
+    /// Strong
+    Strong,
+    /// Extra strong
+    XStrong,
+}
+
+impl BreakStrength {
+    /// The value that the server expects for a particular strength
+    pub fn id(&self) -> &'static str {
+        match self {
+            BreakStrength::None => "none",
+            BreakStrength::XWeak => "x-weak",
+            BreakStrength::Weak => "weak",
+            BreakStrength::Medium => "medium",
+            BreakStrength::Strong => "strong",
+            BreakStrength::XStrong => "x-strong",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The alphabet of a pronunciation given with [`Element::phoneme()`]
+///
+/// [`Element::phoneme()`]: self::Element::phoneme()
+pub enum PhonemeAlphabet {
+    /// The International Phonetic Alphabet
+    Ipa,
+    /// IBM's Symbolic Phonetic Representation
+    Ibm,
+}
+
+impl PhonemeAlphabet {
+    /// The value that the server expects for a particular alphabet
+    pub fn id(&self) -> &'static str {
+        match self {
+            PhonemeAlphabet::Ipa => "ipa",
+            PhonemeAlphabet::Ibm => "ibm",
+        }
+    }
+
+    /// Checks `ph` offline before it is sent in a [`phoneme`] element or a custom word's
+    /// translation, so a mistake is reported with its position instead of as a `400 Bad Request`.
+    /// A pronunciation that passes may still use symbols its voice's language does not have
+    ///
+    /// # Parameters
+    ///
+    /// * `ph` - The pronunciation, written in this alphabet
+    ///
+    /// # Example
+    /// ```
+    /// # use ibm_watson::tts::ssml::{errors::PhonemeError, PhonemeAlphabet};
+    /// assert!(PhonemeAlphabet::Ipa.validate("təˈmɑːtoʊ").is_ok());
+    /// assert!(PhonemeAlphabet::Ibm.validate(".0tx.1me.0fo").is_ok());
+    /// assert_eq!(
+    ///     PhonemeAlphabet::Ibm.validate("təˈmɑːtoʊ"),
+    ///     Err(PhonemeError::LooksLikeIpa { character: 'ə', position: 1 })
+    /// );
+    /// ```
+    /// [`phoneme`]: self::Element::phoneme()
+    pub fn validate(&self, ph: &str) -> Result<(), PhonemeError> {
+        match self {
+            PhonemeAlphabet::Ipa => phonemes::validate_ipa(ph),
+            PhonemeAlphabet::Ibm => phonemes::validate_spr(ph),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// How strongly [`Element::emphasis()`] stresses its content
+///
+/// [`Element::emphasis()`]: self::Element::emphasis()
+pub enum EmphasisLevel {
+    /// Strong emphasis
+    Strong,
+    /// Moderate emphasis
+    Moderate,
+    /// No emphasis
+    None,
+    /// Reduced emphasis
+    Reduced,
+}
+
+impl EmphasisLevel {
+    /// The value that the server expects for a particular level
+    pub fn id(&self) -> &'static str {
+        match self {
+            EmphasisLevel::Strong => "strong",
+            EmphasisLevel::Moderate => "moderate",
+            EmphasisLevel::None => "none",
+            EmphasisLevel::Reduced => "reduced",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The speaking styles available to [`Element::express_as()`] and
+/// [`SynthesisOptions::speaking_style()`]
+///
+/// [`Element::express_as()`]: self::Element::express_as()
+/// [`SynthesisOptions::speaking_style()`]: crate::tts::synthesis::SynthesisOptions::speaking_style()
+pub enum ExpressiveStyle {
+    /// Happy and upbeat
+    Cheerful,
+    /// Caring and understanding
+    Empathetic,
+    /// Objective and even
+    Neutral,
+    /// Hesitant, as when unsure of an answer
+    Uncertain,
+}
+
+impl ExpressiveStyle {
+    /// The value that the server expects for a particular style
+    pub fn id(&self) -> &'static str {
+        match self {
+            ExpressiveStyle::Cheerful => "cheerful",
+            ExpressiveStyle::Empathetic => "empathetic",
+            ExpressiveStyle::Neutral => "neutral",
+            ExpressiveStyle::Uncertain => "uncertain",
+        }
+    }
+}
+
+/// Wraps all of `text`, either plain text or an SSML document, in an `<express-as>` element
+pub(crate) fn express_as(text: &str, style: ExpressiveStyle) -> String {
+    let trimmed = text.trim();
+    let close_start = trimmed.len().saturating_sub("</speak>".len());
+    if trimmed.starts_with("<speak") && trimmed.ends_with("</speak>") {
+        if let Some(open_end) = trimmed.find('>').filter(|&end| end < close_start) {
+            let (open, rest) = trimmed.split_at(open_end + 1);
+            let body = &rest[..rest.len() - "</speak>".len()];
+            return format!(
+                r#"{open}<express-as style="{}">{body}</express-as></speak>"#,
+                style.id()
+            );
+        }
+    }
+    Speak::new()
+        .element(Element::express_as(style).text(text))
+        .to_string()
+}
+
+/// Escapes the characters that have a special meaning in XML
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
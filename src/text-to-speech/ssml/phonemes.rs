@@ -0,0 +1,82 @@
+use super::errors::PhonemeError;
+
+/// Checks the structure of an IBM SPR pronunciation: ASCII symbols in syllables separated by
+/// `.`, each syllable optionally starting with a stress mark of `0`, `1` or `2`. Which symbols a
+/// language has is left to the service
+pub(crate) fn validate_spr(ph: &str) -> Result<(), PhonemeError> {
+    if ph.trim().is_empty() {
+        return Err(PhonemeError::Empty);
+    }
+    // the stress mark of the current syllable, whether it has symbols yet, and whether it
+    // follows a `.`
+    let (mut stress, mut symbols, mut dotted) = (None, false, false);
+    for (position, character) in ph.char_indices() {
+        match character {
+            '.' | ' ' => {
+                if let Some(stress) = stress.filter(|_| !symbols) {
+                    return Err(PhonemeError::MisplacedStress(stress));
+                }
+                if dotted && !symbols && character == '.' {
+                    return Err(PhonemeError::EmptySyllable(position));
+                }
+                (stress, symbols, dotted) = (None, false, character == '.');
+            }
+            '0'..='2' if stress.is_none() && !symbols => stress = Some(position),
+            '0'..='9' => return Err(PhonemeError::MisplacedStress(position)),
+            c if c.is_ascii_alphabetic() => symbols = true,
+            c if !c.is_ascii() => {
+                return Err(PhonemeError::LooksLikeIpa {
+                    character,
+                    position,
+                })
+            }
+            _ => {
+                return Err(PhonemeError::InvalidCharacter {
+                    character,
+                    position,
+                })
+            }
+        }
+    }
+    match stress.filter(|_| !symbols) {
+        Some(stress) => Err(PhonemeError::MisplacedStress(stress)),
+        None => Ok(()),
+    }
+}
+
+/// Checks that an IPA pronunciation only uses the letters, marks and diacritics of the
+/// International Phonetic Alphabet
+pub(crate) fn validate_ipa(ph: &str) -> Result<(), PhonemeError> {
+    if ph.trim().is_empty() {
+        return Err(PhonemeError::Empty);
+    }
+    for (position, character) in ph.char_indices() {
+        match character {
+            'a'..='z' | '.' | ' ' | '|' | '‖' | '‿' => {}
+            '0'..='9' | 'A'..='Z' => {
+                return Err(PhonemeError::LooksLikeSpr {
+                    character,
+                    position,
+                })
+            }
+            c if is_ipa(c) => {}
+            _ => {
+                return Err(PhonemeError::InvalidCharacter {
+                    character,
+                    position,
+                })
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether `c` is one of the IPA's letters, suprasegmentals or diacritics outside of ASCII
+fn is_ipa(c: char) -> bool {
+    matches!(c,
+        // IPA extensions, spacing modifiers such as ˈ ˌ ː, and combining diacritics
+        '\u{0250}'..='\u{036f}'
+        | 'æ' | 'ç' | 'ð' | 'ø' | 'ħ' | 'ŋ' | 'œ' | 'ǀ' | 'ǁ' | 'ǂ' | 'ǃ'
+        | 'β' | 'θ' | 'χ' | 'ⱱ' | 'ᵻ' | 'ᵿ' | '↗' | '↘' | '\u{1dc4}'..='\u{1dc9}'
+    )
+}
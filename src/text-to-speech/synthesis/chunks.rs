@@ -0,0 +1,170 @@
+/// The most text, in bytes, that is sent in a single synthesis request. The service accepts up
+/// to 5 KB, which leaves room for the `<speak>` element wrapped around every chunk of SSML
+pub(crate) const MAX_TEXT_BYTES: usize = 4800;
+
+/// Splits `text` into pieces of at most `max` bytes, preferring to end each one after a
+/// sentence. SSML is only split between the children of its `<speak>` element, and every piece
+/// is wrapped in the original `<speak>` element. A single child that is longer than `max` is
+/// left whole, for the service to reject
+pub(crate) fn split_text(text: &str, max: usize) -> Vec<String> {
+    if text.len() <= max {
+        return vec![text.to_owned()];
+    }
+    match ssml_body(text) {
+        Some((open, body, close)) => {
+            let max = max.saturating_sub(open.len() + close.len()).max(1);
+            pack(ssml_pieces(body), max)
+                .into_iter()
+                .map(|chunk| format!("{open}{chunk}{close}"))
+                .collect()
+        }
+        None => pack(text_pieces(text, max), max),
+    }
+}
+
+/// The opening tag, contents and closing tag of an SSML document, if `text` is one
+fn ssml_body(text: &str) -> Option<(&str, &str, &str)> {
+    let trimmed = text.trim();
+    if !trimmed.starts_with("<speak") || !trimmed.ends_with("</speak>") {
+        return None;
+    }
+    let open_end = trimmed.find('>')? + 1;
+    let close_start = trimmed.len() - "</speak>".len();
+    if open_end > close_start {
+        return None;
+    }
+    Some((
+        &trimmed[..open_end],
+        &trimmed[open_end..close_start],
+        &trimmed[close_start..],
+    ))
+}
+
+/// Greedily joins consecutive `pieces` into chunks of at most `max` bytes
+fn pack<'a>(pieces: impl IntoIterator<Item = &'a str>, max: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut chunk = String::new();
+    for piece in pieces {
+        if !chunk.is_empty() && chunk.len() + piece.len() > max {
+            chunks.push(std::mem::take(&mut chunk));
+        }
+        chunk.push_str(piece);
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+/// Whether a sentence can end with `c`
+fn ends_sentence(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '。' | '！' | '？')
+}
+
+/// Splits plain text after every sentence, then splits sentences that are longer than `max` at
+/// whitespace, and words that are still too long anywhere
+fn text_pieces(text: &str, max: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    for sentence in sentences(text) {
+        if sentence.len() <= max {
+            pieces.push(sentence);
+            continue;
+        }
+        for word in split_after(sentence, |prev, c| {
+            prev.is_whitespace() && !c.is_whitespace()
+        }) {
+            let mut word = word;
+            while word.len() > max {
+                let mut end = max;
+                while !word.is_char_boundary(end) {
+                    end -= 1;
+                }
+                pieces.push(&word[..end]);
+                word = &word[end..];
+            }
+            pieces.push(word);
+        }
+    }
+    pieces
+}
+
+/// Splits `text` after every sentence, keeping the whitespace that follows a sentence with it
+fn sentences(text: &str) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut ended = false;
+    let mut prev = None;
+    for (i, c) in text.char_indices() {
+        if c.is_whitespace() {
+            ended |= prev.is_some_and(ends_sentence);
+        } else if ended {
+            pieces.push(&text[start..i]);
+            start = i;
+            ended = false;
+        }
+        prev = Some(c);
+    }
+    pieces.push(&text[start..]);
+    pieces
+}
+
+/// Splits `text` between every pair of characters for which `split` returns true
+fn split_after(text: &str, split: impl Fn(char, char) -> bool) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut prev = None;
+    for (i, c) in text.char_indices() {
+        if prev.is_some_and(|prev| split(prev, c)) {
+            pieces.push(&text[start..i]);
+            start = i;
+        }
+        prev = Some(c);
+    }
+    pieces.push(&text[start..]);
+    pieces
+}
+
+/// Splits the contents of a `<speak>` element between its children, and after the sentences
+/// of the text directly inside it
+fn ssml_pieces(body: &str) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut ended = false;
+    let mut prev = None;
+    let mut chars = body.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == '<' {
+            if depth == 0 && i > start {
+                pieces.push(&body[start..i]);
+                start = i;
+            }
+            let end = body[i..].find('>').map_or(body.len(), |end| i + end + 1);
+            let tag = &body[i..end];
+            if tag.starts_with("</") {
+                depth = depth.saturating_sub(1);
+            } else if !tag.ends_with("/>") && !tag.starts_with("<!") && !tag.starts_with("<?") {
+                depth += 1;
+            }
+            // like a sentence, an element ends a piece along with the whitespace after it
+            ended = depth == 0;
+            while chars.peek().is_some_and(|&(next, _)| next < end) {
+                chars.next();
+            }
+            prev = Some('>');
+            continue;
+        }
+        if c.is_whitespace() {
+            ended |= depth == 0 && prev.is_some_and(ends_sentence);
+        } else if ended {
+            pieces.push(&body[start..i]);
+            start = i;
+            ended = false;
+        }
+        prev = Some(c);
+    }
+    if start < body.len() {
+        pieces.push(&body[start..]);
+    }
+    pieces
+}
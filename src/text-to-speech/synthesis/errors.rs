@@ -1,27 +1,65 @@
+use reqwest::StatusCode;
 use thiserror::Error;
 
+use crate::errors::{describe, ServiceErrorDetails};
+
 #[derive(Error, Debug)]
 /// Errors that may be returned in speech synethesis
 pub enum SynthesisError {
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid")]
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid"))]
     ///  A required input parameter is null or a specified input parameter or header value is invalid. For example, prompt_id '{prompt_id}' not found in custom model '{customisation_id}' if you attempt to use a nonexistent or deleted custom prompt
-    BadRequest400,
-    #[error("The specified voice does not exist")]
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The specified voice does not exist"))]
     /// The specified voice does not exist or, for IBM Cloud Pak for Data, the voice parameter was not specified but the default voice is not installed. The message is Model '{voice}' not found
-    NotFound404,
-    #[error("The request specified an incompatible content type or failed to specify a required sampling rate")]
+    NotFound404(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The request specified an incompatible content type or failed to specify a required sampling rate"))]
     /// The request specified an incompatible content type or failed to specify a required sampling rate
-    NotAcceptable406,
-    #[error("The request specified an unacceptable media type.")]
+    NotAcceptable406(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The request specified an unacceptable media type."))]
     /// The request specified an unacceptable media type
-    UnsupportedMediaType415,
-    #[error("The service experienced an internal error.")]
+    UnsupportedMediaType415(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "Too many requests have been sent to the service"))]
+    /// The service throttled the request, and it was not retried any further
+    TooManyRequests429(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error."))]
     /// The service experienced an internal error
-    InternalServerError500,
-    #[error("The service is currently unavailable.")]
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable."))]
     /// The service is currently unavailable
-    ServiceUnavailable500,
+    ServiceUnavailable500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
     #[error("Connection error: {0}")]
     /// Some other error occurred in the request
     ConnectionError(String),
+    #[error("{0}")]
+    /// An option sent as a header, such as the customer ID, is not a valid header value
+    InvalidHeader(String),
+    #[error("Could not write the audio to a file: {0}")]
+    /// The synthesised audio could not be written to a file
+    FileError(String),
+    #[error("The service reported an error: {0}")]
+    /// The service reported an error while synthesising over a WebSocket
+    ServiceError(String),
+    #[error("The text is too long for one request, and audio as {0} cannot be joined")]
+    /// The text had to be split, but audio in the requested format cannot be joined
+    UnsplittableFormat(String),
+    #[error("Samples can only be read from audio/l16, not {0}")]
+    /// Samples were wanted, but audio in another format was requested
+    NotLinear16(String),
+    #[error("Requested audio as {requested} but the service returned {received}")]
+    /// The service returned audio in a different format to the one requested
+    ContentTypeMismatch {
+        /// The requested MIME type
+        requested: String,
+        /// The MIME type the service returned
+        received: String,
+    },
 }
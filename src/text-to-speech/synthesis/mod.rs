@@ -1,16 +1,50 @@
-use std::borrow::Cow;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+use std::{borrow::Cow, collections::VecDeque, ops::Deref, pin::pin};
+mod chunks;
 /// Errors that may be returned in speech synthesis requests
 pub mod errors;
+mod wav;
+#[cfg(not(target_arch = "wasm32"))]
+mod websocket;
+pub use self::wav::fix_wav_header;
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::websocket::{MarkReached, SynthesisEvent, TimingEvent};
 
-use reqwest::{Method, Request, StatusCode, Url, Version};
-use url::form_urlencoded::byte_serialize;
+use bytes::Bytes;
+use futures_util::{stream, Stream, StreamExt};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE},
+    Request, Response, StatusCode, Url,
+};
+use serde::Serialize;
 
 use self::errors::SynthesisError;
 
-use super::TextToSpeech;
+use super::{
+    metadata_header,
+    ssml::{self, ExpressiveStyle},
+    voices::VoiceId,
+    TextToSpeech, LEARNING_OPT_OUT, WATSON_METADATA,
+};
+use crate::{auth::Authenticator, errors::ServiceErrorDetails};
 
-/// The service can return audio in the following formats (MIME types):
-#[derive(Clone, Copy)]
+/// The service can return audio in the following formats (MIME types). The sampling rate and
+/// endianness parameters can be set with [`rate()`] and [`endianness()`]
+///
+/// # Example
+/// ```
+/// # use ibm_watson::tts::synthesis::{AudioEndianness, AudioFormat};
+/// let format = AudioFormat::AudioL16 {
+///     sample_rate: 16000,
+///     endianness: None,
+/// }
+/// .endianness(AudioEndianness::BigEndian);
+/// assert_eq!(format.id(), "audio/l16;rate=16000;endianness=big-endian");
+/// ```
+/// [`rate()`]: Self::rate()
+/// [`endianness()`]: Self::endianness()
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AudioFormat {
     /// You must specify the rate of the audio.
     AudioAlaw { sample_rate: u16 },
@@ -21,7 +55,7 @@ pub enum AudioFormat {
     /// You must specify the rate of the audio. You can optionally specify the endianness of the audio. The default endianness is little-endian
     AudioL16 {
         sample_rate: u16,
-        endianess: Option<AudioEndianness>,
+        endianness: Option<AudioEndianness>,
     },
     /// You can optionally specify the rate of the audio. The default sampling rate is 22,050 Hz
     AudioOgg { sample_rate: Option<u16> },
@@ -57,38 +91,87 @@ impl Default for AudioFormat {
 }
 
 impl AudioFormat {
-    /// The value that the server expects for a particular format
+    /// Request audio sampled at `sample_rate` Hz. Formats that are always returned at a fixed
+    /// rate, such as [`AudioBasic`] and [`AudioWebm`], are left unchanged
+    ///
+    /// # Parameters
+    ///
+    /// * `sample_rate` - The sampling rate of the audio in Hz
+    ///
+    /// [`AudioBasic`]: Self::AudioBasic
+    /// [`AudioWebm`]: Self::AudioWebm
+    pub fn rate(mut self, sample_rate: u16) -> Self {
+        match &mut self {
+            AudioFormat::AudioAlaw { sample_rate: rate }
+            | AudioFormat::AudioL16 {
+                sample_rate: rate, ..
+            }
+            | AudioFormat::AudioMulaw { sample_rate: rate } => *rate = sample_rate,
+            AudioFormat::AudioFlac { sample_rate: rate }
+            | AudioFormat::AudioOgg { sample_rate: rate }
+            | AudioFormat::AudioOggCodecsOpus { sample_rate: rate }
+            | AudioFormat::AudioOggCodecsVorbis { sample_rate: rate }
+            | AudioFormat::AudioMp3 { sample_rate: rate }
+            | AudioFormat::AudioMpeg { sample_rate: rate }
+            | AudioFormat::AudioWav { sample_rate: rate }
+            | AudioFormat::AudioWebmCodecsVorbis { sample_rate: rate } => *rate = Some(sample_rate),
+            AudioFormat::AudioBasic | AudioFormat::AudioWebm | AudioFormat::AudioWebmCodecsOpus => {
+            }
+        }
+        self
+    }
+
+    /// Request audio with the given byte order. Only [`AudioL16`] has an endianness, other
+    /// formats are left unchanged
+    ///
+    /// # Parameters
+    ///
+    /// * `endianness` - The [`AudioEndianness`] of the audio
+    ///
+    /// [`AudioL16`]: Self::AudioL16
+    /// [`AudioEndianness`]: super::synthesis::AudioEndianness
+    pub fn endianness(mut self, endianness: AudioEndianness) -> Self {
+        if let AudioFormat::AudioL16 {
+            endianness: value, ..
+        } = &mut self
+        {
+            *value = Some(endianness);
+        }
+        self
+    }
+
+    /// The MIME type that the server expects for a particular format, as sent in the `Accept` header
     pub fn id(&self) -> Cow<'static, str> {
         match &self {
             AudioFormat::AudioAlaw { sample_rate } => {
                 let url = format!("audio/alaw;rate={sample_rate}");
-                serialise_bytes(&url)
+                Cow::from(url)
             }
             AudioFormat::AudioBasic => Cow::from("audio/basic"),
             AudioFormat::AudioFlac { sample_rate } => {
                 let url = format!("audio/flac;rate={}", sample_rate.unwrap_or(22050));
-                serialise_bytes(&url)
+                Cow::from(url)
             }
             AudioFormat::AudioL16 {
                 sample_rate,
-                endianess: endianness,
+                endianness,
             } => {
                 let url = match endianness {
                     Some(endianness) => {
                         format!(
-                            "audio/flac;rate={sample_rate};endianness={}",
+                            "audio/l16;rate={sample_rate};endianness={}",
                             endianness.id()
                         )
                     }
                     None => {
-                        format!("audio/flac;rate={sample_rate}")
+                        format!("audio/l16;rate={sample_rate}")
                     }
                 };
-                serialise_bytes(&url)
+                Cow::from(url)
             }
             AudioFormat::AudioOgg { sample_rate } => {
                 let url = format!("audio/ogg;rate={}", sample_rate.unwrap_or(22050));
-                serialise_bytes(&url)
+                Cow::from(url)
             }
             AudioFormat::AudioOggCodecsOpus { sample_rate } => {
                 let url = format!(
@@ -98,50 +181,109 @@ impl AudioFormat {
                         None => 48000,
                     }
                 );
-                serialise_bytes(&url)
+                Cow::from(url)
             }
             AudioFormat::AudioOggCodecsVorbis { sample_rate } => {
                 let url = format!(
                     "audio/ogg;codecs=vorbis;rate={}",
                     sample_rate.unwrap_or(22050)
                 );
-                serialise_bytes(&url)
+                Cow::from(url)
             }
             AudioFormat::AudioMp3 { sample_rate } => {
                 let url = format!("audio/mp3;rate={}", sample_rate.unwrap_or(22050));
-                serialise_bytes(&url)
+                Cow::from(url)
             }
             AudioFormat::AudioMpeg { sample_rate } => {
                 let url = format!("audio/mpeg;rate={}", sample_rate.unwrap_or(22050));
-                serialise_bytes(&url)
+                Cow::from(url)
             }
             AudioFormat::AudioMulaw { sample_rate } => {
                 let url = format!("audio/mulaw;rate={}", sample_rate);
-                serialise_bytes(&url)
+                Cow::from(url)
             }
             AudioFormat::AudioWav { sample_rate } => {
                 let url = format!("audio/wav;rate={}", sample_rate.unwrap_or(22050));
-                serialise_bytes(&url)
+                Cow::from(url)
             }
-            AudioFormat::AudioWebm => serialise_bytes("audio/webm"),
-            AudioFormat::AudioWebmCodecsOpus => serialise_bytes("audio/webm;codecs=opus"),
+            AudioFormat::AudioWebm => Cow::from("audio/webm"),
+            AudioFormat::AudioWebmCodecsOpus => Cow::from("audio/webm;codecs=opus"),
             AudioFormat::AudioWebmCodecsVorbis { sample_rate } => {
                 let url = format!(
-                    "audio/webm;codecs=vorbis/rate={}",
+                    "audio/webm;codecs=vorbis;rate={}",
                     sample_rate.unwrap_or(22050)
                 );
-                serialise_bytes(&url)
+                Cow::from(url)
             }
         }
     }
 }
 
-fn serialise_bytes(url: &str) -> Cow<'static, str> {
-    let url: String = byte_serialize(url.as_bytes()).collect();
-    Cow::from(url)
+impl AudioFormat {
+    /// The file extension usually given to audio in this format, without the leading dot
+    ///
+    /// # Example
+    /// ```
+    /// # use ibm_watson::tts::synthesis::AudioFormat;
+    /// assert_eq!(AudioFormat::AudioOggCodecsOpus { sample_rate: None }.extension(), "ogg");
+    /// assert_eq!(AudioFormat::AudioMpeg { sample_rate: None }.extension(), "mp3");
+    /// ```
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AudioFormat::AudioAlaw { .. } => "alaw",
+            AudioFormat::AudioBasic => "au",
+            AudioFormat::AudioFlac { .. } => "flac",
+            AudioFormat::AudioL16 { .. } => "pcm",
+            AudioFormat::AudioOgg { .. }
+            | AudioFormat::AudioOggCodecsOpus { .. }
+            | AudioFormat::AudioOggCodecsVorbis { .. } => "ogg",
+            AudioFormat::AudioMp3 { .. } | AudioFormat::AudioMpeg { .. } => "mp3",
+            AudioFormat::AudioMulaw { .. } => "ulaw",
+            AudioFormat::AudioWav { .. } => "wav",
+            AudioFormat::AudioWebm
+            | AudioFormat::AudioWebmCodecsOpus
+            | AudioFormat::AudioWebmCodecsVorbis { .. } => "webm",
+        }
+    }
+
+    /// Whether this is one of the WAV formats
+    pub(crate) fn is_wav(&self) -> bool {
+        matches!(self, AudioFormat::AudioWav { .. })
+    }
+
+    /// Whether audio in this format stays valid when one clip is appended to another. Joined Ogg
+    /// clips form a chained file, which many players stop playing after its first stream
+    pub(crate) fn can_concatenate(&self) -> bool {
+        matches!(
+            self,
+            AudioFormat::AudioAlaw { .. }
+                | AudioFormat::AudioL16 { .. }
+                | AudioFormat::AudioMp3 { .. }
+                | AudioFormat::AudioMpeg { .. }
+                | AudioFormat::AudioMulaw { .. }
+        )
+    }
+
+    /// Whether `content_type`, as returned by the service, is audio in this format. Only the
+    /// media type and codec are compared, as the service does not always repeat the other
+    /// parameters
+    pub(crate) fn matches(&self, content_type: &str) -> bool {
+        essence(&self.id()) == essence(content_type)
+    }
+}
+
+/// The media type and codec of a MIME type, lowercased, treating `audio/mp3` as `audio/mpeg`
+fn essence(mime: &str) -> (String, Option<String>) {
+    let mut parts = mime.split(';').map(|part| part.trim().to_ascii_lowercase());
+    let mut media_type = parts.next().unwrap_or_default();
+    if media_type == "audio/mp3" {
+        media_type = "audio/mpeg".to_owned();
+    }
+    let codecs = parts.find_map(|part| part.strip_prefix("codecs=").map(ToOwned::to_owned));
+    (media_type, codecs)
 }
 
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
 /// The server expects the following values for audio endianness
 pub enum AudioEndianness {
     /// Big Endian
@@ -163,12 +305,367 @@ impl AudioEndianness {
     }
 }
 
-impl TextToSpeech<'_> {
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Synthesised audio, along with the MIME type the service returned it as. It dereferences to
+/// the bytes of the audio
+///
+/// # Example
+/// ``` no_run
+/// # use ibm_watson::{
+/// #     auth::IamAuthenticator,
+/// #     tts::{voices::WatsonVoice, TextToSpeech},
+/// # };
+/// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+/// # let auth = IamAuthenticator::new("api_key").await?;
+/// # let tts = TextToSpeech::new(&auth, "service_url")?;
+/// let audio = tts.synthesise("Hey there", None, None).await?;
+/// println!("{} bytes of {}", audio.len(), audio.content_type());
+/// # Ok(())
+/// # }
+/// ```
+pub struct Audio {
+    content_type: String,
+    data: Bytes,
+}
+
+impl Audio {
+    /// The MIME type of the audio, such as `audio/ogg;codecs=opus`
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    /// The bytes of the audio
+    pub fn into_bytes(self) -> Bytes {
+        self.data
+    }
+}
+
+impl Deref for Audio {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl AsRef<[u8]> for Audio {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl From<Audio> for Bytes {
+    fn from(audio: Audio) -> Self {
+        audio.data
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// How much of the audio has been received, as reported by [`synthesise_with_progress()`]
+///
+/// [`synthesise_with_progress()`]: super::TextToSpeech::synthesise_with_progress()
+pub struct Progress {
+    /// The bytes of audio received so far
+    pub bytes_received: u64,
+    /// The length of the whole clip in bytes, if the service said how long it would be. It
+    /// usually does not, as audio is sent while it is still being synthesised
+    pub total_hint: Option<u64>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// Options for a synthesis request. Anything left unset uses the service's default
+///
+/// # Example
+/// ``` no_run
+/// # use ibm_watson::{
+/// #     auth::IamAuthenticator,
+/// #     tts::{synthesis::{AudioFormat, SynthesisOptions}, voices::WatsonVoice, TextToSpeech},
+/// # };
+/// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+/// # let auth = IamAuthenticator::new("api_key").await?;
+/// # let tts = TextToSpeech::new(&auth, "service_url")?;
+/// let options = SynthesisOptions::new()
+///     .voice(WatsonVoice::EnGbCharlotteV3)
+///     .format(AudioFormat::AudioMp3 { sample_rate: None })
+///     .rate_percentage(-10)
+///     .pitch_percentage(5);
+/// let synth_bytes = tts.synthesise_with("Hey there", &options).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SynthesisOptions {
+    voice: Option<VoiceId>,
+    format: Option<AudioFormat>,
+    customisation_id: Option<String>,
+    rate_percentage: Option<i32>,
+    pitch_percentage: Option<i32>,
+    spell_out_mode: Option<SpellOutMode>,
+    learning_opt_out: Option<bool>,
+    customer_id: Option<String>,
+    split_long_text: bool,
+    speaking_style: Option<ExpressiveStyle>,
+    headers: HeaderMap,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// How German voices read out strings of characters, such as serial numbers, that are spelt out
+/// with an SSML `<say-as interpret-as="letters">` element
+pub enum SpellOutMode {
+    #[default]
+    /// Characters are read in a voice-dependent way, one at a time or in groups
+    Default,
+    /// Characters are read one at a time
+    Singles,
+    /// Characters are read in groups of two
+    Pairs,
+    /// Characters are read in groups of three
+    Triples,
+}
+
+impl SpellOutMode {
+    /// The value that the server expects for a particular mode
+    pub fn id(&self) -> &'static str {
+        match self {
+            SpellOutMode::Default => "default",
+            SpellOutMode::Singles => "singles",
+            SpellOutMode::Pairs => "pairs",
+            SpellOutMode::Triples => "triples",
+        }
+    }
+}
+
+impl SynthesisOptions {
+    /// Create options that use the service's defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Synthesise with `voice` instead of the client's [`voice`]
+    ///
+    /// # Parameters
+    ///
+    /// * `voice` - The voice to speak in, either a [`WatsonVoice`] or a [`VoiceId`] for any voice
+    ///   the service offers
+    ///
+    /// [`voice`]: super::TextToSpeech::set_voice()
+    /// [`WatsonVoice`]: super::voices::WatsonVoice
+    /// [`VoiceId`]: super::voices::VoiceId
+    pub fn voice(mut self, voice: impl Into<VoiceId>) -> Self {
+        self.voice = Some(voice.into());
+        self
+    }
+
+    /// Request audio in `format`. Defaults to [`AudioOggCodecsOpus`]
+    ///
+    /// # Parameters
+    ///
+    /// * `format` - The requested [`AudioFormat`] (MIME type) of the audio
+    ///
+    /// [`AudioFormat`]: super::synthesis::AudioFormat
+    /// [`AudioOggCodecsOpus`]: super::synthesis::AudioFormat::AudioOggCodecsOpus
+    pub fn format(mut self, format: AudioFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Synthesise with a custom model
+    ///
+    /// # Parameters
+    ///
+    /// * `customisation_id` - The customisation ID (GUID) of a custom [`model`] to use for the synthesis. It must match the language of the voice
+    ///
+    /// [`model`]: super::customisations::CustomModel
+    pub fn customisation_id(mut self, customisation_id: impl Into<String>) -> Self {
+        self.customisation_id = Some(customisation_id.into());
+        self
+    }
+
+    /// Speak faster or slower than the voice's default rate
+    ///
+    /// # Parameters
+    ///
+    /// * `rate_percentage` - The percentage change from the default speaking rate, such as `-10`
+    ///   to slow down by a tenth. Applies to the whole request, on top of any SSML `<prosody>`
+    pub fn rate_percentage(mut self, rate_percentage: i32) -> Self {
+        self.rate_percentage = Some(rate_percentage);
+        self
+    }
+
+    /// Speak at a higher or lower pitch than the voice's default
+    ///
+    /// # Parameters
+    ///
+    /// * `pitch_percentage` - The percentage change from the default pitch, such as `5` to
+    ///   raise it slightly. Applies to the whole request, on top of any SSML `<prosody>`
+    pub fn pitch_percentage(mut self, pitch_percentage: i32) -> Self {
+        self.pitch_percentage = Some(pitch_percentage);
+        self
+    }
+
+    /// Choose how German voices read out strings of characters
+    ///
+    /// # Parameters
+    ///
+    /// * `spell_out_mode` - The [`SpellOutMode`] to use. Other languages ignore it
+    ///
+    /// [`SpellOutMode`]: super::synthesis::SpellOutMode
+    pub fn spell_out_mode(mut self, spell_out_mode: SpellOutMode) -> Self {
+        self.spell_out_mode = Some(spell_out_mode);
+        self
+    }
+
+    /// Whether IBM may use the text of this request to improve its services, overriding the
+    /// client's [`set_learning_opt_out()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `opt_out` - Whether to send `X-Watson-Learning-Opt-Out: true`
+    ///
+    /// [`set_learning_opt_out()`]: super::TextToSpeech::set_learning_opt_out()
+    pub fn learning_opt_out(mut self, opt_out: bool) -> Self {
+        self.learning_opt_out = Some(opt_out);
+        self
+    }
+
+    /// Associate the text of this request with a customer ID, in place of the client's
+    /// [`set_customer_id()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `customer_id` - The customer ID to send in the `X-Watson-Metadata` header
+    ///
+    /// [`set_customer_id()`]: super::TextToSpeech::set_customer_id()
+    pub fn customer_id(mut self, customer_id: impl Into<String>) -> Self {
+        self.customer_id = Some(customer_id.into());
+        self
+    }
+
+    /// Split text that is too long for a single request, about 5 KB, into chunks that end on
+    /// sentences and synthesise them one after another, joining their audio. SSML is split
+    /// between the children of its `<speak>` element. Only formats whose audio can simply be
+    /// joined are supported: MP3, l16, mu-law and a-law. Longer text in any other format,
+    /// including the default `audio/ogg;codecs=opus`, fails with [`UnsplittableFormat`]
+    ///
+    /// # Parameters
+    ///
+    /// * `split` - Whether to split long text. Off by default, in which case the service rejects
+    ///   text that is too long. [`synthesise_websocket()`] never splits text
+    ///
+    /// [`UnsplittableFormat`]: super::synthesis::errors::SynthesisError::UnsplittableFormat
+    /// [`synthesise_websocket()`]: super::TextToSpeech::synthesise_websocket()
+    pub fn split_long_text(mut self, split: bool) -> Self {
+        self.split_long_text = split;
+        self
+    }
+
+    /// Speak all of the text in an expressive style, without having to write the SSML for it.
+    /// Plain text is wrapped in an SSML document, so it is escaped first. Only
+    /// [expressive voices] support styles
+    ///
+    /// # Parameters
+    ///
+    /// * `style` - The [`ExpressiveStyle`] to speak in
+    ///
+    /// [`ExpressiveStyle`]: super::ssml::ExpressiveStyle
+    /// [expressive voices]: super::voices::WatsonVoice::is_expressive()
+    pub fn speaking_style(mut self, style: ExpressiveStyle) -> Self {
+        self.speaking_style = Some(style);
+        self
+    }
+
+    /// Send an extra header with the request, such as a tracing header or one that enables an
+    /// experimental feature of the service. Headers are added alongside those of the other
+    /// options, and a header with the same name as one of them replaces it. An `Authorization`
+    /// header from the client's authenticator replaces one given here
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the header
+    /// * `value` - Its value. Calling this again with the same name sends both values
+    ///
+    /// # Example
+    /// ```
+    /// # use ibm_watson::tts::synthesis::SynthesisOptions;
+    /// # use reqwest::header::{HeaderName, HeaderValue};
+    /// let options = SynthesisOptions::new().header(
+    ///     HeaderName::from_static("x-request-id"),
+    ///     HeaderValue::from_static("4bf92f35"),
+    /// );
+    /// ```
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// `text` as it is sent to the service, in the requested speaking style
+    pub(crate) fn styled<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match self.speaking_style {
+            Some(style) => Cow::Owned(ssml::express_as(text, style)),
+            None => Cow::Borrowed(text),
+        }
+    }
+
+    /// Adds the options that are sent as headers to `req`
+    pub(crate) fn apply_headers(&self, req: &mut Request) -> Result<(), SynthesisError> {
+        if let Some(opt_out) = self.learning_opt_out {
+            let value = if opt_out { "true" } else { "false" };
+            req.headers_mut()
+                .insert(LEARNING_OPT_OUT, HeaderValue::from_static(value));
+        }
+        if let Some(customer_id) = &self.customer_id {
+            let value = metadata_header(customer_id).map_err(SynthesisError::InvalidHeader)?;
+            req.headers_mut().insert(WATSON_METADATA, value);
+        }
+        for name in self.headers.keys() {
+            req.headers_mut().remove(name);
+        }
+        for (name, value) in &self.headers {
+            req.headers_mut().append(name, value.clone());
+        }
+        Ok(())
+    }
+
+    /// The requested voice, or `default`, the client's
+    pub(crate) fn voice_or<'a>(&'a self, default: &'a VoiceId) -> &'a VoiceId {
+        self.voice.as_ref().unwrap_or(default)
+    }
+
+    /// The requested format, or the service's default
+    pub(crate) fn format_or_default(&self) -> AudioFormat {
+        self.format.unwrap_or_default()
+    }
+
+    /// Adds the options that are sent as query parameters to `url`
+    pub(crate) fn append_query(&self, url: &mut Url) {
+        let mut query = url.query_pairs_mut();
+        if let Some(customisation_id) = &self.customisation_id {
+            query.append_pair("customization_id", customisation_id);
+        }
+        if let Some(rate_percentage) = self.rate_percentage {
+            query.append_pair("rate_percentage", &rate_percentage.to_string());
+        }
+        if let Some(pitch_percentage) = self.pitch_percentage {
+            query.append_pair("pitch_percentage", &pitch_percentage.to_string());
+        }
+        if let Some(spell_out_mode) = self.spell_out_mode {
+            query.append_pair("spell_out_mode", spell_out_mode.id());
+        }
+    }
+}
+
+impl<A: Authenticator> TextToSpeech<'_, A> {
     /// Synthesises text to audio that is spoken in the [`specified voice`]. The service bases its understanding of the language for the input text on the specified voice. Use a voice that matches the language of the input text.
     ///
+    /// The text is sent in the body of a `POST` request, so it may be long and may contain SSML
+    /// markup without having to be escaped for a URL.
+    ///
+    /// The [`Audio`] returned carries the MIME type the service sent it as. If a format is
+    /// requested and the service returns a different one, this fails with
+    /// [`ContentTypeMismatch`].
+    ///
     /// # Parameters
     ///
-    /// * `text` - The text to synthesise
+    /// * `text` - The text to synthesise, either plain text or SSML
     /// * `format` - The requested [`AudioFormat`] (MIME type) of the audio. Defaults to [`AudioOggCodecsOpus`]
     /// * `customisation_id` - The customisation ID (GUID) of a custom [`model`] to use for the synthesis. If a custom model is specified, it works only if it matches the [`language`] of the indicated voice. You must make the request with credentials for the instance of the service that owns the custom model. Omit the parameter to use the specified voice with no customisation
     ///
@@ -178,7 +675,9 @@ impl TextToSpeech<'_> {
     /// [`language`]: super::voices::Voice::language
     /// [`gender`]: super::voices::Voice::gender
     /// [`specified voice`]: super::TextToSpeech::set_voice()
-    /// [`model`]: super::customisations::Model
+    /// [`model`]: super::customisations::CustomModel
+    /// [`Audio`]: super::synthesis::Audio
+    /// [`ContentTypeMismatch`]: super::synthesis::errors::SynthesisError::ContentTypeMismatch
     ///
     /// # Example
     /// ``` no_run
@@ -188,7 +687,7 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// let synth_bytes = tts.synthesise("Hey there", None, None).await?;
     /// # Ok(())
     /// # }
@@ -198,41 +697,460 @@ impl TextToSpeech<'_> {
         text: impl AsRef<str>,
         format: Option<AudioFormat>,
         customisation_id: Option<&str>,
-    ) -> Result<bytes::Bytes, SynthesisError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path("v1/synthesize");
-        url.set_query(customisation_id);
-        url.query_pairs_mut().append_pair("text", text.as_ref());
-        url.query_pairs_mut().append_pair("voice", self.voice.id());
-        if let Some(format) = format {
-            url.query_pairs_mut().append_pair("accept", &format.id());
-        }
-        let mut req = Request::new(Method::GET, url);
+    ) -> Result<Audio, SynthesisError> {
+        let mut options = SynthesisOptions::new();
+        options.format = format;
+        options.customisation_id = customisation_id.map(ToOwned::to_owned);
+        self.synthesise_with(text, &options).await
+    }
 
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
+    /// Synthesises text like [`synthesise()`], with any of the [`SynthesisOptions`]. The lengths
+    /// in the header of WAV audio are [fixed] before it is returned
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The text to synthesise, either plain text or SSML
+    /// * `options` - The [`SynthesisOptions`] to synthesise with
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     tts::{synthesis::SynthesisOptions, TextToSpeech},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// let options = SynthesisOptions::new().rate_percentage(-20);
+    /// let synth_bytes = tts.synthesise_with("Hey there", &options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`synthesise()`]: Self::synthesise()
+    /// [`SynthesisOptions`]: super::synthesis::SynthesisOptions
+    /// [fixed]: super::synthesis::fix_wav_header()
+    pub async fn synthesise_with(
+        &self,
+        text: impl AsRef<str>,
+        options: &SynthesisOptions,
+    ) -> Result<Audio, SynthesisError> {
+        self.synthesise_with_progress(text, options, |_| {}).await
+    }
+
+    /// Synthesises text like [`synthesise_with()`], calling `progress` each time a chunk of the
+    /// audio arrives, so that a long narration can show a download bar while it is synthesised
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The text to synthesise, either plain text or SSML
+    /// * `options` - The [`SynthesisOptions`] to synthesise with
+    /// * `progress` - Called with the [`Progress`] of the download after every chunk
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     tts::{synthesis::SynthesisOptions, TextToSpeech},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// let options = SynthesisOptions::new();
+    /// let audio = tts
+    ///     .synthesise_with_progress("Once upon a time", &options, |progress| {
+    ///         match progress.total_hint {
+    ///             Some(total) => println!("{} of {total} bytes", progress.bytes_received),
+    ///             None => println!("{} bytes", progress.bytes_received),
+    ///         }
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`synthesise_with()`]: Self::synthesise_with()
+    /// [`SynthesisOptions`]: super::synthesis::SynthesisOptions
+    /// [`Progress`]: super::synthesis::Progress
+    pub async fn synthesise_with_progress(
+        &self,
+        text: impl AsRef<str>,
+        options: &SynthesisOptions,
+        mut progress: impl FnMut(Progress),
+    ) -> Result<Audio, SynthesisError> {
+        let (response, rest) = self.first_response(text.as_ref(), options).await?;
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(|| options.format_or_default().id().into_owned());
+        // the length of the rest of the text's audio is not known until it is requested
+        let total_hint = response.content_length().filter(|_| rest.is_empty());
+        let mut data = Vec::new();
+        let mut audio = pin!(self.audio_chunks(response, rest, options));
+        while let Some(chunk) = audio.next().await {
+            data.extend_from_slice(&chunk?);
+            progress(Progress {
+                bytes_received: data.len() as u64,
+                total_hint,
+            });
+        }
+        if options.format_or_default().is_wav() {
+            fix_wav_header(&mut data);
         }
+        Ok(Audio {
+            content_type,
+            data: Bytes::from(data),
+        })
+    }
+
+    /// Synthesises text to raw 16-bit samples, for DSP pipelines and game engines that consume
+    /// samples rather than audio files. `options` must request [`AudioL16`] audio, at the
+    /// sample rate the samples are wanted at. The bytes are read with the requested endianness,
+    /// or the one the service reports, which is little-endian by default
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The text to synthesise, either plain text or SSML
+    /// * `options` - The [`SynthesisOptions`] to synthesise with, requesting [`AudioL16`]
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     tts::{synthesis::{AudioFormat, SynthesisOptions}, TextToSpeech},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// let format = AudioFormat::AudioL16 {
+    ///     sample_rate: 16000,
+    ///     endianness: None,
+    /// };
+    /// let options = SynthesisOptions::new().format(format);
+    /// let samples: Vec<i16> = tts.synthesise_samples("Hey there", &options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`AudioL16`]: super::synthesis::AudioFormat::AudioL16
+    /// [`SynthesisOptions`]: super::synthesis::SynthesisOptions
+    pub async fn synthesise_samples(
+        &self,
+        text: impl AsRef<str>,
+        options: &SynthesisOptions,
+    ) -> Result<Vec<i16>, SynthesisError> {
+        let format = options.format_or_default();
+        let AudioFormat::AudioL16 { endianness, .. } = format else {
+            return Err(SynthesisError::NotLinear16(format.id().into_owned()));
+        };
+        let audio = self.synthesise_with(text, options).await?;
+        let big_endian = match endianness {
+            Some(endianness) => endianness == AudioEndianness::BigEndian,
+            None => audio
+                .content_type()
+                .to_ascii_lowercase()
+                .contains("endianness=big-endian"),
+        };
+        Ok(linear16(&audio, big_endian))
+    }
+
+    /// Synthesises many texts with the same options, sending up to `max_concurrency` requests at
+    /// a time. The results are returned in the same order as `texts`, and one failing does not
+    /// stop the others from being synthesised
+    ///
+    /// # Parameters
+    ///
+    /// * `texts` - The texts to synthesise, either plain text or SSML
+    /// * `options` - The [`SynthesisOptions`] to synthesise every text with
+    /// * `max_concurrency` - The most requests to have in flight at once. `0` is treated as `1`
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     tts::{synthesis::SynthesisOptions, TextToSpeech},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// let prompts = ["Welcome", "Press one for sales", "Press two for support"];
+    /// let options = SynthesisOptions::new();
+    /// for audio in tts.synthesise_batch(prompts, &options, 4).await {
+    ///     let audio = audio?;
+    ///     println!("{} bytes", audio.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`SynthesisOptions`]: super::synthesis::SynthesisOptions
+    pub async fn synthesise_batch<T: AsRef<str>>(
+        &self,
+        texts: impl IntoIterator<Item = T>,
+        options: &SynthesisOptions,
+        max_concurrency: usize,
+    ) -> Vec<Result<Audio, SynthesisError>> {
+        stream::iter(texts)
+            .map(|text| async move { self.synthesise_with(text, options).await })
+            .buffered(max_concurrency.max(1))
+            .collect()
+            .await
+    }
 
-        let client = self.get_client();
-        let response = client
-            .execute(req)
+    /// Synthesises text like [`synthesise()`], but returns the audio as a [`Stream`] of chunks as
+    /// they arrive from the service rather than once the whole clip has been received. This lets
+    /// long passages be played or forwarded while the rest is still being synthesised. The
+    /// stream ends after the first error
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The text to synthesise, either plain text or SSML
+    /// * `options` - The [`SynthesisOptions`] to synthesise with
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     tts::{synthesis::SynthesisOptions, TextToSpeech},
+    /// # };
+    /// # use futures_util::StreamExt;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// let options = SynthesisOptions::new();
+    /// let mut audio = Box::pin(tts.synthesise_stream("Hey there", &options).await?);
+    /// while let Some(chunk) = audio.next().await {
+    ///     let chunk = chunk?;
+    ///     // play or forward the chunk
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`synthesise()`]: Self::synthesise()
+    /// [`Stream`]: futures_util::Stream
+    /// [`SynthesisOptions`]: super::synthesis::SynthesisOptions
+    pub async fn synthesise_stream(
+        &self,
+        text: impl AsRef<str>,
+        options: &SynthesisOptions,
+    ) -> Result<impl Stream<Item = Result<Bytes, SynthesisError>> + '_, SynthesisError> {
+        let (response, rest) = self.first_response(text.as_ref(), options).await?;
+        Ok(self.audio_chunks(response, rest, options))
+    }
+
+    /// Synthesises text straight to a file, writing each chunk of audio as it arrives so the
+    /// whole clip is never held in memory. If `path` has no extension, one matching the
+    /// requested format is added, such as `ogg` for the default format. An existing file is
+    /// overwritten. The lengths in the header of WAV audio are [fixed] once it has all been
+    /// written
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The text to synthesise, either plain text or SSML
+    /// * `options` - The [`SynthesisOptions`] to synthesise with
+    /// * `path` - Where to write the audio
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     tts::{synthesis::{AudioFormat, SynthesisOptions}, TextToSpeech},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// let options = SynthesisOptions::new().format(AudioFormat::AudioWav { sample_rate: None });
+    /// let path = tts.synthesise_to_file("Hey there", &options, "greeting").await?;
+    /// assert_eq!(path.to_str(), Some("greeting.wav"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`SynthesisOptions`]: super::synthesis::SynthesisOptions
+    /// [fixed]: super::synthesis::fix_wav_header()
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn synthesise_to_file(
+        &self,
+        text: impl AsRef<str>,
+        options: &SynthesisOptions,
+        path: impl AsRef<Path>,
+    ) -> Result<PathBuf, SynthesisError> {
+        use std::io::SeekFrom;
+        use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+        let mut path = path.as_ref().to_path_buf();
+        if path.extension().is_none() {
+            path.set_extension(options.format_or_default().extension());
+        }
+        let (response, rest) = self.first_response(text.as_ref(), options).await?;
+        let mut file = tokio::fs::File::create(&path)
             .await
+            .map_err(|e| SynthesisError::FileError(e.to_string()))?;
+        let mut audio = pin!(self.audio_chunks(response, rest, options));
+        // the start of the audio is kept to find the lengths in a WAV header
+        let mut header = Vec::new();
+        let mut len = 0;
+        while let Some(chunk) = audio.next().await {
+            let chunk = chunk?;
+            let wanted = wav::MAX_HEADER_BYTES.saturating_sub(header.len());
+            header.extend_from_slice(&chunk[..wanted.min(chunk.len())]);
+            len += chunk.len() as u64;
+            file.write_all(&chunk)
+                .await
+                .map_err(|e| SynthesisError::FileError(e.to_string()))?;
+        }
+        if options.format_or_default().is_wav() {
+            for (offset, length) in wav::header_lengths(&header, len).into_iter().flatten() {
+                file.seek(SeekFrom::Start(offset as u64))
+                    .await
+                    .map_err(|e| SynthesisError::FileError(e.to_string()))?;
+                file.write_all(&length.to_le_bytes())
+                    .await
+                    .map_err(|e| SynthesisError::FileError(e.to_string()))?;
+            }
+        }
+        file.flush()
+            .await
+            .map_err(|e| SynthesisError::FileError(e.to_string()))?;
+        Ok(path)
+    }
+
+    /// Sends the request for the first chunk of `text`, returning its response along with the
+    /// chunks that are still to be synthesised. The text is only split if `options` ask for it
+    async fn first_response(
+        &self,
+        text: &str,
+        options: &SynthesisOptions,
+    ) -> Result<(Response, VecDeque<String>), SynthesisError> {
+        let mut chunks = if options.split_long_text {
+            VecDeque::from(chunks::split_text(text, chunks::MAX_TEXT_BYTES))
+        } else {
+            VecDeque::from([text.to_owned()])
+        };
+        let format = options.format_or_default();
+        if chunks.len() > 1 && !format.can_concatenate() {
+            return Err(SynthesisError::UnsplittableFormat(format.id().into_owned()));
+        }
+        let first = chunks.pop_front().unwrap_or_default();
+        let response = self.synthesis_response(&first, options).await?;
+        Ok((response, chunks))
+    }
+
+    /// The audio of `response` as it arrives, followed by that of each of `rest`, which are
+    /// synthesised one at a time. The stream ends after the first error
+    fn audio_chunks(
+        &self,
+        response: Response,
+        rest: VecDeque<String>,
+        options: &SynthesisOptions,
+    ) -> impl Stream<Item = Result<Bytes, SynthesisError>> + '_ {
+        let options = options.clone();
+        let state = Some((Some(response), rest));
+        stream::unfold(state, move |state| {
+            let options = options.clone();
+            async move {
+                let (mut response, mut rest) = state?;
+                loop {
+                    if let Some(current) = response.as_mut() {
+                        match current.chunk().await {
+                            Ok(Some(chunk)) => return Some((Ok(chunk), Some((response, rest)))),
+                            Ok(None) => {}
+                            Err(e) => {
+                                return Some((
+                                    Err(SynthesisError::ConnectionError(e.to_string())),
+                                    None,
+                                ))
+                            }
+                        }
+                    }
+                    let text = rest.pop_front()?;
+                    response = match self.synthesis_response(&text, &options).await {
+                        Ok(next) => Some(next),
+                        Err(e) => return Some((Err(e), None)),
+                    };
+                }
+            }
+        })
+    }
+
+    /// Sends a synthesis request, returning the response once its status has been checked, and
+    /// that its content type matches the requested format
+    async fn synthesis_response(
+        &self,
+        text: &str,
+        options: &SynthesisOptions,
+    ) -> Result<Response, SynthesisError> {
+        #[derive(Serialize)]
+        struct Body<'a> {
+            text: &'a str,
+        }
+        let mut url = self.service.endpoint("v1/synthesize");
+        url.query_pairs_mut()
+            .append_pair("voice", options.voice_or(&self.voice).as_str());
+        options.append_query(&mut url);
+        let text = &options.styled(text);
+        let mut req = self.service.http().post(url).json(&Body { text });
+        if let Some(format) = options.format {
+            req = req.header(ACCEPT, format.id().as_ref());
+        }
+        let mut req = req
+            .build()
             .map_err(|e| SynthesisError::ConnectionError(e.to_string()))?;
-        assert_eq!(response.status(), 200);
-        match response.status() {
-            StatusCode::OK => {
-                let bytes = response.bytes().await.unwrap();
-                Ok(bytes)
-            }
-            StatusCode::NOT_ACCEPTABLE => Err(SynthesisError::NotAcceptable406),
-            StatusCode::UNSUPPORTED_MEDIA_TYPE => Err(SynthesisError::UnsupportedMediaType415),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(SynthesisError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(SynthesisError::ServiceUnavailable500),
-            StatusCode::BAD_REQUEST => Err(SynthesisError::BadRequest400),
-            StatusCode::NOT_FOUND => Err(SynthesisError::NotFound404),
-            _ => {
-                unreachable!()
+        options.apply_headers(&mut req)?;
+
+        let response = self
+            .service
+            .execute_retrying(req)
+            .await
+            .map_err(SynthesisError::ConnectionError)?;
+        if response.status() != StatusCode::OK {
+            let (status, details) = ServiceErrorDetails::read(response).await;
+            return Err(status_error(status, details));
+        }
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok());
+        if let (Some(format), Some(content_type)) = (options.format, content_type) {
+            if !format.matches(content_type) {
+                return Err(SynthesisError::ContentTypeMismatch {
+                    requested: format.id().into_owned(),
+                    received: content_type.to_owned(),
+                });
             }
         }
+        Ok(response)
+    }
+}
+
+/// Reads 16-bit samples, ignoring a trailing odd byte
+pub(crate) fn linear16(bytes: &[u8], big_endian: bool) -> Vec<i16> {
+    bytes
+        .chunks_exact(2)
+        .map(|pair| {
+            let pair = [pair[0], pair[1]];
+            if big_endian {
+                i16::from_be_bytes(pair)
+            } else {
+                i16::from_le_bytes(pair)
+            }
+        })
+        .collect()
+}
+
+/// The error for a synthesis request that failed with `status`, along with the details the
+/// service returned, if any
+pub(crate) fn status_error(
+    status: StatusCode,
+    details: Option<ServiceErrorDetails>,
+) -> SynthesisError {
+    match status {
+        StatusCode::NOT_ACCEPTABLE => SynthesisError::NotAcceptable406(details),
+        StatusCode::UNSUPPORTED_MEDIA_TYPE => SynthesisError::UnsupportedMediaType415(details),
+        StatusCode::INTERNAL_SERVER_ERROR => SynthesisError::InternalServerError500(details),
+        StatusCode::SERVICE_UNAVAILABLE => SynthesisError::ServiceUnavailable500(details),
+        StatusCode::BAD_REQUEST => SynthesisError::BadRequest400(details),
+        StatusCode::NOT_FOUND => SynthesisError::NotFound404(details),
+        StatusCode::TOO_MANY_REQUESTS => SynthesisError::TooManyRequests429(details),
+        StatusCode::UNAUTHORIZED => SynthesisError::Unauthorised401(details),
+        StatusCode::FORBIDDEN => SynthesisError::Forbidden403(details),
+        status => SynthesisError::UnexpectedStatus(status, details),
     }
 }
@@ -0,0 +1,57 @@
+/// How much of the start of a WAV file is searched for its `data` chunk
+pub(crate) const MAX_HEADER_BYTES: usize = 4096;
+
+/// Rewrites the lengths in the header of a WAV file to match the audio it holds. When the service
+/// streams WAV audio it cannot know the length of the audio up front, so the lengths in the RIFF
+/// header are left as placeholders that strict decoders refuse to play. Returns false, leaving
+/// `wav` untouched, if it does not start with a WAV header
+///
+/// [`synthesise_with()`] and [`synthesise_to_file()`] do this for you when WAV audio is
+/// requested
+///
+/// # Parameters
+///
+/// * `wav` - The whole WAV file
+///
+/// # Example
+/// ```
+/// # use ibm_watson::tts::synthesis::fix_wav_header;
+/// let mut wav = b"RIFF\xff\xff\xff\xffWAVEdata\xff\xff\xff\xff\x01\x00\x02\x00".to_vec();
+/// assert!(fix_wav_header(&mut wav));
+/// assert_eq!(wav[4..8], 16u32.to_le_bytes());
+/// assert_eq!(wav[16..20], 4u32.to_le_bytes());
+/// ```
+/// [`synthesise_with()`]: crate::tts::TextToSpeech::synthesise_with()
+/// [`synthesise_to_file()`]: crate::tts::TextToSpeech::synthesise_to_file()
+pub fn fix_wav_header(wav: &mut [u8]) -> bool {
+    let Some(lengths) = header_lengths(wav, wav.len() as u64) else {
+        return false;
+    };
+    for (offset, length) in lengths {
+        wav[offset..offset + 4].copy_from_slice(&length.to_le_bytes());
+    }
+    true
+}
+
+/// Where the RIFF and `data` chunk lengths are in `header`, the start of a WAV file that is `len`
+/// bytes long, along with the lengths they should hold
+pub(crate) fn header_lengths(header: &[u8], len: u64) -> Option<[(usize, u32); 2]> {
+    if header.len() < 12 || &header[..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+        return None;
+    }
+    let mut offset = 12;
+    while offset + 8 <= header.len() {
+        if &header[offset..offset + 4] == b"data" {
+            let data_len = len.checked_sub(offset as u64 + 8)?;
+            return Some([(4, saturate(len - 8)), (offset + 4, saturate(data_len))]);
+        }
+        let size = u32::from_le_bytes(header[offset + 4..offset + 8].try_into().ok()?) as usize;
+        // chunks are padded to an even length
+        offset = offset.checked_add(8 + size + size % 2)?;
+    }
+    None
+}
+
+fn saturate(len: u64) -> u32 {
+    u32::try_from(len).unwrap_or(u32::MAX)
+}
@@ -0,0 +1,212 @@
+use std::collections::VecDeque;
+
+use bytes::Bytes;
+use futures_util::{stream, Stream};
+use reqwest::{Method, Request};
+use serde::{Deserialize, Serialize};
+
+use super::{errors::SynthesisError, status_error, SynthesisOptions};
+use crate::{
+    auth::Authenticator,
+    errors::ServiceErrorDetails,
+    tts::TextToSpeech,
+    websocket::{Handshake, Message, WebSocket},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+/// An event received while synthesising over a WebSocket
+pub enum SynthesisEvent {
+    /// The MIME type of the audio that follows, sent before the first chunk of audio
+    ContentType(String),
+    /// A chunk of the synthesised audio
+    Audio(Bytes),
+    /// When a word is spoken in the audio
+    Word(TimingEvent),
+    /// When an SSML `<mark>` element is reached in the audio
+    Mark(MarkReached),
+    /// A warning about the request, such as an unknown parameter
+    Warning(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "(String, f64, f64)")]
+/// When a word is spoken in the synthesised audio
+pub struct TimingEvent {
+    /// The word, as it appears in the text
+    pub word: String,
+    /// When the word starts, in seconds from the start of the audio
+    pub start: f64,
+    /// When the word ends, in seconds from the start of the audio
+    pub end: f64,
+}
+
+impl From<(String, f64, f64)> for TimingEvent {
+    fn from((word, start, end): (String, f64, f64)) -> Self {
+        Self { word, start, end }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "(String, f64)")]
+/// When an SSML `<mark>` element, such as one made with [`Element::mark()`], is reached in the
+/// synthesised audio
+///
+/// [`Element::mark()`]: crate::tts::ssml::Element::mark()
+pub struct MarkReached {
+    /// The name of the mark
+    pub name: String,
+    /// When the mark is reached, in seconds from the start of the audio
+    pub time: f64,
+}
+
+impl From<(String, f64)> for MarkReached {
+    fn from((name, time): (String, f64)) -> Self {
+        Self { name, time }
+    }
+}
+
+#[derive(Deserialize)]
+struct ContentType {
+    content_type: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ServiceMessage {
+    BinaryStreams(Vec<ContentType>),
+    Words(Vec<TimingEvent>),
+    Marks(Vec<MarkReached>),
+    Warnings(String),
+    Error(String),
+}
+
+impl<A: Authenticator> TextToSpeech<'_, A> {
+    /// Synthesises text over the WebSocket interface, returning a [`Stream`] of
+    /// [`SynthesisEvent`]s. Besides the audio itself, the stream carries the timing of every word
+    /// spoken and of every SSML `<mark>` reached, which the HTTP interface cannot provide. Each
+    /// [`TimingEvent`] and [`MarkReached`] arrives soon after the audio it refers to. The stream
+    /// ends once the service has sent all of the audio, or after the first error
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The text to synthesise, either plain text or SSML
+    /// * `options` - The [`SynthesisOptions`] to synthesise with
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     tts::{synthesis::{SynthesisEvent, SynthesisOptions}, TextToSpeech},
+    /// # };
+    /// # use futures_util::StreamExt;
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// let options = SynthesisOptions::new();
+    /// let mut events = Box::pin(tts.synthesise_websocket("Hey there", &options).await?);
+    /// while let Some(event) = events.next().await {
+    ///     match event? {
+    ///         SynthesisEvent::Audio(chunk) => { /* play or forward the chunk */ }
+    ///         SynthesisEvent::Word(timing) => {
+    ///             println!("{} at {}s", timing.word, timing.start)
+    ///         }
+    ///         _ => {}
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`Stream`]: futures_util::Stream
+    /// [`SynthesisEvent`]: self::SynthesisEvent
+    /// [`TimingEvent`]: self::TimingEvent
+    /// [`MarkReached`]: self::MarkReached
+    /// [`SynthesisOptions`]: super::SynthesisOptions
+    pub async fn synthesise_websocket(
+        &self,
+        text: impl AsRef<str>,
+        options: &SynthesisOptions,
+    ) -> Result<impl Stream<Item = Result<SynthesisEvent, SynthesisError>>, SynthesisError> {
+        #[derive(Serialize)]
+        struct Body<'a> {
+            text: &'a str,
+            accept: &'a str,
+            timings: &'a [&'a str],
+        }
+        let mut url = self.service.endpoint("v1/synthesize");
+        url.query_pairs_mut()
+            .append_pair("voice", options.voice_or(&self.voice).as_str());
+        options.append_query(&mut url);
+        let mut req = Request::new(Method::GET, url);
+        options.apply_headers(&mut req)?;
+        let mut socket = match self
+            .service
+            .connect_websocket(req)
+            .await
+            .map_err(SynthesisError::ConnectionError)?
+        {
+            Handshake::Connected(socket) => socket,
+            Handshake::Rejected(response) => {
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                return Err(status_error(status, details));
+            }
+        };
+
+        let body = serde_json::to_string(&Body {
+            text: &options.styled(text.as_ref()),
+            accept: &options.format_or_default().id(),
+            timings: &["words"],
+        })
+        .map_err(|e| SynthesisError::ConnectionError(e.to_string()))?;
+        socket
+            .send_text(&body)
+            .await
+            .map_err(SynthesisError::ConnectionError)?;
+
+        let state = (Some(socket), VecDeque::new());
+        Ok(stream::unfold(
+            state,
+            |(mut socket, mut pending)| async move {
+                loop {
+                    if let Some(event) = pending.pop_front() {
+                        return Some((Ok(event), (socket, pending)));
+                    }
+                    match next_events(socket.as_mut()?).await? {
+                        Ok(events) => pending.extend(events),
+                        // nothing more is read after an error
+                        Err(e) => return Some((Err(e), (None, pending))),
+                    }
+                }
+            },
+        ))
+    }
+}
+
+/// Receives the next message and the events it makes, returning [`None`] once the service has
+/// closed the connection. A message for the words or marks of the audio makes one event for each
+async fn next_events(
+    socket: &mut WebSocket,
+) -> Option<Result<Vec<SynthesisEvent>, SynthesisError>> {
+    let text = match socket.receive().await {
+        Ok(Some(Message::Binary(audio))) => return Some(Ok(vec![SynthesisEvent::Audio(audio)])),
+        Ok(Some(Message::Text(text))) => text,
+        Ok(None) => return None,
+        Err(e) => return Some(Err(SynthesisError::ConnectionError(e))),
+    };
+    let events = match serde_json::from_str(&text) {
+        Ok(ServiceMessage::BinaryStreams(streams)) => streams
+            .into_iter()
+            .take(1)
+            .map(|stream| SynthesisEvent::ContentType(stream.content_type))
+            .collect(),
+        Ok(ServiceMessage::Words(words)) => words.into_iter().map(SynthesisEvent::Word).collect(),
+        Ok(ServiceMessage::Marks(marks)) => marks.into_iter().map(SynthesisEvent::Mark).collect(),
+        Ok(ServiceMessage::Warnings(warning)) => vec![SynthesisEvent::Warning(warning)],
+        Ok(ServiceMessage::Error(error)) => {
+            let _ = socket.close().await;
+            return Some(Err(SynthesisError::ServiceError(error)));
+        }
+        // ignore messages this version does not know about
+        Err(_) => Vec::new(),
+    };
+    Some(Ok(events))
+}
@@ -1,16 +1,30 @@
+use reqwest::StatusCode;
 use thiserror::Error;
+
+use crate::errors::{describe, ServiceErrorDetails};
+
 #[derive(Error, Debug)]
+/// Errors that may be returned when deleting the data associated with a customer ID
 pub enum DeleteLabeledDataError {
     /// There was an error establishing the connection
     #[error("{0}")]
     ConnectionError(String),
     /// The request did not pass a customer ID
-    #[error("The request did not pass a customer ID")]
-    BadRequest400,
-    #[error("The service is currently unavailable")]
+    #[error("{}", describe(.0, "The request did not pass a customer ID"))]
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable"))]
     /// The service is currently unavailable
-    ServiceUnavailable503,
-    #[error("The service experienced an internal error")]
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error"))]
     /// The service experienced an internal error
-    InternalServerError500,
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
 }
@@ -1,15 +1,22 @@
-use reqwest::{Method, Request, StatusCode, Url, Version};
+use reqwest::{Method, Request, StatusCode};
+/// Errors that may be returned when deleting user data
 pub mod errors;
 
 use self::errors::DeleteLabeledDataError;
 
 use super::TextToSpeech;
+use crate::{auth::Authenticator, errors::ServiceErrorDetails};
 
-impl TextToSpeech<'_> {
+impl<A: Authenticator> TextToSpeech<'_, A> {
     /// Deletes all data that is associated with a specified customer ID. The method deletes all data for the customer ID, regardless of the method by which the information was added. The method has no effect if no data is associated with the customer ID. You must issue the request with credentials for the same instance of the service that was used to associate the customer ID with the data
     ///
+    /// Data is associated with a customer ID by sending it in the `X-Watson-Metadata` header of
+    /// the request that added the data
+    ///
     /// # Parameters
+    ///
     /// * `customer_id` - The customer ID for which all data is to be deleted
+    ///
     /// # Example
     /// ``` no_run
     /// # use ibm_watson::{
@@ -18,7 +25,7 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// if tts.delete_labeled_data("me-id").await.is_ok() {
     ///     println!("user data deleted");
     /// }
@@ -29,28 +36,34 @@ impl TextToSpeech<'_> {
         &self,
         customer_id: impl AsRef<str>,
     ) -> Result<(), DeleteLabeledDataError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        url.set_path(&format!("v1/user_data/{}", customer_id.as_ref()));
-        let mut req = Request::new(Method::DELETE, url);
+        let mut url = self.service.endpoint("v1/user_data");
+        url.query_pairs_mut()
+            .append_pair("customer_id", customer_id.as_ref());
+        let req = Request::new(Method::DELETE, url);
 
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
-        }
-
-        let client = self.get_client();
-        let response = client
+        let response = self
+            .service
             .execute(req)
             .await
-            .map_err(|e| DeleteLabeledDataError::ConnectionError(e.to_string()))?;
+            .map_err(DeleteLabeledDataError::ConnectionError)?;
         match response.status() {
             StatusCode::NO_CONTENT => Ok(()),
-            StatusCode::BAD_REQUEST => Err(DeleteLabeledDataError::BadRequest400),
-            StatusCode::INTERNAL_SERVER_ERROR => {
-                Err(DeleteLabeledDataError::InternalServerError500)
-            }
-            StatusCode::SERVICE_UNAVAILABLE => Err(DeleteLabeledDataError::ServiceUnavailable503),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::BAD_REQUEST => Err(DeleteLabeledDataError::BadRequest400(details)),
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(DeleteLabeledDataError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(DeleteLabeledDataError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => {
+                        Err(DeleteLabeledDataError::Unauthorised401(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(DeleteLabeledDataError::Forbidden403(details)),
+                    status => Err(DeleteLabeledDataError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
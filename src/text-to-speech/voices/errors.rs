@@ -1,22 +1,43 @@
+use reqwest::StatusCode;
 use thiserror::Error;
 
+use crate::errors::{describe, ServiceErrorDetails};
+
 #[derive(Error, Debug)]
 /// Errors that may be returned when listing [`Watson Voices`]
 ///
 /// [`Watson Voices`]: crate::tts::voices::WatsonVoice
 pub enum ListVoicesError {
-    #[error("The request specified an Accept header with an incompatible content type.")]
+    #[error("The voices have not changed since they were fetched with the given entity tag")]
+    /// The voices have not changed since they were fetched with the entity tag passed to
+    /// [`list_voices_if_changed()`]
+    ///
+    /// [`list_voices_if_changed()`]: crate::tts::TextToSpeech::list_voices_if_changed()
+    NotModified304,
+    #[error("{}", describe(.0, "The service did not accept the credentials"))]
+    /// The service did not accept the credentials, which may have expired or been revoked
+    Unauthorised401(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The request specified an Accept header with an incompatible content type."))]
     /// The request specified an Accept header with an incompatible content type
-    NotAcceptable406, //406
-    #[error("The request specified an unacceptable media type.")]
+    NotAcceptable406(Option<ServiceErrorDetails>), //406
+    #[error("{}", describe(.0, "The request specified an unacceptable media type."))]
     /// The request specified an unacceptable media type
-    UnsupportedMediaType415, // 415
-    #[error("The service experienced an internal error.")]
+    UnsupportedMediaType415(Option<ServiceErrorDetails>), // 415
+    #[error("{}", describe(.0, "Too many requests have been sent to the service"))]
+    /// The service throttled the request, and it was not retried any further
+    TooManyRequests429(Option<ServiceErrorDetails>), // 429
+    #[error("{}", describe(.0, "The service experienced an internal error."))]
     /// The service experienced an internal error
-    InternalServerError500, // 500
-    #[error("The service is currently unavailable.")]
+    InternalServerError500(Option<ServiceErrorDetails>), // 500
+    #[error("{}", describe(.0, "The service is currently unavailable."))]
     /// The service is currently unavailable
-    ServiceUnavailable503, // 503
+    ServiceUnavailable503(Option<ServiceErrorDetails>), // 503
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
     #[error("{0}")]
     /// There was an error making the request
     ConnectionError(String),
@@ -30,24 +51,33 @@ pub enum GetVoiceError {
     #[error("The requested resource has not been modified since the time specified by the If-Modified-Since header, as documented in the HTTP specification")]
     /// The requested resource has not been modified since the time specified by the If-Modified-Since header, as documented in the HTTP specification
     NotModified304,
-    #[error("A required input parameter is null or a specified input parameter or header value is invalid or not supported. Please check your customisation id")]
+    #[error("{}", describe(.0, "A required input parameter is null or a specified input parameter or header value is invalid or not supported. Please check your customisation id"))]
     /// A required input parameter is null or a specified input parameter or header value is invalid or not supported. Please check your customisation id
-    BadRequest400,
-    #[error("The specified customisation_id {0} is invalid for the requesting credentials")]
+    BadRequest400(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The specified customisation_id {} is invalid for the requesting credentials", .0)))]
     /// The specified customisation_id is invalid for the requesting credentials
-    Unauthorised401(String),
-    #[error("The request specified an Accept header with an incompatible content type.")]
+    Unauthorised401(String, Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The credentials are not allowed to make this request"))]
+    /// The credentials are valid, but are not allowed to make this request
+    Forbidden403(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The request specified an Accept header with an incompatible content type."))]
     /// The request specified an Accept header with an incompatible content type
-    NotAcceptable406,
-    #[error("The request specified an unacceptable media type.")]
+    NotAcceptable406(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The request specified an unacceptable media type."))]
     /// The request specified an unacceptable media type
-    UnsupportedMediaType415,
-    #[error("The service experienced an internal error.")]
+    UnsupportedMediaType415(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "Too many requests have been sent to the service"))]
+    /// The service throttled the request, and it was not retried any further
+    TooManyRequests429(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service experienced an internal error."))]
     /// The service experienced an internal error
-    InternalServerError500,
-    #[error("The service is currently unavailable.")]
+    InternalServerError500(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.0, "The service is currently unavailable."))]
     /// The service is currently unavailable
-    ServiceUnavailable503,
+    ServiceUnavailable503(Option<ServiceErrorDetails>),
+    #[error("{}", describe(.1, &format!("The service responded with an unexpected status {}", .0)))]
+    /// The service responded with a status that is not otherwise handled
+    UnexpectedStatus(StatusCode, Option<ServiceErrorDetails>),
     #[error("{0}")]
     /// There was an error making the request
     ConnectionError(String),
@@ -1,4 +1,9 @@
-use reqwest::{Method, Request, StatusCode, Url, Version};
+use std::borrow::Cow;
+
+use reqwest::{
+    header::{HeaderValue, ETAG, IF_NONE_MATCH},
+    Method, Request, Response, StatusCode,
+};
 use serde::{Deserialize, Serialize};
 
 /// Errors that may be returned in making Voice requests
@@ -8,7 +13,11 @@ use crate::tts::voices::errors::GetVoiceError;
 
 use self::errors::ListVoicesError;
 
-use super::{customisations::Model, TextToSpeech};
+use super::{
+    customisations::{CustomModel, Language},
+    TextToSpeech,
+};
+use crate::{auth::Authenticator, errors::ServiceErrorDetails};
 #[derive(Clone, Debug, Eq, PartialEq, Default, Serialize, Deserialize)]
 /// Voices available for use in Watson
 pub struct Voice {
@@ -30,12 +39,39 @@ pub struct Voice {
     #[serde(rename = "customizable")]
     /// If true, the voice can be customised; if false, the voice cannot be customised. (Same as custom_pronunciation; maintained for backward compatibility.)
     pub customisable: bool,
-    #[serde(rename = "supported_features")]
-    /// Additional service [features](SupportedFeatures) that are supported with the voice
+    #[serde(rename = "supported_features", default)]
+    /// Additional service [features](SupportedFeatures) that are supported with the voice. A
+    /// voice that is returned without them supports none
     pub supported_features: Box<SupportedFeatures>,
     #[serde(rename = "customization", skip_serializing_if = "Option::is_none")]
-    /// Returns information about a specified custom [model](super::customisations::Model). This field is returned only by the Get a voice method and only when you specify the customisation ID of a custom model
-    pub customisation: Option<Box<Model>>,
+    /// Returns information about a specified custom [model](super::customisations::CustomModel). This field is returned only by the Get a voice method and only when you specify the customisation ID of a custom model
+    pub customisation: Option<Box<CustomModel>>,
+}
+
+impl Voice {
+    /// Whether custom models, such as those made with [`create_custom_model()`], can be used
+    /// with the voice. Check this before synthesising with a customisation ID, which the service
+    /// rejects for voices that cannot be customised
+    ///
+    /// [`create_custom_model()`]: crate::tts::TextToSpeech::create_custom_model()
+    pub fn supports_custom_models(&self) -> bool {
+        self.customisable || self.supported_features.custom_pronunciation
+    }
+
+    /// Whether the voice can be changed with the SSML `<voice-transformation>` element
+    pub fn supports_voice_transformation(&self) -> bool {
+        self.supported_features.voice_transformation
+    }
+
+    /// Whether the voice can speak in the styles of [`Element::express_as()`] and
+    /// [`SynthesisOptions::speaking_style()`]. The service names its expressive voices with an
+    /// `Expressive` suffix, such as `en-US_EmmaExpressive`
+    ///
+    /// [`Element::express_as()`]: crate::tts::ssml::Element::express_as()
+    /// [`SynthesisOptions::speaking_style()`]: crate::tts::synthesis::SynthesisOptions::speaking_style()
+    pub fn is_expressive(&self) -> bool {
+        self.name.ends_with("Expressive")
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Default, Serialize, Deserialize)]
@@ -44,11 +80,23 @@ pub struct SupportedFeatures {
     /// If true, the voice can be customised; if false, the voice cannot be customised. (Same as customisable.)
     #[serde(rename = "custom_pronunciation")]
     pub custom_pronunciation: bool,
-    /// If true, the voice can be transformed by using the SSML <voice-transformation> element; if false, the voice cannot be transformed. The feature was available only for the now-deprecated standard voices. You cannot use the feature with neural voices.
+    /// If true, the voice can be transformed by using the SSML `<voice-transformation>` element; if false, the voice cannot be transformed. The feature was available only for the now-deprecated standard voices. You cannot use the feature with neural voices.
     #[serde(rename = "voice_transformation")]
     pub voice_transformation: bool,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Information returned by the service, along with the entity tag that identifies its version
+pub struct Tagged<T> {
+    /// The information
+    pub value: T,
+    /// Identifies this version of the information. Pass it to a later request, such as
+    /// [`list_voices_if_changed()`], to only fetch the information again if it has changed
+    ///
+    /// [`list_voices_if_changed()`]: crate::tts::TextToSpeech::list_voices_if_changed()
+    pub etag: Option<String>,
+}
+
 #[derive(Default)]
 #[non_exhaustive]
 /// All voices that Watson can use
@@ -75,16 +123,24 @@ pub enum WatsonVoice {
     EnGbJamesV3,
     /// KateV3 - English (United Kingdom)
     EnGbKateV3,
+    /// AllisonExpressive - English (United States)
+    EnUsAllisonExpressive,
     /// AllisonV3 - English (United States)
     EnUsAllisonV3,
     /// EmilyV3 - English (United States)
     EnUsEmilyV3,
+    /// EmmaExpressive - English (United States)
+    EnUsEmmaExpressive,
     /// HenryV3 - English (United States)
     EnUsHenryV3,
     /// KevinV3 - English (United States)
     EnUsKevinV3,
+    /// LisaExpressive - English (United States)
+    EnUsLisaExpressive,
     /// LisaV3 - English (United States)
     EnUsLisaV3,
+    /// MichaelExpressive - English (United States)
+    EnUsMichaelExpressive,
     #[default]
     /// MichaelV3 - English (United States)
     EnUsMichaelV3,
@@ -136,10 +192,10 @@ pub enum WatsonVoice {
     ZhCnZhangJing,
 }
 
-impl ToString for WatsonVoice {
+impl std::fmt::Display for WatsonVoice {
     /// The human readable format for Watson Voices
-    fn to_string(&self) -> String {
-        match &self {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match &self {
             WatsonVoice::ArMsOmar => "ArMsOmar",
             WatsonVoice::CsCzAlena => "Alena - Czech (Czechia)",
             WatsonVoice::DeDeBirgitV3 => "Birgit - German (Germany)",
@@ -151,11 +207,15 @@ impl ToString for WatsonVoice {
             WatsonVoice::EnGbCharlotteV3 => "CharlotteV3 - English (United Kingdom)",
             WatsonVoice::EnGbJamesV3 => "JamesV3 - English (United Kingdom)",
             WatsonVoice::EnGbKateV3 => "KateV3 - English (United Kingdom)",
+            WatsonVoice::EnUsAllisonExpressive => "AllisonExpressive - English (United States)",
             WatsonVoice::EnUsAllisonV3 => "AllisonV3 - English (United States)",
             WatsonVoice::EnUsEmilyV3 => "EmilyV3 - English (United States)",
+            WatsonVoice::EnUsEmmaExpressive => "EmmaExpressive - English (United States)",
             WatsonVoice::EnUsHenryV3 => "HenryV3 - English (United States)",
             WatsonVoice::EnUsKevinV3 => "KevinV3 - English (United States)",
+            WatsonVoice::EnUsLisaExpressive => "LisaExpressive - English (United States)",
             WatsonVoice::EnUsLisaV3 => "LisaV3 - English (United States)",
+            WatsonVoice::EnUsMichaelExpressive => "MichaelExpressive - English (United States)",
             WatsonVoice::EnUsMichaelV3 => "MichaelV3 - English (United States)",
             WatsonVoice::EnUsOliviaV3 => "OliviaV3 - English (United States)",
             WatsonVoice::EsEsEnriqueV3 => "EnriqueV3 - Spanish (Spain)",
@@ -180,14 +240,14 @@ impl ToString for WatsonVoice {
             WatsonVoice::ZhCnLiNa => "LiNa - Chinese (PRC)",
             WatsonVoice::ZhCnWangWei => "WangWei - Chinese (PRC)",
             WatsonVoice::ZhCnZhangJing => "ZhangJing - Chinese (PRC)",
-        }
-        .to_string()
+        };
+        write!(f, "{name}")
     }
 }
 
 impl WatsonVoice {
     /// The id that the server expects for that voice
-    pub fn id(&self) -> &str {
+    pub fn id(&self) -> &'static str {
         match &self {
             WatsonVoice::ArMsOmar => "ar-MS_OmarVoice",
             WatsonVoice::CsCzAlena => "cs-CZ_AlenaVoice",
@@ -200,11 +260,15 @@ impl WatsonVoice {
             WatsonVoice::EnGbCharlotteV3 => "en-GB_CharlotteV3Voice",
             WatsonVoice::EnGbJamesV3 => "en-GB_JamesV3Voice",
             WatsonVoice::EnGbKateV3 => "en-GB_KateV3Voice",
+            WatsonVoice::EnUsAllisonExpressive => "en-US_AllisonExpressive",
             WatsonVoice::EnUsAllisonV3 => "en-US_AllisonV3Voice",
             WatsonVoice::EnUsEmilyV3 => "en-US_EmilyV3Voice",
+            WatsonVoice::EnUsEmmaExpressive => "en-US_EmmaExpressive",
             WatsonVoice::EnUsHenryV3 => "en-US_HenryV3Voice",
             WatsonVoice::EnUsKevinV3 => "en-US_KevinV3Voice",
+            WatsonVoice::EnUsLisaExpressive => "en-US_LisaExpressive",
             WatsonVoice::EnUsLisaV3 => "en-US_LisaV3Voice",
+            WatsonVoice::EnUsMichaelExpressive => "en-US_MichaelExpressive",
             WatsonVoice::EnUsMichaelV3 => "en-US_MichaelV3Voice",
             WatsonVoice::EnUsOliviaV3 => "en-US_OliviaV3Voice",
             WatsonVoice::EsEsEnriqueV3 => "es-ES_EnriqueV3Voice",
@@ -231,9 +295,92 @@ impl WatsonVoice {
             WatsonVoice::ZhCnZhangJing => "zh-CN_ZhangJingVoice",
         }
     }
+
+    /// Whether the voice can speak in the styles of [`Element::express_as()`] and
+    /// [`SynthesisOptions::speaking_style()`]
+    ///
+    /// [`Element::express_as()`]: crate::tts::ssml::Element::express_as()
+    /// [`SynthesisOptions::speaking_style()`]: crate::tts::synthesis::SynthesisOptions::speaking_style()
+    pub fn is_expressive(&self) -> bool {
+        matches!(
+            self,
+            WatsonVoice::EnUsAllisonExpressive
+                | WatsonVoice::EnUsEmmaExpressive
+                | WatsonVoice::EnUsLisaExpressive
+                | WatsonVoice::EnUsMichaelExpressive
+        )
+    }
 }
 
-impl TextToSpeech<'_> {
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// The ID of a voice, such as `en-US_AllisonExpressive`. Any ID the service knows can be used,
+/// including voices added since this version of the crate, and every [`WatsonVoice`] converts
+/// into one
+///
+/// # Example
+/// ```
+/// # use ibm_watson::tts::voices::{VoiceId, WatsonVoice};
+/// let michael = VoiceId::from(WatsonVoice::EnUsMichaelV3);
+/// assert_eq!(michael.as_str(), "en-US_MichaelV3Voice");
+/// let expressive = VoiceId::new("en-US_AllisonExpressive");
+/// assert_eq!(expressive.to_string(), "en-US_AllisonExpressive");
+/// ```
+/// [`WatsonVoice`]: self::WatsonVoice
+pub struct VoiceId(Cow<'static, str>);
+
+impl VoiceId {
+    /// A voice with the given ID, as listed by [`list_voices()`]
+    ///
+    /// # Parameters
+    ///
+    /// * `id` - The [`name`] of the voice
+    ///
+    /// [`list_voices()`]: crate::tts::TextToSpeech::list_voices()
+    /// [`name`]: self::Voice::name
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(Cow::Owned(id.into()))
+    }
+
+    /// The ID as the server expects it
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for VoiceId {
+    /// The ID of the [`default`] voice
+    ///
+    /// [`default`]: self::WatsonVoice::EnUsMichaelV3
+    fn default() -> Self {
+        WatsonVoice::default().into()
+    }
+}
+
+impl std::fmt::Display for VoiceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<WatsonVoice> for VoiceId {
+    fn from(voice: WatsonVoice) -> Self {
+        Self(Cow::Borrowed(voice.id()))
+    }
+}
+
+impl From<String> for VoiceId {
+    fn from(id: String) -> Self {
+        Self(Cow::Owned(id))
+    }
+}
+
+impl From<&str> for VoiceId {
+    fn from(id: &str) -> Self {
+        Self::new(id)
+    }
+}
+
+impl<A: Authenticator> TextToSpeech<'_, A> {
     /// Lists all voices available for use with the service. The information includes the [`name`], [`language`], [`gender`], and other details about the voice.
     /// The ordering of the list of voices can
     /// change from call to call; do not rely on an alphabetized or static list of voices. To see
@@ -247,7 +394,7 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// let voices = tts.list_voices().await?;
     /// println!("Total: {}", voices.len());
     /// # Ok(())
@@ -257,51 +404,163 @@ impl TextToSpeech<'_> {
     /// [`language`]: super::voices::Voice::language
     /// [`gender`]: super::voices::Voice::gender
     pub async fn list_voices(&self) -> Result<Vec<Voice>, ListVoicesError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        Self::set_voices_path(&mut url);
-        let mut req = Request::new(Method::GET, url);
+        self.list_voices_if_changed(None)
+            .await
+            .map(|voices| voices.value)
+    }
 
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
-        }
+    /// Lists all voices like [`list_voices()`], unless they have not changed since they were
+    /// fetched with the entity tag `etag`. Store the [`etag`] of the result and pass it to the
+    /// next call to save fetching voices that have not changed
+    ///
+    /// # Parameters
+    ///
+    /// * `etag` - The entity tag returned with the voices fetched previously, if any
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     tts::{voices::errors::ListVoicesError, TextToSpeech},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// let mut voices = tts.list_voices_if_changed(None).await?;
+    /// match tts.list_voices_if_changed(voices.etag.as_deref()).await {
+    ///     Ok(changed) => voices = changed,
+    ///     Err(ListVoicesError::NotModified304) => println!("no new voices"),
+    ///     Err(e) => return Err(e.into()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`list_voices()`]: Self::list_voices()
+    /// [`etag`]: self::Tagged::etag
+    pub async fn list_voices_if_changed(
+        &self,
+        etag: Option<&str>,
+    ) -> Result<Tagged<Vec<Voice>>, ListVoicesError> {
+        let url = self.service.endpoint("v1/voices");
+        let mut req = Request::new(Method::GET, url);
+        if_none_match(&mut req, etag).map_err(ListVoicesError::ConnectionError)?;
 
-        let client = self.get_client();
-        let response = client
-            .execute(req)
+        let response = self
+            .service
+            .execute_retrying(req)
             .await
-            .map_err(|e| ListVoicesError::ConnectionError(e.to_string()))?;
+            .map_err(ListVoicesError::ConnectionError)?;
         match response.status() {
             StatusCode::OK => {
                 #[derive(Deserialize)]
                 struct Root {
                     voices: Vec<Voice>,
                 }
-                let root: Root = response.json().await.unwrap();
-
-                Ok(root.voices)
+                let etag = entity_tag(&response);
+                let root: Root = response
+                    .json()
+                    .await
+                    .map_err(|e| ListVoicesError::ConnectionError(e.to_string()))?;
+                Ok(Tagged {
+                    value: root.voices,
+                    etag,
+                })
             }
-            StatusCode::NOT_ACCEPTABLE => Err(ListVoicesError::NotAcceptable406),
-            StatusCode::UNSUPPORTED_MEDIA_TYPE => Err(ListVoicesError::UnsupportedMediaType415),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(ListVoicesError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(ListVoicesError::ServiceUnavailable503),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::NOT_MODIFIED => Err(ListVoicesError::NotModified304),
+                    StatusCode::NOT_ACCEPTABLE => Err(ListVoicesError::NotAcceptable406(details)),
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE => {
+                        Err(ListVoicesError::UnsupportedMediaType415(details))
+                    }
+                    StatusCode::TOO_MANY_REQUESTS => {
+                        Err(ListVoicesError::TooManyRequests429(details))
+                    }
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(ListVoicesError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(ListVoicesError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::UNAUTHORIZED => Err(ListVoicesError::Unauthorised401(details)),
+                    StatusCode::FORBIDDEN => Err(ListVoicesError::Forbidden403(details)),
+                    status => Err(ListVoicesError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
 
-    fn set_voices_path(uri: &mut Url) {
-        uri.set_path("v1/voices");
+    /// Lists the voices available for `language`, as [`list_voices()`] would. The service has no
+    /// way to filter voices itself, so all of them are still fetched
+    ///
+    /// # Parameters
+    ///
+    /// * `language` - The [`Language`] whose voices are wanted
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     tts::{customisations::Language, TextToSpeech},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// for voice in tts.list_voices_for_language(Language::EnGb).await? {
+    ///     println!("{}: {}", voice.name, voice.description);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`list_voices()`]: Self::list_voices()
+    /// [`Language`]: super::customisations::Language
+    pub async fn list_voices_for_language(
+        &self,
+        language: Language,
+    ) -> Result<Vec<Voice>, ListVoicesError> {
+        let language = language.id();
+        let mut voices = self.list_voices().await?;
+        voices.retain(|voice| voice.language.eq_ignore_ascii_case(&language));
+        Ok(voices)
+    }
+
+    /// Lists the expressive voices, which can speak in the styles of [`Element::express_as()`]
+    /// and [`SynthesisOptions::speaking_style()`], as [`list_voices()`] would
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     tts::TextToSpeech,
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// for voice in tts.list_expressive_voices().await? {
+    ///     println!("{}: {}", voice.name, voice.description);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`list_voices()`]: Self::list_voices()
+    /// [`Element::express_as()`]: crate::tts::ssml::Element::express_as()
+    /// [`SynthesisOptions::speaking_style()`]: crate::tts::synthesis::SynthesisOptions::speaking_style()
+    pub async fn list_expressive_voices(&self) -> Result<Vec<Voice>, ListVoicesError> {
+        let mut voices = self.list_voices().await?;
+        voices.retain(Voice::is_expressive);
+        Ok(voices)
     }
 
     /// Returns information about the specified [`Voice`]. The information includes the [`name`], [`language`], [`gender`], and other details about the voice. Specify a customisation ID to obtain information for a custom model that is defined for the language of the specified voice. To list information about all available voices, use  [list_voices()](`Self::list_voices()`)
     ///
     /// # Parameters
     ///
-    /// * `voice` - The particular [`WatsonVoice`] you want information about
+    /// * `voice` - The particular voice you want information about, either a [`WatsonVoice`] or a [`VoiceId`] for any voice the service offers
     /// * `customisation_id` - The customisation ID (GUID) of a custom model for which information is to be returned. You must make the request with credentials for the instance of the service that owns the custom model. Omit the parameter to see information about the specified voice with no customisation
     ///
     /// [`WatsonVoice`]: super::voices::WatsonVoice
+    /// [`VoiceId`]: super::voices::VoiceId
     /// [`Voice`]: super::voices::Voice
     /// [`name`]: super::voices::Voice::name
     /// [`language`]: super::voices::Voice::language
@@ -315,47 +574,124 @@ impl TextToSpeech<'_> {
     /// # };
     /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
     /// # let auth = IamAuthenticator::new("api_key").await?;
-    /// # let tts = TextToSpeech::new(&auth, "service_url");
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
     /// let kate = tts.get_voice(WatsonVoice::EnGbKateV3, None).await?;
     /// println!("Gender: {}", kate.gender);
     /// # Ok(())
     /// # }
     /// ```
-
     pub async fn get_voice(
         &self,
-        voice: WatsonVoice,
+        voice: impl Into<VoiceId>,
         customisation_id: Option<&str>,
     ) -> Result<Voice, GetVoiceError> {
-        let mut url = Url::parse(self.service_url).unwrap();
-        let id = voice.id();
-        Self::set_voices_path(&mut url);
-        let mut url = Url::parse(&format!("{}/{}", url, id)).unwrap();
-        url.set_query(customisation_id);
-        let mut req = Request::new(Method::GET, url);
+        self.get_voice_if_changed(voice, customisation_id, None)
+            .await
+            .map(|voice| voice.value)
+    }
 
-        if cfg!(feature = "http2") {
-            *req.version_mut() = Version::HTTP_2;
+    /// Returns information about the specified voice like [`get_voice()`], unless it has not
+    /// changed since it was fetched with the entity tag `etag`
+    ///
+    /// # Parameters
+    ///
+    /// * `voice` - The particular voice you want information about
+    /// * `customisation_id` - The customisation ID (GUID) of a custom model for which information is to be returned
+    /// * `etag` - The entity tag returned with the voice fetched previously, if any
+    ///
+    /// # Example
+    /// ``` no_run
+    /// # use ibm_watson::{
+    /// #     auth::IamAuthenticator,
+    /// #     tts::{voices::{errors::GetVoiceError, WatsonVoice}, TextToSpeech},
+    /// # };
+    /// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+    /// # let auth = IamAuthenticator::new("api_key").await?;
+    /// # let tts = TextToSpeech::new(&auth, "service_url")?;
+    /// let kate = tts.get_voice_if_changed(WatsonVoice::EnGbKateV3, None, None).await?;
+    /// let etag = kate.etag.as_deref();
+    /// match tts.get_voice_if_changed(WatsonVoice::EnGbKateV3, None, etag).await {
+    ///     Ok(kate) => println!("Gender: {}", kate.value.gender),
+    ///     Err(GetVoiceError::NotModified304) => println!("unchanged"),
+    ///     Err(e) => return Err(e.into()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// [`get_voice()`]: Self::get_voice()
+    pub async fn get_voice_if_changed(
+        &self,
+        voice: impl Into<VoiceId>,
+        customisation_id: Option<&str>,
+        etag: Option<&str>,
+    ) -> Result<Tagged<Voice>, GetVoiceError> {
+        let mut url = self.service.endpoint("v1/voices");
+        url.path_segments_mut().unwrap().push(voice.into().as_str());
+        if let Some(customisation_id) = customisation_id {
+            url.query_pairs_mut()
+                .append_pair("customization_id", customisation_id);
         }
-        let client = self.get_client();
-        let response = client
-            .execute(req)
+        let mut req = Request::new(Method::GET, url);
+        if_none_match(&mut req, etag).map_err(GetVoiceError::ConnectionError)?;
+        let response = self
+            .service
+            .execute_retrying(req)
             .await
-            .map_err(|e| GetVoiceError::ConnectionError(e.to_string()))?;
-        assert_eq!(response.status(), 200);
+            .map_err(GetVoiceError::ConnectionError)?;
         match response.status() {
             StatusCode::OK => {
-                let root: Voice = response.json().await.unwrap();
-
-                Ok(root)
+                let etag = entity_tag(&response);
+                let value = response
+                    .json()
+                    .await
+                    .map_err(|e| GetVoiceError::ConnectionError(e.to_string()))?;
+                Ok(Tagged { value, etag })
             }
-            StatusCode::NOT_ACCEPTABLE => Err(GetVoiceError::NotAcceptable406),
-            StatusCode::UNSUPPORTED_MEDIA_TYPE => Err(GetVoiceError::UnsupportedMediaType415),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(GetVoiceError::InternalServerError500),
-            StatusCode::SERVICE_UNAVAILABLE => Err(GetVoiceError::ServiceUnavailable503),
             _ => {
-                unreachable!()
+                let (status, details) = ServiceErrorDetails::read(response).await;
+                match status {
+                    StatusCode::NOT_MODIFIED => Err(GetVoiceError::NotModified304),
+                    StatusCode::BAD_REQUEST => Err(GetVoiceError::BadRequest400(details)),
+                    StatusCode::UNAUTHORIZED => Err(GetVoiceError::Unauthorised401(
+                        customisation_id.unwrap_or_default().to_owned(),
+                        details,
+                    )),
+                    StatusCode::NOT_ACCEPTABLE => Err(GetVoiceError::NotAcceptable406(details)),
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE => {
+                        Err(GetVoiceError::UnsupportedMediaType415(details))
+                    }
+                    StatusCode::TOO_MANY_REQUESTS => {
+                        Err(GetVoiceError::TooManyRequests429(details))
+                    }
+                    StatusCode::INTERNAL_SERVER_ERROR => {
+                        Err(GetVoiceError::InternalServerError500(details))
+                    }
+                    StatusCode::SERVICE_UNAVAILABLE => {
+                        Err(GetVoiceError::ServiceUnavailable503(details))
+                    }
+                    StatusCode::FORBIDDEN => Err(GetVoiceError::Forbidden403(details)),
+                    status => Err(GetVoiceError::UnexpectedStatus(status, details)),
+                }
             }
         }
     }
 }
+
+/// Makes `req` conditional on its resource having changed since it was fetched with `etag`
+fn if_none_match(req: &mut Request, etag: Option<&str>) -> Result<(), String> {
+    if let Some(etag) = etag {
+        let value =
+            HeaderValue::from_str(etag).map_err(|_| format!("invalid entity tag {etag:?}"))?;
+        req.headers_mut().insert(IF_NONE_MATCH, value);
+    }
+    Ok(())
+}
+
+/// The entity tag the service sent with `response`, if any
+fn entity_tag(response: &Response) -> Option<String> {
+    response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(ToOwned::to_owned)
+}
@@ -0,0 +1,115 @@
+use std::{fmt::Debug, future::Future, pin::Pin};
+
+use bytes::Bytes;
+use reqwest::{header::CONTENT_TYPE, Client, Request, Response, StatusCode};
+
+/// The future returned by [`Transport::send()`]
+///
+/// [`Transport::send()`]: self::Transport::send()
+pub type TransportFuture<'a> = Pin<
+    Box<
+        dyn Future<Output = Result<Response, Box<dyn std::error::Error + Send + Sync>>> + Send + 'a,
+    >,
+>;
+
+/// Sends the HTTP requests of a service client, such as [`TextToSpeech`]. Requests reach the
+/// transport once every header, including `Authorization`, has been added, so a transport sees
+/// exactly what would be sent to IBM. By default requests are sent with a [`reqwest::Client`];
+/// a [`MockTransport`] answers them without a network, for tests
+///
+/// The handshakes of WebSocket connections, such as those of [`synthesise_websocket()`], are
+/// sent through the transport too. A transport that rejects the handshake has its response
+/// reported as the connection's error; one that accepts it must return a `101 Switching
+/// Protocols` response that can be upgraded, as a [`reqwest::Client`] does
+///
+/// [`TextToSpeech`]: crate::tts::TextToSpeech
+/// [`reqwest::Client`]: reqwest::Client
+/// [`MockTransport`]: self::MockTransport
+/// [`synthesise_websocket()`]: crate::tts::TextToSpeech::synthesise_websocket()
+pub trait Transport: Send + Sync {
+    /// Sends `request`, returning the service's response
+    fn send(&self, request: Request) -> TransportFuture<'_>;
+}
+
+impl Transport for Client {
+    fn send(&self, request: Request) -> TransportFuture<'_> {
+        Box::pin(async move { Ok(self.execute(request).await?) })
+    }
+}
+
+/// A [`Transport`] that answers every request with a function instead of sending it, so code
+/// that uses a service client can be tested without IBM
+///
+/// # Example
+/// ```
+/// # #[cfg(feature = "tts")]
+/// # use ibm_watson::{
+/// #     auth::NoAuthAuthenticator,
+/// #     transport::MockTransport,
+/// #     tts::TextToSpeech,
+/// # };
+/// # use reqwest::StatusCode;
+/// # #[cfg(feature = "tts")]
+/// # async fn foo()-> Result<(), Box<dyn std::error::Error>> {
+/// let mock = MockTransport::new(|request| {
+///     assert_eq!(request.url().path(), "/v1/synthesize");
+///     MockTransport::response(StatusCode::OK, "audio/ogg;codecs=opus", "OggS")
+/// });
+/// let auth = NoAuthAuthenticator;
+/// let tts = TextToSpeech::builder(&auth)
+///     .service_url("https://example.com")
+///     .transport(mock)
+///     .build()?;
+/// let audio = tts.synthesise("Hey there", None, None).await?;
+/// assert_eq!(&audio[..], b"OggS");
+/// # Ok(())
+/// # }
+/// ```
+/// [`Transport`]: self::Transport
+pub struct MockTransport {
+    handler: Box<dyn Fn(Request) -> Response + Send + Sync>,
+}
+
+impl MockTransport {
+    /// Create a transport that answers each request with `handler`
+    ///
+    /// # Parameters
+    ///
+    /// * `handler` - Returns the response to a request, such as one made with [`response()`]
+    ///
+    /// [`response()`]: Self::response()
+    pub fn new(handler: impl Fn(Request) -> Response + Send + Sync + 'static) -> Self {
+        Self {
+            handler: Box::new(handler),
+        }
+    }
+
+    /// A response with `status`, and `body` of the MIME type `content_type`
+    ///
+    /// # Parameters
+    ///
+    /// * `status` - The status of the response
+    /// * `content_type` - The `Content-Type` of the body, such as `application/json`
+    /// * `body` - The body of the response
+    pub fn response(status: StatusCode, content_type: &str, body: impl Into<Bytes>) -> Response {
+        let response = http::Response::builder()
+            .status(status)
+            .header(CONTENT_TYPE, content_type)
+            .body(body.into())
+            .expect("a status and content type make a valid response");
+        Response::from(response)
+    }
+}
+
+impl Transport for MockTransport {
+    fn send(&self, request: Request) -> TransportFuture<'_> {
+        let response = (self.handler)(request);
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+impl Debug for MockTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockTransport").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,282 @@
+use std::sync::Arc;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::Bytes;
+use reqwest::{
+    header::{
+        HeaderValue, CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, SEC_WEBSOCKET_VERSION,
+        UPGRADE,
+    },
+    Request, Response, StatusCode, Upgraded,
+};
+use ring::rand::{SecureRandom, SystemRandom};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt, ReadHalf, WriteHalf},
+    sync::Mutex,
+};
+
+/// Appended to the `Sec-WebSocket-Key` before hashing it, as defined by RFC 6455
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// The largest message accepted from the service
+const MAX_MESSAGE_SIZE: u64 = 64 << 20;
+
+const OP_CONTINUATION: u8 = 0x0;
+const OP_TEXT: u8 = 0x1;
+const OP_BINARY: u8 = 0x2;
+const OP_CLOSE: u8 = 0x8;
+const OP_PING: u8 = 0x9;
+const OP_PONG: u8 = 0xa;
+
+/// A message received over a [`WebSocket`]
+pub(crate) enum Message {
+    Text(String),
+    #[cfg_attr(not(feature = "tts"), allow(dead_code))]
+    Binary(Bytes),
+}
+
+/// The outcome of the opening handshake
+pub(crate) enum Handshake {
+    /// The server accepted the upgrade
+    Connected(WebSocket),
+    /// The server responded with this instead of switching protocols
+    Rejected(Response),
+}
+
+/// A minimal WebSocket client, enough to talk to Watson's streaming interfaces
+pub(crate) struct WebSocket {
+    reader: ReadHalf<Upgraded>,
+    sender: Sender,
+    /// The opcode and payload of a fragmented message that is still being received
+    partial: Option<(u8, Vec<u8>)>,
+    closed: bool,
+}
+
+impl WebSocket {
+    /// Turns `req`, whose URL may use the `ws` or `wss` scheme, into an opening handshake,
+    /// returning the key the server must accept. The handshake must be sent over HTTP/1.1
+    pub(crate) fn handshake(req: &mut Request) -> Result<String, String> {
+        let scheme = match req.url().scheme() {
+            "ws" => Some("http"),
+            "wss" => Some("https"),
+            _ => None,
+        };
+        if let Some(scheme) = scheme {
+            req.url_mut()
+                .set_scheme(scheme)
+                .map_err(|_| "invalid WebSocket URL".to_owned())?;
+        }
+        let key = STANDARD.encode(random_bytes::<16>()?);
+        let headers = req.headers_mut();
+        headers.insert(CONNECTION, HeaderValue::from_static("Upgrade"));
+        headers.insert(UPGRADE, HeaderValue::from_static("websocket"));
+        headers.insert(SEC_WEBSOCKET_VERSION, HeaderValue::from_static("13"));
+        headers.insert(SEC_WEBSOCKET_KEY, HeaderValue::from_str(&key).unwrap());
+        Ok(key)
+    }
+
+    /// Completes the opening handshake sent with `key` once the server's `response` to it arrives
+    pub(crate) async fn accept(response: Response, key: &str) -> Result<Handshake, String> {
+        let status = response.status();
+        if status != StatusCode::SWITCHING_PROTOCOLS {
+            return Ok(Handshake::Rejected(response));
+        }
+        let accept = response
+            .headers()
+            .get(SEC_WEBSOCKET_ACCEPT)
+            .and_then(|value| value.to_str().ok());
+        if accept != Some(&accept_key(key)) {
+            return Err("the server did not accept the WebSocket handshake".to_owned());
+        }
+        let io = response.upgrade().await.map_err(|e| e.to_string())?;
+        let (reader, writer) = tokio::io::split(io);
+        Ok(Handshake::Connected(Self {
+            reader,
+            sender: Sender {
+                writer: Arc::new(Mutex::new(writer)),
+            },
+            partial: None,
+            closed: false,
+        }))
+    }
+
+    #[cfg_attr(not(feature = "stt"), allow(dead_code))]
+    /// A handle that sends messages over this connection while messages are being received
+    pub(crate) fn sender(&self) -> Sender {
+        self.sender.clone()
+    }
+
+    /// Sends `text` in a single text frame
+    pub(crate) async fn send_text(&mut self, text: &str) -> Result<(), String> {
+        self.sender.send_text(text).await
+    }
+
+    /// Receives the next message, answering pings along the way. Returns [`None`] once the
+    /// connection has been closed
+    pub(crate) async fn receive(&mut self) -> Result<Option<Message>, String> {
+        while !self.closed {
+            let (fin, opcode, payload) = self.read_frame().await?;
+            match opcode {
+                OP_TEXT | OP_BINARY if self.partial.is_some() => {
+                    return Err("a new message started before the last one ended".to_owned());
+                }
+                OP_TEXT | OP_BINARY if !fin => self.partial = Some((opcode, payload)),
+                OP_TEXT | OP_BINARY => return message(opcode, payload).map(Some),
+                OP_CONTINUATION => {
+                    let (opcode, mut data) = self
+                        .partial
+                        .take()
+                        .ok_or_else(|| "unexpected continuation frame".to_owned())?;
+                    if data.len() as u64 + payload.len() as u64 > MAX_MESSAGE_SIZE {
+                        return Err("the message is too large".to_owned());
+                    }
+                    data.extend_from_slice(&payload);
+                    if fin {
+                        return message(opcode, data).map(Some);
+                    }
+                    self.partial = Some((opcode, data));
+                }
+                OP_CLOSE => {
+                    // echo the status code back, as the protocol requires
+                    let code = payload.get(..2).unwrap_or_default().to_vec();
+                    let _ = self.sender.write_frame(OP_CLOSE, &code).await;
+                    self.closed = true;
+                }
+                OP_PING => self.sender.write_frame(OP_PONG, &payload).await?,
+                OP_PONG => {}
+                opcode => return Err(format!("unsupported WebSocket opcode {opcode}")),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Starts closing the connection with a normal closure
+    pub(crate) async fn close(&mut self) -> Result<(), String> {
+        self.sender.close().await
+    }
+
+    async fn read_frame(&mut self) -> Result<(bool, u8, Vec<u8>), String> {
+        let mut head = [0; 2];
+        self.read_exact(&mut head).await?;
+        let fin = head[0] & 0x80 != 0;
+        let opcode = head[0] & 0x0f;
+        let masked = head[1] & 0x80 != 0;
+        let len = match head[1] & 0x7f {
+            126 => {
+                let mut len = [0; 2];
+                self.read_exact(&mut len).await?;
+                u16::from_be_bytes(len) as u64
+            }
+            127 => {
+                let mut len = [0; 8];
+                self.read_exact(&mut len).await?;
+                u64::from_be_bytes(len)
+            }
+            len => len as u64,
+        };
+        if len > MAX_MESSAGE_SIZE {
+            return Err("the message is too large".to_owned());
+        }
+        let mut mask = [0; 4];
+        if masked {
+            self.read_exact(&mut mask).await?;
+        }
+        let mut payload = vec![0; len as usize];
+        self.read_exact(&mut payload).await?;
+        if masked {
+            apply_mask(&mut payload, mask);
+        }
+        Ok((fin, opcode, payload))
+    }
+
+    async fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), String> {
+        self.reader
+            .read_exact(buf)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Clone)]
+/// Sends messages over a [`WebSocket`], which may be receiving messages at the same time
+pub(crate) struct Sender {
+    writer: Arc<Mutex<WriteHalf<Upgraded>>>,
+}
+
+impl Sender {
+    /// Sends `text` in a single text frame
+    pub(crate) async fn send_text(&self, text: &str) -> Result<(), String> {
+        self.write_frame(OP_TEXT, text.as_bytes()).await
+    }
+
+    #[cfg_attr(not(feature = "stt"), allow(dead_code))]
+    /// Sends `data` in a single binary frame
+    pub(crate) async fn send_binary(&self, data: &[u8]) -> Result<(), String> {
+        self.write_frame(OP_BINARY, data).await
+    }
+
+    /// Starts closing the connection with a normal closure
+    pub(crate) async fn close(&self) -> Result<(), String> {
+        self.write_frame(OP_CLOSE, &1000u16.to_be_bytes()).await
+    }
+
+    /// Writes a single, final frame. Frames sent by a client must always be masked
+    async fn write_frame(&self, opcode: u8, payload: &[u8]) -> Result<(), String> {
+        let mut frame = Vec::with_capacity(payload.len() + 14);
+        frame.push(0x80 | opcode);
+        match payload.len() {
+            len @ 0..=125 => frame.push(0x80 | len as u8),
+            len @ 126..=0xffff => {
+                frame.push(0x80 | 126);
+                frame.extend_from_slice(&(len as u16).to_be_bytes());
+            }
+            len => {
+                frame.push(0x80 | 127);
+                frame.extend_from_slice(&(len as u64).to_be_bytes());
+            }
+        }
+        let mask = random_bytes::<4>()?;
+        frame.extend_from_slice(&mask);
+        let start = frame.len();
+        frame.extend_from_slice(payload);
+        apply_mask(&mut frame[start..], mask);
+        let mut writer = self.writer.lock().await;
+        writer.write_all(&frame).await.map_err(|e| e.to_string())?;
+        writer.flush().await.map_err(|e| e.to_string())
+    }
+}
+
+fn message(opcode: u8, payload: Vec<u8>) -> Result<Message, String> {
+    match opcode {
+        OP_TEXT => String::from_utf8(payload)
+            .map(Message::Text)
+            .map_err(|_| "received a text message that is not UTF-8".to_owned()),
+        _ => Ok(Message::Binary(Bytes::from(payload))),
+    }
+}
+
+/// The `Sec-WebSocket-Accept` value a server must respond with for `key`
+pub(crate) fn accept_key(key: &str) -> String {
+    let digest = ring::digest::digest(
+        &ring::digest::SHA1_FOR_LEGACY_USE_ONLY,
+        format!("{key}{ACCEPT_GUID}").as_bytes(),
+    );
+    STANDARD.encode(digest.as_ref())
+}
+
+pub(crate) fn apply_mask(payload: &mut [u8], mask: [u8; 4]) {
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+}
+
+/// Random bytes for handshake keys and frame masks, from the operating system's secure random
+/// number generator
+fn random_bytes<const N: usize>() -> Result<[u8; N], String> {
+    let mut bytes = [0; N];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| "no random bytes could be generated".to_owned())?;
+    Ok(bytes)
+}